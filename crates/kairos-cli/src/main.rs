@@ -1,6 +1,12 @@
-use clap::{Arg, Command};
-use kairos_client::GatewayClient;
+use clap::{Arg, ArgAction, Command};
+use kairos_client::{parse_prometheus, GatewayClient};
+use std::path::Path;
 use std::process;
+use std::time::Duration;
+use tabled::{settings::Style, Table, Tabled};
+
+mod validate;
+mod watch;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -21,6 +27,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .help("Gateway URL")
                         .default_value("http://localhost:5900")
                 )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Print the raw health status as JSON")
+                        .action(ArgAction::SetTrue)
+                )
         )
         .subcommand(
             Command::new("metrics")
@@ -33,6 +45,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .help("Gateway URL")
                         .default_value("http://localhost:5900")
                 )
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Print the parsed metric samples as JSON")
+                        .action(ArgAction::SetTrue)
+                )
+                .subcommand(
+                    Command::new("watch")
+                        .about("Refresh key metrics until Ctrl-C is pressed")
+                        .arg(
+                            Arg::new("url")
+                                .short('u')
+                                .long("url")
+                                .value_name("URL")
+                                .help("Gateway URL")
+                                .default_value("http://localhost:5900")
+                        )
+                        .arg(
+                            Arg::new("interval")
+                                .short('i')
+                                .long("interval")
+                                .value_name("SECONDS")
+                                .help("Seconds between refreshes")
+                                .value_parser(clap::value_parser!(u64).range(1..))
+                                .default_value("2")
+                        )
+                )
         )
         .subcommand(
             Command::new("config")
@@ -68,26 +107,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     match matches.subcommand() {
         Some(("status", sub_matches)) => {
             let url = sub_matches.get_one::<String>("url").unwrap();
-            println!("🔍 Checking gateway status at: {}", url);
-            
-            // TODO: Implement actual status check
-            println!("✅ Gateway is healthy");
+            let json = sub_matches.get_flag("json");
+            if !json {
+                println!("🔍 Checking gateway status at: {}", url);
+            }
+
+            if let Err(e) = print_status(url, json).await {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
         },
         Some(("metrics", sub_matches)) => {
+            if let Some(("watch", watch_matches)) = sub_matches.subcommand() {
+                let url = watch_matches.get_one::<String>("url").unwrap();
+                let interval = *watch_matches.get_one::<u64>("interval").unwrap();
+
+                if let Err(e) = watch::watch_metrics(url, Duration::from_secs(interval)).await {
+                    eprintln!("❌ {}", e);
+                    process::exit(1);
+                }
+                return Ok(());
+            }
+
             let url = sub_matches.get_one::<String>("url").unwrap();
-            println!("📊 Fetching metrics from: {}", url);
-            
-            // TODO: Implement metrics fetching
-            println!("Requests: 1,234 | Success Rate: 99.5% | Avg Latency: 12ms");
+            let json = sub_matches.get_flag("json");
+            if !json {
+                println!("📊 Fetching metrics from: {}", url);
+            }
+
+            if let Err(e) = print_metrics(url, json).await {
+                eprintln!("❌ {}", e);
+                process::exit(1);
+            }
         },
         Some(("config", sub_matches)) => {
             match sub_matches.subcommand() {
                 Some(("validate", config_matches)) => {
                     let file = config_matches.get_one::<String>("file").unwrap();
                     println!("🔧 Validating configuration file: {}", file);
-                    
-                    // TODO: Implement config validation
-                    println!("✅ Configuration is valid");
+
+                    if !print_validation(file) {
+                        process::exit(1);
+                    }
                 },
                 Some(("generate", config_matches)) => {
                     let output = config_matches.get_one::<String>("output").unwrap();
@@ -103,4 +164,130 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Fetches and prints the gateway health status.
+async fn print_status(url: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let client = GatewayClient::new(url)?;
+    let health = client.health().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&health)?);
+        return Ok(());
+    }
+
+    let icon = if health.status == "healthy" { "✅" } else { "⚠️ " };
+    println!("{} Gateway is {}", icon, health.status);
+    println!("   Version: {}", health.version);
+    println!("   Uptime:  {}", format_uptime(health.uptime_seconds));
+    Ok(())
+}
+
+/// Row of the metrics table.
+#[derive(Tabled)]
+struct MetricRow {
+    #[tabled(rename = "Metric")]
+    name: String,
+    #[tabled(rename = "Labels")]
+    labels: String,
+    #[tabled(rename = "Value")]
+    value: f64,
+}
+
+/// Fetches gateway metrics and prints them as a table.
+async fn print_metrics(url: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let client = GatewayClient::new(url)?;
+    let samples = parse_prometheus(&client.metrics().await?);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&samples)?);
+        return Ok(());
+    }
+
+    let rows = samples.into_iter().map(|sample| MetricRow {
+        name: sample.name,
+        labels: sample
+            .labels
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(", "),
+        value: sample.value,
+    });
+    println!("{}", Table::new(rows).with(Style::rounded()));
+    Ok(())
+}
+
+/// Formats seconds as a compact duration, e.g. `1d 2h 3m 4s`.
+fn format_uptime(seconds: u64) -> String {
+    let (days, hours, minutes, secs) = (
+        seconds / 86_400,
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", secs),
+        (0, 0, _) => format!("{}m {}s", minutes, secs),
+        (0, _, _) => format!("{}h {}m {}s", hours, minutes, secs),
+        _ => format!("{}d {}h {}m {}s", days, hours, minutes, secs),
+    }
+}
+
+/// Validates `file` and prints its findings. Returns whether the configuration is valid.
+fn print_validation(file: &str) -> bool {
+    let report = match validate::validate_file(Path::new(file)) {
+        Ok(report) => report,
+        Err(validate::LoadError::Parse {
+            message,
+            line,
+            column,
+            source_line,
+        }) => {
+            eprintln!("❌ Invalid JSON: {}", message);
+            eprintln!("   --> {}:{}:{}", file, line, column);
+            if let Some(source_line) = source_line {
+                eprintln!("    | {}", source_line);
+                eprintln!("    | {}^", " ".repeat(column.saturating_sub(1)));
+            }
+            return false;
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return false;
+        }
+    };
+
+    let print_finding = |icon: &str, finding: &validate::Finding| {
+        println!("{} {}", icon, finding.message);
+        if let Some((line, text)) = &finding.context {
+            println!("   --> {}:{}", file, line);
+            println!("    | {}", text);
+        }
+    };
+
+    for error in &report.errors {
+        print_finding("❌", error);
+    }
+    for warning in &report.warnings {
+        print_finding("⚠️ ", warning);
+    }
+    for recommendation in &report.recommendations {
+        println!("💡 {}", recommendation);
+    }
+
+    if report.is_valid {
+        println!(
+            "✅ Configuration is valid ({} warnings)",
+            report.warnings.len()
+        );
+    } else {
+        println!(
+            "❌ Configuration is invalid ({} errors, {} warnings)",
+            report.errors.len(),
+            report.warnings.len()
+        );
+    }
+
+    report.is_valid
+}
@@ -0,0 +1,216 @@
+//! Configuration file validation for the `config validate` subcommand.
+//!
+//! Loads a configuration file from an explicit path, runs the gateway's
+//! comprehensive validation and attaches the source line each finding
+//! refers to, when it can be located.
+
+use kairos_rs::config::validation::ConfigValidator;
+use kairos_rs::models::settings::Settings;
+use std::path::Path;
+use thiserror::Error;
+
+/// Largest configuration file accepted, matching the gateway's own limit.
+const MAX_CONFIG_SIZE: u64 = 10 * 1024 * 1024; // 10MB
+
+/// Errors that prevent a configuration file from being validated at all.
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("Configuration file not found: {path}")]
+    NotFound { path: String },
+
+    #[error("Cannot read configuration file {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Configuration file too large: {size} bytes (max: {MAX_CONFIG_SIZE} bytes)")]
+    TooLarge { size: u64 },
+
+    #[error("Invalid JSON at line {line}, column {column}: {message}")]
+    Parse {
+        message: String,
+        line: usize,
+        column: usize,
+        source_line: Option<String>,
+    },
+}
+
+/// A validation finding with the configuration line it refers to.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub message: String,
+    /// 1-based line number and its contents
+    pub context: Option<(usize, String)>,
+}
+
+/// Outcome of validating a configuration file.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub is_valid: bool,
+    pub errors: Vec<Finding>,
+    pub warnings: Vec<Finding>,
+    pub recommendations: Vec<String>,
+}
+
+/// Reads, parses and validates the configuration file at `path`.
+pub fn validate_file(path: &Path) -> Result<Report, LoadError> {
+    let display = path.display().to_string();
+
+    let metadata = std::fs::metadata(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => LoadError::NotFound {
+            path: display.clone(),
+        },
+        _ => LoadError::Read {
+            path: display.clone(),
+            source: e,
+        },
+    })?;
+
+    if metadata.len() > MAX_CONFIG_SIZE {
+        return Err(LoadError::TooLarge {
+            size: metadata.len(),
+        });
+    }
+
+    let source = std::fs::read_to_string(path).map_err(|e| LoadError::Read {
+        path: display,
+        source: e,
+    })?;
+
+    validate_source(&source)
+}
+
+/// Parses and validates configuration JSON held in memory.
+pub fn validate_source(source: &str) -> Result<Report, LoadError> {
+    let mut settings: Settings = serde_json::from_str(source).map_err(|e| LoadError::Parse {
+        message: e.to_string(),
+        line: e.line(),
+        column: e.column(),
+        source_line: source
+            .lines()
+            .nth(e.line().saturating_sub(1))
+            .map(str::to_string),
+    })?;
+
+    // Unknown pools surface as router validation errors below
+    let _ = ConfigValidator::resolve_backend_pools(&mut settings);
+    let mut result = ConfigValidator::validate_comprehensive(&settings);
+
+    // Global checks (JWT, metrics credentials) only live in Settings::validate
+    if let Err(error) = settings.validate() {
+        if !result.errors.iter().any(|e| e.contains(&error)) {
+            result.add_error(error);
+        }
+    }
+
+    let locate = |message: String| Finding {
+        context: locate_line(source, &settings, &message),
+        message,
+    };
+
+    Ok(Report {
+        is_valid: result.is_valid,
+        errors: result.errors.into_iter().map(locate).collect(),
+        warnings: result.warnings.into_iter().map(locate).collect(),
+        recommendations: result.recommendations,
+    })
+}
+
+/// Finds the `external_path` line of the route a validation message refers to.
+fn locate_line(source: &str, settings: &Settings, message: &str) -> Option<(usize, String)> {
+    let route_path = router_index(message)
+        .and_then(|index| settings.routers.get(index))
+        .map(|router| router.external_path.as_str())
+        .or_else(|| {
+            // Prefer the longest path so "/api/users/{id}" wins over "/api"
+            settings
+                .routers
+                .iter()
+                .map(|router| router.external_path.as_str())
+                .filter(|path| message.contains(path))
+                .max_by_key(|path| path.len())
+        })?;
+
+    let needle = format!("\"{}\"", route_path);
+    source
+        .lines()
+        .enumerate()
+        .find(|(_, line)| line.contains("external_path") && line.contains(&needle))
+        .map(|(index, line)| (index + 1, line.trim().to_string()))
+}
+
+/// Extracts `N` from messages of the form "Router N validation failed: ...".
+fn router_index(message: &str) -> Option<usize> {
+    message
+        .strip_prefix("Router ")?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = r#"{
+  "version": 1,
+  "routers": [
+    {
+      "external_path": "/api/users",
+      "internal_path": "/users",
+      "methods": ["GET"],
+      "backends": [{ "host": "https://users.internal", "port": 443 }]
+    }
+  ]
+}"#;
+
+    #[test]
+    fn test_valid_config() {
+        let report = validate_source(VALID).unwrap();
+        assert!(report.is_valid);
+        assert!(report.errors.is_empty());
+    }
+
+    #[test]
+    fn test_router_error_points_at_route_line() {
+        let source = VALID.replace("443", "0");
+        let report = validate_source(&source).unwrap();
+
+        assert!(!report.is_valid);
+        let (line, text) = report.errors[0].context.clone().unwrap();
+        assert_eq!(line, 5);
+        assert!(text.contains("/api/users"));
+    }
+
+    #[test]
+    fn test_global_errors_are_reported() {
+        let source = VALID.replace("\"methods\"", "\"auth_required\": true,\n      \"methods\"");
+        let report = validate_source(&source).unwrap();
+
+        assert!(!report.is_valid);
+        assert!(report.errors.iter().any(|e| e.message.contains("JWT")));
+    }
+
+    #[test]
+    fn test_malformed_json_reports_position() {
+        let source = VALID.replace("\"internal_path\": \"/users\",", "\"internal_path\": \"/users\"");
+        match validate_source(&source) {
+            Err(LoadError::Parse {
+                line, source_line, ..
+            }) => {
+                assert_eq!(line, 7);
+                assert!(source_line.unwrap().contains("methods"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let result = validate_file(Path::new("/nonexistent/kairos-config.json"));
+        assert!(matches!(result, Err(LoadError::NotFound { .. })));
+    }
+}
@@ -0,0 +1,95 @@
+//! Live metrics view for `kairos metrics watch`.
+
+use console::Term;
+use kairos_client::{GatewayClient, MetricsSnapshot};
+use std::time::Duration;
+
+/// Refreshes the metrics view every `interval` until Ctrl-C is pressed.
+///
+/// Failed fetches are shown in place of the metrics and retried on the next
+/// refresh, so a restarting gateway does not end the watch.
+pub async fn watch_metrics(url: &str, interval: Duration) -> Result<(), Box<dyn std::error::Error>> {
+    let client = GatewayClient::new(url)?;
+    let term = Term::stdout();
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = ticker.tick() => {}
+        }
+
+        let lines = match client.metrics_snapshot().await {
+            Ok(snapshot) => render(url, &snapshot),
+            Err(e) => vec![header(url), format!("❌ {}", e)],
+        };
+
+        term.clear_screen()?;
+        for line in lines {
+            println!("{}", line);
+        }
+        println!();
+        println!("Refreshing every {}s, press Ctrl-C to exit", interval.as_secs());
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Formats one refresh of the metrics view.
+pub fn render(url: &str, snapshot: &MetricsSnapshot) -> Vec<String> {
+    let success_rate = if snapshot.requests_total > 0 {
+        format!(
+            "{:.1}%",
+            snapshot.requests_success as f64 / snapshot.requests_total as f64 * 100.0
+        )
+    } else {
+        "n/a".to_string()
+    };
+
+    vec![
+        header(url),
+        format!("   Requests:           {}", snapshot.requests_total),
+        format!("   Success rate:       {}", success_rate),
+        format!("   Avg latency:        {:.2} ms", snapshot.average_response_time_ms),
+        format!("   Active connections: {}", snapshot.active_connections),
+    ]
+}
+
+fn header(url: &str) -> String {
+    format!("📊 Kairos metrics at {}", url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_cycle() {
+        let text = "# TYPE kairos_requests_total counter\n\
+                    kairos_requests_total 200\n\
+                    kairos_requests_success_total 190\n\
+                    kairos_requests_error_total 10\n\
+                    kairos_active_connections 4\n\
+                    kairos_response_time_avg 12.345\n";
+        let snapshot = MetricsSnapshot::from_prometheus(text);
+
+        assert_eq!(
+            render("http://localhost:5900", &snapshot),
+            [
+                "📊 Kairos metrics at http://localhost:5900",
+                "   Requests:           200",
+                "   Success rate:       95.0%",
+                "   Avg latency:        12.35 ms",
+                "   Active connections: 4",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_without_requests() {
+        let snapshot = MetricsSnapshot::from_prometheus("");
+        let lines = render("http://gateway", &snapshot);
+        assert_eq!(lines[2], "   Success rate:       n/a");
+    }
+}
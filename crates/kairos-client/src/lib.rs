@@ -7,6 +7,7 @@
 //! compilation targets with completely separate implementations.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use thiserror::Error;
 use url::Url;
 
@@ -18,8 +19,6 @@ use std::time::Duration;
 
 #[cfg(feature = "wasm")]
 use gloo_net::http::Request;
-#[cfg(feature = "wasm")]
-use js_sys;
 
 #[derive(Error, Debug)]
 pub enum ClientError {
@@ -50,19 +49,170 @@ pub struct HealthStatus {
     pub status: String,
     pub timestamp: String,
     pub version: String,
+    /// The gateway reports this as `uptime`
+    #[serde(alias = "uptime")]
     pub uptime_seconds: u64,
 }
 
+impl MetricsSnapshot {
+    /// Builds a snapshot from the gateway's Prometheus exposition text.
+    ///
+    /// Series missing from `text` default to 0.
+    pub fn from_prometheus(text: &str) -> Self {
+        let samples = parse_prometheus(text);
+        let value = |name: &str| {
+            samples
+                .iter()
+                .find(|sample| sample.name == name && sample.labels.is_empty())
+                .map(|sample| sample.value)
+                .unwrap_or(0.0)
+        };
+
+        Self {
+            requests_total: value("kairos_requests_total") as u64,
+            requests_success: value("kairos_requests_success_total") as u64,
+            requests_error: value("kairos_requests_error_total") as u64,
+            active_connections: value("kairos_active_connections") as u64,
+            average_response_time_ms: value("kairos_response_time_avg"),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// A single sample from the Prometheus exposition format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricSample {
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+}
+
+/// Parses Prometheus exposition text into samples.
+///
+/// `# HELP` / `# TYPE` comments, blank lines and malformed lines are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use kairos_client::parse_prometheus;
+///
+/// let samples = parse_prometheus(
+///     "# TYPE kairos_requests_total counter\n\
+///      kairos_requests_total 42\n\
+///      kairos_circuit_breaker_state{service=\"api:80\"} 0\n",
+/// );
+/// assert_eq!(samples.len(), 2);
+/// assert_eq!(samples[0].value, 42.0);
+/// assert_eq!(samples[1].labels["service"], "api:80");
+/// ```
+pub fn parse_prometheus(text: &str) -> Vec<MetricSample> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(parse_sample_line)
+        .collect()
+}
+
+fn parse_sample_line(line: &str) -> Option<MetricSample> {
+    let (name, labels, rest) = match line.find('{') {
+        Some(open) => {
+            let close = open + line[open..].rfind('}')?;
+            (
+                &line[..open],
+                parse_labels(&line[open + 1..close]),
+                &line[close + 1..],
+            )
+        }
+        None => {
+            let (name, rest) = line.split_once(char::is_whitespace)?;
+            (name, BTreeMap::new(), rest)
+        }
+    };
+
+    // An optional timestamp may follow the value
+    let value = rest.split_whitespace().next()?.parse().ok()?;
+
+    Some(MetricSample {
+        name: name.trim().to_string(),
+        labels,
+        value,
+    })
+}
+
+fn parse_labels(raw: &str) -> BTreeMap<String, String> {
+    let mut labels = BTreeMap::new();
+    let mut chars = raw.chars().peekable();
+
+    loop {
+        let key: String = chars
+            .by_ref()
+            .skip_while(|c| *c == ',' || c.is_whitespace())
+            .take_while(|c| *c != '=')
+            .collect();
+        if key.is_empty() || chars.next() != Some('"') {
+            break;
+        }
+
+        let mut value = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some(escaped) => value.push(escaped),
+                    None => break,
+                },
+                '"' => break,
+                _ => value.push(c),
+            }
+        }
+        labels.insert(key.trim().to_string(), value);
+    }
+
+    labels
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]  
 pub struct MetricsSnapshot {
     pub requests_total: u64,
     pub requests_success: u64,
     pub requests_error: u64,
     pub active_connections: u64,
+    /// The gateway reports this as `response_time_avg`
+    #[serde(alias = "response_time_avg")]
     pub average_response_time_ms: f64,
     pub timestamp: String,
 }
 
+/// Outcome of a configuration reload triggered through the admin API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReloadResult {
+    /// Whether the new configuration was loaded
+    pub success: bool,
+    /// Confirmation, or why the configuration was rejected
+    pub message: String,
+    /// Version of the loaded configuration (if successful)
+    pub version: Option<u64>,
+    /// RFC 3339 timestamp of when the configuration was loaded (if successful)
+    pub timestamp: Option<String>,
+}
+
+impl ReloadResult {
+    /// Interprets the gateway's answer to `POST /admin/config/reload`.
+    ///
+    /// A rejected configuration is answered with an error status but still
+    /// describes the failure, so it is returned as an unsuccessful result;
+    /// any other error response becomes [`ClientError::Gateway`].
+    fn from_response(status: u16, body: String) -> Result<Self, ClientError> {
+        match serde_json::from_str::<ReloadResult>(&body) {
+            Ok(result) => Ok(result),
+            Err(_) => Err(ClientError::Gateway {
+                status,
+                message: body,
+            }),
+        }
+    }
+}
+
 /// Client for interacting with Kairos API Gateway
 pub struct GatewayClient {
     #[cfg(feature = "native")]
@@ -88,6 +238,7 @@ impl GatewayClient {
     }
     
     /// Check gateway health status
+    #[allow(clippy::needless_return)] // Early returns keep the cfg-gated branches symmetric
     pub async fn health(&self) -> Result<HealthStatus, ClientError> {
         let url = self.base_url.join("/health")?;
         
@@ -124,6 +275,7 @@ impl GatewayClient {
     }
     
     /// Get gateway metrics
+    #[allow(clippy::needless_return)] // Early returns keep the cfg-gated branches symmetric
     pub async fn metrics(&self) -> Result<String, ClientError> {
         let url = self.base_url.join("/metrics")?;
         
@@ -159,35 +311,186 @@ impl GatewayClient {
         }
     }
     
-    /// Get parsed metrics snapshot
+    /// Get a metrics snapshot from the gateway's `/metrics.json` endpoint
+    #[allow(clippy::needless_return)] // Early returns keep the cfg-gated branches symmetric
     pub async fn metrics_snapshot(&self) -> Result<MetricsSnapshot, ClientError> {
-        // For now, return mock data with some variation
-        // TODO: Parse actual Prometheus format or add JSON endpoint
-        let timestamp = chrono::Utc::now().to_rfc3339();
+        let url = self.base_url.join("/metrics.json")?;
+        
+        #[cfg(feature = "native")]
+        {
+            let response = self.client.get(url).send().await?;
+            
+            if response.status().is_success() {
+                let snapshot = response.json::<MetricsSnapshot>().await?;
+                return Ok(snapshot);
+            } else {
+                return Err(ClientError::Gateway {
+                    status: response.status().as_u16(),
+                    message: response.text().await.unwrap_or_default(),
+                });
+            }
+        }
         
-        // Simulate some realistic metrics with conditional random generation
         #[cfg(feature = "wasm")]
-        let (_random_factor, _error_factor, _conn_factor, _latency_factor) = 
-            (js_sys::Math::random(), js_sys::Math::random(), js_sys::Math::random(), js_sys::Math::random());
+        {
+            let response = Request::get(url.as_str()).send().await?;
+            
+            if response.ok() {
+                let snapshot = response.json::<MetricsSnapshot>().await?;
+                return Ok(snapshot);
+            } else {
+                let text = response.text().await.unwrap_or_default();
+                return Err(ClientError::Gateway {
+                    status: response.status(),
+                    message: text,
+                });
+            }
+        }
+    }
+    
+    /// Reload the gateway configuration from its config file
+    ///
+    /// Posts to `/admin/config/reload` with `token` as bearer token. A
+    /// configuration that fails validation is returned as a result with
+    /// `success: false` and the validation errors in `message`; the gateway
+    /// keeps its current configuration in that case.
+    #[allow(clippy::needless_return)] // Early returns keep the cfg-gated branches symmetric
+    pub async fn reload_config(&self, token: &str) -> Result<ReloadResult, ClientError> {
+        let url = self.base_url.join("/admin/config/reload")?;
         
         #[cfg(feature = "native")]
-        let (_random_factor, _error_factor, _conn_factor, _latency_factor) = {
-            use rand::Rng;
-            let mut rng = rand::thread_rng();
-            (rng.gen::<f64>(), rng.gen::<f64>(), rng.gen::<f64>(), rng.gen::<f64>())
-        };
-        
-        let requests_total = 1000 + (_random_factor * 500.0) as u64;
-        let requests_error = (requests_total as f64 * 0.02 + _error_factor * 10.0) as u64;
-        let requests_success = requests_total - requests_error;
+        {
+            let response = self.client.post(url).bearer_auth(token).send().await?;
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return ReloadResult::from_response(status, body);
+        }
         
-        Ok(MetricsSnapshot {
-            requests_total,
-            requests_success,
-            requests_error,
-            active_connections: (10.0 + _conn_factor * 20.0) as u64,
-            average_response_time_ms: 10.0 + _latency_factor * 50.0,
-            timestamp,
-        })
+        #[cfg(feature = "wasm")]
+        {
+            let response = Request::post(url.as_str())
+                .header("Authorization", &format!("Bearer {}", token))
+                .send()
+                .await?;
+            let body = response.text().await.unwrap_or_default();
+            return ReloadResult::from_response(response.status(), body);
+        }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prometheus_skips_comments() {
+        let text = "# HELP kairos_requests_total Total requests\n\
+                    # TYPE kairos_requests_total counter\n\
+                    kairos_requests_total 1547\n\
+                    \n\
+                    kairos_response_time_avg 12.5\n";
+        let samples = parse_prometheus(text);
+
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].name, "kairos_requests_total");
+        assert_eq!(samples[0].value, 1547.0);
+        assert_eq!(samples[1].value, 12.5);
+    }
+
+    #[test]
+    fn test_parse_prometheus_labels() {
+        let text = r#"kairos_circuit_breaker_state{service="http://api:80",kind="a\"b"} 2 1700000000"#;
+        let samples = parse_prometheus(text);
+
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].name, "kairos_circuit_breaker_state");
+        assert_eq!(samples[0].labels["service"], "http://api:80");
+        assert_eq!(samples[0].labels["kind"], "a\"b");
+        assert_eq!(samples[0].value, 2.0);
+    }
+
+    #[test]
+    fn test_parse_prometheus_ignores_malformed_lines() {
+        let samples = parse_prometheus("garbage\nkairos_up not_a_number\nkairos_ok 1");
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].name, "kairos_ok");
+    }
+
+    #[test]
+    fn test_metrics_snapshot_from_gateway_json() {
+        let json = r#"{
+            "timestamp": "2024-03-15T10:30:00Z",
+            "requests_total": 120,
+            "requests_success": 117,
+            "requests_error": 3,
+            "success_rate": 97.5,
+            "response_time_avg": 12.34,
+            "active_connections": 2,
+            "circuit_breakers": []
+        }"#;
+        let snapshot: MetricsSnapshot = serde_json::from_str(json).unwrap();
+
+        assert_eq!(snapshot.requests_total, 120);
+        assert_eq!(snapshot.requests_error, 3);
+        assert_eq!(snapshot.active_connections, 2);
+        assert_eq!(snapshot.average_response_time_ms, 12.34);
+    }
+
+    #[test]
+    fn test_metrics_snapshot_from_prometheus() {
+        let text = "# HELP kairos_requests_total Total number of HTTP requests\n\
+                    # TYPE kairos_requests_total counter\n\
+                    kairos_requests_total 120\n\
+                    kairos_requests_success_total 117\n\
+                    kairos_requests_error_total 3\n\
+                    kairos_response_time_avg 12.34\n\
+                    kairos_circuit_breaker_state{service=\"x\"} 0\n";
+        let snapshot = MetricsSnapshot::from_prometheus(text);
+
+        assert_eq!(snapshot.requests_total, 120);
+        assert_eq!(snapshot.requests_success, 117);
+        assert_eq!(snapshot.requests_error, 3);
+        assert_eq!(snapshot.average_response_time_ms, 12.34);
+        // Missing series default to zero
+        assert_eq!(snapshot.active_connections, 0);
+    }
+
+    #[test]
+    fn test_reload_result_from_response() {
+        let ok = ReloadResult::from_response(
+            200,
+            r#"{"success": true, "message": "Configuration reloaded successfully",
+                "version": 4, "timestamp": "2024-03-15T10:30:00Z"}"#
+                .to_string(),
+        )
+        .unwrap();
+        assert!(ok.success);
+        assert_eq!(ok.version, Some(4));
+
+        let rejected = ReloadResult::from_response(
+            500,
+            r#"{"success": false, "message": "Failed to reload configuration: invalid route",
+                "version": null, "timestamp": null}"#
+                .to_string(),
+        )
+        .unwrap();
+        assert!(!rejected.success);
+        assert!(rejected.message.contains("invalid route"));
+        assert_eq!(rejected.version, None);
+
+        let unauthorized = ReloadResult::from_response(401, "Unauthorized".to_string());
+        assert!(matches!(
+            unauthorized,
+            Err(ClientError::Gateway { status: 401, .. })
+        ));
+    }
+
+    #[test]
+    fn test_health_status_accepts_gateway_uptime_field() {
+        let health: HealthStatus = serde_json::from_str(
+            r#"{"status": "healthy", "timestamp": "now", "version": "0.3.0", "uptime": 42}"#,
+        )
+        .unwrap();
+        assert_eq!(health.uptime_seconds, 42);
+    }
+}
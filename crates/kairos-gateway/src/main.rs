@@ -6,21 +6,41 @@
 //! configuring and starting the HTTP server with all required middleware
 //! and routing capabilities.
 
+use kairos_rs::config::hot_reload::{apply_route_updates, record_validation_warnings, ConfigManager};
 use kairos_rs::config::settings::load_settings;
+use kairos_rs::config::tls::TlsSettings;
 use kairos_rs::config::validation::ConfigValidator;
 use kairos_rs::logs::logger::configure_logger;
-use kairos_rs::middleware::rate_limit::AdvancedRateLimit;
+use kairos_rs::middleware::auth::JwtConfig;
+use kairos_rs::middleware::catch_panic::CatchPanic;
+use kairos_rs::middleware::connection_limit::ConnectionLimit;
+use kairos_rs::middleware::error_format::ErrorFormatting;
+use kairos_rs::middleware::load_shedding::LoadShedding;
+use kairos_rs::middleware::rate_limit::{basic_governor_config, AdvancedRateLimit};
 use kairos_rs::middleware::security::security_headers;
-use kairos_rs::models::settings::Settings;
-use kairos_rs::routes::{auth_http, health, management, metrics, websocket, websocket_admin};
+use kairos_rs::models::router::TrailingSlash;
+use kairos_rs::models::settings::{parse_listen_addresses, MetricsBackend, Settings};
+use kairos_rs::routes::{
+    auth_http, config_reload, health, https_redirect, management, metrics, root, websocket,
+    websocket_admin,
+};
+use kairos_rs::services::discovery::{spawn_dns_discovery, DnsDiscoveryResolver};
+use kairos_rs::services::health_check::spawn_health_checks;
 use kairos_rs::services::http::RouteHandler;
+use kairos_rs::services::metrics_sink::StatsdSink;
 use kairos_rs::services::metrics_store::MetricsStore;
+use kairos_rs::services::tcp_proxy::spawn_tcp_proxy;
 use kairos_rs::services::websocket::WebSocketHandler;
 
-use actix_governor::{Governor, GovernorConfigBuilder};
-use actix_web::{middleware::Logger, App, HttpServer};
+use actix_governor::Governor;
+use actix_web::{
+    middleware::{Condition, Logger},
+    App, HttpServer,
+};
 use chrono::Duration;
-use log::{error, info};
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use log::{error, info, warn};
 use tokio::signal;
 
 #[actix_web::main]
@@ -47,7 +67,85 @@ async fn main() -> std::io::Result<()> {
         validation_result.warnings.len()
     );
 
-    let mut route_handler = RouteHandler::new(config.routers.clone(), 30); // 30 second timeout
+    if config.trailing_slash != TrailingSlash::Strict {
+        info!("Trailing slash mode: {:?}", config.trailing_slash);
+    }
+    let mut route_handler = RouteHandler::builder(config.routers.clone(), 30) // 30 second timeout
+        .trailing_slash(config.trailing_slash)
+        .build()
+        .unwrap_or_else(|e| {
+            error!("{}", e);
+            std::process::exit(1);
+        });
+
+    if let Some(streaming) = &config.streaming {
+        route_handler = route_handler
+            .with_streaming_threshold(streaming.threshold_bytes)
+            .with_mirror_body_limit(streaming.mirror_body_limit_bytes);
+    }
+
+    if let Some(errors) = &config.errors {
+        if let Some(message) = errors.timeout_message.clone() {
+            route_handler = route_handler.with_timeout_message(message);
+        }
+        route_handler = route_handler.with_empty_pool_status(errors.empty_pool_status);
+    }
+
+    if let Some(forwarded_headers) = config.forwarded_headers.clone() {
+        route_handler = route_handler.with_forwarded_headers(forwarded_headers);
+    }
+
+    if !config.default_upstream_headers.is_empty() {
+        let headers = config
+            .resolved_default_upstream_headers()
+            .expect("Default upstream headers are checked when settings are loaded");
+        info!("Adding {} default headers to upstream requests", headers.len());
+        route_handler = route_handler.with_default_upstream_headers(headers);
+    }
+
+    if let Some(bypass) = &config.circuit_breaker_bypass {
+        let trusted = bypass
+            .trusted_networks()
+            .expect("Trusted proxies are checked when settings are loaded");
+        info!("Circuit breaker bypass enabled for {} trusted proxies", trusted.len());
+        route_handler = route_handler.with_circuit_breaker_bypass(trusted);
+    }
+
+    if let Some(overrides) = &config.debug_overrides {
+        let trusted = overrides
+            .trusted_networks()
+            .expect("Trusted proxies are checked when settings are loaded");
+        warn!("==============================================================");
+        warn!("DEBUG OVERRIDES ARE ENABLED. DO NOT USE THIS IN PRODUCTION.");
+        warn!(
+            "Requests with X-Kairos-Override-Path from {} may target any upstream path",
+            overrides.trusted_proxies.join(", ")
+        );
+        warn!("==============================================================");
+        route_handler = route_handler.with_debug_overrides(trusted);
+    }
+
+    if let Some(header) = &config.request_start_header {
+        route_handler = route_handler.with_request_start_header(header.clone());
+    }
+
+    if config.fault_injection_enabled {
+        warn!("Fault injection is enabled, routes with fault_injection will fail or be delayed on purpose");
+        for router in config.routers.iter().filter(|r| r.fault_injection.is_some()) {
+            warn!("Fault injection is active on route {}", router.external_path);
+        }
+        route_handler = route_handler.with_fault_injection(true);
+    }
+
+    // Hold proxy traffic back while the connection pool warms up
+    if let Some(warmup) = config.warmup.clone() {
+        route_handler = route_handler
+            .with_warmup(std::time::Duration::from_secs(warmup.grace_period_secs));
+        info!(
+            "Warming up for up to {}s before serving proxy traffic",
+            warmup.grace_period_secs
+        );
+    }
 
     // Initialize AI Service if configured
     if let Some(ai_settings) = config.ai.clone() {
@@ -57,8 +155,39 @@ async fn main() -> std::io::Result<()> {
         info!("AI Service initialized successfully");
     }
 
+    // Keep DNS-discovered backends up to date; routes added by a reload are picked up too
+    spawn_dns_discovery(route_handler.clone(), DnsDiscoveryResolver::from_system_conf());
+
+    // Probe backends of routes with health_check and take failing ones out of rotation
+    spawn_health_checks(route_handler.clone());
+
+    let warming_handler = route_handler.clone();
+    let warm_connections = config.warmup.as_ref().is_some_and(|w| w.warm_connections);
+    tokio::spawn(async move { warming_handler.warm_up(warm_connections).await });
+
+    // Metrics endpoint is open unless credentials are configured
+    let metrics_config = config.metrics.clone().unwrap_or_default();
+    if metrics_config.auth.is_none() {
+        warn!(
+            "Metrics endpoint is unauthenticated and exposes traffic and backend details; \
+             set metrics.auth to protect it in production"
+        );
+    }
+    // Also accepted by /metrics when metrics.auth is "jwt"
+    let metrics_jwt = config.jwt.as_ref().map(JwtConfig::from);
+
     // Initialize metrics collector
-    let metrics_collector = metrics::MetricsCollector::default();
+    let mut metrics_collector = metrics::MetricsCollector::default();
+    if let Some(buckets) = metrics_config.body_size_buckets.clone() {
+        metrics_collector = metrics_collector.with_body_size_buckets(buckets);
+    }
+    metrics_collector = metrics_collector.with_per_route_metrics(metrics_config.collect_per_route);
+    if let MetricsBackend::Statsd { address, prefix, tags } = &metrics_config.backend {
+        let sink = StatsdSink::connect(address, prefix.clone(), *tags)?;
+        info!("Sending metrics to StatsD at {}", address);
+        metrics_collector = metrics_collector.with_sink(Arc::new(sink));
+    }
+    metrics_collector.set_config_validation_warnings(validation_result.warnings.len());
 
     // Initialize historical metrics store (10,000 points max, 24 hour retention)
     let metrics_store = MetricsStore::new(10_000, Duration::hours(24));
@@ -111,15 +240,33 @@ async fn main() -> std::io::Result<()> {
     let config_path =
         std::env::var("KAIROS_CONFIG_PATH").unwrap_or_else(|_| "config.json".to_string());
 
+    // Hot-reload routes on config file changes; middleware keeps its state
+    let config_manager = Arc::new(ConfigManager::new(config.clone(), config_path.clone()));
+    config_manager.start().await;
+    tokio::spawn(apply_route_updates(
+        config_manager.subscribe_to_updates(),
+        route_handler.clone(),
+        config.clone(),
+    ));
+    tokio::spawn(record_validation_warnings(
+        config_manager.subscribe_to_updates(),
+        metrics_collector.clone(),
+    ));
+
     // Initialize route manager for dynamic configuration
     let route_manager = management::RouteManager::new(config.clone(), config_path);
 
+    if config.jwt.is_none() {
+        warn!("==============================================================");
+        warn!("NO JWT CONFIGURED. STATE-CHANGING ADMIN ENDPOINTS ARE DISABLED.");
+        warn!("Circuit breaker overrides, /admin/config/reload and /admin/rate-limit/restore need a jwt section");
+        warn!("Read-only /admin endpoints are served without authentication");
+        warn!("==============================================================");
+    }
+
     // Configure basic rate limiting as fallback
-    let governor_conf = GovernorConfigBuilder::default()
-        .per_second(100) // 100 requests per second
-        .burst_size(200) // Allow bursts up to 200 requests
-        .finish()
-        .unwrap();
+    let governor_conf = basic_governor_config();
+    let rate_limiting_enabled = !config.disable_rate_limiting;
 
     // Get server configuration from environment
     let host = std::env::var("KAIROS_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
@@ -128,22 +275,125 @@ async fn main() -> std::io::Result<()> {
         .parse::<u16>()
         .unwrap_or(5900);
 
-    info!("Starting server on {}:{}", host, port);
+    // Plain TCP passthrough for non-HTTP services, next to the HTTP server
+    for proxy in &config.tcp_proxies {
+        let (address, _) = spawn_tcp_proxy(&host, proxy, metrics_collector.clone()).await?;
+        let upstreams: Vec<String> = proxy
+            .upstreams()
+            .iter()
+            .map(|upstream| format!("{}:{}", upstream.host, upstream.port))
+            .collect();
+        info!("Forwarding TCP connections on {} to {}", address, upstreams.join(", "));
+    }
+
+    // Terminate TLS ourselves when a certificate and key are configured
+    let tls = match TlsSettings::from_env() {
+        Ok(tls) => tls,
+        Err(e) => {
+            error!("Invalid TLS configuration: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let tls_config = match &tls {
+        Some(tls) => match tls.load_server_config() {
+            Ok(tls_config) => Some(tls_config),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // Listen on KAIROS_BIND, else server.listen, else KAIROS_HOST:KAIROS_PORT
+    let listen = match std::env::var("KAIROS_BIND") {
+        Ok(bind) => {
+            parse_listen_addresses(bind.split(',').filter(|address| !address.trim().is_empty()))
+        }
+        Err(_) => config
+            .server
+            .as_ref()
+            .map_or(Ok(Vec::new()), |server| server.listen_addresses()),
+    };
+    let mut listen = match listen {
+        Ok(listen) => listen,
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if listen.is_empty() {
+        listen = (host.as_str(), port).to_socket_addrs()?.collect();
+    }
+    let https_port = listen.first().map_or(port, |address| address.port());
+
+    for address in &listen {
+        if tls.is_some() {
+            info!("Starting HTTPS server on {}", address);
+        } else {
+            info!("Starting server on {}", address);
+        }
+    }
+
+    let root_response = config.resolved_root_response();
+
+    let connection_limit_enabled = config.max_connections_per_ip.is_some();
+    let connection_limit = ConnectionLimit::new(config.max_connections_per_ip.unwrap_or(usize::MAX));
+    if let Some(max) = config.max_connections_per_ip {
+        info!("Limiting each client IP to {} concurrent requests", max);
+    }
+
+    let load_shedding_enabled = config.max_concurrent_requests.is_some();
+    let load_shedding = LoadShedding::new(config.max_concurrent_requests.unwrap_or(usize::MAX));
+    if let Some(max) = config.max_concurrent_requests {
+        info!("Shedding requests beyond {} concurrent requests", max);
+    }
+
+    let error_format = config.errors.as_ref().map(|errors| errors.format).unwrap_or_default();
 
     // Create server with appropriate rate limiting middleware
+    if !rate_limiting_enabled {
+        info!("Rate limiting disabled by configuration");
+    }
+
     let server = if let Some(rate_limit_config) = config.rate_limit.clone() {
-        info!(
-            "Using advanced rate limiting with strategy: {:?}",
-            rate_limit_config.strategy
-        );
+        if rate_limiting_enabled {
+            info!(
+                "Using advanced rate limiting with strategy: {:?}",
+                rate_limit_config.strategy
+            );
+        }
         let advanced_rate_limit = AdvancedRateLimit::new(rate_limit_config);
-        HttpServer::new(move || {
+        let rate_limit_store = advanced_rate_limit.store();
+        let mut server = HttpServer::new(move || {
             App::new()
                 .app_data(actix_web::web::Data::new(metrics_collector.clone()))
                 .app_data(actix_web::web::Data::new(metrics_store.clone()))
+                .app_data(actix_web::web::Data::new(metrics_config.clone()))
+                .configure(|cfg| {
+                    if let Some(jwt) = &metrics_jwt {
+                        cfg.app_data(actix_web::web::Data::new(jwt.clone()));
+                    }
+                })
                 .app_data(actix_web::web::Data::new(route_manager.clone()))
                 .app_data(actix_web::web::Data::new(route_handler.clone()))
-                .wrap(advanced_rate_limit.clone())
+                .app_data(actix_web::web::Data::new(config_manager.clone()))
+                .app_data(actix_web::web::Data::new(error_format))
+                .app_data(actix_web::web::Data::from(rate_limit_store.clone()))
+                .wrap(CatchPanic)
+                .wrap(Condition::new(
+                    rate_limiting_enabled,
+                    advanced_rate_limit.clone(),
+                ))
+                .wrap(Condition::new(
+                    connection_limit_enabled,
+                    connection_limit.clone(),
+                ))
+                .wrap(Condition::new(
+                    load_shedding_enabled,
+                    load_shedding.clone(),
+                ))
+                .wrap(ErrorFormatting)
                 .wrap(Logger::new(
                     r#"%a "%r" %s %b "%{Referer}i" "%{User-Agent}i" %T"#,
                 ))
@@ -153,22 +403,53 @@ async fn main() -> std::io::Result<()> {
                 .configure(metrics::configure_metrics)
                 .configure(websocket_admin::configure_admin_websocket)
                 .configure(management::configure_management)
+                .configure(config_reload::configure_config_reload)
+                .configure(|cfg| management::configure_admin(cfg, &config))
                 .configure(|cfg| websocket::configure_websocket(cfg, websocket_handler.clone()))
+                .configure(|cfg| root::configure_root(cfg, root_response.clone()))
                 .configure(|cfg| {
                     auth_http::configure_auth_routes(cfg, route_handler.clone(), &config)
                 })
-        })
-        .bind((host.as_str(), port))?
-        .run()
+        });
+        for address in &listen {
+            server = match tls_config.clone() {
+                Some(tls_config) => server.bind_rustls_0_23(address, tls_config)?,
+                None => server.bind(address)?,
+            };
+        }
+        server.run()
     } else {
-        info!("Using basic rate limiting (100 req/sec, 200 burst)");
-        HttpServer::new(move || {
+        if rate_limiting_enabled {
+            info!("Using basic rate limiting (100 req/sec, 200 burst)");
+        }
+        let mut server = HttpServer::new(move || {
             App::new()
                 .app_data(actix_web::web::Data::new(metrics_collector.clone()))
                 .app_data(actix_web::web::Data::new(metrics_store.clone()))
+                .app_data(actix_web::web::Data::new(metrics_config.clone()))
+                .configure(|cfg| {
+                    if let Some(jwt) = &metrics_jwt {
+                        cfg.app_data(actix_web::web::Data::new(jwt.clone()));
+                    }
+                })
                 .app_data(actix_web::web::Data::new(route_manager.clone()))
                 .app_data(actix_web::web::Data::new(route_handler.clone()))
-                .wrap(Governor::new(&governor_conf))
+                .app_data(actix_web::web::Data::new(config_manager.clone()))
+                .app_data(actix_web::web::Data::new(error_format))
+                .wrap(CatchPanic)
+                .wrap(Condition::new(
+                    rate_limiting_enabled,
+                    Governor::new(&governor_conf),
+                ))
+                .wrap(Condition::new(
+                    connection_limit_enabled,
+                    connection_limit.clone(),
+                ))
+                .wrap(Condition::new(
+                    load_shedding_enabled,
+                    load_shedding.clone(),
+                ))
+                .wrap(ErrorFormatting)
                 .wrap(Logger::new(
                     r#"%a "%r" %s %b "%{Referer}i" "%{User-Agent}i" %T"#,
                 ))
@@ -178,15 +459,37 @@ async fn main() -> std::io::Result<()> {
                 .configure(metrics::configure_metrics)
                 .configure(websocket_admin::configure_admin_websocket)
                 .configure(management::configure_management)
+                .configure(config_reload::configure_config_reload)
+                .configure(|cfg| management::configure_admin(cfg, &config))
                 .configure(|cfg| websocket::configure_websocket(cfg, websocket_handler.clone()))
+                .configure(|cfg| root::configure_root(cfg, root_response.clone()))
                 .configure(|cfg| {
                     auth_http::configure_auth_routes(cfg, route_handler.clone(), &config)
                 })
-        })
-        .bind((host.as_str(), port))?
-        .run()
+        });
+        for address in &listen {
+            server = match tls_config.clone() {
+                Some(tls_config) => server.bind_rustls_0_23(address, tls_config)?,
+                None => server.bind(address)?,
+            };
+        }
+        server.run()
     };
 
+    // Send plain HTTP clients to the HTTPS listener
+    if let Some(redirect_port) = tls.as_ref().and_then(|tls| tls.redirect_port) {
+        info!(
+            "Redirecting HTTP on {}:{} to HTTPS port {}",
+            host, redirect_port, https_port
+        );
+        let redirect_server = HttpServer::new(move || {
+            App::new().configure(|cfg| https_redirect::configure_https_redirect(cfg, https_port))
+        })
+        .bind((host.as_str(), redirect_port))?
+        .run();
+        tokio::spawn(redirect_server);
+    }
+
     info!("Server started successfully");
 
     // Graceful shutdown handling
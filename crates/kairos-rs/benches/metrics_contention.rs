@@ -0,0 +1,89 @@
+//! Metrics contention benchmark
+//!
+//! Measures how many increments per second the hot gateway-wide counters
+//! sustain as threads are added, for a single shared atomic and for a
+//! [`ShardedCounter`], then for [`MetricsCollector::record_request`] as a
+//! whole. Run with:
+//!
+//! ```text
+//! cargo bench -p kairos-rs --bench metrics_contention
+//! ```
+
+use kairos_rs::routes::metrics::MetricsCollector;
+use kairos_rs::utils::sharded_counter::ShardedCounter;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Barrier};
+use std::time::{Duration, Instant};
+
+/// Operations each thread performs per run.
+const OPERATIONS_PER_THREAD: u64 = 2_000_000;
+
+/// Runs `operation` `OPERATIONS_PER_THREAD` times on each of `threads`
+/// threads at once and returns the total throughput in millions of
+/// operations per second.
+fn throughput(threads: usize, operation: impl Fn() + Send + Sync + 'static) -> f64 {
+    let operation = Arc::new(operation);
+    let barrier = Arc::new(Barrier::new(threads + 1));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let operation = operation.clone();
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                for _ in 0..OPERATIONS_PER_THREAD {
+                    operation();
+                }
+            })
+        })
+        .collect();
+
+    barrier.wait();
+    let started = Instant::now();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let elapsed = started.elapsed();
+    (threads as u64 * OPERATIONS_PER_THREAD) as f64 / elapsed.as_secs_f64() / 1_000_000.0
+}
+
+fn main() {
+    let cores = std::thread::available_parallelism().map_or(8, |cores| cores.get());
+    let mut thread_counts = vec![1, 2, 4, 8, 16, 32];
+    thread_counts.retain(|&threads| threads <= cores.max(4) * 2);
+
+    println!("{} cores, {} operations per thread", cores, OPERATIONS_PER_THREAD);
+    println!(
+        "{:>8} {:>16} {:>16} {:>20}",
+        "threads", "atomic Mops/s", "sharded Mops/s", "record_request Mops/s"
+    );
+    for threads in thread_counts {
+        let atomic = Arc::new(AtomicU64::new(0));
+        let shared = atomic.clone();
+        let atomic_rate = throughput(threads, move || {
+            shared.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(atomic.load(Ordering::Relaxed), threads as u64 * OPERATIONS_PER_THREAD);
+
+        let sharded = Arc::new(ShardedCounter::new(0));
+        let shared = sharded.clone();
+        let sharded_rate = throughput(threads, move || {
+            shared.fetch_add(1, Ordering::Relaxed);
+        });
+        assert_eq!(sharded.load(Ordering::Relaxed), threads as u64 * OPERATIONS_PER_THREAD);
+
+        let collector = MetricsCollector::default();
+        let shared = collector.clone();
+        let record_rate = throughput(threads, move || {
+            shared.record_request(true, Duration::from_millis(12), 200, Some(512), Some(2048), None);
+        });
+        assert_eq!(
+            collector.requests_total.load(Ordering::Relaxed),
+            threads as u64 * OPERATIONS_PER_THREAD
+        );
+
+        println!(
+            "{:>8} {:>16.1} {:>16.1} {:>20.1}",
+            threads, atomic_rate, sharded_rate, record_rate
+        );
+    }
+}
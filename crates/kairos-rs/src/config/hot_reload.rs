@@ -4,14 +4,23 @@
 //! restarting the service, enabling dynamic route updates and configuration
 //! changes in production environments.
 
+use crate::config::settings::parse_settings;
 use crate::config::validation::ConfigValidator;
 use crate::models::settings::Settings;
+use crate::routes::metrics::MetricsCollector;
+use crate::services::http::RouteHandler;
 use log::{error, info, warn};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::time::interval;
 
+/// How long to wait for a burst of file events to settle before reloading.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
 /// Represents a configuration update event.
 ///
 /// Contains the new settings, timestamp of the update, and a monotonically
@@ -27,13 +36,32 @@ use tokio::time::interval;
 ///     version: 1,
 ///     jwt: None,
 ///     rate_limit: None,
+///     disable_rate_limiting: false,
 ///     ai: None,
+///     streaming: None,
+///     metrics: None,
+///     warmup: None,
+///     errors: None,
+///     fault_injection_enabled: false,
+///     max_connections_per_ip: None,
+///     max_concurrent_requests: None,
+///     forwarded_headers: None,
+///     default_upstream_headers: Default::default(),
+///     circuit_breaker_bypass: None,
+///     debug_overrides: None,
+///     request_start_header: None,
+///     trailing_slash: Default::default(),
+///     server: None,
+///     root_response: None,
+///     backend_pools: Default::default(),
+///     tcp_proxies: Vec::new(),
 ///     routers: vec![],
 /// };
 /// let update = ConfigUpdate {
 ///     settings,
 ///     timestamp: chrono::Utc::now(),
 ///     version: 1,
+///     validation_warnings: 0,
 /// };
 ///
 /// println!("Config version: {}", update.version);
@@ -47,12 +75,16 @@ pub struct ConfigUpdate {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     /// Monotonically increasing version number
     pub version: u64,
+    /// Number of warnings reported when this configuration was validated;
+    /// 0 for the initial configuration, which the caller validates
+    pub validation_warnings: usize,
 }
 
 /// Watches a configuration file for changes and broadcasts updates.
 ///
 /// Monitors the configuration file for modifications and automatically reloads
 /// and validates the new configuration. Broadcasts updates to all subscribers.
+/// Invalid configurations are logged and never broadcast.
 ///
 /// # Examples
 ///
@@ -65,7 +97,25 @@ pub struct ConfigUpdate {
 ///     version: 1,
 ///     jwt: None,
 ///     rate_limit: None,
+///     disable_rate_limiting: false,
 ///     ai: None,
+///     streaming: None,
+///     metrics: None,
+///     warmup: None,
+///     errors: None,
+///     fault_injection_enabled: false,
+///     max_connections_per_ip: None,
+///     max_concurrent_requests: None,
+///     forwarded_headers: None,
+///     default_upstream_headers: Default::default(),
+///     circuit_breaker_bypass: None,
+///     debug_overrides: None,
+///     request_start_header: None,
+///     trailing_slash: Default::default(),
+///     server: None,
+///     root_response: None,
+///     backend_pools: Default::default(),
+///     tcp_proxies: Vec::new(),
 ///     routers: vec![],
 /// };
 /// let watcher = ConfigWatcher::new(settings, "./config.json".to_string());
@@ -77,6 +127,7 @@ pub struct ConfigUpdate {
 /// let mut receiver = watcher.subscribe();
 /// # }
 /// ```
+#[derive(Clone)]
 #[allow(dead_code)] // Used in tests and future features
 pub struct ConfigWatcher {
     current_config: Arc<RwLock<ConfigUpdate>>,
@@ -108,7 +159,25 @@ impl ConfigWatcher {
     ///     version: 1,
     ///     jwt: None,
     ///     rate_limit: None,
+    ///     disable_rate_limiting: false,
     ///     ai: None,
+    ///     streaming: None,
+    ///     metrics: None,
+    ///     warmup: None,
+    ///     errors: None,
+    ///     fault_injection_enabled: false,
+    ///     max_connections_per_ip: None,
+    ///     max_concurrent_requests: None,
+    ///     forwarded_headers: None,
+    ///     default_upstream_headers: Default::default(),
+    ///     circuit_breaker_bypass: None,
+    ///     debug_overrides: None,
+    ///     request_start_header: None,
+    ///     trailing_slash: Default::default(),
+    ///     server: None,
+    ///     root_response: None,
+    ///     backend_pools: Default::default(),
+    ///     tcp_proxies: Vec::new(),
     ///     routers: vec![],
     /// };
     /// let watcher = ConfigWatcher::new(settings, "./config.json".to_string());
@@ -120,6 +189,7 @@ impl ConfigWatcher {
             settings: initial_config,
             timestamp: chrono::Utc::now(),
             version: 1,
+            validation_warnings: 0,
         };
 
         Self {
@@ -146,7 +216,25 @@ impl ConfigWatcher {
     /// #     version: 1,
     /// #     jwt: None,
     /// #     rate_limit: None,
+    /// #     disable_rate_limiting: false,
     /// #     ai: None,
+    /// #     streaming: None,
+    /// #     metrics: None,
+    /// #     warmup: None,
+    /// #     errors: None,
+    /// #     fault_injection_enabled: false,
+    /// #     max_connections_per_ip: None,
+    /// #     max_concurrent_requests: None,
+    /// #     forwarded_headers: None,
+    /// #     default_upstream_headers: Default::default(),
+    /// #     circuit_breaker_bypass: None,
+    /// #     debug_overrides: None,
+    /// #     request_start_header: None,
+    /// #     trailing_slash: Default::default(),
+    /// #     server: None,
+    /// #     root_response: None,
+    /// #     backend_pools: Default::default(),
+    /// #     tcp_proxies: Vec::new(),
     /// #     routers: vec![],
     /// # };
     /// # let watcher = ConfigWatcher::new(settings, "./config.json".to_string());
@@ -174,7 +262,25 @@ impl ConfigWatcher {
     /// #     version: 1,
     /// #     jwt: None,
     /// #     rate_limit: None,
+    /// #     disable_rate_limiting: false,
     /// #     ai: None,
+    /// #     streaming: None,
+    /// #     metrics: None,
+    /// #     warmup: None,
+    /// #     errors: None,
+    /// #     fault_injection_enabled: false,
+    /// #     max_connections_per_ip: None,
+    /// #     max_concurrent_requests: None,
+    /// #     forwarded_headers: None,
+    /// #     default_upstream_headers: Default::default(),
+    /// #     circuit_breaker_bypass: None,
+    /// #     debug_overrides: None,
+    /// #     request_start_header: None,
+    /// #     trailing_slash: Default::default(),
+    /// #     server: None,
+    /// #     root_response: None,
+    /// #     backend_pools: Default::default(),
+    /// #     tcp_proxies: Vec::new(),
     /// #     routers: vec![],
     /// # };
     /// # let watcher = ConfigWatcher::new(settings, "./config.json".to_string());
@@ -192,8 +298,11 @@ impl ConfigWatcher {
 
     /// Starts watching the configuration file for changes.
     ///
-    /// Spawns a background task that checks the file every 5 seconds for
-    /// modifications and automatically reloads when changes are detected.
+    /// Spawns a background task that is notified by the operating system
+    /// when the file's directory changes, so replacing the file (as editors
+    /// and Kubernetes config maps do) is picked up too. The file is reloaded
+    /// once its contents differ from the last load. If the file cannot be
+    /// watched, it is checked for modifications every 5 seconds instead.
     ///
     /// # Examples
     ///
@@ -205,7 +314,25 @@ impl ConfigWatcher {
     ///     version: 1,
     ///     jwt: None,
     ///     rate_limit: None,
+    ///     disable_rate_limiting: false,
     ///     ai: None,
+    ///     streaming: None,
+    ///     metrics: None,
+    ///     warmup: None,
+    ///     errors: None,
+    ///     fault_injection_enabled: false,
+    ///     max_connections_per_ip: None,
+    ///     max_concurrent_requests: None,
+    ///     forwarded_headers: None,
+    ///     default_upstream_headers: Default::default(),
+    ///     circuit_breaker_bypass: None,
+    ///     debug_overrides: None,
+    ///     request_start_header: None,
+    ///     trailing_slash: Default::default(),
+    ///     server: None,
+    ///     root_response: None,
+    ///     backend_pools: Default::default(),
+    ///     tcp_proxies: Vec::new(),
     ///     routers: vec![],
     /// };
     /// let watcher = ConfigWatcher::new(settings, "./config.json".to_string());
@@ -215,66 +342,133 @@ impl ConfigWatcher {
     /// # }
     /// ```
     pub async fn start_watching(&self) {
-        let mut interval = interval(Duration::from_secs(5)); // Check every 5 seconds
-        let config_path = self.config_path.clone();
-        let current_config = self.current_config.clone();
-        let update_sender = self.update_sender.clone();
-        let version_counter = self.version_counter.clone();
+        if let Err(e) = self.spawn_file_watcher() {
+            warn!(
+                "Cannot watch config file {} ({}); polling for changes every 5 seconds",
+                self.config_path, e
+            );
+            self.spawn_polling();
+        }
+    }
+
+    /// Reloads the configuration whenever the OS reports a change next to it.
+    fn spawn_file_watcher(&self) -> notify::Result<()> {
+        let path = Path::new(&self.config_path);
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+        let mut fs_watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                if !matches!(event.kind, EventKind::Access(_)) {
+                    let _ = events_tx.send(());
+                }
+            }
+        })?;
+        // Watch the directory: replacing the file would end a watch on it
+        fs_watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        info!("Watching {} for configuration changes", self.config_path);
 
+        let watcher = self.clone();
         tokio::spawn(async move {
-            let mut last_modified = get_file_modified_time(&config_path).await;
+            let _fs_watcher = fs_watcher;
+            let mut last_content = std::fs::read_to_string(&watcher.config_path).ok();
+
+            while events_rx.recv().await.is_some() {
+                // Editors often save in several steps; wait for them to settle
+                tokio::time::sleep(WATCH_DEBOUNCE).await;
+                while events_rx.try_recv().is_ok() {}
+
+                // Other files in the directory changed, or the write was a no-op
+                let content = std::fs::read_to_string(&watcher.config_path).ok();
+                if content.is_none() || content == last_content {
+                    continue;
+                }
+
+                info!("Configuration file changed, reloading...");
+                match watcher.reload_and_publish().await {
+                    Ok(update) => {
+                        info!("Configuration reloaded successfully (version {})", update.version);
+                        last_content = content;
+                    }
+                    Err(e) => {
+                        error!("Failed to reload configuration, keeping the current one: {}", e);
+                        // Remember the rejected content so it is not reloaded again
+                        last_content = content;
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Reloads the configuration whenever the file's modification time changes.
+    fn spawn_polling(&self) {
+        let watcher = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = interval(Duration::from_secs(5));
+            let mut last_modified = get_file_modified_time(&watcher.config_path).await;
 
             loop {
                 interval.tick().await;
 
-                match get_file_modified_time(&config_path).await {
-                    Some(modified_time) => {
-                        if Some(modified_time) != last_modified {
-                            info!("Configuration file changed, reloading...");
-
-                            match Self::reload_config(&config_path).await {
-                                Ok(new_settings) => {
-                                    let version = version_counter
-                                        .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
-                                        + 1;
-                                    let update = ConfigUpdate {
-                                        settings: new_settings,
-                                        timestamp: chrono::Utc::now(),
-                                        version,
-                                    };
-
-                                    *current_config.write().await = update.clone();
-
-                                    if let Err(e) = update_sender.send(update) {
-                                        warn!("Failed to broadcast config update: {}", e);
-                                    } else {
-                                        info!(
-                                            "Configuration reloaded successfully (version {})",
-                                            version
-                                        );
-                                    }
-
-                                    last_modified = Some(modified_time);
-                                }
-                                Err(e) => {
-                                    error!("Failed to reload configuration: {}", e);
-                                    // Don't update last_modified so we'll try again
-                                }
-                            }
-                        }
+                let Some(modified_time) = get_file_modified_time(&watcher.config_path).await else {
+                    warn!(
+                        "Could not get modification time for config file: {}",
+                        watcher.config_path
+                    );
+                    continue;
+                };
+                if Some(modified_time) == last_modified {
+                    continue;
+                }
+
+                info!("Configuration file changed, reloading...");
+                match watcher.reload_and_publish().await {
+                    Ok(update) => {
+                        info!("Configuration reloaded successfully (version {})", update.version);
+                        last_modified = Some(modified_time);
                     }
-                    None => {
-                        warn!(
-                            "Could not get modification time for config file: {}",
-                            config_path
-                        );
+                    Err(e) => {
+                        error!("Failed to reload configuration: {}", e);
+                        // Don't update last_modified so we'll try again
                     }
                 }
             }
         });
     }
 
-    async fn reload_config(config_path: &str) -> Result<Settings, String> {
+    /// Loads and validates the file, then stores and broadcasts the update.
+    async fn reload_and_publish(&self) -> Result<ConfigUpdate, String> {
+        let (new_settings, validation_warnings) = Self::reload_config(&self.config_path).await?;
+
+        let version = self
+            .version_counter
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            + 1;
+        let update = ConfigUpdate {
+            settings: new_settings,
+            timestamp: chrono::Utc::now(),
+            version,
+            validation_warnings,
+        };
+
+        *self.current_config.write().await = update.clone();
+
+        if let Err(e) = self.update_sender.send(update.clone()) {
+            warn!("Failed to broadcast config update: {}", e);
+        }
+
+        Ok(update)
+    }
+
+    /// Loads and validates the file, returning the settings and the number of
+    /// validation warnings.
+    async fn reload_config(config_path: &str) -> Result<(Settings, usize), String> {
         // Load new configuration
         let new_settings = load_settings_from_path(config_path)
             .map_err(|e| format!("Failed to load config: {}", e))?;
@@ -288,12 +482,8 @@ impl ConfigWatcher {
             ));
         }
 
-        // Log warnings but don't fail
-        for warning in &validation_result.warnings {
-            warn!("Config validation warning: {}", warning);
-        }
-
-        Ok(new_settings)
+        // Warnings are logged by the validator but don't fail the reload
+        Ok((new_settings, validation_result.warnings.len()))
     }
 
     /// Manually triggers a configuration reload.
@@ -319,7 +509,25 @@ impl ConfigWatcher {
     /// #     version: 1,
     /// #     jwt: None,
     /// #     rate_limit: None,
+    /// #     disable_rate_limiting: false,
     /// #     ai: None,
+    /// #     streaming: None,
+    /// #     metrics: None,
+    /// #     warmup: None,
+    /// #     errors: None,
+    /// #     fault_injection_enabled: false,
+    /// #     max_connections_per_ip: None,
+    /// #     max_concurrent_requests: None,
+    /// #     forwarded_headers: None,
+    /// #     default_upstream_headers: Default::default(),
+    /// #     circuit_breaker_bypass: None,
+    /// #     debug_overrides: None,
+    /// #     request_start_header: None,
+    /// #     trailing_slash: Default::default(),
+    /// #     server: None,
+    /// #     root_response: None,
+    /// #     backend_pools: Default::default(),
+    /// #     tcp_proxies: Vec::new(),
     /// #     routers: vec![],
     /// # };
     /// # let watcher = ConfigWatcher::new(settings, "./config.json".to_string());
@@ -330,25 +538,8 @@ impl ConfigWatcher {
     /// # }
     /// ```
     pub async fn manual_reload(&self) -> Result<ConfigUpdate, String> {
-        let new_settings = Self::reload_config(&self.config_path).await?;
-
-        let version = self
-            .version_counter
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
-            + 1;
-        let update = ConfigUpdate {
-            settings: new_settings,
-            timestamp: chrono::Utc::now(),
-            version,
-        };
-
-        *self.current_config.write().await = update.clone();
-
-        if let Err(e) = self.update_sender.send(update.clone()) {
-            warn!("Failed to broadcast manual config update: {}", e);
-        }
-
-        info!("Configuration manually reloaded (version {})", version);
+        let update = self.reload_and_publish().await?;
+        info!("Configuration manually reloaded (version {})", update.version);
         Ok(update)
     }
 }
@@ -361,10 +552,96 @@ async fn get_file_modified_time(path: &str) -> Option<std::time::SystemTime> {
 #[allow(dead_code)] // Used for configuration loading
 fn load_settings_from_path(path: &str) -> Result<Settings, Box<dyn std::error::Error>> {
     let config_content = std::fs::read_to_string(path)?;
-    let settings: Settings = serde_json::from_str(&config_content)?;
+    let mut settings = parse_settings(&config_content)?;
+    ConfigValidator::resolve_backend_pools(&mut settings)?;
     Ok(settings)
 }
 
+/// Applies the routes of each configuration update to a running handler.
+///
+/// Only the `routers` and `backend_pools` sections are hot-reloaded: the
+/// route matcher, backends and circuit breakers are swapped while middleware
+/// keeps its state, so rate limit counters are not reset by a reload. Changes
+/// to any other section are logged and take effect on the next restart. Runs
+/// until the update channel is closed.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use kairos_rs::config::hot_reload::{apply_route_updates, ConfigManager};
+/// # use kairos_rs::models::settings::Settings;
+/// # use kairos_rs::services::http::RouteHandler;
+/// # async fn example(settings: Settings) {
+/// let handler = RouteHandler::new(settings.routers.clone(), 30);
+/// let manager = ConfigManager::new(settings.clone(), "./config.json".to_string());
+/// manager.start().await;
+///
+/// tokio::spawn(apply_route_updates(manager.subscribe_to_updates(), handler, settings));
+/// # }
+/// ```
+pub async fn apply_route_updates(
+    mut updates: broadcast::Receiver<ConfigUpdate>,
+    handler: RouteHandler,
+    mut current: Settings,
+) {
+    loop {
+        let update = match updates.recv().await {
+            Ok(update) => update,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("Skipped {} configuration updates", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+
+        match handler.reload_routes(update.settings.routers.clone()) {
+            Ok(()) => info!(
+                "Applied routes from configuration version {}",
+                update.version
+            ),
+            Err(e) => {
+                error!("Failed to apply reloaded routes, keeping current ones: {}", e);
+                continue;
+            }
+        }
+
+        if non_route_settings_changed(&current, &update.settings) {
+            warn!("Only routes are hot-reloaded; restart the gateway to apply other changes");
+        }
+        current = update.settings;
+    }
+}
+
+/// Keeps the `kairos_config_validation_warnings` gauge of `metrics` at the
+/// warning count of the latest configuration update until `updates` closes.
+///
+/// The count for the initial configuration has to be set by the caller.
+pub async fn record_validation_warnings(
+    mut updates: broadcast::Receiver<ConfigUpdate>,
+    metrics: MetricsCollector,
+) {
+    loop {
+        match updates.recv().await {
+            Ok(update) => metrics.set_config_validation_warnings(update.validation_warnings),
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Compares everything except `routers` and the `backend_pools` they use.
+fn non_route_settings_changed(old: &Settings, new: &Settings) -> bool {
+    let without_routes = |settings: &Settings| {
+        let mut value = serde_json::to_value(settings).unwrap_or_default();
+        if let Some(map) = value.as_object_mut() {
+            map.remove("routers");
+            map.remove("backend_pools");
+        }
+        value
+    };
+    without_routes(old) != without_routes(new)
+}
+
 /// Configuration management service that handles hot-reload and provides
 /// current configuration to other services
 #[allow(dead_code)] // Used in future features
@@ -8,6 +8,7 @@
 //! # Module Organization
 //! 
 //! - [`settings`] - Configuration file loading and validation logic
+//! - [`tls`] - Optional TLS termination from environment variables
 //! 
 //! # Configuration Sources
 //! 
@@ -48,6 +49,8 @@
 //! - `KAIROS_CONFIG_PATH`: Path to configuration file (default: `./config.json`)
 //! - `KAIROS_HOST`: Server bind address (default: `0.0.0.0`)
 //! - `KAIROS_PORT`: Server port number (default: `5900`)
+//! - `KAIROS_TLS_CERT` / `KAIROS_TLS_KEY`: PEM certificate chain and key; serve HTTPS when both are set
+//! - `KAIROS_HTTP_REDIRECT_PORT`: Plain HTTP port redirecting to HTTPS (requires TLS)
 //! - `NO_COLOR`: Disable colored log output
 //! 
 //! # Examples
@@ -80,4 +83,5 @@
 
 pub mod hot_reload;
 pub mod settings;
+pub mod tls;
 pub mod validation;
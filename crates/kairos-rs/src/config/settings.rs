@@ -1,5 +1,9 @@
+use crate::config::validation::ConfigValidator;
+use crate::models::router::Router;
 use crate::models::settings::Settings;
 use log::{debug, warn};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 use std::fs;
 use std::path::Path;
 
@@ -71,6 +75,9 @@ use std::path::Path;
 /// - **File Too Large**: Configuration file exceeds 10MB size limit
 /// - **Invalid JSON**: Malformed JSON syntax in configuration file
 /// - **Schema Validation**: JSON doesn't match expected Settings structure
+///
+/// Parse errors report the line and column, and for structure errors the
+/// offending field, as described in [`parse_settings`].
 /// 
 /// # Environment Variables
 /// 
@@ -133,8 +140,8 @@ pub fn load_settings() -> Result<Settings, Box<dyn std::error::Error>> {
     let config_data = fs::read_to_string(&canonical_path)
         .map_err(|e| format!("Cannot read config file: {}", e))?;
     
-    let settings: Settings = serde_json::from_str(&config_data)
-        .map_err(|e| format!("Invalid JSON: {}", e))?;
+    let mut settings = parse_settings(&config_data)?;
+    ConfigValidator::resolve_backend_pools(&mut settings)?;
     
     // Validate configuration
     settings.validate()
@@ -144,3 +151,98 @@ pub fn load_settings() -> Result<Settings, Box<dyn std::error::Error>> {
     
     Ok(settings)
 }
+
+/// Parses configuration JSON, describing where it is invalid on failure.
+///
+/// Syntax errors such as trailing commas report the line and column.
+/// Structure errors such as a wrong value type also name the offending
+/// field, with the index of the route it belongs to, e.g. `routers[1].port`.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::config::settings::parse_settings;
+///
+/// let error = parse_settings(r#"{
+///   "version": 1,
+///   "routers": [
+///     {
+///       "host": "http://backend",
+///       "port": "8080",
+///       "external_path": "/api",
+///       "internal_path": "/api",
+///       "methods": ["GET"]
+///     }
+///   ]
+/// }"#).unwrap_err();
+///
+/// assert!(error.contains("line 6"));
+/// assert!(error.contains("`routers[0].port`"));
+/// ```
+pub fn parse_settings(data: &str) -> Result<Settings, String> {
+    let value: Value = serde_json::from_str(data).map_err(|e| {
+        format!(
+            "Invalid JSON at line {}, column {}: {}",
+            e.line(),
+            e.column(),
+            error_message(&e)
+        )
+    })?;
+
+    serde_json::from_str(data).map_err(|e| {
+        let field = invalid_field(&value)
+            .map(|field| format!(" in `{}`", field))
+            .unwrap_or_default();
+        format!(
+            "Invalid JSON at line {}, column {}{}: {}",
+            e.line(),
+            e.column(),
+            field,
+            error_message(&e)
+        )
+    })
+}
+
+/// Returns serde's error message without the location it appends.
+fn error_message(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    let location = format!(" at line {} column {}", error.line(), error.column());
+    message
+        .strip_suffix(&location)
+        .unwrap_or(&message)
+        .to_string()
+}
+
+/// Finds the field that stops `value` from deserializing as [`Settings`].
+///
+/// Routes are checked one by one so the failing route's index can be named.
+fn invalid_field(value: &Value) -> Option<String> {
+    if let Some(routers) = value.get("routers").and_then(Value::as_array) {
+        for (index, route) in routers.iter().enumerate() {
+            if serde_json::from_value::<Router>(route.clone()).is_err() {
+                return Some(match invalid_key::<Router>(route) {
+                    Some(key) => format!("routers[{}].{}", index, key),
+                    None => format!("routers[{}]", index),
+                });
+            }
+        }
+    }
+    invalid_key::<Settings>(value)
+}
+
+/// Finds the key of `value` whose removal lets it deserialize as `T`, or
+/// only leaves that key missing.
+fn invalid_key<T: DeserializeOwned>(value: &Value) -> Option<String> {
+    let object = value.as_object()?;
+    object
+        .keys()
+        .find(|key| {
+            let mut without = object.clone();
+            without.remove(key.as_str());
+            match serde_json::from_value::<T>(Value::Object(without)) {
+                Ok(_) => true,
+                Err(e) => e.to_string().starts_with(&format!("missing field `{}`", key)),
+            }
+        })
+        .cloned()
+}
@@ -0,0 +1,98 @@
+//! TLS termination settings for standalone deployments.
+//!
+//! TLS is normally terminated in front of the gateway. When `KAIROS_TLS_CERT`
+//! and `KAIROS_TLS_KEY` point at PEM files, the gateway serves HTTPS itself;
+//! `KAIROS_HTTP_REDIRECT_PORT` additionally opens a plain HTTP listener that
+//! redirects every request to HTTPS.
+
+use rustls::crypto::ring::default_provider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::ServerConfig;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Certificate and key locations for TLS termination.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TlsSettings {
+    /// PEM file with the certificate chain, leaf certificate first.
+    pub cert_path: PathBuf,
+
+    /// PEM file with the private key (PKCS#8, PKCS#1 or SEC1).
+    pub key_path: PathBuf,
+
+    /// Port of an optional plain HTTP listener that redirects to HTTPS.
+    pub redirect_port: Option<u16>,
+}
+
+impl TlsSettings {
+    /// Reads TLS settings from `KAIROS_TLS_CERT`, `KAIROS_TLS_KEY` and
+    /// `KAIROS_HTTP_REDIRECT_PORT`.
+    ///
+    /// Returns `Ok(None)` when neither certificate nor key is set, and an
+    /// error when only one of them is, or the redirect port is set without
+    /// them or is not a number.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        let (cert_path, key_path) = match (var("KAIROS_TLS_CERT"), var("KAIROS_TLS_KEY")) {
+            (Some(cert), Some(key)) => (PathBuf::from(cert), PathBuf::from(key)),
+            (None, None) if var("KAIROS_HTTP_REDIRECT_PORT").is_some() => {
+                return Err("KAIROS_HTTP_REDIRECT_PORT requires KAIROS_TLS_CERT and KAIROS_TLS_KEY".to_string())
+            }
+            (None, None) => return Ok(None),
+            (Some(_), None) => return Err("KAIROS_TLS_CERT is set but KAIROS_TLS_KEY is not".to_string()),
+            (None, Some(_)) => return Err("KAIROS_TLS_KEY is set but KAIROS_TLS_CERT is not".to_string()),
+        };
+
+        let redirect_port = var("KAIROS_HTTP_REDIRECT_PORT")
+            .map(|port| {
+                port.parse::<u16>()
+                    .map_err(|_| format!("KAIROS_HTTP_REDIRECT_PORT '{}' is not a valid port", port))
+            })
+            .transpose()?;
+
+        Ok(Some(Self {
+            cert_path,
+            key_path,
+            redirect_port,
+        }))
+    }
+
+    /// Loads the certificate chain and key into a rustls server configuration.
+    ///
+    /// Fails with a message naming the offending file when a file cannot be
+    /// read, contains no certificate or key, or the key does not belong to
+    /// the certificate.
+    pub fn load_server_config(&self) -> Result<ServerConfig, String> {
+        let certs = CertificateDer::pem_file_iter(&self.cert_path)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| {
+                format!("Cannot read TLS certificate '{}': {}", self.cert_path.display(), e)
+            })?;
+        if certs.is_empty() {
+            return Err(format!(
+                "TLS certificate '{}' contains no PEM certificates",
+                self.cert_path.display()
+            ));
+        }
+
+        let key = PrivateKeyDer::from_pem_file(&self.key_path).map_err(|e| {
+            format!("Cannot read TLS private key '{}': {}", self.key_path.display(), e)
+        })?;
+
+        ServerConfig::builder_with_provider(Arc::new(default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| format!("Cannot configure TLS: {}", e))?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| {
+                format!(
+                    "TLS private key '{}' cannot be used with certificate '{}': {}",
+                    self.key_path.display(),
+                    self.cert_path.display(),
+                    e
+                )
+            })
+    }
+}
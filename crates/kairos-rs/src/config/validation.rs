@@ -4,9 +4,12 @@
 //! including security checks, performance recommendations, and detailed
 //! error reporting for troubleshooting.
 
+use crate::models::router::Router;
 use crate::models::settings::Settings;
+use crate::routes::http::MAX_PAYLOAD_BYTES;
+use crate::services::response_schema::ResponseSchema;
 use log::{info, warn};
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 /// Result of configuration validation containing errors, warnings, and recommendations.
 ///
@@ -189,6 +192,13 @@ impl ConfigValidator {
     pub fn validate_comprehensive(settings: &Settings) -> ValidationResult {
         let mut result = ValidationResult::new();
 
+        // Validate routes with their backend pools resolved; routes naming an
+        // unknown pool are reported by router validation
+        let mut resolved = settings.clone();
+        let _ = Self::resolve_backend_pools(&mut resolved);
+        let settings = &resolved;
+        Self::validate_backend_pools(settings, &mut result);
+
         // Basic validation
         Self::validate_basic_structure(settings, &mut result);
 
@@ -210,6 +220,65 @@ impl ConfigValidator {
         result
     }
 
+    /// Copies each referenced backend pool into the routes that use it.
+    ///
+    /// Call this when settings are loaded, before validating them. Routes
+    /// with a `backend_pool` get that pool as their `backends`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first route that references an unknown
+    /// pool. Routes with known pools are still resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kairos_rs::config::validation::ConfigValidator;
+    /// use kairos_rs::models::settings::Settings;
+    ///
+    /// let mut settings: Settings = serde_json::from_str(r#"{
+    ///     "version": 1,
+    ///     "backend_pools": {
+    ///         "users": [{"host": "http://users", "port": 8080}]
+    ///     },
+    ///     "routers": [{
+    ///         "backend_pool": "users",
+    ///         "external_path": "/api/users",
+    ///         "internal_path": "/users",
+    ///         "methods": ["GET"]
+    ///     }]
+    /// }"#).unwrap();
+    ///
+    /// ConfigValidator::resolve_backend_pools(&mut settings).unwrap();
+    /// assert_eq!(settings.routers[0].get_backends()[0].host, "http://users");
+    /// ```
+    pub fn resolve_backend_pools(settings: &mut Settings) -> Result<(), String> {
+        let mut first_error = None;
+        for router in &mut settings.routers {
+            if let Err(error) = router.resolve_backend_pool(&settings.backend_pools) {
+                first_error.get_or_insert(format!("Route {}: {}", router.external_path, error));
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn validate_backend_pools(settings: &Settings, result: &mut ValidationResult) {
+        let mut names: Vec<_> = settings.backend_pools.keys().collect();
+        names.sort();
+        for name in names {
+            if !settings
+                .routers
+                .iter()
+                .any(|r| r.backend_pool.as_deref() == Some(name.as_str()))
+            {
+                result.add_recommendation(format!(
+                    "Backend pool '{}' is not used by any route - consider removing it",
+                    name
+                ));
+            }
+        }
+    }
+
     fn validate_basic_structure(settings: &Settings, result: &mut ValidationResult) {
         if settings.routers.is_empty() {
             result.add_error(
@@ -221,6 +290,12 @@ impl ConfigValidator {
             if let Err(error) = router.validate() {
                 result.add_error(format!("Router {} validation failed: {}", index, error));
             }
+
+            if let Some(path) = &router.response_schema {
+                if let Err(error) = ResponseSchema::load(path) {
+                    result.add_error(format!("Router {} validation failed: {}", index, error));
+                }
+            }
         }
     }
 
@@ -251,6 +326,21 @@ impl ConfigValidator {
                 }
             }
 
+            // Chaos testing faults are deliberate failures; make them visible
+            if router.fault_injection.is_some() {
+                if settings.fault_injection_enabled {
+                    result.add_warning(format!(
+                        "Fault injection is active on route {} - requests will fail or be delayed on purpose",
+                        router.external_path
+                    ));
+                } else {
+                    result.add_warning(format!(
+                        "Route {} configures fault_injection but fault_injection_enabled is false - it is ignored",
+                        router.external_path
+                    ));
+                }
+            }
+
             // Check for overly permissive methods
             if router.methods.len() > 4 {
                 result.add_warning(format!(
@@ -269,6 +359,19 @@ impl ConfigValidator {
             }
         }
 
+        if settings.disable_rate_limiting {
+            if settings.rate_limit.is_some() {
+                result.add_warning(
+                    "rate_limit is ignored because disable_rate_limiting is true".to_string(),
+                );
+            } else {
+                result.add_warning(
+                    "Rate limiting is disabled - gateway is unprotected against request floods"
+                        .to_string(),
+                );
+            }
+        }
+
         if http_routes > 0 && https_routes == 0 {
             result.add_warning(
                 "All routes use HTTP - consider HTTPS for production security".to_string(),
@@ -299,6 +402,23 @@ impl ConfigValidator {
 
         // Check for complex patterns
         for router in &settings.routers {
+            // Streamed bodies are only bound by the route's own limit
+            if let Some(limit) = router.max_body_bytes {
+                if limit > MAX_PAYLOAD_BYTES && !router.streams_request_body() {
+                    result.add_warning(format!(
+                        "Route {} max_body_bytes ({}) exceeds the gateway payload limit ({}) - larger bodies are still rejected",
+                        router.external_path, limit, MAX_PAYLOAD_BYTES
+                    ));
+                }
+            }
+
+            if router.stream_request_body && !router.streams_request_body() {
+                result.add_warning(format!(
+                    "Route {} sets stream_request_body but uses a feature that needs the whole body - bodies are buffered",
+                    router.external_path
+                ));
+            }
+
             let param_count = router.external_path.matches('{').count();
             if param_count > 3 {
                 result.add_warning(format!(
@@ -310,21 +430,38 @@ impl ConfigValidator {
     }
 
     fn validate_route_conflicts(settings: &Settings, result: &mut ValidationResult) {
-        let mut seen_paths = HashSet::new();
+        // Routes are matched by path alone, so routes with the same path
+        // shadow each other whatever their methods
+        let mut seen_paths: HashMap<String, &Router> = HashMap::new();
         let mut potential_conflicts = Vec::new();
 
         for router in &settings.routers {
-            if seen_paths.contains(&router.external_path) {
-                result.add_error(format!(
-                    "Duplicate route path detected: {}",
-                    router.external_path
-                ));
+            match seen_paths.get(&Self::path_shape(&router.external_path)) {
+                Some(existing) => {
+                    let overlapping: Vec<&str> = router
+                        .methods
+                        .iter()
+                        .filter(|m| existing.methods.iter().any(|e| e.eq_ignore_ascii_case(m)))
+                        .map(String::as_str)
+                        .collect();
+                    let detail = if overlapping.is_empty() {
+                        "merge them into one route listing all their methods".to_string()
+                    } else {
+                        format!("both handle {}", overlapping.join(", "))
+                    };
+                    result.add_error(format!(
+                        "Duplicate route path detected: {} matches the same paths as {} ({})",
+                        router.external_path, existing.external_path, detail
+                    ));
+                }
+                None => {
+                    seen_paths.insert(Self::path_shape(&router.external_path), router);
+                }
             }
-            seen_paths.insert(&router.external_path);
 
             // Check for potential conflicts between static and dynamic routes
             for other_router in &settings.routers {
-                if router.external_path != other_router.external_path
+                if Self::path_shape(&router.external_path) != Self::path_shape(&other_router.external_path)
                     && Self::routes_may_conflict(&router.external_path, &other_router.external_path)
                 {
                     potential_conflicts.push((
@@ -343,6 +480,25 @@ impl ConfigValidator {
         }
     }
 
+    /// Replaces every parameter in `path` with `{}`, so that patterns
+    /// matching the same request paths compare equal.
+    fn path_shape(path: &str) -> String {
+        let mut shape = String::with_capacity(path.len());
+        let mut in_param = false;
+        for ch in path.chars() {
+            match ch {
+                '{' => in_param = true,
+                '}' if in_param => {
+                    in_param = false;
+                    shape.push_str("{}");
+                }
+                _ if !in_param => shape.push(ch),
+                _ => {}
+            }
+        }
+        shape
+    }
+
     fn routes_may_conflict(path1: &str, path2: &str) -> bool {
         // Simple heuristic: if one is static and matches the pattern of a dynamic route
         let path1_segments: Vec<&str> = path1.split('/').collect();
@@ -11,6 +11,7 @@ use actix_web::{
 };
 use futures_util::future::{ok, LocalBoxFuture, Ready};
 use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use crate::models::settings::JwtSettings;
 use log::{debug, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -121,6 +122,18 @@ impl Default for JwtConfig {
     }
 }
 
+impl From<&JwtSettings> for JwtConfig {
+    fn from(settings: &JwtSettings) -> Self {
+        Self {
+            secret: settings.secret.clone(),
+            required_claims: settings.required_claims.iter().cloned().collect(),
+            issuer: settings.issuer.clone(),
+            audience: settings.audience.clone(),
+            ..Default::default()
+        }
+    }
+}
+
 impl JwtConfig {
     /// Creates a new JWT configuration with the specified secret key.
     ///
@@ -394,7 +407,7 @@ fn extract_jwt_token(req: &ServiceRequest) -> Result<String, &'static str> {
     Ok(auth_str[7..].to_string()) // Remove "Bearer " prefix
 }
 
-fn validate_jwt_token(token: &str, config: &JwtConfig) -> Result<Claims, String> {
+pub(crate) fn validate_jwt_token(token: &str, config: &JwtConfig) -> Result<Claims, String> {
     let mut validation = Validation::new(config.algorithm);
     
     // Configure validation parameters
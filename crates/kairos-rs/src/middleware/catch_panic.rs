@@ -0,0 +1,120 @@
+//! Panic recovery middleware.
+//!
+//! A panic in a handler, such as a transformation hitting input it did not
+//! expect, would otherwise drop the client's connection without a response.
+//! This middleware catches it, logs it under a request ID and answers with a
+//! `500 Internal Server Error` carrying the same ID, while the worker keeps
+//! serving other requests.
+
+use crate::models::error::GatewayError;
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    Error as ActixError,
+};
+use futures::future::{FutureExt, LocalBoxFuture, Ready};
+use log::error;
+use std::{
+    any::Any,
+    panic::AssertUnwindSafe,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+/// Middleware converting panics in the wrapped services into
+/// [`GatewayError::Internal`] responses.
+///
+/// Wrap it first, so it sits closest to the handlers and the outer
+/// middleware, such as access logging, sees the `500`.
+///
+/// # Examples
+///
+/// ```rust
+/// use actix_web::{web, App, HttpResponse};
+/// use kairos_rs::middleware::catch_panic::CatchPanic;
+///
+/// let app = App::new()
+///     .wrap(CatchPanic)
+///     .route("/", web::get().to(HttpResponse::Ok));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CatchPanic;
+
+impl<S, B> Transform<S, ServiceRequest> for CatchPanic
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Transform = CatchPanicMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        futures::future::ready(Ok(CatchPanicMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+/// Panic recovery middleware implementation.
+pub struct CatchPanicMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for CatchPanicMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let method = req.method().clone();
+        let path = req.path().to_string();
+
+        Box::pin(async move {
+            // Calling the service runs inside the guarded future, so panics
+            // while setting up the handler are caught as well
+            match AssertUnwindSafe(async move { service.call(req).await })
+                .catch_unwind()
+                .await
+            {
+                Ok(result) => result,
+                Err(panic) => {
+                    let request_id = uuid::Uuid::new_v4().to_string();
+                    let message = panic_message(panic.as_ref());
+                    error!(
+                        "Panic while handling {} {} (request {}): {}",
+                        method, path, request_id, message
+                    );
+                    Err(GatewayError::Internal {
+                        message,
+                        request_id,
+                    }
+                    .into())
+                }
+            }
+        })
+    }
+}
+
+/// Extracts the message passed to `panic!`, if it was a string.
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
@@ -0,0 +1,160 @@
+//! Per-client-IP concurrent connection limiting middleware.
+//!
+//! Unlike rate limiting, which counts requests over a time window, this caps
+//! how many requests a single client IP may have in flight at once. A client
+//! holding slow requests open cannot tie up more than its share of the
+//! gateway, while other clients are unaffected.
+
+use crate::models::error::GatewayError;
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    Error as ActixError,
+};
+use dashmap::DashMap;
+use futures::future::{LocalBoxFuture, Ready};
+use log::warn;
+use std::{
+    net::IpAddr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// Seconds rejected clients are asked to wait before retrying.
+const CONNECTION_LIMIT_RETRY_AFTER_SECS: u64 = 1;
+
+/// Middleware capping the number of in-flight requests per client IP.
+///
+/// Requests over the cap are rejected with [`GatewayError::ConnectionLimited`],
+/// a `503 Service Unavailable` with a `Retry-After`. A slot
+/// is released when the request completes, whether it succeeded or failed.
+/// Requests without a known peer address are not limited.
+///
+/// Clones share the same counters, so one instance can be cloned into every
+/// worker's `App`.
+///
+/// # Examples
+///
+/// ```rust
+/// use actix_web::{web, App, HttpResponse};
+/// use kairos_rs::middleware::connection_limit::ConnectionLimit;
+///
+/// let app = App::new()
+///     .wrap(ConnectionLimit::new(10))
+///     .route("/", web::get().to(HttpResponse::Ok));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConnectionLimit {
+    max_per_ip: usize,
+    connections: Arc<DashMap<IpAddr, usize>>,
+}
+
+impl ConnectionLimit {
+    /// Creates a limiter allowing `max_per_ip` concurrent requests per client IP.
+    pub fn new(max_per_ip: usize) -> Self {
+        Self {
+            max_per_ip,
+            connections: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Returns the number of requests from `ip` currently in flight.
+    pub fn active_connections(&self, ip: IpAddr) -> usize {
+        self.connections.get(&ip).map_or(0, |count| *count)
+    }
+
+    /// Takes a slot for `ip`, or returns `None` if it is at the cap.
+    fn acquire(&self, ip: IpAddr) -> Option<ConnectionGuard> {
+        let mut count = self.connections.entry(ip).or_insert(0);
+        if *count >= self.max_per_ip {
+            return None;
+        }
+        *count += 1;
+        Some(ConnectionGuard {
+            ip,
+            connections: self.connections.clone(),
+        })
+    }
+}
+
+/// Releases a connection slot when dropped.
+struct ConnectionGuard {
+    ip: IpAddr,
+    connections: Arc<DashMap<IpAddr, usize>>,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.connections.remove_if_mut(&self.ip, |_, count| {
+            *count = count.saturating_sub(1);
+            *count == 0
+        });
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ConnectionLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Transform = ConnectionLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        futures::future::ready(Ok(ConnectionLimitMiddleware {
+            service: Arc::new(service),
+            limit: self.clone(),
+        }))
+    }
+}
+
+/// Connection limiting middleware implementation.
+///
+/// Counts each request against its client IP for as long as it is being
+/// handled.
+pub struct ConnectionLimitMiddleware<S> {
+    service: Arc<S>,
+    limit: ConnectionLimit,
+}
+
+impl<S, B> Service<ServiceRequest> for ConnectionLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        let Some(ip) = req.peer_addr().map(|addr| addr.ip()) else {
+            return Box::pin(async move { service.call(req).await });
+        };
+
+        let Some(guard) = self.limit.acquire(ip) else {
+            warn!("Connection limit exceeded for client {}", ip);
+            return Box::pin(async move {
+                Err(GatewayError::ConnectionLimited {
+                    retry_after: CONNECTION_LIMIT_RETRY_AFTER_SECS,
+                }
+                .into())
+            });
+        };
+
+        Box::pin(async move {
+            let result = service.call(req).await;
+            drop(guard);
+            result
+        })
+    }
+}
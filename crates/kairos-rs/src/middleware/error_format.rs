@@ -0,0 +1,126 @@
+//! Error body format middleware.
+//!
+//! Actix renders errors through `ResponseError::error_response`, which has no
+//! access to app data, so gateway errors always come out as plain JSON. This
+//! middleware re-renders them in the `ErrorFormat` found in the app data,
+//! whether they were raised by a handler or by middleware it wraps, such as
+//! rate or connection limiting.
+
+use crate::models::error::GatewayError;
+use crate::models::settings::ErrorFormat;
+use actix_web::{
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    web, Error as ActixError,
+};
+use futures::future::{LocalBoxFuture, Ready};
+use std::{
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+/// Middleware rendering [`GatewayError`] responses in the app's
+/// [`ErrorFormat`].
+///
+/// The format is read from `web::Data<ErrorFormat>`; without it errors keep
+/// the default JSON body. Errors other than [`GatewayError`] are left as
+/// they are.
+///
+/// Wrap it after the middleware whose rejections should be formatted and
+/// before access logging and response compression, so those see the
+/// formatted response.
+///
+/// # Examples
+///
+/// ```rust
+/// use actix_web::{web, App, HttpResponse};
+/// use kairos_rs::middleware::error_format::ErrorFormatting;
+/// use kairos_rs::models::settings::ErrorFormat;
+///
+/// let app = App::new()
+///     .app_data(web::Data::new(ErrorFormat::ProblemDetails))
+///     .wrap(ErrorFormatting)
+///     .route("/", web::get().to(HttpResponse::Ok));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ErrorFormatting;
+
+impl<S, B> Transform<S, ServiceRequest> for ErrorFormatting
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = ActixError;
+    type Transform = ErrorFormattingMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        futures::future::ready(Ok(ErrorFormattingMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+/// Error format middleware implementation.
+pub struct ErrorFormattingMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ErrorFormattingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let format = req
+            .app_data::<web::Data<ErrorFormat>>()
+            .map_or(ErrorFormat::Json, |format| *format.get_ref());
+
+        // JSON is what the errors render as on their own
+        if format == ErrorFormat::Json {
+            return Box::pin(async move {
+                service.call(req).await.map(ServiceResponse::map_into_left_body)
+            });
+        }
+
+        Box::pin(async move {
+            match service.call(req).await {
+                Ok(res) => {
+                    let formatted = res
+                        .response()
+                        .error()
+                        .and_then(|error| error.as_error::<GatewayError>())
+                        .map(|error| error.to_response(format));
+                    Ok(match formatted {
+                        Some(response) => res.into_response(response).map_into_right_body(),
+                        None => res.map_into_left_body(),
+                    })
+                }
+                // Rejections by the wrapped middleware are still errors; they
+                // carry the formatted response for actix to send
+                Err(error) => match error.as_error::<GatewayError>() {
+                    Some(gateway_error) => Err(InternalError::from_response(
+                        gateway_error.to_string(),
+                        gateway_error.to_response(format),
+                    )
+                    .into()),
+                    None => Err(error),
+                },
+            }
+        })
+    }
+}
@@ -0,0 +1,148 @@
+//! Gateway-wide concurrent request limiting.
+//!
+//! Under extreme load, queueing requests only makes them time out later and
+//! keeps the gateway busy with work nobody is waiting for. With a cap on the
+//! requests in flight, anything over it is answered right away with
+//! `503 Service Unavailable` and a `Retry-After`, so the admitted requests
+//! keep their latency and clients back off.
+
+use crate::models::error::GatewayError;
+use crate::routes::metrics::MetricsCollector;
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    web, Error as ActixError,
+};
+use futures::future::{LocalBoxFuture, Ready};
+use log::warn;
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::Semaphore;
+
+/// Seconds shed clients are asked to wait before retrying.
+const SHED_RETRY_AFTER_SECS: u64 = 1;
+
+/// Middleware capping the number of requests the gateway handles at once.
+///
+/// Each request holds a permit of a shared semaphore until it completes,
+/// whether it succeeded or failed. Requests finding no permit left are
+/// rejected with [`GatewayError::Overloaded`] without waiting. When a
+/// [`MetricsCollector`] is in the app data, the number of requests in flight
+/// and of shed requests are recorded in it.
+///
+/// Clones share the same semaphore, so one instance can be cloned into every
+/// worker's `App`.
+///
+/// # Examples
+///
+/// ```rust
+/// use actix_web::{web, App, HttpResponse};
+/// use kairos_rs::middleware::load_shedding::LoadShedding;
+///
+/// let app = App::new()
+///     .wrap(LoadShedding::new(1000))
+///     .route("/", web::get().to(HttpResponse::Ok));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LoadShedding {
+    max_concurrent_requests: usize,
+    permits: Arc<Semaphore>,
+}
+
+impl LoadShedding {
+    /// Creates a limiter admitting `max_concurrent_requests` requests at once.
+    ///
+    /// Values above [`Semaphore::MAX_PERMITS`] are capped to it.
+    pub fn new(max_concurrent_requests: usize) -> Self {
+        let max_concurrent_requests = max_concurrent_requests.min(Semaphore::MAX_PERMITS);
+        Self {
+            max_concurrent_requests,
+            permits: Arc::new(Semaphore::new(max_concurrent_requests)),
+        }
+    }
+
+    /// Returns the number of requests currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.max_concurrent_requests - self.permits.available_permits()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for LoadShedding
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Transform = LoadSheddingMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        futures::future::ready(Ok(LoadSheddingMiddleware {
+            service: Arc::new(service),
+            limit: self.clone(),
+        }))
+    }
+}
+
+/// Load shedding middleware implementation.
+///
+/// Holds a permit for each request for as long as it is being handled.
+pub struct LoadSheddingMiddleware<S> {
+    service: Arc<S>,
+    limit: LoadShedding,
+}
+
+impl<S, B> Service<ServiceRequest> for LoadSheddingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let limit = self.limit.clone();
+        let metrics = req.app_data::<web::Data<MetricsCollector>>().cloned();
+
+        let Ok(permit) = limit.permits.clone().try_acquire_owned() else {
+            warn!(
+                "Shedding {} {}: {} requests already in flight",
+                req.method(),
+                req.path(),
+                limit.max_concurrent_requests
+            );
+            if let Some(metrics) = &metrics {
+                metrics.record_request_shed();
+            }
+            return Box::pin(async move {
+                Err(GatewayError::Overloaded {
+                    retry_after: SHED_RETRY_AFTER_SECS,
+                }
+                .into())
+            });
+        };
+        if let Some(metrics) = &metrics {
+            metrics.set_concurrent_requests(limit.in_flight());
+        }
+
+        Box::pin(async move {
+            let result = service.call(req).await;
+            drop(permit);
+            if let Some(metrics) = &metrics {
+                metrics.set_concurrent_requests(limit.in_flight());
+            }
+            result
+        })
+    }
+}
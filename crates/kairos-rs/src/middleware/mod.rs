@@ -7,6 +7,10 @@
 //! 
 //! # Module Organization
 //! 
+//! - [`catch_panic`] - Turns panics in request handling into 500 responses
+//! - [`connection_limit`] - Per-client-IP concurrent connection limiting
+//! - [`error_format`] - Renders gateway errors in the configured body format
+//! - [`load_shedding`] - Gateway-wide concurrent request limiting
 //! - [`security`] - Security headers and HTTPS enforcement middleware
 //! - [`validation`] - Request validation and security checks middleware
 //! 
@@ -96,6 +100,10 @@
 //! - **Configuration Management**: Dynamic security policy updates
 
 pub mod auth;
+pub mod catch_panic;
+pub mod connection_limit;
+pub mod error_format;
+pub mod load_shedding;
 pub mod rate_limit;
 pub mod security;
 pub mod transform;
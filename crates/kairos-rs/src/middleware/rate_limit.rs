@@ -4,6 +4,11 @@
 //! per-IP limiting, including per-user, per-route, and composite limiting
 //! strategies with sliding window algorithms and burst allowances.
 
+use crate::models::error::GatewayError;
+use actix_governor::{
+    governor::middleware::NoOpMiddleware, GovernorConfig, GovernorConfigBuilder,
+    PeerIpKeyExtractor,
+};
 use actix_web::{
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
     Error as ActixError, HttpMessage,
@@ -12,7 +17,7 @@ use futures::future::{LocalBoxFuture, Ready};
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     sync::{Arc, RwLock},
     task::{Context, Poll},
     time::{Duration, Instant},
@@ -137,6 +142,70 @@ impl RateLimitEntry {
     }
 }
 
+/// A [`RateLimitEntry`] with its timestamps stored as ages, so it can be
+/// serialized and restored in another process.
+///
+/// Ages are milliseconds before the snapshot was taken.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitEntrySnapshot {
+    /// Number of requests in the current window
+    pub request_count: u64,
+    /// Age of the window start
+    pub window_age_ms: u64,
+    /// Ages of individual requests for sliding window
+    pub request_ages_ms: Vec<u64>,
+    /// Available tokens for token bucket algorithm
+    pub available_tokens: f64,
+    /// Age of the last token refill
+    pub last_refill_age_ms: u64,
+}
+
+impl RateLimitEntrySnapshot {
+    fn capture(entry: &RateLimitEntry, now: Instant) -> Self {
+        let age = |time: Instant| now.saturating_duration_since(time).as_millis() as u64;
+        Self {
+            request_count: entry.request_count,
+            window_age_ms: age(entry.window_start),
+            request_ages_ms: entry.request_times.iter().map(|&time| age(time)).collect(),
+            available_tokens: entry.available_tokens,
+            last_refill_age_ms: age(entry.last_refill),
+        }
+    }
+
+    /// Rebuilds the entry relative to `now`. Ages older than this process's
+    /// clock can represent are clamped to `now`.
+    fn to_entry(&self, now: Instant) -> RateLimitEntry {
+        let at = |age_ms: u64| now.checked_sub(Duration::from_millis(age_ms)).unwrap_or(now);
+        RateLimitEntry {
+            request_count: self.request_count,
+            window_start: at(self.window_age_ms),
+            request_times: self.request_ages_ms.iter().map(|&age| at(age)).collect(),
+            available_tokens: self.available_tokens,
+            last_refill: at(self.last_refill_age_ms),
+        }
+    }
+}
+
+/// Serializable copy of a [`RateLimitStore`]'s entries, keyed like the store
+/// (e.g. `ip:10.0.0.1`).
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::middleware::rate_limit::RateLimitStore;
+///
+/// let old = RateLimitStore::new();
+/// let snapshot = old.snapshot().unwrap();
+///
+/// let new = RateLimitStore::new();
+/// assert_eq!(new.restore(&snapshot).unwrap(), 0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitSnapshot {
+    /// Entries by rate limiting key
+    pub entries: BTreeMap<String, RateLimitEntrySnapshot>,
+}
+
 /// In-memory rate limiting store with time-based cleanup.
 ///
 /// Maintains rate limiting state for different keys (IP, user, route)
@@ -202,6 +271,47 @@ impl RateLimitStore {
         }
     }
 
+    /// Copies every entry, with timestamps converted to ages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entries lock is poisoned.
+    pub fn snapshot(&self) -> Result<RateLimitSnapshot, String> {
+        let entries = self
+            .entries
+            .read()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        let now = Instant::now();
+        Ok(RateLimitSnapshot {
+            entries: entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), RateLimitEntrySnapshot::capture(entry, now)))
+                .collect(),
+        })
+    }
+
+    /// Loads the entries of `snapshot`, replacing entries with the same key
+    /// and keeping all others.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries restored
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entries lock is poisoned.
+    pub fn restore(&self, snapshot: &RateLimitSnapshot) -> Result<usize, String> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|e| format!("Lock error: {}", e))?;
+        let now = Instant::now();
+        for (key, entry) in &snapshot.entries {
+            entries.insert(key.clone(), entry.to_entry(now));
+        }
+        Ok(snapshot.entries.len())
+    }
+
     fn check_fixed_window(
         &self,
         entry: &mut RateLimitEntry,
@@ -346,6 +456,14 @@ impl AdvancedRateLimit {
         }
     }
 
+    /// Returns the store holding this limiter's counters.
+    ///
+    /// Instances created by the middleware share it, so it can be registered
+    /// as app data for the rate limit snapshot endpoints.
+    pub fn store(&self) -> Arc<RateLimitStore> {
+        self.store.clone()
+    }
+
     /// Extracts the rate limiting key based on the configured strategy.
     ///
     /// Different strategies require different key extraction logic to
@@ -475,17 +593,10 @@ where
                 }
                 Ok(false) => {
                     warn!("Rate limit exceeded for key: {}", key);
-
-                    // Return an actix error which will be handled properly
-                    let error_msg = serde_json::json!({
-                        "error": "Rate limit exceeded",
-                        "message": "Too many requests. Please try again later.",
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "type": "rate_limit_error"
-                    })
-                    .to_string();
-
-                    Err(actix_web::error::ErrorTooManyRequests(error_msg))
+                    Err(GatewayError::RateLimited {
+                        retry_after: config.window_duration.as_secs().max(1),
+                    }
+                    .into())
                 }
                 Err(err) => {
                     warn!("Rate limiting error: {}", err);
@@ -518,6 +629,19 @@ mod duration_serde {
     }
 }
 
+/// Builds the basic per-IP Governor configuration used when no `rate_limit`
+/// section is configured.
+///
+/// The gateway installs this limiter by default; set
+/// `disable_rate_limiting: true` in the configuration to run without it.
+pub fn basic_governor_config() -> GovernorConfig<PeerIpKeyExtractor, NoOpMiddleware> {
+    GovernorConfigBuilder::default()
+        .per_second(100) // 100 requests per second
+        .burst_size(200) // Allow bursts up to 200 requests
+        .finish()
+        .expect("basic governor configuration is valid")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
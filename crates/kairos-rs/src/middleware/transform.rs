@@ -1,4 +1,4 @@
-use actix_web::http::{header::{HeaderMap, HeaderName, HeaderValue}, StatusCode};
+use actix_web::http::{header::{HeaderMap, HeaderName, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE}, StatusCode};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -126,6 +126,8 @@ pub struct PathTransformation {
 ///     action: TransformAction::Add,
 ///     name: "api_key".to_string(),
 ///     value: Some("secret123".to_string()),
+///     pattern: None,
+///     replacement: None,
 /// };
 /// 
 /// // Remove debug parameter
@@ -133,6 +135,17 @@ pub struct PathTransformation {
 ///     action: TransformAction::Remove,
 ///     name: "debug".to_string(),
 ///     value: None,
+///     pattern: None,
+///     replacement: None,
+/// };
+///
+/// // Rewrite `v=1.2` to `v=1`
+/// let major_version = QueryTransformation {
+///     action: TransformAction::Replace,
+///     name: "v".to_string(),
+///     value: None,
+///     pattern: Some(r"^(\d+)\.\d+$".to_string()),
+///     replacement: Some("$1".to_string()),
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -145,6 +158,12 @@ pub struct QueryTransformation {
     
     /// Value to set/add (required for Add and Set)
     pub value: Option<String>,
+    
+    /// Regex pattern for Replace action
+    pub pattern: Option<String>,
+    
+    /// Replacement template for Replace action
+    pub replacement: Option<String>,
 }
 
 /// Request transformation configuration.
@@ -172,6 +191,8 @@ pub struct QueryTransformation {
 ///         replacement: "/$1".to_string(),
 ///     }),
 ///     query_params: vec![],
+///     when: None,
+///     body: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -186,6 +207,27 @@ pub struct RequestTransformation {
     /// Query parameter transformations
     #[serde(default)]
     pub query_params: Vec<QueryTransformation>,
+
+    /// Optional [`Condition`] gating the whole transformation
+    /// (e.g. `"has_header 'X-Debug'"`); applies to every request if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+
+    /// Optional rewrite of the request body; bodies that don't match its
+    /// content type are forwarded unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<BodyTransformation>,
+}
+
+/// Request body rewrite applied before forwarding.
+///
+/// Configured as a plain string, e.g. `"body": "form_to_json"`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyTransformation {
+    /// Converts `application/x-www-form-urlencoded` bodies into a JSON object
+    /// of string values. Keys that repeat become arrays in their original order.
+    FormToJson,
 }
 
 /// Response transformation configuration.
@@ -257,7 +299,9 @@ pub struct StatusCodeMapping {
     #[serde(with = "status_code_serde")]
     pub to: StatusCode,
     
-    /// Optional condition expression (future: support simple conditions)
+    /// Optional condition that must match for the mapping to apply, e.g.
+    /// `path == '/health'`, `path starts_with '/api'` or `method == 'GET'`.
+    /// See [`Condition`].
     pub condition: Option<String>,
 }
 
@@ -304,6 +348,8 @@ mod status_code_serde {
 ///     ],
 ///     path: None,
 ///     query_params: vec![],
+///     when: None,
+///     body: None,
 /// };
 /// 
 /// let transformer = RequestTransformer::new(config);
@@ -311,6 +357,12 @@ mod status_code_serde {
 pub struct RequestTransformer {
     config: RequestTransformation,
     path_regex: Option<Regex>,
+    /// Compiled `Replace` patterns, aligned with `config.headers`
+    header_regexes: Vec<Option<Regex>>,
+    /// Compiled `Replace` patterns, aligned with `config.query_params`
+    query_regexes: Vec<Option<Regex>>,
+    /// Parsed `config.when` condition
+    condition: ParsedCondition,
 }
 
 impl RequestTransformer {
@@ -332,60 +384,90 @@ impl RequestTransformer {
         let path_regex = config.path.as_ref().and_then(|p| {
             Regex::new(&p.pattern).ok()
         });
+        let header_regexes = compile_header_patterns(&config.headers);
+        let query_regexes = config
+            .query_params
+            .iter()
+            .map(|rule| match rule.action {
+                TransformAction::Replace => rule.pattern.as_deref().and_then(|p| Regex::new(p).ok()),
+                _ => None,
+            })
+            .collect();
+        let condition =
+            ParsedCondition::new(config.when.as_deref(), || "request transformation".to_string());
 
         Self {
             config,
             path_regex,
+            header_regexes,
+            query_regexes,
+            condition,
         }
     }
 
+    /// Whether the transformation applies to a request, according to its
+    /// `when` condition.
+    ///
+    /// Transformations with a malformed condition never apply.
+    pub fn applies_to(&self, path: &str, method: &str, headers: &HeaderMap) -> bool {
+        self.condition.matches(path, method, headers)
+    }
+
+    /// Whether any query parameter transformations are configured.
+    pub fn transforms_query_params(&self) -> bool {
+        !self.config.query_params.is_empty()
+    }
+
     /// Transforms request headers according to configuration.
     /// 
     /// # Arguments
     /// 
     /// * `headers` - Mutable reference to request headers
     pub fn transform_headers(&self, headers: &mut HeaderMap) {
-        for transform in &self.config.headers {
-            match transform.action {
-                TransformAction::Add | TransformAction::Set => {
-                    if let Some(value) = &transform.value {
-                        if let (Ok(name), Ok(val)) = (
-                            HeaderName::from_str(&transform.name),
-                            HeaderValue::from_str(value),
-                        ) {
-                            if transform.action == TransformAction::Add {
-                                if !headers.contains_key(&name) {
-                                    headers.insert(name, val);
-                                }
-                            } else {
-                                headers.insert(name, val);
-                            }
-                        }
-                    }
-                }
-                TransformAction::Remove => {
-                    if let Ok(name) = HeaderName::from_str(&transform.name) {
-                        headers.remove(&name);
-                    }
-                }
-                TransformAction::Replace => {
-                    if let (Some(pattern), Some(replacement)) =
-                        (&transform.pattern, &transform.replacement)
-                    {
-                        if let Ok(regex) = Regex::new(pattern) {
-                            if let Ok(name) = HeaderName::from_str(&transform.name) {
-                                if let Some(value) = headers.get(&name) {
-                                    if let Ok(value_str) = value.to_str() {
-                                        let new_value = regex.replace_all(value_str, replacement.as_str());
-                                        if let Ok(new_val) = HeaderValue::from_str(&new_value) {
-                                            headers.insert(name, new_val);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+        apply_header_transformations(&self.config.headers, &self.header_regexes, headers);
+    }
+
+    /// Rewrites the request body according to configuration.
+    ///
+    /// Returns the new body, with `Content-Type` and `Content-Length` updated
+    /// in `headers`, or `None` when no body transformation is configured or
+    /// the request's content type doesn't match it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use actix_web::http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+    /// use kairos_rs::middleware::transform::{BodyTransformation, RequestTransformation, RequestTransformer};
+    ///
+    /// let transformer = RequestTransformer::new(RequestTransformation {
+    ///     body: Some(BodyTransformation::FormToJson),
+    ///     ..Default::default()
+    /// });
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/x-www-form-urlencoded"));
+    ///
+    /// let body = transformer.transform_body(&mut headers, b"tag=a&tag=b&name=kairos").unwrap();
+    /// assert_eq!(body, br#"{"name":"kairos","tag":["a","b"]}"#);
+    /// assert_eq!(headers.get(CONTENT_TYPE).unwrap(), "application/json");
+    /// ```
+    pub fn transform_body(&self, headers: &mut HeaderMap, body: &[u8]) -> Option<Vec<u8>> {
+        match self.config.body? {
+            BodyTransformation::FormToJson => {
+                let is_form = headers
+                    .get(CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.split(';').next())
+                    .is_some_and(|media_type| {
+                        media_type.trim().eq_ignore_ascii_case("application/x-www-form-urlencoded")
+                    });
+                if !is_form {
+                    return None;
                 }
+
+                let json = form_to_json(body)?;
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+                headers.insert(CONTENT_LENGTH, HeaderValue::from(json.len()));
+                Some(json)
             }
         }
     }
@@ -409,11 +491,14 @@ impl RequestTransformer {
 
     /// Transforms query parameters according to configuration.
     /// 
+    /// `Replace` rewrites the named parameter's value with its regex and is a
+    /// no-op when the parameter is absent.
+    /// 
     /// # Arguments
     /// 
     /// * `params` - Mutable reference to query parameters map
     pub fn transform_query_params(&self, params: &mut HashMap<String, String>) {
-        for transform in &self.config.query_params {
+        for (transform, regex) in self.config.query_params.iter().zip(&self.query_regexes) {
             match transform.action {
                 TransformAction::Add => {
                     if let Some(value) = &transform.value {
@@ -429,13 +514,111 @@ impl RequestTransformer {
                     params.remove(&transform.name);
                 }
                 TransformAction::Replace => {
-                    // Query param replace not implemented yet
+                    if let (Some(regex), Some(replacement)) = (regex, &transform.replacement) {
+                        if let Some(value) = params.get_mut(&transform.name) {
+                            *value = regex.replace_all(value, replacement.as_str()).into_owned();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Transforms query parameters kept as ordered name/value pairs.
+    ///
+    /// Unlike [`transform_query_params`](Self::transform_query_params),
+    /// repeated names and the client's order are kept. `Add` appends the
+    /// parameter if the name is absent, `Set` replaces every occurrence with a
+    /// single one at the position of the first, `Remove` drops every
+    /// occurrence and `Replace` rewrites each occurrence's value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kairos_rs::middleware::transform::{
+    ///     QueryTransformation, RequestTransformation, RequestTransformer, TransformAction,
+    /// };
+    ///
+    /// let transformer = RequestTransformer::new(RequestTransformation {
+    ///     query_params: vec![QueryTransformation {
+    ///         action: TransformAction::Add,
+    ///         name: "api_key".to_string(),
+    ///         value: Some("secret".to_string()),
+    ///         pattern: None,
+    ///         replacement: None,
+    ///     }],
+    ///     ..Default::default()
+    /// });
+    /// let mut pairs = vec![
+    ///     ("tag".to_string(), "a".to_string()),
+    ///     ("tag".to_string(), "b".to_string()),
+    /// ];
+    /// transformer.transform_query_pairs(&mut pairs);
+    /// assert_eq!(pairs[1], ("tag".to_string(), "b".to_string()));
+    /// assert_eq!(pairs[2], ("api_key".to_string(), "secret".to_string()));
+    /// ```
+    pub fn transform_query_pairs(&self, pairs: &mut Vec<(String, String)>) {
+        for (transform, regex) in self.config.query_params.iter().zip(&self.query_regexes) {
+            let name = transform.name.as_str();
+            match transform.action {
+                TransformAction::Add => {
+                    if let Some(value) = &transform.value {
+                        if !pairs.iter().any(|(key, _)| key == name) {
+                            pairs.push((name.to_string(), value.clone()));
+                        }
+                    }
+                }
+                TransformAction::Set => {
+                    if let Some(value) = &transform.value {
+                        match pairs.iter().position(|(key, _)| key == name) {
+                            Some(first) => {
+                                pairs[first].1 = value.clone();
+                                let mut index = 0;
+                                pairs.retain(|(key, _)| {
+                                    index += 1;
+                                    index - 1 == first || key != name
+                                });
+                            }
+                            None => pairs.push((name.to_string(), value.clone())),
+                        }
+                    }
+                }
+                TransformAction::Remove => {
+                    pairs.retain(|(key, _)| key != name);
+                }
+                TransformAction::Replace => {
+                    if let (Some(regex), Some(replacement)) = (regex, &transform.replacement) {
+                        for (_, value) in pairs.iter_mut().filter(|(key, _)| key == name) {
+                            *value = regex.replace_all(value, replacement.as_str()).into_owned();
+                        }
+                    }
                 }
             }
         }
     }
 }
 
+/// Converts a form-encoded body into a JSON object, turning repeated keys
+/// into arrays. Returns `None` if the body isn't valid form data.
+fn form_to_json(body: &[u8]) -> Option<Vec<u8>> {
+    let pairs: Vec<(String, String)> = serde_urlencoded::from_bytes(body).ok()?;
+
+    let mut object = serde_json::Map::new();
+    for (key, value) in pairs {
+        match object.get_mut(&key) {
+            Some(serde_json::Value::Array(values)) => values.push(value.into()),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = serde_json::Value::Array(vec![first, value.into()]);
+            }
+            None => {
+                object.insert(key, value.into());
+            }
+        }
+    }
+    serde_json::to_vec(&object).ok()
+}
+
 /// Response transformer service.
 ///
 /// Applies transformation rules to backend responses before sending to clients.
@@ -450,6 +633,10 @@ impl RequestTransformer {
 /// ```
 pub struct ResponseTransformer {
     config: ResponseTransformation,
+    /// Compiled `Replace` patterns, aligned with `config.headers`
+    header_regexes: Vec<Option<Regex>>,
+    /// Parsed mapping conditions, aligned with `config.status_code_mappings`
+    conditions: Vec<ParsedCondition>,
 }
 
 impl ResponseTransformer {
@@ -459,7 +646,26 @@ impl ResponseTransformer {
     /// 
     /// * `config` - Response transformation configuration
     pub fn new(config: ResponseTransformation) -> Self {
-        Self { config }
+        let header_regexes = compile_header_patterns(&config.headers);
+        let conditions = config
+            .status_code_mappings
+            .iter()
+            .map(|mapping| {
+                ParsedCondition::new(mapping.condition.as_deref(), || {
+                    format!(
+                        "status code mapping {} -> {}",
+                        mapping.from.as_u16(),
+                        mapping.to.as_u16()
+                    )
+                })
+            })
+            .collect();
+
+        Self {
+            config,
+            header_regexes,
+            conditions,
+        }
     }
 
     /// Transforms response headers according to configuration.
@@ -468,49 +674,7 @@ impl ResponseTransformer {
     /// 
     /// * `headers` - Mutable reference to response headers
     pub fn transform_headers(&self, headers: &mut HeaderMap) {
-        for transform in &self.config.headers {
-            match transform.action {
-                TransformAction::Add | TransformAction::Set => {
-                    if let Some(value) = &transform.value {
-                        if let (Ok(name), Ok(val)) = (
-                            HeaderName::from_str(&transform.name),
-                            HeaderValue::from_str(value),
-                        ) {
-                            if transform.action == TransformAction::Add {
-                                if !headers.contains_key(&name) {
-                                    headers.insert(name, val);
-                                }
-                            } else {
-                                headers.insert(name, val);
-                            }
-                        }
-                    }
-                }
-                TransformAction::Remove => {
-                    if let Ok(name) = HeaderName::from_str(&transform.name) {
-                        headers.remove(&name);
-                    }
-                }
-                TransformAction::Replace => {
-                    if let (Some(pattern), Some(replacement)) =
-                        (&transform.pattern, &transform.replacement)
-                    {
-                        if let Ok(regex) = Regex::new(pattern) {
-                            if let Ok(name) = HeaderName::from_str(&transform.name) {
-                                if let Some(value) = headers.get(&name) {
-                                    if let Ok(value_str) = value.to_str() {
-                                        let new_value = regex.replace_all(value_str, replacement.as_str());
-                                        if let Ok(new_val) = HeaderValue::from_str(&new_value) {
-                                            headers.insert(name, new_val);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        apply_header_transformations(&self.config.headers, &self.header_regexes, headers);
     }
 
     /// Transforms status code according to configuration mappings.
@@ -519,15 +683,22 @@ impl ResponseTransformer {
     /// 
     /// * `status` - Original status code
     /// * `path` - Request path (for condition evaluation)
+    /// * `method` - Request method (for condition evaluation)
+    /// * `headers` - Request headers (for condition evaluation)
     /// 
     /// # Returns
     /// 
-    /// Transformed status code
-    pub fn transform_status_code(&self, status: StatusCode, _path: &str) -> StatusCode {
-        for mapping in &self.config.status_code_mappings {
-            if status == mapping.from {
-                // TODO: Evaluate condition if present
-                // For now, always apply if status matches
+    /// Status code of the first mapping whose `from` and condition match,
+    /// or `status` unchanged
+    pub fn transform_status_code(
+        &self,
+        status: StatusCode,
+        path: &str,
+        method: &str,
+        headers: &HeaderMap,
+    ) -> StatusCode {
+        for (mapping, condition) in self.config.status_code_mappings.iter().zip(&self.conditions) {
+            if status == mapping.from && condition.matches(path, method, headers) {
                 return mapping.to;
             }
         }
@@ -535,10 +706,227 @@ impl ResponseTransformer {
     }
 }
 
+/// Condition expression for status code mappings and request transformations.
+///
+/// Supports a single comparison of `path`, `method` or a request header
+/// (`header.<name>`) against a quoted string, using `==` or `starts_with`,
+/// and `has_header '<name>'` to test for the presence of a header:
+///
+/// ```rust
+/// use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+/// use kairos_rs::middleware::transform::Condition;
+///
+/// let headers = HeaderMap::new();
+/// let condition = Condition::parse("path starts_with '/api'").unwrap();
+/// assert!(condition.matches("/api/users", "GET", &headers));
+/// assert!(!condition.matches("/health", "GET", &headers));
+///
+/// assert!(Condition::parse("method == 'GET'").unwrap().matches("/", "get", &headers));
+/// assert!(Condition::parse("path = /api").is_none());
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(HeaderName::from_static("x-env"), HeaderValue::from_static("staging"));
+/// assert!(Condition::parse("header.X-Env == 'staging'").unwrap().matches("/", "GET", &headers));
+/// assert!(Condition::parse("has_header 'x-env'").unwrap().matches("/", "GET", &headers));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// `path == '<value>'`
+    PathEquals(String),
+    /// `path starts_with '<value>'`
+    PathStartsWith(String),
+    /// `method == '<value>'`, compared case-insensitively
+    MethodEquals(String),
+    /// `method starts_with '<value>'`, compared case-insensitively
+    MethodStartsWith(String),
+    /// `header.<name> == '<value>'`, matching any value of the header
+    HeaderEquals(HeaderName, String),
+    /// `header.<name> starts_with '<value>'`, matching any value of the header
+    HeaderStartsWith(HeaderName, String),
+    /// `has_header '<name>'`
+    HasHeader(HeaderName),
+}
+
+impl Condition {
+    /// Parses a condition expression, returning `None` if it is malformed.
+    pub fn parse(expr: &str) -> Option<Self> {
+        let expr = expr.trim();
+        let (field, rest) = expr.split_once(char::is_whitespace)?;
+        let rest = rest.trim_start();
+
+        if field == "has_header" {
+            let name = parse_quoted(rest)?;
+            return HeaderName::from_str(&name).ok().map(Self::HasHeader);
+        }
+
+        let (operator, literal) = if let Some(literal) = rest.strip_prefix("==") {
+            ("==", literal)
+        } else if let Some(literal) = rest.strip_prefix("starts_with") {
+            // Require a separator so `starts_withx` is not accepted
+            if !literal.starts_with(char::is_whitespace) {
+                return None;
+            }
+            ("starts_with", literal)
+        } else {
+            return None;
+        };
+        let value = parse_quoted(literal.trim())?;
+
+        if let Some(name) = field.strip_prefix("header.") {
+            let name = HeaderName::from_str(name).ok()?;
+            return Some(match operator {
+                "==" => Self::HeaderEquals(name, value),
+                _ => Self::HeaderStartsWith(name, value),
+            });
+        }
+
+        match (field, operator) {
+            ("path", "==") => Some(Self::PathEquals(value)),
+            ("path", _) => Some(Self::PathStartsWith(value)),
+            ("method", "==") => Some(Self::MethodEquals(value)),
+            ("method", _) => Some(Self::MethodStartsWith(value)),
+            _ => None,
+        }
+    }
+
+    /// Evaluates the condition against a request's path, method and headers.
+    pub fn matches(&self, path: &str, method: &str, headers: &HeaderMap) -> bool {
+        let header_values = |name: &HeaderName| {
+            headers
+                .get_all(name)
+                .filter_map(|value| value.to_str().ok())
+                .collect::<Vec<_>>()
+        };
+
+        match self {
+            Self::PathEquals(value) => path == value,
+            Self::PathStartsWith(value) => path.starts_with(value.as_str()),
+            Self::MethodEquals(value) => method.eq_ignore_ascii_case(value),
+            Self::MethodStartsWith(value) => method
+                .get(..value.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(value)),
+            Self::HeaderEquals(name, value) => header_values(name).contains(&value.as_str()),
+            Self::HeaderStartsWith(name, value) => header_values(name)
+                .iter()
+                .any(|v| v.starts_with(value.as_str())),
+            Self::HasHeader(name) => headers.contains_key(name),
+        }
+    }
+}
+
+/// Parsed form of an optional condition expression.
+enum ParsedCondition {
+    /// No condition configured; always applies
+    Always,
+    /// Applies when the condition matches
+    When(Condition),
+    /// The condition could not be parsed; never applies
+    Invalid,
+}
+
+impl ParsedCondition {
+    /// Parses `expr`, logging a warning naming `context` if it is malformed.
+    fn new(expr: Option<&str>, context: impl FnOnce() -> String) -> Self {
+        match expr {
+            None => Self::Always,
+            Some(expr) => match Condition::parse(expr) {
+                Some(condition) => Self::When(condition),
+                None => {
+                    log::warn!("Ignoring {}: cannot parse condition '{}'", context(), expr);
+                    Self::Invalid
+                }
+            },
+        }
+    }
+
+    fn matches(&self, path: &str, method: &str, headers: &HeaderMap) -> bool {
+        match self {
+            Self::Always => true,
+            Self::When(condition) => condition.matches(path, method, headers),
+            Self::Invalid => false,
+        }
+    }
+}
+
+/// Parses a single- or double-quoted string that makes up all of `literal`.
+fn parse_quoted(literal: &str) -> Option<String> {
+    let quote = literal.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let inner = literal[1..].strip_suffix(quote)?;
+    if inner.contains(quote) {
+        return None;
+    }
+    Some(inner.to_string())
+}
+
+/// Compiles the regex of every `Replace` rule; other rules get `None`.
+fn compile_header_patterns(rules: &[HeaderTransformation]) -> Vec<Option<Regex>> {
+    rules
+        .iter()
+        .map(|rule| match rule.action {
+            TransformAction::Replace => rule.pattern.as_deref().and_then(|p| Regex::new(p).ok()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Applies header rules in order, using the patterns from [`compile_header_patterns`].
+fn apply_header_transformations(
+    rules: &[HeaderTransformation],
+    regexes: &[Option<Regex>],
+    headers: &mut HeaderMap,
+) {
+    for (transform, regex) in rules.iter().zip(regexes) {
+        match transform.action {
+            TransformAction::Add | TransformAction::Set => {
+                if let Some(value) = &transform.value {
+                    if let (Ok(name), Ok(val)) = (
+                        HeaderName::from_str(&transform.name),
+                        HeaderValue::from_str(value),
+                    ) {
+                        if transform.action == TransformAction::Add {
+                            if !headers.contains_key(&name) {
+                                headers.insert(name, val);
+                            }
+                        } else {
+                            headers.insert(name, val);
+                        }
+                    }
+                }
+            }
+            TransformAction::Remove => {
+                if let Ok(name) = HeaderName::from_str(&transform.name) {
+                    headers.remove(&name);
+                }
+            }
+            TransformAction::Replace => {
+                if let (Some(regex), Some(replacement)) = (regex, &transform.replacement) {
+                    if let Ok(name) = HeaderName::from_str(&transform.name) {
+                        // Repeated headers such as Set-Cookie keep every value
+                        let values: Vec<HeaderValue> = headers
+                            .get_all(&name)
+                            .map(|value| {
+                                value
+                                    .to_str()
+                                    .ok()
+                                    .map(|value_str| regex.replace_all(value_str, replacement.as_str()))
+                                    .and_then(|new_value| HeaderValue::from_str(&new_value).ok())
+                                    .unwrap_or_else(|| value.clone())
+                            })
+                            .collect();
+                        headers.remove(&name);
+                        for value in values {
+                            headers.append(name.clone(), value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use actix_web::http::header::HeaderName;
 
     #[test]
     fn test_header_transformation_add() {
@@ -552,6 +940,8 @@ mod tests {
             }],
             path: None,
             query_params: vec![],
+            when: None,
+            body: None,
         };
 
         let transformer = RequestTransformer::new(config);
@@ -577,6 +967,8 @@ mod tests {
             }],
             path: None,
             query_params: vec![],
+            when: None,
+            body: None,
         };
 
         let transformer = RequestTransformer::new(config);
@@ -591,6 +983,73 @@ mod tests {
         assert!(headers.get("Authorization").is_none());
     }
 
+    #[test]
+    fn test_form_to_json_body() {
+        let transformer = RequestTransformer::new(RequestTransformation {
+            body: Some(BodyTransformation::FormToJson),
+            ..Default::default()
+        });
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("Application/X-WWW-Form-Urlencoded; charset=utf-8"),
+        );
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("23"));
+
+        let body = transformer
+            .transform_body(&mut headers, b"a=1&b=x+y&a=2&a=3&c=%26")
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json, serde_json::json!({ "a": ["1", "2", "3"], "b": "x y", "c": "&" }));
+        assert_eq!(headers.get(CONTENT_TYPE).unwrap(), "application/json");
+        assert_eq!(headers.get(CONTENT_LENGTH).unwrap(), body.len().to_string().as_str());
+
+        // Other content types and transformers without a body rule are left alone
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        assert!(transformer.transform_body(&mut headers, b"{}").is_none());
+        assert!(RequestTransformer::new(RequestTransformation::default())
+            .transform_body(&mut HeaderMap::new(), b"a=1")
+            .is_none());
+    }
+
+    #[test]
+    fn test_header_replace_keeps_repeated_values() {
+        let config = ResponseTransformation {
+            headers: vec![HeaderTransformation {
+                action: TransformAction::Replace,
+                name: "Set-Cookie".to_string(),
+                value: None,
+                pattern: Some("Domain=internal".to_string()),
+                replacement: Some("Domain=example.com".to_string()),
+            }],
+            status_code_mappings: vec![],
+        };
+
+        let transformer = ResponseTransformer::new(config);
+        let mut headers = HeaderMap::new();
+        headers.append(
+            HeaderName::from_static("set-cookie"),
+            HeaderValue::from_static("session=abc; Domain=internal"),
+        );
+        headers.append(
+            HeaderName::from_static("set-cookie"),
+            HeaderValue::from_static("theme=dark; Domain=internal"),
+        );
+
+        transformer.transform_headers(&mut headers);
+
+        let cookies: Vec<_> = headers
+            .get_all("set-cookie")
+            .map(|value| value.to_str().unwrap())
+            .collect();
+        assert_eq!(
+            cookies,
+            ["session=abc; Domain=example.com", "theme=dark; Domain=example.com"]
+        );
+    }
+
     #[test]
     fn test_path_transformation() {
         let config = RequestTransformation {
@@ -600,6 +1059,8 @@ mod tests {
                 replacement: "/$1".to_string(),
             }),
             query_params: vec![],
+            when: None,
+            body: None,
         };
 
         let transformer = RequestTransformer::new(config);
@@ -618,13 +1079,19 @@ mod tests {
                     action: TransformAction::Add,
                     name: "api_key".to_string(),
                     value: Some("secret123".to_string()),
+                    pattern: None,
+                    replacement: None,
                 },
                 QueryTransformation {
                     action: TransformAction::Remove,
                     name: "debug".to_string(),
                     value: None,
+                    pattern: None,
+                    replacement: None,
                 },
             ],
+            when: None,
+            body: None,
         };
 
         let transformer = RequestTransformer::new(config);
@@ -649,8 +1116,116 @@ mod tests {
         };
 
         let transformer = ResponseTransformer::new(config);
-        let mapped = transformer.transform_status_code(StatusCode::NOT_FOUND, "/test");
+        let mapped = transformer.transform_status_code(StatusCode::NOT_FOUND, "/test", "GET", &HeaderMap::new());
         
         assert_eq!(mapped, StatusCode::OK);
     }
+
+    #[test]
+    fn test_status_code_mapping_conditions() {
+        let mapping = |from, to, condition: &str| StatusCodeMapping {
+            from,
+            to,
+            condition: Some(condition.to_string()),
+        };
+        let config = ResponseTransformation {
+            headers: vec![],
+            status_code_mappings: vec![
+                mapping(StatusCode::NOT_FOUND, StatusCode::OK, "path == '/health'"),
+                mapping(StatusCode::NOT_FOUND, StatusCode::GONE, "path starts_with '/api'"),
+                mapping(StatusCode::BAD_GATEWAY, StatusCode::SERVICE_UNAVAILABLE, "method == \"POST\""),
+            ],
+        };
+
+        let transformer = ResponseTransformer::new(config);
+
+        assert_eq!(transformer.transform_status_code(StatusCode::NOT_FOUND, "/health", "GET", &HeaderMap::new()), StatusCode::OK);
+        assert_eq!(transformer.transform_status_code(StatusCode::NOT_FOUND, "/api/users", "GET", &HeaderMap::new()), StatusCode::GONE);
+        assert_eq!(transformer.transform_status_code(StatusCode::NOT_FOUND, "/other", "GET", &HeaderMap::new()), StatusCode::NOT_FOUND);
+        assert_eq!(transformer.transform_status_code(StatusCode::BAD_GATEWAY, "/api", "post", &HeaderMap::new()), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(transformer.transform_status_code(StatusCode::BAD_GATEWAY, "/api", "GET", &HeaderMap::new()), StatusCode::BAD_GATEWAY);
+    }
+
+    #[test]
+    fn test_header_conditions() {
+        let mut headers = HeaderMap::new();
+        headers.append(HeaderName::from_static("x-env"), HeaderValue::from_static("prod"));
+        headers.append(HeaderName::from_static("x-env"), HeaderValue::from_static("staging-eu"));
+
+        let matches = |expr: &str| Condition::parse(expr).unwrap().matches("/", "GET", &headers);
+        assert!(matches("header.X-Env == 'prod'"));
+        assert!(matches("header.x-env starts_with 'staging'"));
+        assert!(!matches("header.X-Env == 'staging'"));
+        assert!(!matches("header.X-Other == 'prod'"));
+        assert!(matches("has_header 'X-ENV'"));
+        assert!(!matches("has_header 'X-Debug'"));
+    }
+
+    #[test]
+    fn test_malformed_conditions_never_match() {
+        for expr in [
+            "",
+            "path",
+            "path == /health",
+            "path == '/health",
+            "path == '/a' and method == 'GET'",
+            "path = '/health'",
+            "path starts_with'/api'",
+            "path starts_withx '/api'",
+            "host == 'example.com'",
+            "path == '/a'b'",
+            "header. == 'x'",
+            "header.X Env == 'x'",
+            "has_header X-Debug",
+            "has_header 'X Debug'",
+        ] {
+            assert_eq!(Condition::parse(expr), None, "{expr:?} should not parse");
+        }
+
+        let config = ResponseTransformation {
+            headers: vec![],
+            status_code_mappings: vec![StatusCodeMapping {
+                from: StatusCode::NOT_FOUND,
+                to: StatusCode::OK,
+                condition: Some("path ~= '/health'".to_string()),
+            }],
+        };
+        let transformer = ResponseTransformer::new(config);
+        assert_eq!(transformer.transform_status_code(StatusCode::NOT_FOUND, "/health", "GET", &HeaderMap::new()), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_query_param_replace() {
+        let config = RequestTransformation {
+            headers: vec![],
+            path: None,
+            query_params: vec![
+                QueryTransformation {
+                    action: TransformAction::Replace,
+                    name: "v".to_string(),
+                    value: None,
+                    pattern: Some(r"^(\d+)\.(\d+)$".to_string()),
+                    replacement: Some("$1-$2".to_string()),
+                },
+                QueryTransformation {
+                    action: TransformAction::Replace,
+                    name: "missing".to_string(),
+                    value: None,
+                    pattern: Some(".*".to_string()),
+                    replacement: Some("added".to_string()),
+                },
+            ],
+            when: None,
+            body: None,
+        };
+
+        let transformer = RequestTransformer::new(config);
+        let mut params = HashMap::new();
+        params.insert("v".to_string(), "2.1".to_string());
+
+        transformer.transform_query_params(&mut params);
+
+        assert_eq!(params.get("v").map(String::as_str), Some("2-1"));
+        assert!(!params.contains_key("missing"));
+    }
 }
@@ -1,6 +1,11 @@
 use actix_web::HttpResponse;
 use serde_json::json;
 
+use crate::models::settings::{EmptyPoolStatus, ErrorFormat};
+
+/// Seconds clients are asked to wait before retrying a route with no backends.
+const EMPTY_POOL_RETRY_AFTER_SECS: u64 = 5;
+
 /// Error types for the kairos-rs API gateway.
 /// 
 /// This enum represents all possible error conditions that can occur during
@@ -12,10 +17,20 @@ use serde_json::json;
 /// 
 /// - **Timeout**: Request processing exceeded configured time limits
 /// - **Config**: Route configuration validation or parsing errors  
-/// - **Upstream**: Errors from target services (connection, HTTP errors)
+/// - **Upstream**: Errors from target services (HTTP errors)
+/// - **Connection**: The gateway could not connect to the target service
+/// - **NoBackends**: The route's backend pool is empty
 /// - **RouteNotFound**: No matching route configuration found
 /// - **MethodNotAllowed**: HTTP method not allowed for the matched route
 /// - **BadRequest**: Client request validation failures
+/// - **Unauthorized**: Request failed the route's signature verification
+/// - **PayloadTooLarge**: Request body exceeds the route's size limit
+/// - **WarmingUp**: Gateway has not finished its startup warmup
+/// - **Overloaded**: Gateway is at its concurrent request limit
+/// - **RateLimited**: Client exceeded its rate limit
+/// - **ConnectionLimited**: Client IP is at its concurrent request limit
+/// - **FaultInjected**: Request aborted by the route's chaos testing faults
+/// - **Internal**: Request handling panicked
 /// 
 /// # Examples
 /// 
@@ -23,7 +38,7 @@ use serde_json::json;
 /// use kairos_rs::models::error::GatewayError;
 /// 
 /// // Create a timeout error
-/// let error = GatewayError::Timeout { timeout: 30 };
+/// let error = GatewayError::Timeout { timeout: 30, message: None };
 /// 
 /// // Create an upstream service error
 /// let error = GatewayError::Upstream {
@@ -41,7 +56,9 @@ pub enum GatewayError {
     #[error("Request timeout after {timeout}s")]
     Timeout { 
         /// Timeout duration in seconds that was exceeded
-        timeout: u64 
+        timeout: u64,
+        /// Configured client-facing message replacing the default one
+        message: Option<String>,
     },
     
     /// Route configuration is invalid or malformed.
@@ -70,6 +87,31 @@ pub enum GatewayError {
         status: Option<u16>,
     },
     
+    /// The gateway could not establish a connection to the upstream service.
+    ///
+    /// Kept apart from other upstream errors so refused connections and
+    /// unreachable hosts can be alerted on separately.
+    #[error("Could not connect to upstream service: {message} (url: {url})")]
+    Connection {
+        /// Detailed error message from the HTTP client
+        message: String,
+        /// The target URL that could not be reached
+        url: String,
+    },
+
+    /// The route has no backends to forward the request to.
+    ///
+    /// Happens at runtime when a route's pool is drained, for example when
+    /// DNS discovery resolves to no addresses. The status is configurable
+    /// through `errors.empty_pool_status`.
+    #[error("No backends available for route: {route}")]
+    NoBackends {
+        /// The route pattern whose pool is empty
+        route: String,
+        /// Status returned to the client
+        status: EmptyPoolStatus,
+    },
+
     /// No route configuration matches the requested path.
     /// 
     /// This occurs when a client requests a path that doesn't match any
@@ -97,12 +139,21 @@ pub enum GatewayError {
     /// This covers various client-side errors such as malformed requests,
     /// invalid headers, or other request validation failures.
     #[error("Invalid request: {reason}")]
-    #[allow(dead_code)] // Available for request validation features
     BadRequest { 
         /// Specific reason why the request was invalid
         reason: String 
     },
 
+    /// The request failed the route's webhook signature verification.
+    ///
+    /// Raised before anything is forwarded, so forged webhooks never reach
+    /// the backend.
+    #[error("Unauthorized: {reason}")]
+    Unauthorized {
+        /// Why the request was rejected, without secret material
+        reason: String
+    },
+
     /// Circuit breaker is open, protecting upstream service.
     /// 
     /// This occurs when an upstream service has experienced too many failures
@@ -112,9 +163,117 @@ pub enum GatewayError {
         /// The upstream service identifier (host:port)
         service: String
     },
+
+    /// Request body exceeds the route's configured size limit.
+    /// 
+    /// This occurs when a route sets `max_body_bytes` and the client sends
+    /// a larger body. The request is rejected before reaching any backend.
+    #[error("Request body of {size} bytes exceeds limit of {limit} bytes")]
+    PayloadTooLarge {
+        /// Size of the received body in bytes
+        size: usize,
+        /// Configured limit in bytes
+        limit: usize,
+    },
+
+    /// The gateway is still warming up and not yet forwarding traffic.
+    ///
+    /// Returned for proxy routes until readiness flips, so clients retry
+    /// instead of hitting cold backends.
+    #[error("Gateway is warming up, retry after {retry_after}s")]
+    WarmingUp {
+        /// Suggested delay in seconds before retrying
+        retry_after: u64,
+    },
+
+    /// The gateway is at `max_concurrent_requests` and shed the request.
+    ///
+    /// Returned right away instead of queueing, so clients back off rather
+    /// than time out.
+    #[error("Gateway is overloaded, retry after {retry_after}s")]
+    Overloaded {
+        /// Suggested delay in seconds before retrying
+        retry_after: u64,
+    },
+
+    /// The client exceeded its rate limit.
+    ///
+    /// Raised by the rate limiting middleware before the request is routed.
+    #[error("Rate limit exceeded, retry after {retry_after}s")]
+    RateLimited {
+        /// Suggested delay in seconds before retrying
+        retry_after: u64,
+    },
+
+    /// The client IP is at `max_connections_per_ip` and the request was
+    /// rejected.
+    ///
+    /// Other clients are still served; only this client has to back off.
+    #[error("Too many concurrent requests from this client, retry after {retry_after}s")]
+    ConnectionLimited {
+        /// Suggested delay in seconds before retrying
+        retry_after: u64,
+    },
+
+    /// The request was aborted by the route's fault injection.
+    ///
+    /// Only happens on routes with `fault_injection` while chaos testing is
+    /// enabled; the request never reaches a backend.
+    #[error("Request aborted by fault injection on {path}")]
+    FaultInjected {
+        /// The requested path
+        path: String,
+        /// The route's configured `abort_status`
+        status: u16,
+    },
+
+    /// The gateway failed unexpectedly while handling the request.
+    ///
+    /// Raised when request handling panics. The panic message is logged
+    /// under `request_id` and kept out of the response.
+    #[error("Internal gateway error: {message} (request: {request_id})")]
+    Internal {
+        /// Description of the failure, for logs only
+        message: String,
+        /// Request ID reported to the client and in the log
+        request_id: String,
+    },
 }
 
 impl actix_web::error::ResponseError for GatewayError {
+    /// Maps each error variant to its HTTP status code.
+    ///
+    /// Overriding this keeps the status visible to code that only holds an
+    /// `actix_web::Error`, such as metrics recording.
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        use actix_web::http::StatusCode;
+
+        match self {
+            GatewayError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            GatewayError::Config { .. } => StatusCode::BAD_GATEWAY,
+            GatewayError::Upstream { .. } => StatusCode::BAD_GATEWAY,
+            GatewayError::Connection { .. } => StatusCode::BAD_GATEWAY,
+            GatewayError::NoBackends { status, .. } => match status {
+                EmptyPoolStatus::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+                EmptyPoolStatus::BadGateway => StatusCode::BAD_GATEWAY,
+            },
+            GatewayError::RouteNotFound { .. } => StatusCode::NOT_FOUND,
+            GatewayError::MethodNotAllowed { .. } => StatusCode::METHOD_NOT_ALLOWED,
+            GatewayError::BadRequest { .. } => StatusCode::BAD_REQUEST,
+            GatewayError::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            GatewayError::CircuitOpen { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            GatewayError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            GatewayError::WarmingUp { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            GatewayError::Overloaded { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            GatewayError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            GatewayError::ConnectionLimited { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            GatewayError::FaultInjected { status, .. } => {
+                StatusCode::from_u16(*status).unwrap_or(StatusCode::SERVICE_UNAVAILABLE)
+            }
+            GatewayError::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
     /// Converts a `GatewayError` into an appropriate HTTP response.
     /// 
     /// This implementation provides structured error responses with consistent
@@ -123,17 +282,27 @@ impl actix_web::error::ResponseError for GatewayError {
     /// - Structured JSON error message
     /// - Error type classification
     /// - RFC3339 timestamp
-    /// - Unique request ID for tracing
+    /// - Unique request ID for tracing, also sent as the `X-Request-ID` header
     /// 
     /// # HTTP Status Code Mapping
     /// 
     /// - `Timeout` → 504 Gateway Timeout
     /// - `Config` → 502 Bad Gateway  
     /// - `Upstream` → 502 Bad Gateway
+    /// - `Connection` → 502 Bad Gateway
+    /// - `NoBackends` → 503 Service Unavailable (with `Retry-After`) or 502 Bad Gateway
     /// - `CircuitOpen` → 503 Service Unavailable
     /// - `RouteNotFound` → 404 Not Found
     /// - `MethodNotAllowed` → 405 Method Not Allowed
     /// - `BadRequest` → 400 Bad Request
+    /// - `Unauthorized` → 401 Unauthorized
+    /// - `PayloadTooLarge` → 413 Payload Too Large
+    /// - `WarmingUp` → 503 Service Unavailable (with `Retry-After`)
+    /// - `Overloaded` → 503 Service Unavailable (with `Retry-After`)
+    /// - `RateLimited` → 429 Too Many Requests (with `Retry-After`)
+    /// - `ConnectionLimited` → 503 Service Unavailable (with `Retry-After`)
+    /// - `FaultInjected` → the route's `abort_status` (503 by default)
+    /// - `Internal` → 500 Internal Server Error
     /// 
     /// # Response Format
     /// 
@@ -157,50 +326,164 @@ impl actix_web::error::ResponseError for GatewayError {
     ///   "request_id": "550e8400-e29b-41d4-a716-446655440000"
     /// }
     /// ```
+    /// 
+    /// The same error as `application/problem+json`, as rendered by
+    /// [`GatewayError::to_response`] with [`ErrorFormat::ProblemDetails`]:
+    /// ```json
+    /// {
+    ///   "type": "urn:kairos-rs:error:timeout",
+    ///   "title": "Gateway Timeout",
+    ///   "status": 504,
+    ///   "detail": "Request timeout after 30s",
+    ///   "instance": "urn:uuid:550e8400-e29b-41d4-a716-446655440000",
+    ///   "timestamp": "2024-03-15T10:30:00Z"
+    /// }
+    /// ```
     fn error_response(&self) -> HttpResponse {
-        let (status, error_type, error_message) = match self {
-            GatewayError::Timeout { timeout } => (
-                actix_web::http::StatusCode::GATEWAY_TIMEOUT,
+        self.to_response(ErrorFormat::Json)
+    }
+}
+
+impl GatewayError {
+    /// Renders the error response with its body in `format`.
+    ///
+    /// [`ResponseError::error_response`](actix_web::ResponseError::error_response)
+    /// always uses [`ErrorFormat::Json`]; the
+    /// [`ErrorFormatting`](crate::middleware::error_format::ErrorFormatting)
+    /// middleware re-renders gateway errors with the app's configured format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kairos_rs::models::error::GatewayError;
+    /// use kairos_rs::models::settings::ErrorFormat;
+    ///
+    /// let error = GatewayError::RouteNotFound { path: "/missing".to_string() };
+    /// let response = error.to_response(ErrorFormat::ProblemDetails);
+    /// assert_eq!(response.status(), 404);
+    /// assert_eq!(
+    ///     response.headers().get("Content-Type").unwrap(),
+    ///     "application/problem+json"
+    /// );
+    /// ```
+    pub fn to_response(&self, format: ErrorFormat) -> HttpResponse {
+        use actix_web::ResponseError;
+
+        let status = self.status_code();
+        let (error_type, error_message) = match self {
+            GatewayError::Timeout { timeout, message } => (
                 "timeout",
-                format!("Request timeout after {}s", timeout)
+                message
+                    .clone()
+                    .unwrap_or_else(|| format!("Request timeout after {}s", timeout))
             ),
             GatewayError::Config { message, route } => (
-                actix_web::http::StatusCode::BAD_GATEWAY,
                 "config",
                 format!("Configuration error for route {}: {}", route, message)
             ),
             GatewayError::Upstream { message, url, status } => (
-                actix_web::http::StatusCode::BAD_GATEWAY,
                 "upstream",
                 format!("Upstream error for {}: {} (status: {:?})", url, message, status)
             ),
+            GatewayError::Connection { message, url } => (
+                "connection",
+                format!("Could not connect to upstream {}: {}", url, message)
+            ),
+            GatewayError::NoBackends { route, .. } => (
+                "no_backends",
+                format!("No backends are currently available for route {}", route)
+            ),
             GatewayError::RouteNotFound { path } => (
-                actix_web::http::StatusCode::NOT_FOUND,
                 "route_not_found",
                 format!("No route found for path: {}", path)
             ),
             GatewayError::MethodNotAllowed { method, path } => (
-                actix_web::http::StatusCode::METHOD_NOT_ALLOWED,
                 "method_not_allowed",
                 format!("Method {} not allowed for path: {}", method, path)
             ),
             GatewayError::BadRequest { reason } => (
-                actix_web::http::StatusCode::BAD_REQUEST,
                 "bad_request",
                 reason.clone()
             ),
+            GatewayError::Unauthorized { reason } => (
+                "unauthorized",
+                reason.clone()
+            ),
             GatewayError::CircuitOpen { service } => (
-                actix_web::http::StatusCode::SERVICE_UNAVAILABLE,
                 "circuit_open",
                 format!("Service {} is currently unavailable (circuit breaker open)", service)
             ),
+            GatewayError::PayloadTooLarge { size, limit } => (
+                "payload_too_large",
+                format!("Request body of {} bytes exceeds limit of {} bytes", size, limit)
+            ),
+            GatewayError::WarmingUp { retry_after } => (
+                "warming_up",
+                format!("Gateway is warming up, retry after {}s", retry_after)
+            ),
+            GatewayError::Overloaded { retry_after } => (
+                "overloaded",
+                format!("Gateway is overloaded, retry after {}s", retry_after)
+            ),
+            GatewayError::RateLimited { retry_after } => (
+                "rate_limited",
+                format!("Rate limit exceeded, retry after {}s", retry_after)
+            ),
+            GatewayError::ConnectionLimited { retry_after } => (
+                "connection_limited",
+                format!("Too many concurrent requests from this client, retry after {}s", retry_after)
+            ),
+            GatewayError::FaultInjected { .. } => (
+                "fault_injected",
+                status.canonical_reason().unwrap_or("Injected fault").to_string()
+            ),
+            GatewayError::Internal { .. } => (
+                "internal",
+                "Internal gateway error".to_string()
+            ),
+        };
+        
+        let request_id = match self {
+            GatewayError::Internal { request_id, .. } => request_id.clone(),
+            _ => uuid::Uuid::new_v4().to_string(),
         };
+        let mut builder = HttpResponse::build(status);
+        builder.insert_header(("X-Request-ID", request_id.as_str()));
+        if let GatewayError::WarmingUp { retry_after }
+        | GatewayError::Overloaded { retry_after }
+        | GatewayError::RateLimited { retry_after }
+        | GatewayError::ConnectionLimited { retry_after } = self
+        {
+            builder.insert_header((actix_web::http::header::RETRY_AFTER, retry_after.to_string()));
+        }
+        if let GatewayError::NoBackends { status: EmptyPoolStatus::ServiceUnavailable, .. } = self {
+            builder.insert_header((
+                actix_web::http::header::RETRY_AFTER,
+                EMPTY_POOL_RETRY_AFTER_SECS.to_string(),
+            ));
+        }
         
-        HttpResponse::build(status).json(json!({
-            "error": error_message,
-            "type": error_type,
-            "timestamp": chrono::Utc::now().to_rfc3339(),
-            "request_id": uuid::Uuid::new_v4().to_string()
-        }))
+        let timestamp = chrono::Utc::now().to_rfc3339();
+        match format {
+            ErrorFormat::Json => builder.json(json!({
+                "error": error_message,
+                "type": error_type,
+                "timestamp": timestamp,
+                "request_id": request_id
+            })),
+            ErrorFormat::ProblemDetails => builder
+                .content_type("application/problem+json")
+                .body(
+                    json!({
+                        "type": format!("urn:kairos-rs:error:{}", error_type),
+                        "title": status.canonical_reason().unwrap_or("Error"),
+                        "status": status.as_u16(),
+                        "detail": error_message,
+                        "instance": format!("urn:uuid:{}", request_id),
+                        "timestamp": timestamp
+                    })
+                    .to_string(),
+                ),
+        }
     }
 }
@@ -22,7 +22,7 @@
 //! # Examples
 //! 
 //! ```rust
-//! use kairos_rs::models::{router::{Router, Backend, Protocol}, settings::Settings, error::GatewayError};
+//! use kairos_rs::models::{router::{Router, Backend}, settings::Settings, error::GatewayError};
 //! 
 //! // Create a route configuration
 //! let route = Router {
@@ -31,19 +31,14 @@
 //!     external_path: "/api/users/{id}".to_string(),
 //!     internal_path: "/v1/user/{id}".to_string(),
 //!     methods: vec!["GET".to_string(), "PUT".to_string()],
-//!     auth_required: false,
 //!     backends: Some(vec![Backend {
 //!         host: "http://backend".to_string(),
 //!         port: 8080,
 //!         weight: 1,
 //!         health_check_path: None,
+//!         timeout_secs: None,
 //!     }]),
-//!     load_balancing_strategy: Default::default(),
-//!     retry: None,
-//!     protocol: Protocol::Http,
-//!     request_transformation: None,
-//!     response_transformation: None,
-//!     ai_policy: None,
+//!     ..Default::default()
 //! };
 //! 
 //! // Validate the configuration
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 use crate::middleware::transform::{RequestTransformation, ResponseTransformation};
+use crate::utils::route_matcher::ParamEncoding;
 
 /// Protocol type for the gateway route.
 /// 
@@ -12,11 +15,12 @@ use crate::middleware::transform::{RequestTransformation, ResponseTransformation
 /// - **WebSocket**: Bidirectional, real-time communication protocol
 /// - **FTP**: File Transfer Protocol for file operations
 /// - **DNS**: Domain Name System for DNS query forwarding
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Protocol {
     /// HTTP/HTTPS protocol (default).
     /// Used for standard REST APIs and web applications.
+    #[default]
     Http,
     
     /// WebSocket protocol.
@@ -32,12 +36,6 @@ pub enum Protocol {
     Dns,
 }
 
-impl Default for Protocol {
-    fn default() -> Self {
-        Self::Http
-    }
-}
-
 impl std::fmt::Display for Protocol {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -62,12 +60,15 @@ impl std::fmt::Display for Protocol {
 /// - **Random**: Randomly selects a backend server
 /// - **Weighted**: Distributes based on configured weights
 /// - **IPHash**: Routes based on client IP hash (sticky sessions)
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// - **LeastLatency**: Routes to the backend with the lowest average response time
+/// - **ConsistentHash**: Routes requests with the same key to the same backend
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum LoadBalancingStrategy {
     /// Round-robin load balancing (default).
     /// Distributes requests evenly across all backends in order.
     /// Best for: Backends with similar capacity
+    #[default]
     RoundRobin,
     
     /// Routes to backend with fewest active connections.
@@ -85,12 +86,41 @@ pub enum LoadBalancingStrategy {
     /// Hash-based routing using client IP for session persistence.
     /// Best for: Applications requiring sticky sessions
     IpHash,
+
+    /// Routes to the backend with the lowest moving average response time.
+    /// Best for: Backends whose latency differs or changes over time
+    LeastLatency,
+
+    /// Hash ring routing on a key taken from each request, so adding or
+    /// removing a backend only moves about 1/N of the keys.
+    /// Configured as `{"consistent_hash": {"header": "X-Tenant-Id"}}`.
+    /// Best for: Backends that cache data per tenant or entity
+    ConsistentHash(HashKey),
 }
 
-impl Default for LoadBalancingStrategy {
-    fn default() -> Self {
-        Self::RoundRobin
-    }
+/// Where the [`LoadBalancingStrategy::ConsistentHash`] key of a request
+/// comes from.
+///
+/// Requests without the key are hashed by client IP instead.
+///
+/// # Examples
+///
+/// ```json
+/// { "header": "X-Tenant-Id" }
+/// ```
+///
+/// ```json
+/// { "path_param": "tenant" }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HashKey {
+    /// Value of a request header.
+    Header(String),
+
+    /// Value of a parameter of the route's external path, e.g. `tenant` in
+    /// `/api/{tenant}/orders`.
+    PathParam(String),
 }
 
 /// Backend server configuration for a route.
@@ -105,10 +135,11 @@ impl Default for LoadBalancingStrategy {
 ///   "host": "http://backend-1.example.com",
 ///   "port": 8080,
 ///   "weight": 2,
-///   "health_check_path": "/health"
+///   "health_check_path": "/health",
+///   "timeout_secs": 10
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Backend {
     /// Target host URL including protocol (http:// or https://).
     pub host: String,
@@ -126,6 +157,12 @@ pub struct Backend {
     /// If specified, the gateway will periodically check this endpoint.
     #[serde(default)]
     pub health_check_path: Option<String>,
+    
+    /// Optional upstream request timeout in seconds for this backend.
+    /// When set, it overrides the gateway-wide timeout whenever this backend
+    /// is selected, so slow archival backends can share a route with fast ones.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
 }
 
 fn default_weight() -> u32 {
@@ -154,6 +191,10 @@ impl Backend {
             return Err("Backend weight must be greater than 0".to_string());
         }
         
+        if self.timeout_secs == Some(0) {
+            return Err("Backend timeout_secs must be greater than 0".to_string());
+        }
+        
         Ok(())
     }
 }
@@ -240,8 +281,28 @@ pub struct RetryConfig {
     /// Whether to retry on network/connection errors (default: true).
     #[serde(default = "default_retry_on_connection_error")]
     pub retry_on_connection_error: bool,
+
+    /// Retries allowed per request forwarded to an upstream, e.g. `0.1` for
+    /// one retry per ten requests (default: unlimited).
+    ///
+    /// Each upstream (`host:port`) has a retry budget refilled by this ratio
+    /// for every request. Retries are only made while the budget has tokens;
+    /// otherwise the first failure is returned as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_budget_ratio: Option<f64>,
+
+    /// Whether requests with non-idempotent methods such as `POST` and
+    /// `PATCH` are retried too (default: false).
+    ///
+    /// Only enable this for backends that deduplicate requests, e.g. by an
+    /// idempotency key; otherwise a retry can apply the operation twice.
+    #[serde(default)]
+    pub retry_non_idempotent: bool,
 }
 
+/// Methods retried without `retry_non_idempotent`.
+const IDEMPOTENT_METHODS: &[&str] = &["GET", "HEAD", "PUT", "DELETE", "OPTIONS"];
+
 fn default_max_retries() -> u32 {
     3
 }
@@ -275,11 +336,18 @@ impl Default for RetryConfig {
             backoff_multiplier: default_backoff_multiplier(),
             retry_on_status_codes: default_retry_status_codes(),
             retry_on_connection_error: default_retry_on_connection_error(),
+            retry_budget_ratio: None,
+            retry_non_idempotent: false,
         }
     }
 }
 
 impl RetryConfig {
+    /// Returns whether requests with `method` may be retried.
+    pub fn retries_method(&self, method: &str) -> bool {
+        self.retry_non_idempotent || IDEMPOTENT_METHODS.contains(&method)
+    }
+
     /// Validates retry configuration.
     pub fn validate(&self) -> Result<(), String> {
         if self.max_retries > 10 {
@@ -293,6 +361,12 @@ impl RetryConfig {
         if self.backoff_multiplier < 1.0 {
             return Err("backoff_multiplier must be >= 1.0".to_string());
         }
+
+        if let Some(ratio) = self.retry_budget_ratio {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err("retry_budget_ratio must be between 0.0 and 1.0".to_string());
+            }
+        }
         
         Ok(())
     }
@@ -325,6 +399,8 @@ impl RetryConfig {
     ///     backoff_multiplier: 2.0,
     ///     retry_on_status_codes: vec![502, 503, 504],
     ///     retry_on_connection_error: true,
+    ///     retry_budget_ratio: None,
+    ///     retry_non_idempotent: false,
     /// };
     /// 
     /// assert_eq!(config.calculate_backoff(0), 100);   // 100 * 2^0
@@ -333,8 +409,10 @@ impl RetryConfig {
     /// assert_eq!(config.calculate_backoff(3), 800);   // 100 * 2^3
     /// ```
     pub fn calculate_backoff(&self, attempt: u32) -> u64 {
+        // Saturate instead of wrapping to a negative exponent
+        let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
         let backoff = (self.initial_backoff_ms as f64) 
-            * self.backoff_multiplier.powi(attempt as i32);
+            * self.backoff_multiplier.powi(exponent);
         backoff.min(self.max_backoff_ms as f64) as u64
     }
 }
@@ -373,6 +451,811 @@ pub struct AiPolicy {
     pub fallback_backend_index: Option<usize>,
 }
 
+/// Chaos testing faults injected into a route's traffic.
+///
+/// Before forwarding, the connection of a request is dropped without a
+/// response with probability `drop_rate`, and the remaining requests are
+/// aborted with `abort_status` with probability `abort_rate`; requests that
+/// are forwarded are delayed by `delay_ms` with probability `delay_rate`.
+/// Faults are only injected when the gateway-wide `fault_injection_enabled`
+/// flag is set.
+///
+/// # Examples
+///
+/// Fail one request in ten with `500` and slow down a quarter of the rest by 500ms:
+/// ```json
+/// {
+///   "abort_rate": 0.1,
+///   "abort_status": 500,
+///   "delay_ms": 500,
+///   "delay_rate": 0.25
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FaultInjection {
+    /// Fraction of requests answered with `abort_status` without reaching a backend (default: 0.0).
+    #[serde(default)]
+    pub abort_rate: f64,
+
+    /// Status of aborted requests (default: 503).
+    #[serde(default = "default_abort_status")]
+    pub abort_status: u16,
+
+    /// Fraction of requests whose connection is closed without a response (default: 0.0).
+    #[serde(default)]
+    pub drop_rate: f64,
+
+    /// Delay in milliseconds added before forwarding a delayed request (default: 0).
+    #[serde(default)]
+    pub delay_ms: u64,
+
+    /// Fraction of forwarded requests that are delayed by `delay_ms` (default: 0.0).
+    #[serde(default)]
+    pub delay_rate: f64,
+}
+
+fn default_abort_status() -> u16 {
+    503
+}
+
+impl Default for FaultInjection {
+    fn default() -> Self {
+        Self {
+            abort_rate: 0.0,
+            abort_status: default_abort_status(),
+            drop_rate: 0.0,
+            delay_ms: 0,
+            delay_rate: 0.0,
+        }
+    }
+}
+
+impl FaultInjection {
+    /// Validates that all rates are between 0.0 and 1.0 and that
+    /// `abort_status` is an error status.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.abort_rate) {
+            return Err("abort_rate must be between 0.0 and 1.0".to_string());
+        }
+        if !(400..=599).contains(&self.abort_status) {
+            return Err(format!(
+                "abort_status must be between 400 and 599, got {}",
+                self.abort_status
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.drop_rate) {
+            return Err("drop_rate must be between 0.0 and 1.0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.delay_rate) {
+            return Err("delay_rate must be between 0.0 and 1.0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Background health checking of a route's backends.
+///
+/// Every backend with a `health_check_path` is requested each `interval_secs`.
+/// A backend is taken out of rotation after `unhealthy_threshold` consecutive
+/// failed probes and put back after `healthy_threshold` consecutive successful
+/// ones. A probe succeeds when a 2xx response arrives within `timeout_secs`,
+/// or, if `expected_status` or `expected_body_contains` are set, a response
+/// matching both.
+///
+/// # Examples
+///
+/// ```json
+/// {
+///   "interval_secs": 5,
+///   "timeout_secs": 2,
+///   "unhealthy_threshold": 3,
+///   "healthy_threshold": 2,
+///   "expected_status": 200,
+///   "expected_body_contains": "\"status\":\"ok\""
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HealthCheckSettings {
+    /// Seconds between probes (default: 10).
+    #[serde(default = "default_health_check_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Seconds a probe waits for a response (default: 2).
+    #[serde(default = "default_health_check_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Consecutive failed probes that mark a backend down (default: 3).
+    #[serde(default = "default_unhealthy_threshold")]
+    pub unhealthy_threshold: u32,
+
+    /// Consecutive successful probes that mark a backend up again (default: 2).
+    #[serde(default = "default_healthy_threshold")]
+    pub healthy_threshold: u32,
+
+    /// Status a probe response must have, instead of any 2xx.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_status: Option<u16>,
+
+    /// Text the probe response body must contain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_body_contains: Option<String>,
+}
+
+fn default_health_check_interval_secs() -> u64 {
+    10
+}
+
+fn default_health_check_timeout_secs() -> u64 {
+    2
+}
+
+fn default_unhealthy_threshold() -> u32 {
+    3
+}
+
+fn default_healthy_threshold() -> u32 {
+    2
+}
+
+impl Default for HealthCheckSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_health_check_interval_secs(),
+            timeout_secs: default_health_check_timeout_secs(),
+            unhealthy_threshold: default_unhealthy_threshold(),
+            healthy_threshold: default_healthy_threshold(),
+            expected_status: None,
+            expected_body_contains: None,
+        }
+    }
+}
+
+impl HealthCheckSettings {
+    /// Validates that the interval, timeout and thresholds are non-zero, that
+    /// `expected_status` is a valid HTTP status and that
+    /// `expected_body_contains` is not empty.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_secs == 0 {
+            return Err("interval_secs must be greater than 0".to_string());
+        }
+
+        if self.timeout_secs == 0 {
+            return Err("timeout_secs must be greater than 0".to_string());
+        }
+
+        if self.unhealthy_threshold == 0 {
+            return Err("unhealthy_threshold must be greater than 0".to_string());
+        }
+
+        if self.healthy_threshold == 0 {
+            return Err("healthy_threshold must be greater than 0".to_string());
+        }
+
+        if let Some(status) = self.expected_status {
+            if !(100..=599).contains(&status) {
+                return Err(format!("expected_status {} is not a valid HTTP status", status));
+            }
+        }
+
+        if self.expected_body_contains.as_deref() == Some("") {
+            return Err("expected_body_contains must not be empty".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Header telling a backend how long the gateway will wait for it.
+///
+/// Added to every forwarded request so backends that honor deadlines can
+/// give up on work the gateway would discard after timing out. The value is
+/// derived from the attempt's timeout (the backend's `timeout_secs`, or the
+/// gateway default).
+///
+/// # Examples
+///
+/// ```json
+/// { "name": "grpc-timeout", "format": "grpc_timeout" }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeadlineHeader {
+    /// Header name, e.g. `X-Request-Timeout-Ms`.
+    pub name: String,
+
+    /// How the remaining time is written (default: milliseconds).
+    #[serde(default)]
+    pub format: DeadlineFormat,
+}
+
+/// Value format of a [`DeadlineHeader`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DeadlineFormat {
+    /// Remaining milliseconds, e.g. `30000`.
+    #[default]
+    Milliseconds,
+    /// gRPC `grpc-timeout` syntax, e.g. `30000m`.
+    GrpcTimeout,
+    /// Absolute deadline in milliseconds since the Unix epoch.
+    UnixMillis,
+}
+
+impl DeadlineHeader {
+    /// Validates that the header name is a valid HTTP header name.
+    pub fn validate(&self) -> Result<(), String> {
+        actix_web::http::header::HeaderName::from_bytes(self.name.as_bytes())
+            .map(|_| ())
+            .map_err(|_| format!("invalid header name '{}'", self.name))
+    }
+
+    /// Formats `remaining` as this header's value.
+    ///
+    /// ```rust
+    /// use kairos_rs::models::router::{DeadlineFormat, DeadlineHeader};
+    /// use std::time::Duration;
+    ///
+    /// let header = DeadlineHeader {
+    ///     name: "grpc-timeout".to_string(),
+    ///     format: DeadlineFormat::GrpcTimeout,
+    /// };
+    /// assert_eq!(header.value(Duration::from_secs(30)), "30000m");
+    /// ```
+    pub fn value(&self, remaining: std::time::Duration) -> String {
+        match self.format {
+            DeadlineFormat::Milliseconds => remaining.as_millis().to_string(),
+            DeadlineFormat::GrpcTimeout => {
+                // gRPC allows at most eight digits per value
+                let millis = remaining.as_millis();
+                if millis < 100_000_000 {
+                    format!("{}m", millis)
+                } else {
+                    format!("{}S", remaining.as_secs().min(99_999_999))
+                }
+            }
+            DeadlineFormat::UnixMillis => (std::time::SystemTime::now() + remaining)
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()
+                .to_string(),
+        }
+    }
+}
+
+/// Header carrying the time the gateway received a request.
+///
+/// Added to forwarded requests with the milliseconds since the Unix epoch at
+/// which the gateway started handling the request, so backends can subtract
+/// it from their own receive time to measure the gateway and network
+/// overhead. Any value sent by the client is replaced.
+///
+/// Configured gateway-wide in the settings and per route; a route's setting
+/// takes precedence, so `{ "enabled": false }` turns the header off for one
+/// route, and a route without a `name` uses the gateway-wide name.
+///
+/// # Examples
+///
+/// ```json
+/// { "name": "X-Request-Start" }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RequestStartHeader {
+    /// Whether the header is added (default: true).
+    #[serde(default = "default_request_start_enabled")]
+    pub enabled: bool,
+
+    /// Header name (default: `X-Request-Start`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+fn default_request_start_enabled() -> bool {
+    true
+}
+
+impl RequestStartHeader {
+    /// Header name used when none is configured.
+    pub const DEFAULT_NAME: &'static str = "X-Request-Start";
+
+    /// Validates that the header name, if any, is a valid HTTP header name.
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.name {
+            Some(name) => actix_web::http::header::HeaderName::from_bytes(name.as_bytes())
+                .map(|_| ())
+                .map_err(|_| format!("invalid header name '{}'", name)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Signature check of webhooks received on a route.
+///
+/// Webhook senders sign the raw request body with a shared secret and send
+/// the HMAC in `header`. The gateway computes the same HMAC over the body as
+/// the client sent it, before any transformation, and answers requests
+/// without a matching signature with `401 Unauthorized` instead of
+/// forwarding them. `${NAME}` in `secret` is replaced with the environment
+/// variable `NAME`, so the secret can be kept out of the configuration.
+///
+/// # Examples
+///
+/// GitHub's `X-Hub-Signature-256: sha256=<hex>`:
+///
+/// ```json
+/// {
+///   "header": "X-Hub-Signature-256",
+///   "algorithm": "sha256",
+///   "secret": "${GITHUB_WEBHOOK_SECRET}",
+///   "prefix": "sha256="
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WebhookVerification {
+    /// Header carrying the signature, e.g. `X-Hub-Signature-256`.
+    pub header: String,
+
+    /// Hash function of the HMAC (default: SHA-256).
+    #[serde(default)]
+    pub algorithm: WebhookAlgorithm,
+
+    /// Shared secret, with `${NAME}` environment variable references.
+    pub secret: String,
+
+    /// Text before the signature in the header value, e.g. `sha256=`
+    /// (default: none).
+    #[serde(default)]
+    pub prefix: String,
+
+    /// How the signature is written (default: hex).
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+}
+
+/// Hash function of a [`WebhookVerification`] HMAC.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookAlgorithm {
+    /// HMAC-SHA1, for senders that have not moved to SHA-256.
+    Sha1,
+    /// HMAC-SHA256.
+    #[default]
+    Sha256,
+    /// HMAC-SHA384.
+    Sha384,
+    /// HMAC-SHA512.
+    Sha512,
+}
+
+/// Encoding of a [`WebhookVerification`] signature.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureEncoding {
+    /// Hexadecimal digits, in either case.
+    #[default]
+    Hex,
+    /// Standard base64 with padding.
+    Base64,
+}
+
+impl WebhookVerification {
+    /// Returns `secret` with its environment variable references replaced.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a referenced variable is unset or the secret is empty.
+    pub fn resolved_secret(&self) -> Result<String, String> {
+        let secret = crate::models::settings::interpolate_env(&self.secret)?;
+        if secret.is_empty() {
+            return Err("secret must not be empty".to_string());
+        }
+        Ok(secret)
+    }
+
+    /// Validates the header name and that the secret resolves.
+    pub fn validate(&self) -> Result<(), String> {
+        actix_web::http::header::HeaderName::from_bytes(self.header.as_bytes())
+            .map_err(|_| format!("invalid header name '{}'", self.header))?;
+        self.resolved_secret().map(|_| ())
+    }
+}
+
+/// Selects a route's backend from a field of the JSON request body.
+///
+/// `field` is a JSONPath made of `.name` and `[index]` steps, such as
+/// `$.event_type` or `$.events[0].type`. When the value at `field` equals one
+/// of the keys of `backends`, the request is sent to the route's backend at
+/// that index. Bodies larger than `max_body_bytes`, bodies that are not JSON
+/// and values without an entry are load balanced as usual.
+///
+/// # Examples
+///
+/// ```json
+/// {
+///   "field": "$.event_type",
+///   "backends": { "payment.succeeded": 0, "invoice.created": 1 },
+///   "max_body_bytes": 65536
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BodyRouting {
+    /// JSONPath of the body field to match, e.g. `$.event_type`.
+    pub field: String,
+
+    /// Field values mapped to the index of the backend handling them.
+    pub backends: HashMap<String, usize>,
+
+    /// Largest body in bytes that is inspected (default: 65536).
+    #[serde(default = "default_body_routing_max_body_bytes")]
+    pub max_body_bytes: usize,
+}
+
+fn default_body_routing_max_body_bytes() -> usize {
+    64 * 1024
+}
+
+/// One step of a [`BodyRouting`] field path.
+enum JsonPathStep<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+/// Splits a JSONPath like `$.events[0].type` into its steps.
+fn parse_json_path(path: &str) -> Result<Vec<JsonPathStep<'_>>, String> {
+    let mut rest = path
+        .strip_prefix('$')
+        .ok_or_else(|| format!("field '{}' must start with '$'", path))?;
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            if end == 0 {
+                return Err(format!("field '{}' has an empty name", path));
+            }
+            steps.push(JsonPathStep::Field(&after_dot[..end]));
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let end = after_bracket
+                .find(']')
+                .ok_or_else(|| format!("field '{}' has an unclosed '['", path))?;
+            let index = after_bracket[..end]
+                .parse()
+                .map_err(|_| format!("field '{}' has an invalid array index", path))?;
+            steps.push(JsonPathStep::Index(index));
+            rest = &after_bracket[end + 1..];
+        } else {
+            return Err(format!(
+                "field '{}' only supports '.name' and '[index]' steps",
+                path
+            ));
+        }
+    }
+    Ok(steps)
+}
+
+impl BodyRouting {
+    /// Validates the field path, the body limit and, when the route's number
+    /// of backends is known, the backend indices.
+    pub fn validate(&self, backend_count: Option<usize>) -> Result<(), String> {
+        parse_json_path(&self.field)?;
+        if self.backends.is_empty() {
+            return Err("backends must map at least one value".to_string());
+        }
+        if self.max_body_bytes == 0 {
+            return Err("max_body_bytes must be greater than 0".to_string());
+        }
+        if let Some(count) = backend_count {
+            if let Some((value, index)) = self.backends.iter().find(|(_, index)| **index >= count) {
+                return Err(format!(
+                    "value '{}' maps to backend {}, but the route has {} backends",
+                    value, index, count
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the index of the backend for `body`, or `None` when the body
+    /// is too large, is not JSON, or its field has no configured backend.
+    ///
+    /// String fields are matched as is; numbers and booleans by their JSON
+    /// text.
+    ///
+    /// ```rust
+    /// use kairos_rs::models::router::BodyRouting;
+    ///
+    /// let routing: BodyRouting = serde_json::from_str(r#"{
+    ///     "field": "$.event.type",
+    ///     "backends": { "refund": 1 }
+    /// }"#).unwrap();
+    /// assert_eq!(routing.backend_index(br#"{"event": {"type": "refund"}}"#), Some(1));
+    /// assert_eq!(routing.backend_index(br#"{"event": {"type": "charge"}}"#), None);
+    /// ```
+    pub fn backend_index(&self, body: &[u8]) -> Option<usize> {
+        if body.len() > self.max_body_bytes {
+            return None;
+        }
+        let steps = parse_json_path(&self.field).ok()?;
+        let document: serde_json::Value = serde_json::from_slice(body).ok()?;
+        let mut value = &document;
+        for step in steps {
+            value = match step {
+                JsonPathStep::Field(name) => value.get(name)?,
+                JsonPathStep::Index(index) => value.get(index)?,
+            };
+        }
+        let key = match value {
+            serde_json::Value::String(text) => text.clone(),
+            serde_json::Value::Number(_) | serde_json::Value::Bool(_) => value.to_string(),
+            _ => return None,
+        };
+        self.backends.get(&key).copied()
+    }
+}
+
+/// Circuit breaker thresholds for a route's backends.
+///
+/// When several routes share a backend, the backend's breaker uses the most
+/// conservative values among them: the lowest failure threshold and
+/// warmup, and the highest success threshold and reset timeout.
+///
+/// # Examples
+///
+/// ```json
+/// {
+///   "failure_threshold": 10,
+///   "success_threshold": 2,
+///   "reset_timeout_secs": 15,
+///   "warmup_secs": 60
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CircuitBreakerSettings {
+    /// Consecutive failures that open the circuit (default: 5).
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u64,
+
+    /// Consecutive half-open successes that close the circuit (default: 3).
+    #[serde(default = "default_success_threshold")]
+    pub success_threshold: u64,
+
+    /// Seconds an open circuit waits before letting test traffic through (default: 30).
+    #[serde(default = "default_reset_timeout_secs")]
+    pub reset_timeout_secs: u64,
+
+    /// Seconds after the breaker is created during which failures are only
+    /// logged, so backends still warming up do not trip it (default: 0).
+    #[serde(default)]
+    pub warmup_secs: u64,
+}
+
+fn default_failure_threshold() -> u64 {
+    5
+}
+
+fn default_success_threshold() -> u64 {
+    3
+}
+
+fn default_reset_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for CircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_failure_threshold(),
+            success_threshold: default_success_threshold(),
+            reset_timeout_secs: default_reset_timeout_secs(),
+            warmup_secs: 0,
+        }
+    }
+}
+
+impl CircuitBreakerSettings {
+    /// Validates that thresholds are non-zero.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.failure_threshold == 0 {
+            return Err("failure_threshold must be greater than 0".to_string());
+        }
+
+        if self.success_threshold == 0 {
+            return Err("success_threshold must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// A static response served by the gateway without contacting a backend.
+///
+/// String bodies are sent as `text/plain`, any other JSON value as
+/// `application/json`. Configured headers are applied last, so they can
+/// override the content type.
+///
+/// # Examples
+///
+/// ```json
+/// {
+///   "status": 200,
+///   "headers": { "Cache-Control": "no-store" },
+///   "body": { "items": [], "degraded": true }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FixedResponse {
+    /// HTTP status code of the response (default: 200).
+    #[serde(default = "default_fixed_response_status")]
+    pub status: u16,
+
+    /// Headers added to the response.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+
+    /// Response body; omitted for an empty body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+fn default_fixed_response_status() -> u16 {
+    200
+}
+
+impl FixedResponse {
+    /// Validates the status code and header names and values.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(100..=599).contains(&self.status) {
+            return Err(format!("Invalid status code: {}", self.status));
+        }
+
+        for (name, value) in &self.headers {
+            actix_web::http::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("Invalid header name: {}", name))?;
+            actix_web::http::header::HeaderValue::from_str(value)
+                .map_err(|_| format!("Invalid value for header {}", name))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// DNS record type resolved for backend discovery.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DnsRecordType {
+    /// A and AAAA records; every address becomes a backend on the configured port (default).
+    #[default]
+    A,
+
+    /// SRV records; each target of the lowest priority becomes a backend
+    /// with the record's port and weight.
+    Srv,
+}
+
+/// DNS-based discovery of a route's backends.
+///
+/// The record is resolved on a background task every `ttl_secs` seconds and the
+/// results replace the route's backends. When a resolution fails, or returns
+/// no records, the previously discovered backends are kept.
+///
+/// # Examples
+///
+/// Pointing at a Kubernetes headless service:
+/// ```json
+/// {
+///   "name": "users.default.svc.cluster.local",
+///   "record_type": "a",
+///   "port": 8080,
+///   "ttl_secs": 15
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DnsDiscovery {
+    /// Record name to resolve, e.g. `_http._tcp.users.default.svc.cluster.local` for SRV.
+    pub name: String,
+
+    /// Record type to resolve (default: `a`).
+    #[serde(default)]
+    pub record_type: DnsRecordType,
+
+    /// Port of the discovered backends. Required for `a` records; SRV
+    /// records carry their own port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    /// URL scheme of the discovered backends, `http` or `https` (default: `http`).
+    #[serde(default = "default_discovery_scheme")]
+    pub scheme: String,
+
+    /// Seconds between resolutions (default: 30).
+    #[serde(default = "default_discovery_ttl_secs")]
+    pub ttl_secs: u64,
+}
+
+fn default_discovery_scheme() -> String {
+    "http".to_string()
+}
+
+fn default_discovery_ttl_secs() -> u64 {
+    30
+}
+
+impl DnsDiscovery {
+    /// Validates the record name, scheme, port and TTL.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Record name must not be empty".to_string());
+        }
+
+        if self.scheme != "http" && self.scheme != "https" {
+            return Err(format!("Scheme must be http or https, got '{}'", self.scheme));
+        }
+
+        match (self.record_type, self.port) {
+            (_, Some(0)) => return Err("Port must be between 1 and 65535".to_string()),
+            (DnsRecordType::A, None) => return Err("Port is required for A records".to_string()),
+            _ => {}
+        }
+
+        if self.ttl_secs == 0 {
+            return Err("ttl_secs must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// How a route reacts to upstream responses that violate its `response_schema`.
+///
+/// Violations are always logged and counted in
+/// `kairos_response_schema_violations_total`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseSchemaMode {
+    /// Report violations and pass the response through unchanged (default).
+    #[default]
+    Shadow,
+
+    /// Reject violating responses with `502 Bad Gateway`.
+    Enforce,
+}
+
+/// Where a route's responses get compressed for the client.
+///
+/// Without a mode, the client's `Accept-Encoding` is forwarded and the
+/// gateway compresses whatever the backend left uncompressed. Picking a mode
+/// makes a single side responsible for compression.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AcceptEncodingMode {
+    /// Ask the backend for uncompressed responses with
+    /// `Accept-Encoding: identity`; the gateway compresses them according to
+    /// the client's `Accept-Encoding`.
+    Strip,
+
+    /// Forward the client's `Accept-Encoding` and relay the backend's
+    /// response as is; the gateway never compresses it.
+    Passthrough,
+}
+
+/// How request paths that differ from a route only by a trailing slash are
+/// matched, e.g. `/users/` for a route with `external_path` `/users`.
+///
+/// A path matching a route exactly is always routed there, so routes with
+/// and without the slash can coexist.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlash {
+    /// Paths must match exactly; the other form is not found.
+    #[default]
+    Strict,
+
+    /// The other form is routed like the configured one.
+    Ignore,
+
+    /// The other form is redirected to the configured one with
+    /// `301 Moved Permanently`.
+    Redirect,
+}
+
 /// Configuration for HTTP route forwarding in the kairos-rs gateway.
 /// 
 /// A `Router` defines how external requests are mapped to internal services,
@@ -412,7 +1295,7 @@ pub struct AiPolicy {
 ///   }
 /// }
 /// ```
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Router {
     /// Legacy: Single target host URL (deprecated, use backends instead).
     /// This is where the gateway will forward matching requests.
@@ -513,6 +1396,118 @@ pub struct Router {
     /// Configures intelligent routing decisions based on content analysis or prediction.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ai_policy: Option<AiPolicy>,
+
+    /// Optional backend that receives a fire-and-forget copy of each request.
+    /// Clients are always served by the primary backends; mirror responses
+    /// and errors are ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mirror_to: Option<Backend>,
+
+    /// Maximum request body size in bytes accepted by this route.
+    /// Larger requests are rejected with `413 Payload Too Large` before being
+    /// forwarded. Cannot raise the gateway-wide payload limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_body_bytes: Option<usize>,
+
+    /// Path to a JSON Schema that upstream response bodies are validated against.
+    /// Responses on routes with a schema are always buffered so they can be checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<PathBuf>,
+
+    /// Whether schema violations are only reported or also rejected.
+    #[serde(default)]
+    pub response_schema_mode: ResponseSchemaMode,
+
+    /// Response served instead of `503 Service Unavailable` when the circuit
+    /// breaker of the selected backend is open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_open_fallback: Option<FixedResponse>,
+
+    /// Circuit breaker tuning for this route's backends.
+    /// If not specified, the gateway defaults (5 failures, 3 successes,
+    /// 30s reset) are used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerSettings>,
+
+    /// Name of a shared backend pool from the top-level `backend_pools`.
+    /// Resolved when the configuration is loaded, replacing any inline
+    /// `backends`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend_pool: Option<String>,
+
+    /// Resolve this route's backends from DNS instead of listing them.
+    /// Replaces any inline `backends` once the first resolution succeeds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_discovery: Option<DnsDiscovery>,
+
+    /// Chaos testing faults injected into this route's traffic. Ignored
+    /// unless the gateway-wide `fault_injection_enabled` flag is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault_injection: Option<FaultInjection>,
+
+    /// Background health checking of this route's backends. Backends that
+    /// keep failing their `health_check_path` are taken out of rotation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheckSettings>,
+
+    /// Honor `X-Accel-Redirect` on upstream responses: the gateway fetches
+    /// the named path from the same backend and serves that response instead.
+    #[serde(default)]
+    pub x_accel_redirect: bool,
+
+    /// Tell backends how long the gateway will wait for them, via a header
+    /// on each forwarded request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_header: Option<DeadlineHeader>,
+
+    /// Pick the backend from a field of the JSON request body instead of
+    /// load balancing, for bodies within the configured size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_routing: Option<BodyRouting>,
+
+    /// Forward request bodies to the backend as they arrive instead of
+    /// buffering them first. Ignored while the route uses a feature that
+    /// needs the whole body; see [`Router::streams_request_body`].
+    #[serde(default)]
+    pub stream_request_body: bool,
+
+    /// Logs a warning for responses larger than this many bytes, counted as
+    /// they are sent to the client (default: disabled).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warn_response_bytes: Option<u64>,
+
+    /// Logs each forwarded request's line, headers, target URL and response
+    /// status at DEBUG, with a capped body snippet and credentials redacted.
+    #[serde(default)]
+    pub debug_logging: bool,
+
+    /// Static metadata such as `team` or `tier`, added as labels to the
+    /// route's per-route metrics and as fields to log lines about its
+    /// requests.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+
+    /// Latency target in milliseconds. Requests taking longer are counted as
+    /// breaches in `kairos_sla_breach_total`, and the share of recent
+    /// requests within it is reported as `kairos_sla_compliance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sla_ms: Option<u64>,
+
+    /// Rejects requests whose body does not carry a valid webhook
+    /// signature. Routes with it always buffer request bodies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_verify: Option<WebhookVerification>,
+
+    /// Whether the backend or the gateway compresses responses. If not
+    /// specified, the backend may compress and the gateway compresses what
+    /// it did not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accept_encoding: Option<AcceptEncodingMode>,
+
+    /// Adds the time the gateway received the request to the forwarded
+    /// request, overriding the gateway-wide `request_start_header`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_start_header: Option<RequestStartHeader>,
 }
 
 impl Router {
@@ -536,22 +1531,15 @@ impl Router {
     /// 
     /// Legacy configuration:
     /// ```rust
-    /// use kairos_rs::models::router::{Router, Protocol};
+    /// use kairos_rs::models::router::Router;
     /// 
     /// let router = Router {
     ///     host: Some("http://localhost".to_string()),
     ///     port: Some(8080),
-    ///     backends: None,
-    ///     load_balancing_strategy: Default::default(),
     ///     external_path: "/api/users".to_string(),
     ///     internal_path: "/v1/users".to_string(),
     ///     methods: vec!["GET".to_string(), "POST".to_string()],
-    ///     auth_required: false,
-    ///     retry: None,
-    ///     protocol: Protocol::Http,
-    ///     request_transformation: None,
-    ///     response_transformation: None,
-    ///     ai_policy: None,
+    ///     ..Default::default()
     /// };
     /// 
     /// assert!(router.validate().is_ok());
@@ -560,14 +1548,28 @@ impl Router {
     /// # Errors
     /// 
     /// This method will return an error if:
-    /// - Neither host/port nor backends are specified
+    /// - None of host/port, backends or `dns_discovery` are specified
+    /// - `backend_pool` names a pool that was not resolved
+    /// - DNS discovery has an empty name, unknown scheme, missing port or zero TTL
     /// - Host doesn't start with `http://` or `https://`
     /// - Port is 0 (ports 1-65535 are valid)
     /// - External or internal path doesn't start with `/`
+    /// - An internal path placeholder has an encoding other than `raw` or `encoded`
     /// - No HTTP methods are specified
     /// - An invalid HTTP method is provided
     /// - Backend validation fails
     /// - Retry configuration is invalid
+    /// - Mirror backend validation fails
+    /// - `max_body_bytes` is 0
+    /// - `warn_response_bytes` is 0
+    /// - `sla_ms` is 0
+    /// - A tag name is not a valid metric label name, or is reserved
+    /// - Circuit-open fallback has an invalid status code or header
+    /// - Circuit breaker thresholds are 0
+    /// - Fault injection rates are outside 0.0 to 1.0
+    /// - Health check interval, timeout or thresholds are 0
+    /// - Webhook verification has an invalid header name or an unresolvable secret
+    /// - A consistent hash key names an invalid header or an unknown path parameter
     pub fn validate(&self) -> Result<(), String> {
         // Validate paths start with '/'
         if !self.external_path.starts_with('/') {
@@ -578,6 +1580,16 @@ impl Router {
             return Err("Internal path must start with '/'".to_string());
         }
 
+        for placeholder in self.internal_path.split('{').skip(1) {
+            let placeholder = placeholder.split('}').next().unwrap_or_default();
+            if ParamEncoding::parse_placeholder(placeholder).is_none() {
+                return Err(format!(
+                    "Unknown encoding in internal path placeholder '{{{}}}', expected raw or encoded",
+                    placeholder
+                ));
+            }
+        }
+
         // Validate HTTP methods
         if self.methods.is_empty() {
             return Err("At least one HTTP method must be specified".to_string());
@@ -590,6 +1602,29 @@ impl Router {
             }
         }
 
+        match &self.load_balancing_strategy {
+            LoadBalancingStrategy::ConsistentHash(HashKey::Header(name))
+                if actix_web::http::header::HeaderName::from_bytes(name.as_bytes()).is_err() =>
+            {
+                return Err(format!("Invalid consistent hash header name: '{}'", name));
+            }
+            LoadBalancingStrategy::ConsistentHash(HashKey::PathParam(name))
+                if name.is_empty() || !self.external_path.contains(&format!("{{{}}}", name)) =>
+            {
+                return Err(format!(
+                    "Consistent hash path parameter '{}' is not in external path {}",
+                    name, self.external_path
+                ));
+            }
+            _ => {}
+        }
+
+        if let Some(discovery) = &self.dns_discovery {
+            discovery
+                .validate()
+                .map_err(|e| format!("DNS discovery validation failed: {}", e))?;
+        }
+
         // Validate backends configuration
         if let Some(backends) = &self.backends {
             if backends.is_empty() {
@@ -620,6 +1655,10 @@ impl Router {
             if *port == 0 {
                 return Err("Port must be between 1 and 65535".to_string());
             }
+        } else if let Some(pool) = &self.backend_pool {
+            return Err(format!("Unknown backend pool '{}'", pool));
+        } else if self.dns_discovery.is_some() {
+            // Backends are filled in at runtime by discovery
         } else {
             return Err("Either backends or host/port must be specified".to_string());
         }
@@ -629,6 +1668,122 @@ impl Router {
             retry_config.validate()?;
         }
 
+        if self.max_body_bytes == Some(0) {
+            return Err("max_body_bytes must be greater than 0".to_string());
+        }
+
+        if self.warn_response_bytes == Some(0) {
+            return Err("warn_response_bytes must be greater than 0".to_string());
+        }
+
+        if self.sla_ms == Some(0) {
+            return Err("sla_ms must be greater than 0".to_string());
+        }
+
+        for name in self.tags.keys() {
+            let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid || name.starts_with("__") || name == "route" || name == "le" {
+                return Err(format!("Tag name '{}' is not a valid metric label name", name));
+            }
+        }
+
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            circuit_breaker
+                .validate()
+                .map_err(|e| format!("Circuit breaker validation failed: {}", e))?;
+        }
+
+        if let Some(fallback) = &self.circuit_open_fallback {
+            fallback
+                .validate()
+                .map_err(|e| format!("Circuit-open fallback validation failed: {}", e))?;
+        }
+
+        if let Some(fault_injection) = &self.fault_injection {
+            fault_injection
+                .validate()
+                .map_err(|e| format!("Fault injection validation failed: {}", e))?;
+        }
+
+        if let Some(health_check) = &self.health_check {
+            health_check
+                .validate()
+                .map_err(|e| format!("Health check validation failed: {}", e))?;
+        }
+
+        if let Some(deadline_header) = &self.deadline_header {
+            deadline_header
+                .validate()
+                .map_err(|e| format!("Deadline header validation failed: {}", e))?;
+        }
+
+        if let Some(request_start_header) = &self.request_start_header {
+            request_start_header
+                .validate()
+                .map_err(|e| format!("Request start header validation failed: {}", e))?;
+        }
+
+        if let Some(webhook_verify) = &self.webhook_verify {
+            webhook_verify
+                .validate()
+                .map_err(|e| format!("Webhook verification validation failed: {}", e))?;
+        }
+
+        if let Some(body_routing) = &self.body_routing {
+            // Pools and DNS discovery replace the backends after validation
+            let backend_count = self
+                .backends
+                .as_ref()
+                .filter(|_| self.backend_pool.is_none() && self.dns_discovery.is_none())
+                .map(Vec::len);
+            body_routing
+                .validate(backend_count)
+                .map_err(|e| format!("Body routing validation failed: {}", e))?;
+        }
+
+        // Validate mirror backend if present
+        if let Some(mirror) = &self.mirror_to {
+            mirror
+                .validate()
+                .map_err(|e| format!("Mirror backend validation failed: {}", e))?;
+        }
+
+        Ok(())
+    }
+    
+    /// Returns whether request bodies are streamed to the backend.
+    ///
+    /// Requires `stream_request_body` on an HTTP route. Body transformation,
+    /// body routing, webhook verification, mirroring, AI routing and retries
+    /// need the whole body, so routes using any of them are always buffered.
+    pub fn streams_request_body(&self) -> bool {
+        self.stream_request_body
+            && matches!(self.protocol, Protocol::Http)
+            && self
+                .request_transformation
+                .as_ref()
+                .is_none_or(|transformation| transformation.body.is_none())
+            && self.body_routing.is_none()
+            && self.webhook_verify.is_none()
+            && self.mirror_to.is_none()
+            && self.ai_policy.is_none()
+            && self.retry.is_none()
+    }
+
+    /// Replaces `backends` with the pool named by `backend_pool`, if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pools` has no pool with that name.
+    pub fn resolve_backend_pool(&mut self, pools: &HashMap<String, Vec<Backend>>) -> Result<(), String> {
+        let Some(name) = &self.backend_pool else {
+            return Ok(());
+        };
+        let pool = pools
+            .get(name)
+            .ok_or_else(|| format!("Unknown backend pool '{}'", name))?;
+        self.backends = Some(pool.clone());
         Ok(())
     }
     
@@ -643,6 +1798,7 @@ impl Router {
                 port: *port,
                 weight: 1,
                 health_check_path: None,
+                timeout_secs: None,
             }]
         } else {
             vec![]
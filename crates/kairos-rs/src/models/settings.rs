@@ -1,6 +1,13 @@
 use crate::middleware::rate_limit::RateLimitConfig;
-use crate::models::router::Router;
+use crate::models::router::{
+    Backend, CircuitBreakerSettings, LoadBalancingStrategy, RequestStartHeader, Router,
+    TrailingSlash,
+};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use ipnet::IpNet;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 
 /// Configuration for AI capabilities.
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -14,6 +21,423 @@ pub struct AiSettings {
     pub api_key: Option<String>,
 }
 
+/// Configuration for request and response body streaming.
+///
+/// Responses whose `Content-Length` exceeds `threshold_bytes`, or whose length
+/// is unknown (chunked), are streamed to the client instead of being buffered
+/// in memory. Request bodies are buffered up to `mirror_body_limit_bytes` so
+/// they can be teed to a route's mirror backend.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StreamingSettings {
+    /// Size in bytes above which response bodies are always streamed.
+    #[serde(default = "default_streaming_threshold")]
+    pub threshold_bytes: u64,
+
+    /// Largest request body in bytes that is copied to a mirror backend.
+    /// Larger requests are only sent to the primary backend.
+    #[serde(default = "default_mirror_body_limit")]
+    pub mirror_body_limit_bytes: u64,
+}
+
+fn default_streaming_threshold() -> u64 {
+    1024 * 1024 // 1 MiB
+}
+
+fn default_mirror_body_limit() -> u64 {
+    256 * 1024 // 256 KiB
+}
+
+impl Default for StreamingSettings {
+    fn default() -> Self {
+        Self {
+            threshold_bytes: default_streaming_threshold(),
+            mirror_body_limit_bytes: default_mirror_body_limit(),
+        }
+    }
+}
+
+/// Startup warmup configuration.
+///
+/// Until the warmup finishes, proxy routes answer `503` with `Retry-After`
+/// instead of sending traffic to cold backends. Health endpoints keep
+/// responding, and `/ready` reports `503` until the gateway is ready.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WarmupSettings {
+    /// Longest time in seconds proxy traffic is held back.
+    #[serde(default = "default_warmup_grace_period")]
+    pub grace_period_secs: u64,
+
+    /// Opens pooled connections to every backend during the grace period.
+    /// Readiness flips as soon as all backends have been contacted; when
+    /// disabled, the full grace period is waited out.
+    #[serde(default = "default_warm_connections")]
+    pub warm_connections: bool,
+}
+
+fn default_warmup_grace_period() -> u64 {
+    10
+}
+
+fn default_warm_connections() -> bool {
+    true
+}
+
+impl Default for WarmupSettings {
+    fn default() -> Self {
+        Self {
+            grace_period_secs: default_warmup_grace_period(),
+            warm_connections: default_warm_connections(),
+        }
+    }
+}
+
+/// Client-facing error response configuration.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ErrorSettings {
+    /// Message returned with `504 Gateway Timeout` when an upstream does not
+    /// answer in time. Defaults to `Request timeout after {N}s`.
+    #[serde(default)]
+    pub timeout_message: Option<String>,
+
+    /// Status returned when a route has no backends left, for example after
+    /// DNS discovery drained its pool.
+    #[serde(default)]
+    pub empty_pool_status: EmptyPoolStatus,
+
+    /// Body format of gateway error responses.
+    #[serde(default)]
+    pub format: ErrorFormat,
+}
+
+/// Body format of gateway error responses.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorFormat {
+    /// `application/json` with `error`, `type`, `timestamp` and `request_id`
+    /// (default).
+    #[default]
+    Json,
+
+    /// RFC 7807 `application/problem+json` with `type`, `title`, `status`,
+    /// `detail` and `instance`; the request ID is the `instance`.
+    ProblemDetails,
+}
+
+/// Status returned for requests to a route whose backend pool is empty.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyPoolStatus {
+    /// `503 Service Unavailable` with a `Retry-After` header (default), for
+    /// pools that are expected to refill.
+    #[default]
+    ServiceUnavailable,
+
+    /// `502 Bad Gateway`, like other upstream failures.
+    BadGateway,
+}
+
+/// Lets trusted clients send requests past open circuit breakers.
+///
+/// Uptime monitors probing a backend through the gateway otherwise get
+/// `503` while its breaker is open and report the gateway as down. Requests
+/// carrying `X-Kairos-Bypass-Breaker` from one of `trusted_proxies` go to
+/// the backend directly and are not counted by its breaker.
+///
+/// # Examples
+///
+/// ```json
+/// { "trusted_proxies": ["10.0.0.0/8", "192.168.1.20"] }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CircuitBreakerBypassSettings {
+    /// Addresses or CIDR ranges whose bypass header is honored. Only the
+    /// address of the direct connection is checked.
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+}
+
+impl CircuitBreakerBypassSettings {
+    /// Parses `trusted_proxies` into networks; single addresses become
+    /// host networks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kairos_rs::models::settings::CircuitBreakerBypassSettings;
+    ///
+    /// let settings = CircuitBreakerBypassSettings {
+    ///     trusted_proxies: vec!["10.0.0.0/8".to_string(), "::1".to_string()],
+    /// };
+    /// let networks = settings.trusted_networks().unwrap();
+    /// assert!(networks[0].contains(&"10.1.2.3".parse::<std::net::IpAddr>().unwrap()));
+    /// assert_eq!(networks[1].prefix_len(), 128);
+    /// ```
+    pub fn trusted_networks(&self) -> Result<Vec<IpNet>, String> {
+        parse_trusted_networks(&self.trusted_proxies)
+    }
+}
+
+/// Lets trusted clients choose the upstream path of a request.
+///
+/// Meant for testing backends through the gateway's TLS and authentication
+/// without reconfiguring routes: requests carrying `X-Kairos-Override-Path`
+/// from one of `trusted_proxies` are forwarded to that path instead of the
+/// route's computed internal path. Never enable it in production.
+///
+/// # Examples
+///
+/// ```json
+/// { "trusted_proxies": ["10.20.0.0/16"] }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DebugOverridesSettings {
+    /// Addresses or CIDR ranges whose override header is honored. Only the
+    /// address of the direct connection is checked.
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+}
+
+impl DebugOverridesSettings {
+    /// Parses `trusted_proxies` into networks; single addresses become
+    /// host networks.
+    pub fn trusted_networks(&self) -> Result<Vec<IpNet>, String> {
+        parse_trusted_networks(&self.trusted_proxies)
+    }
+}
+
+/// Addresses the HTTP server listens on.
+///
+/// # Examples
+///
+/// ```json
+/// { "listen": ["10.0.0.5:5900", "[2001:db8::5]:5900", "127.0.0.1:5901"] }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ServerSettings {
+    /// Socket addresses to bind, as `ip:port`; IPv6 addresses are written in
+    /// brackets. Every address serves the same routes.
+    #[serde(default)]
+    pub listen: Vec<String>,
+}
+
+impl ServerSettings {
+    /// Parses `listen` into socket addresses.
+    pub fn listen_addresses(&self) -> Result<Vec<SocketAddr>, String> {
+        parse_listen_addresses(self.listen.iter().map(String::as_str))
+    }
+}
+
+/// Parses `ip:port` listen addresses, such as the comma-separated entries of
+/// `KAIROS_BIND`.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::models::settings::parse_listen_addresses;
+///
+/// let addresses = parse_listen_addresses("0.0.0.0:5900, [::1]:5901".split(',')).unwrap();
+/// assert_eq!(addresses.len(), 2);
+/// assert!(addresses[1].is_ipv6());
+/// assert!(parse_listen_addresses(["localhost"]).is_err());
+/// ```
+pub fn parse_listen_addresses<'a>(
+    addresses: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<SocketAddr>, String> {
+    addresses
+        .into_iter()
+        .map(|address| {
+            address.trim().parse::<SocketAddr>().map_err(|_| {
+                format!(
+                    "Invalid listen address '{}', expected ip:port such as 0.0.0.0:5900 or [::]:5900",
+                    address.trim()
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parses addresses or CIDR ranges of trusted peers into networks.
+fn parse_trusted_networks(proxies: &[String]) -> Result<Vec<IpNet>, String> {
+    proxies
+        .iter()
+        .map(|proxy| {
+            proxy
+                .parse::<IpNet>()
+                .or_else(|_| proxy.parse::<IpAddr>().map(IpNet::from))
+                .map_err(|_| {
+                    format!(
+                        "Invalid trusted proxy '{}', expected an IP address or CIDR range",
+                        proxy
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Response to `GET /` when no route should handle it.
+///
+/// # Examples
+///
+/// ```json
+/// { "type": "info", "name": "acme-gateway", "docs_url": "https://docs.example.com" }
+/// ```
+///
+/// ```json
+/// { "type": "redirect", "location": "/docs", "permanent": false }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RootResponse {
+    /// Small JSON document with the gateway name and version.
+    Info {
+        /// Service name reported as `service` (default: `kairos-rs`).
+        #[serde(default)]
+        name: Option<String>,
+        /// Documentation link reported as `docs`, omitted if not set.
+        #[serde(default)]
+        docs_url: Option<String>,
+    },
+
+    /// Redirect to another location.
+    Redirect {
+        /// Target of the redirect, absolute or relative to the gateway.
+        location: String,
+        /// Sends `301 Moved Permanently` instead of `302 Found`.
+        #[serde(default)]
+        permanent: bool,
+    },
+
+    /// Forward `/` through the proxy like any other path, so a route with
+    /// `external_path` `/` can serve it.
+    Proxy,
+}
+
+impl Default for RootResponse {
+    fn default() -> Self {
+        RootResponse::Info {
+            name: None,
+            docs_url: None,
+        }
+    }
+}
+
+/// `X-Forwarded-Proto`, `X-Forwarded-Host` and `X-Forwarded-Port` headers
+/// sent to backends.
+///
+/// Backends that build absolute URLs need the scheme, host and port clients
+/// used rather than the gateway's bind address. Values are taken from the
+/// request unless overridden, which is needed when TLS is terminated in
+/// front of the gateway.
+///
+/// # Examples
+///
+/// ```json
+/// { "scheme": "https", "port": 443 }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ForwardedHeadersSettings {
+    /// Scheme clients connect with. Defaults to the request's scheme.
+    #[serde(default)]
+    pub scheme: Option<String>,
+
+    /// Port clients connect to. Defaults to the port the gateway listens
+    /// on.
+    #[serde(default)]
+    pub port: Option<u16>,
+}
+
+/// Credentials required to scrape the `/metrics` endpoint.
+///
+/// Static credentials are kept separate from the admin JWT so scrapers can be
+/// issued one without access to anything else. Gateways that already hand
+/// out JWTs can accept those instead.
+///
+/// # Examples
+///
+/// ```json
+/// { "type": "bearer", "token": "scrape-token" }
+/// ```
+///
+/// ```json
+/// { "type": "basic", "username": "prometheus", "password": "secret" }
+/// ```
+///
+/// ```json
+/// { "type": "jwt" }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetricsAuth {
+    /// Requires `Authorization: Bearer <token>`.
+    Bearer {
+        /// Expected bearer token.
+        token: String,
+    },
+    /// Requires HTTP basic authentication.
+    Basic {
+        /// Expected username.
+        username: String,
+        /// Expected password.
+        password: String,
+    },
+    /// Requires `Authorization: Bearer <token>` with a token valid under the
+    /// gateway's `jwt` settings.
+    Jwt,
+}
+
+/// Configuration for the Prometheus metrics endpoint.
+///
+/// The endpoint is open by default for compatibility with Prometheus scrapers.
+/// Anyone who can reach an open endpoint can read request volumes and error
+/// rates, so set `auth` when the gateway is reachable from untrusted networks.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MetricsConfig {
+    /// Optional credentials required to read `/metrics`.
+    #[serde(default)]
+    pub auth: Option<MetricsAuth>,
+
+    /// Upper bounds in bytes of the per-route request body size histogram.
+    /// Defaults to 1 KiB, 16 KiB, 128 KiB, 1 MiB and 10 MiB.
+    #[serde(default)]
+    pub body_size_buckets: Option<Vec<u64>>,
+
+    /// Whether to export request counts, error counts and average response
+    /// times per route, labelled with the route's `external_path` pattern.
+    #[serde(default)]
+    pub collect_per_route: bool,
+
+    /// Where metrics are sent besides the `/metrics` endpoint.
+    #[serde(default)]
+    pub backend: MetricsBackend,
+}
+
+/// Destination of gateway metrics, set with `metrics.backend`.
+///
+/// `/metrics` serves the Prometheus exposition with every backend.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetricsBackend {
+    /// Metrics are only scraped from `/metrics`.
+    #[default]
+    Prometheus,
+    /// Metrics are also pushed to a StatsD server over UDP.
+    Statsd {
+        /// `host:port` of the StatsD server.
+        address: String,
+        /// Prefix of every metric name (default: `kairos`).
+        #[serde(default = "default_statsd_prefix")]
+        prefix: String,
+        /// Whether to add DogStatsD `route` and `status` tags to request
+        /// metrics, for Datadog agents.
+        #[serde(default)]
+        tags: bool,
+    },
+}
+
+fn default_statsd_prefix() -> String {
+    "kairos".to_string()
+}
+
 /// JWT authentication configuration for the gateway.
 ///
 /// This structure defines the JWT validation parameters used by the
@@ -61,6 +485,113 @@ impl Default for JwtSettings {
     }
 }
 
+/// Plain TCP passthrough on a dedicated port, for non-HTTP services.
+///
+/// Every connection accepted on `listen_port` is connected to one upstream
+/// and bytes are copied both ways until either side closes. The upstream is
+/// picked per connection by the load balancer, and a circuit breaker per
+/// upstream stops connection attempts to one that keeps refusing them.
+///
+/// # Examples
+///
+/// ```json
+/// {
+///   "listen_port": 15432,
+///   "upstream_host": "db.internal",
+///   "upstream_port": 5432
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TcpProxySettings {
+    /// Port the gateway accepts connections on, on `KAIROS_HOST`.
+    pub listen_port: u16,
+
+    /// Host name or IP address of a single upstream, without a scheme.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_host: Option<String>,
+
+    /// Port of the single upstream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_port: Option<u16>,
+
+    /// Several upstreams to spread connections across, instead of
+    /// `upstream_host` and `upstream_port`. Hosts have no scheme.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backends: Option<Vec<Backend>>,
+
+    /// How an upstream is picked for each connection (default: round robin).
+    #[serde(default)]
+    pub load_balancing_strategy: LoadBalancingStrategy,
+
+    /// Circuit breaker settings of every upstream. If not specified, the
+    /// defaults are used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerSettings>,
+
+    /// Seconds to wait for an upstream to accept a connection (default: 5).
+    #[serde(default = "default_tcp_connect_timeout")]
+    pub connect_timeout_secs: u64,
+}
+
+fn default_tcp_connect_timeout() -> u64 {
+    5
+}
+
+impl TcpProxySettings {
+    /// Returns the configured upstreams, from `backends` or from
+    /// `upstream_host` and `upstream_port`.
+    pub fn upstreams(&self) -> Vec<Backend> {
+        if let Some(backends) = &self.backends {
+            backends.clone()
+        } else if let (Some(host), Some(port)) = (&self.upstream_host, self.upstream_port) {
+            vec![Backend {
+                host: host.clone(),
+                port,
+                weight: 1,
+                health_check_path: None,
+                timeout_secs: None,
+            }]
+        } else {
+            vec![]
+        }
+    }
+
+    /// Validates the listen port, the upstreams and the circuit breaker.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.listen_port == 0 {
+            return Err("TCP proxy listen_port must be between 1 and 65535".to_string());
+        }
+        let upstreams = self.upstreams();
+        if upstreams.is_empty() {
+            return Err(format!(
+                "TCP proxy on port {} needs upstream_host and upstream_port, or backends",
+                self.listen_port
+            ));
+        }
+        for upstream in &upstreams {
+            if upstream.host.is_empty() || upstream.host.contains("://") {
+                return Err(format!(
+                    "TCP proxy upstream host must be a host name without a scheme: '{}'",
+                    upstream.host
+                ));
+            }
+            if upstream.port == 0 {
+                return Err("TCP proxy upstream port must be between 1 and 65535".to_string());
+            }
+            if upstream.weight == 0 {
+                return Err("TCP proxy upstream weight must be greater than 0".to_string());
+            }
+        }
+        if self.connect_timeout_secs == 0 {
+            return Err("TCP proxy connect_timeout_secs must be greater than 0".to_string());
+        }
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            circuit_breaker.validate()?;
+        }
+        Ok(())
+    }
+}
+
 /// Application configuration settings for the kairos-rs gateway.
 ///
 /// This structure contains the complete configuration needed to run the gateway,
@@ -132,10 +663,130 @@ pub struct Settings {
     #[serde(default)]
     pub rate_limit: Option<RateLimitConfig>,
 
+    /// Disables all rate limiting.
+    ///
+    /// When `true`, neither the advanced limiter configured by `rate_limit`
+    /// nor the basic per-IP fallback limiter is installed.
+    #[serde(default)]
+    pub disable_rate_limiting: bool,
+
     /// AI capabilities configuration.
     #[serde(default)]
     pub ai: Option<AiSettings>,
 
+    /// Upstream response streaming configuration.
+    ///
+    /// If not specified, responses larger than 1 MiB are streamed.
+    #[serde(default)]
+    pub streaming: Option<StreamingSettings>,
+
+    /// Metrics endpoint configuration.
+    ///
+    /// If not specified, `/metrics` is served without authentication.
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
+
+    /// Startup warmup configuration.
+    ///
+    /// If not specified, proxy routes serve traffic immediately.
+    #[serde(default)]
+    pub warmup: Option<WarmupSettings>,
+
+    /// Client-facing error response configuration.
+    ///
+    /// If not specified, default error messages are used.
+    #[serde(default)]
+    pub errors: Option<ErrorSettings>,
+
+    /// Allows routes to inject faults for chaos testing.
+    ///
+    /// Routes' `fault_injection` settings are ignored unless this is `true`.
+    /// Refused when `KAIROS_ENV` is `production`.
+    #[serde(default)]
+    pub fault_injection_enabled: bool,
+
+    /// Maximum number of concurrent requests from a single client IP.
+    ///
+    /// Requests over the cap are rejected with `503 Service Unavailable`.
+    /// If not specified, there is no per-IP limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_connections_per_ip: Option<usize>,
+
+    /// Maximum number of requests the gateway handles at once.
+    ///
+    /// Requests over the cap are rejected right away with `503 Service
+    /// Unavailable` and a `Retry-After` header instead of queueing. If not
+    /// specified, there is no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<usize>,
+
+    /// Adds `X-Forwarded-*` headers describing the external request to
+    /// upstream requests.
+    ///
+    /// If not specified, no forwarded headers are added.
+    #[serde(default)]
+    pub forwarded_headers: Option<ForwardedHeadersSettings>,
+
+    /// Headers added to every request forwarded to a backend.
+    ///
+    /// They replace client-sent headers of the same name, and route request
+    /// transformations can still change or remove them. Values may reference
+    /// environment variables as `${NAME}`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub default_upstream_headers: HashMap<String, String>,
+
+    /// Trusted clients allowed to bypass open circuit breakers.
+    ///
+    /// If not specified, the bypass header is ignored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker_bypass: Option<CircuitBreakerBypassSettings>,
+
+    /// Trusted clients allowed to override the upstream path of requests.
+    ///
+    /// A debugging aid, off by default. If not specified, the override
+    /// header is ignored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug_overrides: Option<DebugOverridesSettings>,
+
+    /// Adds the time the gateway received each request to forwarded
+    /// requests, for routes that do not configure it themselves.
+    ///
+    /// If not specified, only routes with `request_start_header` get it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_start_header: Option<RequestStartHeader>,
+
+    /// How request paths differing from a route only by a trailing slash
+    /// are matched (default: `strict`).
+    #[serde(default)]
+    pub trailing_slash: TrailingSlash,
+
+    /// Response to `GET /`.
+    ///
+    /// If not specified, a JSON document with the gateway name and version
+    /// is returned, unless a route has `external_path` `/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_response: Option<RootResponse>,
+
+    /// Named backend lists shared by several routes.
+    ///
+    /// Routes reference a pool with `backend_pool` instead of repeating
+    /// the same `backends` block.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub backend_pools: HashMap<String, Vec<Backend>>,
+
+    /// Addresses the HTTP server listens on.
+    ///
+    /// `KAIROS_BIND` takes precedence. If neither is set, the server listens
+    /// on `KAIROS_HOST` and `KAIROS_PORT`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<ServerSettings>,
+
+    /// Plain TCP listeners forwarding to non-HTTP upstreams.
+    ///
+    /// Configured separately from `routers` and not hot-reloaded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tcp_proxies: Vec<TcpProxySettings>,
+
     /// Collection of route configurations defining how requests are forwarded.
     ///
     /// Each router defines a mapping from external client requests to internal
@@ -170,13 +821,31 @@ impl Settings {
     ///
     /// ```rust
     /// use kairos_rs::models::settings::Settings;
-    /// use kairos_rs::models::router::{Router, Backend, Protocol};
+    /// use kairos_rs::models::router::{Router, Backend};
     ///
     /// let settings = Settings {
     ///     version: 1,
     ///     jwt: None,
     ///     rate_limit: None,
+    ///     disable_rate_limiting: false,
     ///     ai: None,
+    ///     streaming: None,
+    ///     metrics: None,
+    ///     warmup: None,
+    ///     errors: None,
+    ///     fault_injection_enabled: false,
+    ///     max_connections_per_ip: None,
+    ///     max_concurrent_requests: None,
+    ///     forwarded_headers: None,
+    ///     default_upstream_headers: Default::default(),
+    ///     circuit_breaker_bypass: None,
+    ///     debug_overrides: None,
+    ///     request_start_header: None,
+    ///     trailing_slash: Default::default(),
+    ///     server: None,
+    ///     root_response: None,
+    ///     backend_pools: Default::default(),
+    ///     tcp_proxies: Vec::new(),
     ///     routers: vec![
     ///         Router {
     ///             host: Some("http://localhost".to_string()),
@@ -184,19 +853,14 @@ impl Settings {
     ///             external_path: "/api/test".to_string(),
     ///             internal_path: "/test".to_string(),
     ///             methods: vec!["GET".to_string()],
-    ///             auth_required: false,
     ///             backends: Some(vec![Backend {
     ///                 host: "http://localhost".to_string(),
     ///                 port: 8080,
     ///                 weight: 1,
     ///                 health_check_path: None,
+    ///                 timeout_secs: None,
     ///             }]),
-    ///             load_balancing_strategy: Default::default(),
-    ///             retry: None,
-    ///             protocol: Protocol::Http,
-    ///             request_transformation: None,
-    ///             response_transformation: None,
-    ///             ai_policy: None,
+    ///             ..Default::default()
     ///         }
     ///     ],
     /// };
@@ -237,6 +901,101 @@ impl Settings {
             }
         }
 
+        // Validate metrics credentials if present
+        if let Some(auth) = self.metrics.as_ref().and_then(|m| m.auth.as_ref()) {
+            match auth {
+                MetricsAuth::Bearer { token } if token.is_empty() => {
+                    return Err("Metrics bearer token cannot be empty".to_string());
+                }
+                MetricsAuth::Basic { username, password }
+                    if username.is_empty() || password.is_empty() =>
+                {
+                    return Err("Metrics basic auth username and password cannot be empty".to_string());
+                }
+                MetricsAuth::Jwt if self.jwt.is_none() => {
+                    return Err("Metrics JWT auth requires jwt settings".to_string());
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(MetricsBackend::Statsd { address, prefix, .. }) =
+            self.metrics.as_ref().map(|m| &m.backend)
+        {
+            let port = address.rsplit_once(':').map(|(_, port)| port.parse::<u16>());
+            if !matches!(port, Some(Ok(_))) {
+                return Err(format!("StatsD address '{}' must be host:port", address));
+            }
+            if prefix.contains([':', '|', '@', '#', '\n']) {
+                return Err(format!("StatsD prefix '{}' contains a reserved character", prefix));
+            }
+        }
+
+        if let Some(RootResponse::Redirect { location, .. }) = &self.root_response {
+            if location.trim().is_empty() {
+                return Err("root_response redirect location cannot be empty".to_string());
+            }
+        }
+
+        if let Some(bypass) = &self.circuit_breaker_bypass {
+            if bypass.trusted_proxies.is_empty() {
+                return Err("circuit_breaker_bypass requires at least one trusted proxy".to_string());
+            }
+            bypass.trusted_networks()?;
+        }
+
+        if let Some(overrides) = &self.debug_overrides {
+            if overrides.trusted_proxies.is_empty() {
+                return Err("debug_overrides requires at least one trusted proxy".to_string());
+            }
+            overrides.trusted_networks()?;
+        }
+
+        if let Some(request_start_header) = &self.request_start_header {
+            request_start_header
+                .validate()
+                .map_err(|e| format!("request_start_header: {}", e))?;
+        }
+
+        if let Some(server) = &self.server {
+            if server.listen.is_empty() {
+                return Err("server.listen requires at least one address".to_string());
+            }
+            server.listen_addresses()?;
+        }
+
+        if self.max_connections_per_ip == Some(0) {
+            return Err("max_connections_per_ip must be greater than 0".to_string());
+        }
+
+        if self.max_concurrent_requests == Some(0) {
+            return Err("max_concurrent_requests must be greater than 0".to_string());
+        }
+
+        if let Some(scheme) = self.forwarded_headers.as_ref().and_then(|f| f.scheme.as_deref()) {
+            if scheme != "http" && scheme != "https" {
+                return Err(format!(
+                    "forwarded_headers scheme must be http or https, got '{}'",
+                    scheme
+                ));
+            }
+        }
+
+        self.resolved_default_upstream_headers()?;
+
+        self.validate_fault_injection(is_production_environment())?;
+
+        let mut listen_ports = std::collections::HashSet::new();
+        for proxy in &self.tcp_proxies {
+            proxy.validate()?;
+            if !listen_ports.insert(proxy.listen_port) {
+                return Err(format!(
+                    "Several TCP proxies listen on port {}",
+                    proxy.listen_port
+                ));
+            }
+        }
+
         // Validate all routers
         for route in &self.routers {
             route.validate()?;
@@ -244,4 +1003,94 @@ impl Settings {
 
         Ok(())
     }
+
+    /// Returns the response to serve for `GET /`.
+    ///
+    /// Without a configured `root_response`, `/` is left to the proxy if a
+    /// route has `external_path` `/`, and answered with the gateway info
+    /// otherwise.
+    pub fn resolved_root_response(&self) -> RootResponse {
+        match &self.root_response {
+            Some(response) => response.clone(),
+            None if self.routers.iter().any(|route| route.external_path == "/") => {
+                RootResponse::Proxy
+            }
+            None => RootResponse::default(),
+        }
+    }
+
+    /// Returns `default_upstream_headers` with environment variables
+    /// interpolated, sorted by header name.
+    ///
+    /// Fails if a header name or interpolated value is not valid in HTTP, or
+    /// a value references an unset variable.
+    pub fn resolved_default_upstream_headers(&self) -> Result<Vec<(String, String)>, String> {
+        let mut headers = self
+            .default_upstream_headers
+            .iter()
+            .map(|(name, value)| {
+                HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| format!("default_upstream_headers name '{}' is not a valid header", name))?;
+                let value = interpolate_env(value)
+                    .map_err(|e| format!("default_upstream_headers {}: {}", name, e))?;
+                HeaderValue::from_str(&value)
+                    .map_err(|_| format!("default_upstream_headers {} has an invalid value", name))?;
+                Ok((name.clone(), value))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        headers.sort();
+        Ok(headers)
+    }
+
+    /// Refuses `fault_injection_enabled` in a production environment.
+    ///
+    /// `production` is normally [`is_production_environment`]; it is a
+    /// parameter so the check can be exercised without touching the
+    /// process environment.
+    pub fn validate_fault_injection(&self, production: bool) -> Result<(), String> {
+        if self.fault_injection_enabled && production {
+            return Err(
+                "fault_injection_enabled cannot be set when KAIROS_ENV is production".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Replaces each `${NAME}` in `value` with the environment variable `NAME`.
+///
+/// Fails if a variable is unset or a `${` is not closed.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::models::settings::interpolate_env;
+///
+/// std::env::set_var("GATEWAY_ID", "edge-1");
+/// assert_eq!(interpolate_env("kairos/${GATEWAY_ID}").unwrap(), "kairos/edge-1");
+/// assert!(interpolate_env("${KAIROS_UNSET_VARIABLE}").is_err());
+/// ```
+pub fn interpolate_env(value: &str) -> Result<String, String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("unclosed '${{' in '{}'", value))?;
+        let name = &rest[start + 2..start + end];
+        let resolved = std::env::var(name)
+            .map_err(|_| format!("environment variable {} is not set", name))?;
+        result.push_str(&resolved);
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Returns whether `KAIROS_ENV` marks this gateway as running in production.
+///
+/// Chaos testing features refuse to start in production.
+pub fn is_production_environment() -> bool {
+    std::env::var("KAIROS_ENV").is_ok_and(|env| env.eq_ignore_ascii_case("production"))
 }
@@ -7,9 +7,10 @@
 use crate::middleware::auth::{JwtAuth, JwtConfig};
 use crate::models::settings::Settings;
 use crate::models::router::Protocol;
+use crate::routes::http::MAX_PAYLOAD_BYTES;
 use crate::services::http::RouteHandler;
 use crate::services::websocket::WebSocketHandler;
-use actix_web::{web, HttpRequest, HttpResponse, Error as ActixError};
+use actix_web::{http::Method, web, HttpRequest, HttpResponse, Error as ActixError};
 use std::sync::Arc;
 use log::{debug, warn};
 
@@ -20,6 +21,9 @@ use log::{debug, warn};
 pub fn configure_auth_routes(cfg: &mut web::ServiceConfig, handler: RouteHandler, settings: &Settings) {
     let handler = Arc::new(handler);
     let ws_handler = WebSocketHandler::new(30);
+
+    // Same gateway-wide body limit as the plain proxy configuration
+    cfg.app_data(web::PayloadConfig::new(MAX_PAYLOAD_BYTES));
     
     // Configure public routes first (no authentication required)
     for router in &settings.routers {
@@ -50,34 +54,34 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig, handler: RouteHandler
                 
                 match method.to_uppercase().as_str() {
                     "GET" => {
-                        cfg.route(&path_clone, web::get().to(move |req: HttpRequest, body: web::Bytes| {
+                        cfg.route(&path_clone, web::get().to(move |req: HttpRequest, payload: web::Payload| {
                             let handler = handler_clone.clone();
                             async move {
-                                handler.handle_request(req, body).await
+                                handler.handle_payload(req, payload).await
                             }
                         }));
                     }
                     "POST" => {
-                        cfg.route(&path_clone, web::post().to(move |req: HttpRequest, body: web::Bytes| {
+                        cfg.route(&path_clone, web::post().to(move |req: HttpRequest, payload: web::Payload| {
                             let handler = handler_clone.clone();
                             async move {
-                                handler.handle_request(req, body).await
+                                handler.handle_payload(req, payload).await
                             }
                         }));
                     }
                     "PUT" => {
-                        cfg.route(&path_clone, web::put().to(move |req: HttpRequest, body: web::Bytes| {
+                        cfg.route(&path_clone, web::put().to(move |req: HttpRequest, payload: web::Payload| {
                             let handler = handler_clone.clone();
                             async move {
-                                handler.handle_request(req, body).await
+                                handler.handle_payload(req, payload).await
                             }
                         }));
                     }
                     "DELETE" => {
-                        cfg.route(&path_clone, web::delete().to(move |req: HttpRequest, body: web::Bytes| {
+                        cfg.route(&path_clone, web::delete().to(move |req: HttpRequest, payload: web::Payload| {
                             let handler = handler_clone.clone();
                             async move {
-                                handler.handle_request(req, body).await
+                                handler.handle_payload(req, payload).await
                             }
                         }));
                     }
@@ -98,6 +102,18 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig, handler: RouteHandler
                 let path = router.external_path.clone();
                 let methods = router.methods.clone();
                 let handler_clone = handler.clone();
+
+                // CORS preflights carry no credentials, so OPTIONS is answered
+                // from the route's methods without JWT
+                if !methods.iter().any(|m| m.eq_ignore_ascii_case("OPTIONS")) {
+                    let handler_for_options = handler_clone.clone();
+                    cfg.route(&path, web::method(Method::OPTIONS).to(move |req: HttpRequest, payload: web::Payload| {
+                        let handler = handler_for_options.clone();
+                        async move {
+                            handler.handle_payload(req, payload).await
+                        }
+                    }));
+                }
                 
                 for method in methods {
                     let handler_for_method = handler_clone.clone();
@@ -109,10 +125,10 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig, handler: RouteHandler
                             cfg.service(
                                 web::resource(&path_for_method)
                                     .wrap(jwt_middleware)
-                                    .route(web::get().to(move |req: HttpRequest, body: web::Bytes| {
+                                    .route(web::get().to(move |req: HttpRequest, payload: web::Payload| {
                                         let handler = handler_for_method.clone();
                                         async move {
-                                            handler.handle_request(req, body).await
+                                            handler.handle_payload(req, payload).await
                                         }
                                     }))
                             );
@@ -121,10 +137,10 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig, handler: RouteHandler
                             cfg.service(
                                 web::resource(&path_for_method)
                                     .wrap(jwt_middleware)
-                                    .route(web::post().to(move |req: HttpRequest, body: web::Bytes| {
+                                    .route(web::post().to(move |req: HttpRequest, payload: web::Payload| {
                                         let handler = handler_for_method.clone();
                                         async move {
-                                            handler.handle_request(req, body).await
+                                            handler.handle_payload(req, payload).await
                                         }
                                     }))
                             );
@@ -133,10 +149,10 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig, handler: RouteHandler
                             cfg.service(
                                 web::resource(&path_for_method)
                                     .wrap(jwt_middleware)
-                                    .route(web::put().to(move |req: HttpRequest, body: web::Bytes| {
+                                    .route(web::put().to(move |req: HttpRequest, payload: web::Payload| {
                                         let handler = handler_for_method.clone();
                                         async move {
-                                            handler.handle_request(req, body).await
+                                            handler.handle_payload(req, payload).await
                                         }
                                     }))
                             );
@@ -145,10 +161,10 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig, handler: RouteHandler
                             cfg.service(
                                 web::resource(&path_for_method)
                                     .wrap(jwt_middleware)
-                                    .route(web::delete().to(move |req: HttpRequest, body: web::Bytes| {
+                                    .route(web::delete().to(move |req: HttpRequest, payload: web::Payload| {
                                         let handler = handler_for_method.clone();
                                         async move {
-                                            handler.handle_request(req, body).await
+                                            handler.handle_payload(req, payload).await
                                         }
                                     }))
                             );
@@ -162,10 +178,10 @@ pub fn configure_auth_routes(cfg: &mut web::ServiceConfig, handler: RouteHandler
     
     // Fallback catch-all route for any unmatched requests
     let fallback_handler = handler.clone();
-    cfg.default_service(web::route().to(move |req: HttpRequest, body: web::Bytes| {
+    cfg.default_service(web::route().to(move |req: HttpRequest, payload: web::Payload| {
         let handler = fallback_handler.clone();
         async move {
-            handler.handle_request(req, body).await
+            handler.handle_payload(req, payload).await
         }
     }));
 }
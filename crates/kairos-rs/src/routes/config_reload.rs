@@ -49,6 +49,32 @@ pub struct ReloadResponse {
 /// - File system errors occur
 #[post("/api/config/reload")]
 pub async fn reload_config(manager: web::Data<Arc<ConfigManager>>) -> impl Responder {
+    trigger_reload(&manager).await
+}
+
+/// Trigger a configuration reload from the admin API
+///
+/// # Endpoint
+///
+/// `POST /admin/config/reload`
+///
+/// Runs the same reload as a change to the watched config file: the file is
+/// re-read and validated, and the new routes replace the current ones. An
+/// invalid configuration is rejected and the current one stays active.
+/// Registered by [`configure_admin`](crate::routes::management::configure_admin),
+/// so it requires a bearer token and is only served when JWT is configured.
+///
+/// # Example
+///
+/// ```bash
+/// curl -X POST http://localhost:5900/admin/config/reload \
+///   -H "Authorization: Bearer $TOKEN"
+/// ```
+pub async fn admin_reload_config(manager: web::Data<Arc<ConfigManager>>) -> HttpResponse {
+    trigger_reload(&manager).await
+}
+
+async fn trigger_reload(manager: &ConfigManager) -> HttpResponse {
     match manager.reload_now().await {
         Ok(update) => HttpResponse::Ok().json(ReloadResponse {
             success: true,
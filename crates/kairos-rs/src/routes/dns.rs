@@ -160,6 +160,7 @@ pub async fn handle_dns_cache_stats(handler: web::Data<DnsHandler>) -> Result<Ht
 ///     port: 53,
 ///     weight: 1,
 ///     health_check_path: None,
+///     timeout_secs: None,
 /// };
 ///
 /// let app = App::new()
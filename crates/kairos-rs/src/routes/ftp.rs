@@ -210,6 +210,7 @@ pub async fn handle_ftp_upload(
 ///     port: 21,
 ///     weight: 1,
 ///     health_check_path: None,
+///     timeout_secs: None,
 /// };
 ///
 /// let app = App::new()
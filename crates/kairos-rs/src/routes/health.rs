@@ -1,10 +1,19 @@
+use crate::services::http::{RouteHandler, UpstreamHealth};
 use actix_web::{web, HttpResponse, Result};
+use once_cell::sync::Lazy;
 use serde_json::json;
+use std::time::{Duration, Instant};
+
+/// Time the health routes were first configured, used to report uptime.
+static START_TIME: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Time each backend gets to answer an upstream health probe.
+const UPSTREAM_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// General health check endpoint providing service status and basic information.
 /// 
 /// This endpoint provides comprehensive health information including service status,
-/// version, current timestamp, and uptime. It's designed for general monitoring
+/// version, current timestamp, and uptime in seconds. It's designed for general monitoring
 /// and service discovery purposes.
 /// 
 /// # Response Format
@@ -38,10 +47,7 @@ pub async fn health_check() -> Result<HttpResponse> {
         "status": "healthy",
         "version": env!("CARGO_PKG_VERSION"),
         "timestamp": chrono::Utc::now().to_rfc3339(),
-        "uptime": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
+        "uptime": START_TIME.elapsed().as_secs()
     })))
 }
 
@@ -63,7 +69,8 @@ pub async fn health_check() -> Result<HttpResponse> {
 /// # Returns
 /// 
 /// - `200 OK` when service is ready to receive traffic
-/// - `503 Service Unavailable` when service is not ready (if dependency checks fail)
+/// - `503 Service Unavailable` with `"status": "warming_up"` while the startup
+///   warmup is still holding back proxy traffic
 /// 
 /// # Kubernetes Configuration
 /// 
@@ -83,9 +90,16 @@ pub async fn health_check() -> Result<HttpResponse> {
 /// - Upstream service connectivity
 /// - Required configuration presence
 /// - Resource availability (memory, disk)
-pub async fn readiness_check() -> Result<HttpResponse> {
-    // In a real implementation, you might check database connections,
-    // external service availability, etc.
+pub async fn readiness_check(
+    route_handler: Option<web::Data<RouteHandler>>,
+) -> Result<HttpResponse> {
+    if route_handler.is_some_and(|handler| !handler.is_ready()) {
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "status": "warming_up",
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        })));
+    }
+
     Ok(HttpResponse::Ok().json(json!({
         "status": "ready",
         "timestamp": chrono::Utc::now().to_rfc3339()
@@ -134,6 +148,57 @@ pub async fn liveness_check() -> Result<HttpResponse> {
     })))
 }
 
+/// Upstream health endpoint that actively probes every backend.
+///
+/// Each backend with a `health_check_path` gets a `GET` request through the
+/// gateway's pooled client. Probes run concurrently with a 2 second timeout
+/// each, so the endpoint answers within about 2 seconds. Backends without a
+/// `health_check_path` are reported as `"unknown"`.
+///
+/// # Response Format
+///
+/// ```json
+/// {
+///   "http://users:8080": { "healthy": true, "status_code": 200, "latency_ms": 4 },
+///   "http://orders:8080": { "healthy": false, "status_code": null, "latency_ms": 2001 },
+///   "http://legacy:8080": "unknown"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// - `200 OK` with the probe results, which is empty when no route handler
+///   is registered
+///
+/// # Design Notes
+///
+/// Intended for debugging deployments, not for load balancer probes: it
+/// generates traffic to every backend on each call.
+pub async fn upstream_health_check(
+    route_handler: Option<web::Data<RouteHandler>>,
+) -> Result<HttpResponse> {
+    let mut upstreams = serde_json::Map::new();
+    if let Some(handler) = route_handler {
+        for (service_key, health) in handler.probe_upstreams(UPSTREAM_PROBE_TIMEOUT).await {
+            let value = match health {
+                UpstreamHealth::Unknown => json!("unknown"),
+                UpstreamHealth::Probed {
+                    healthy,
+                    status_code,
+                    latency_ms,
+                } => json!({
+                    "healthy": healthy,
+                    "status_code": status_code,
+                    "latency_ms": latency_ms
+                }),
+            };
+            upstreams.insert(service_key, value);
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(upstreams))
+}
+
 /// Configures health check routes for Actix Web service.
 /// 
 /// This function registers all health-related endpoints with the Actix Web service
@@ -145,6 +210,7 @@ pub async fn liveness_check() -> Result<HttpResponse> {
 /// - `GET /health` - General health check with detailed information
 /// - `GET /ready` - Kubernetes readiness probe endpoint
 /// - `GET /live` - Kubernetes liveness probe endpoint
+/// - `GET /health/upstreams` - Active health probes of every backend
 /// 
 /// # Parameters
 /// 
@@ -164,10 +230,12 @@ pub async fn liveness_check() -> Result<HttpResponse> {
 /// All health endpoints are optimized for:
 /// - Minimal response time (< 1ms typical)
 /// - Low CPU usage
-/// - No external dependencies
+/// - No external dependencies, except `/health/upstreams`
 /// - High concurrent request handling
 pub fn configure_health(cfg: &mut web::ServiceConfig) {
+    Lazy::force(&START_TIME);
     cfg.route("/health", web::get().to(health_check))
        .route("/ready", web::get().to(readiness_check))
-       .route("/live", web::get().to(liveness_check));
+       .route("/live", web::get().to(liveness_check))
+       .route("/health/upstreams", web::get().to(upstream_health_check));
 }
\ No newline at end of file
@@ -1,6 +1,11 @@
 use crate::services::http::RouteHandler;
 use actix_web::{web, HttpRequest};
 
+/// Gateway-wide request body limit in bytes. Per-route `max_body_bytes`
+/// limits can only be stricter than this, except on routes that stream
+/// request bodies, which are only bound by their own limit.
+pub const MAX_PAYLOAD_BYTES: usize = 1024 * 1024; // 1MB
+
 /// Configures the main HTTP proxy route for the kairos-rs gateway.
 /// 
 /// This function sets up the primary route handler that processes all incoming
@@ -31,7 +36,7 @@ use actix_web::{web, HttpRequest};
 /// # Payload Size Limits
 /// 
 /// Security limits are enforced to prevent resource exhaustion:
-/// - **Raw Payload**: 1MB maximum for any request body
+/// - **Raw Payload**: 1MB maximum for any buffered request body
 /// - **JSON Payload**: 1MB maximum for JSON-formatted requests
 /// - **Protection**: Guards against memory exhaustion attacks
 /// 
@@ -49,7 +54,7 @@ use actix_web::{web, HttpRequest};
 /// use actix_web::{App, web};
 /// use kairos_rs::routes::http::configure_route;
 /// use kairos_rs::services::http::RouteHandler;
-/// use kairos_rs::models::router::{Router, Backend, Protocol};
+/// use kairos_rs::models::router::{Router, Backend};
 /// 
 /// // Create route handler with configuration
 /// let routes = vec![
@@ -59,19 +64,14 @@ use actix_web::{web, HttpRequest};
 ///         external_path: "/api/users/{id}".to_string(),
 ///         internal_path: "/v1/user/{id}".to_string(),
 ///         methods: vec!["GET".to_string(), "PUT".to_string()],
-///         auth_required: false,
 ///         backends: Some(vec![Backend {
 ///             host: "http://backend".to_string(),
 ///             port: 8080,
 ///             weight: 1,
 ///             health_check_path: None,
+///             timeout_secs: None,
 ///         }]),
-///         load_balancing_strategy: Default::default(),
-///         retry: None,
-///         protocol: Protocol::Http,
-///         request_transformation: None,
-///         response_transformation: None,
-///         ai_policy: None,
+///         ..Default::default()
 ///     }
 /// ];
 /// let handler = RouteHandler::new(routes, 30);
@@ -99,12 +99,12 @@ use actix_web::{web, HttpRequest};
 /// resources (HTTP client, route matcher) safely across threads.
 #[allow(dead_code)] // Public API for custom route configuration
 pub fn configure_route(cfg: &mut web::ServiceConfig, handler: RouteHandler) {
-    cfg.app_data(web::PayloadConfig::new(1024 * 1024)) // 1MB payload limit (reduced from 10MB)
-        .app_data(web::JsonConfig::default().limit(1024 * 1024)) // 1MB JSON limit
+    cfg.app_data(web::PayloadConfig::new(MAX_PAYLOAD_BYTES)) // 1MB payload limit (reduced from 10MB)
+        .app_data(web::JsonConfig::default().limit(MAX_PAYLOAD_BYTES)) // 1MB JSON limit
         .service(
-            web::resource("/{tail:.*}").to(move |req: HttpRequest, body: web::Bytes| {
+            web::resource("/{tail:.*}").to(move |req: HttpRequest, payload: web::Payload| {
                 let handler: RouteHandler = handler.clone();
-                async move { handler.handle_request(req, body).await }
+                async move { handler.handle_payload(req, payload).await }
             }),
         );
 }
@@ -0,0 +1,56 @@
+//! Redirects plain HTTP requests to the gateway's HTTPS listener.
+//!
+//! Served on `KAIROS_HTTP_REDIRECT_PORT` when the gateway terminates TLS
+//! itself, so clients that start on `http://` end up on `https://`. The HSTS
+//! header on HTTPS responses keeps them there afterwards.
+
+use actix_web::{
+    http::{header, uri::Authority},
+    web, HttpRequest, HttpResponse,
+};
+
+/// Answers every request with `301 Moved Permanently` to the same host, path
+/// and query on `https_port`.
+///
+/// The port is left out of the location when it is 443. Requests without a
+/// usable `Host` header get `400 Bad Request`.
+///
+/// # Examples
+///
+/// ```rust
+/// use actix_web::App;
+/// use kairos_rs::routes::https_redirect::configure_https_redirect;
+///
+/// let app = App::new().configure(|cfg| configure_https_redirect(cfg, 443));
+/// ```
+pub fn configure_https_redirect(cfg: &mut web::ServiceConfig, https_port: u16) {
+    cfg.default_service(web::to(move |req: HttpRequest| async move {
+        https_redirect(&req, https_port)
+    }));
+}
+
+fn https_redirect(req: &HttpRequest, https_port: u16) -> HttpResponse {
+    let host = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<Authority>().ok());
+    let Some(host) = host else {
+        return HttpResponse::BadRequest().body("Missing or invalid Host header");
+    };
+
+    let port = if https_port == 443 {
+        String::new()
+    } else {
+        format!(":{}", https_port)
+    };
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+
+    HttpResponse::MovedPermanently()
+        .insert_header((header::LOCATION, format!("https://{}{}{}", host.host(), port, path)))
+        .finish()
+}
@@ -9,8 +9,13 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::middleware::auth::{JwtAuth, JwtConfig};
+use crate::middleware::rate_limit::{RateLimitSnapshot, RateLimitStore};
 use crate::models::router::Router;
 use crate::models::settings::{AiSettings, Settings};
+use crate::routes::config_reload;
+use crate::services::circuit_breaker::CircuitState;
+use crate::services::http::RouteHandler;
 
 /// Shared state for route management operations.
 ///
@@ -45,7 +50,25 @@ impl RouteManager {
     ///     version: 1,
     ///     jwt: None,
     ///     rate_limit: None,
+    ///     disable_rate_limiting: false,
     ///     ai: None,
+    ///     streaming: None,
+    ///     metrics: None,
+    ///     warmup: None,
+    ///     errors: None,
+    ///     fault_injection_enabled: false,
+    ///     max_connections_per_ip: None,
+    ///     max_concurrent_requests: None,
+    ///     forwarded_headers: None,
+    ///     default_upstream_headers: Default::default(),
+    ///     circuit_breaker_bypass: None,
+    ///     debug_overrides: None,
+    ///     request_start_header: None,
+    ///     trailing_slash: Default::default(),
+    ///     server: None,
+    ///     root_response: None,
+    ///     backend_pools: Default::default(),
+    ///     tcp_proxies: Vec::new(),
     ///     routers: vec![],
     /// };
     /// let manager = RouteManager::new(settings, "config.json".to_string());
@@ -229,8 +252,13 @@ pub async fn create_route(
     manager: web::Data<RouteManager>,
     route: web::Json<Router>,
 ) -> impl Responder {
-    // Validate the route
-    if let Err(e) = route.validate() {
+    // Validate the route with its backend pool resolved
+    let pools = manager.settings.read().await.backend_pools.clone();
+    let mut resolved = Router::clone(&route);
+    if let Err(e) = resolved
+        .resolve_backend_pool(&pools)
+        .and_then(|_| resolved.validate())
+    {
         return HttpResponse::BadRequest().json(RouteResponse {
             success: false,
             message: format!("Route validation failed: {}", e),
@@ -315,8 +343,13 @@ pub async fn update_route(
 ) -> impl Responder {
     let external_path = format!("/{}", path.into_inner());
 
-    // Validate the route
-    if let Err(e) = route.validate() {
+    // Validate the route with its backend pool resolved
+    let pools = manager.settings.read().await.backend_pools.clone();
+    let mut resolved = Router::clone(&route);
+    if let Err(e) = resolved
+        .resolve_backend_pool(&pools)
+        .and_then(|_| resolved.validate())
+    {
         return HttpResponse::BadRequest().json(RouteResponse {
             success: false,
             message: format!("Route validation failed: {}", e),
@@ -841,6 +874,316 @@ pub async fn update_ai_config(
     }))
 }
 
+/// Force a circuit breaker back to closed
+///
+/// # Endpoint
+///
+/// `POST /admin/circuit-breakers/{service}/reset`
+///
+/// # Parameters
+///
+/// * `service` - Circuit breaker key as reported by `/metrics`, i.e. the
+///   backend's `host:port` (URL-encode it when it contains `/`)
+///
+/// # Response
+///
+/// `200 OK` once the breaker is closed and its counters are cleared, or
+/// `404 Not Found` if no breaker exists for `service`. A breaker forced open
+/// or closed returns to automatic control.
+///
+/// # Example
+///
+/// ```bash
+/// curl -X POST http://localhost:5900/admin/circuit-breakers/http%3A%2F%2Fbackend%3A8080/reset \
+///   -H "Authorization: Bearer $TOKEN"
+/// ```
+pub async fn reset_circuit_breaker(
+    handler: web::Data<RouteHandler>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let service = path.into_inner();
+
+    if handler.reset_circuit_breaker(&service).await {
+        circuit_breaker_state_response(&service, CircuitState::Closed, "reset to closed")
+    } else {
+        circuit_breaker_not_found(&service)
+    }
+}
+
+/// Hold a circuit breaker open for maintenance
+///
+/// # Endpoint
+///
+/// `POST /admin/circuit-breakers/{service}/open`
+///
+/// # Parameters
+///
+/// * `service` - Circuit breaker key, as for the reset endpoint
+///
+/// # Response
+///
+/// `200 OK` once the breaker is forced open, or `404 Not Found` if no
+/// breaker exists for `service`. Requests to the backend fail fast until
+/// `POST /admin/circuit-breakers/{service}/auto` releases the breaker.
+///
+/// # Example
+///
+/// ```bash
+/// curl -X POST http://localhost:5900/admin/circuit-breakers/http%3A%2F%2Fbackend%3A8080/open \
+///   -H "Authorization: Bearer $TOKEN"
+/// ```
+pub async fn force_circuit_breaker_open(
+    handler: web::Data<RouteHandler>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let service = path.into_inner();
+
+    if handler.force_circuit_breaker_open(&service).await {
+        circuit_breaker_state_response(&service, CircuitState::ForcedOpen, "forced open")
+    } else {
+        circuit_breaker_not_found(&service)
+    }
+}
+
+/// Hold a circuit breaker closed
+///
+/// # Endpoint
+///
+/// `POST /admin/circuit-breakers/{service}/close`
+///
+/// # Response
+///
+/// `200 OK` once the breaker is forced closed, or `404 Not Found` if no
+/// breaker exists for `service`. Failures do not open the breaker until
+/// `POST /admin/circuit-breakers/{service}/auto` releases it.
+pub async fn force_circuit_breaker_closed(
+    handler: web::Data<RouteHandler>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let service = path.into_inner();
+
+    if handler.force_circuit_breaker_closed(&service).await {
+        circuit_breaker_state_response(&service, CircuitState::ForcedClosed, "forced closed")
+    } else {
+        circuit_breaker_not_found(&service)
+    }
+}
+
+/// Return a forced circuit breaker to automatic control
+///
+/// # Endpoint
+///
+/// `POST /admin/circuit-breakers/{service}/auto`
+///
+/// # Response
+///
+/// `200 OK` with the state the breaker resumes from: `half_open` after a
+/// forced open, so recovery is probed as usual, or `closed` after a forced
+/// close. Breakers that are not forced are left unchanged. `404 Not Found`
+/// if no breaker exists for `service`.
+pub async fn release_circuit_breaker(
+    handler: web::Data<RouteHandler>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let service = path.into_inner();
+
+    match handler.release_circuit_breaker(&service).await {
+        Some(state) => circuit_breaker_state_response(&service, state, "under automatic control"),
+        None => circuit_breaker_not_found(&service),
+    }
+}
+
+fn circuit_breaker_state_response(service: &str, state: CircuitState, action: &str) -> HttpResponse {
+    let state = match state {
+        CircuitState::Closed => "closed",
+        CircuitState::Open => "open",
+        CircuitState::HalfOpen => "half_open",
+        CircuitState::ForcedOpen => "forced_open",
+        CircuitState::ForcedClosed => "forced_closed",
+    };
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "message": format!("Circuit breaker {} {}", service, action),
+        "service": service,
+        "state": state
+    }))
+}
+
+fn circuit_breaker_not_found(service: &str) -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({
+        "success": false,
+        "message": format!("Circuit breaker not found: {}", service)
+    }))
+}
+
+/// Show how a dynamic route is matched
+///
+/// # Endpoint
+///
+/// `GET /admin/routes/{external_path}/debug`
+///
+/// # Parameters
+///
+/// * `external_path` - URL-encoded external path (e.g., `/api/users/{id}`)
+///
+/// # Response
+///
+/// The regex the route's pattern was compiled to, its parameter names, and
+/// how many request paths matched it (`hits`) or were tried against it
+/// without matching (`misses`). Counters survive reloads that keep the
+/// route's pattern. Static routes are matched by exact lookup and return
+/// `404 Not Found`, like unknown routes.
+///
+/// # Example
+///
+/// ```bash
+/// curl http://localhost:5900/admin/routes/%2Fapi%2Fusers%2F%7Bid%7D/debug
+/// ```
+pub async fn route_debug(
+    handler: web::Data<RouteHandler>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let external_path = path.into_inner();
+
+    match handler.route_debug(&external_path) {
+        Some(info) => HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "external_path": info.external_path,
+            "regex": info.regex,
+            "param_names": info.param_names,
+            "hits": info.hits,
+            "misses": info.misses
+        })),
+        None => HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "message": format!("Dynamic route not found: {}", external_path)
+        })),
+    }
+}
+
+/// Export the advanced rate limiter's counters
+///
+/// # Endpoint
+///
+/// `GET /admin/rate-limit/snapshot`
+///
+/// # Response
+///
+/// Every rate limit entry by key, with timestamps given as ages in
+/// milliseconds so another replica can restore them. Returns
+/// `404 Not Found` when no `rate_limit` section is configured; the basic
+/// limiter's state cannot be exported.
+///
+/// # Example
+///
+/// ```bash
+/// curl http://localhost:5900/admin/rate-limit/snapshot > rate-limit.json
+/// ```
+pub async fn rate_limit_snapshot(store: Option<web::Data<RateLimitStore>>) -> impl Responder {
+    let Some(store) = store else {
+        return rate_limiting_not_configured();
+    };
+
+    match store.snapshot() {
+        Ok(snapshot) => HttpResponse::Ok().json(snapshot),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "success": false,
+            "message": format!("Failed to snapshot rate limits: {}", e)
+        })),
+    }
+}
+
+/// Import rate limiter counters exported by another replica
+///
+/// # Endpoint
+///
+/// `POST /admin/rate-limit/restore`
+///
+/// # Request Body
+///
+/// A snapshot as returned by `GET /admin/rate-limit/snapshot`. Entries
+/// replace existing entries with the same key; other entries are kept.
+///
+/// Only served when JWT is configured, since restored counters can lift
+/// any client's limits.
+///
+/// # Example
+///
+/// ```bash
+/// curl -X POST http://localhost:5900/admin/rate-limit/restore \
+///   -H "Authorization: Bearer $TOKEN" \
+///   -H "Content-Type: application/json" -d @rate-limit.json
+/// ```
+pub async fn rate_limit_restore(
+    store: Option<web::Data<RateLimitStore>>,
+    snapshot: web::Json<RateLimitSnapshot>,
+) -> impl Responder {
+    let Some(store) = store else {
+        return rate_limiting_not_configured();
+    };
+
+    match store.restore(&snapshot) {
+        Ok(restored) => HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": format!("Restored {} rate limit entries", restored),
+            "restored": restored
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "success": false,
+            "message": format!("Failed to restore rate limits: {}", e)
+        })),
+    }
+}
+
+fn rate_limiting_not_configured() -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({
+        "success": false,
+        "message": "Advanced rate limiting is not configured"
+    }))
+}
+
+/// Configure gateway administration endpoints
+///
+/// When JWT is configured, every endpoint registered here requires a valid
+/// bearer token. Without JWT settings the endpoints that change gateway
+/// state, such as forcing circuit breakers, reloading the configuration or
+/// restoring rate limit counters, are not registered at all, and the others are open like the route
+/// management endpoints.
+pub fn configure_admin(cfg: &mut web::ServiceConfig, settings: &Settings) {
+    let resources = [
+        web::resource("/admin/routes/{external_path:.+}/debug")
+            .route(web::get().to(route_debug)),
+        web::resource("/admin/rate-limit/snapshot").route(web::get().to(rate_limit_snapshot)),
+    ];
+    let state_changing = [
+        web::resource("/admin/circuit-breakers/{service:.+}/reset")
+            .route(web::post().to(reset_circuit_breaker)),
+        web::resource("/admin/circuit-breakers/{service:.+}/open")
+            .route(web::post().to(force_circuit_breaker_open)),
+        web::resource("/admin/circuit-breakers/{service:.+}/close")
+            .route(web::post().to(force_circuit_breaker_closed)),
+        web::resource("/admin/circuit-breakers/{service:.+}/auto")
+            .route(web::post().to(release_circuit_breaker)),
+        web::resource("/admin/config/reload")
+            .route(web::post().to(config_reload::admin_reload_config)),
+        web::resource("/admin/rate-limit/restore").route(web::post().to(rate_limit_restore)),
+    ];
+
+    let Some(jwt) = &settings.jwt else {
+        for resource in resources {
+            cfg.service(resource);
+        }
+        return;
+    };
+    let mut jwt_config = JwtConfig::new(jwt.secret.clone())
+        .with_required_claims(jwt.required_claims.clone());
+    jwt_config.issuer = jwt.issuer.clone();
+    jwt_config.audience = jwt.audience.clone();
+    for resource in resources.into_iter().chain(state_changing) {
+        cfg.service(resource.wrap(JwtAuth::new(jwt_config.clone())));
+    }
+}
+
 /// Configure route management endpoints
 pub fn configure_management(cfg: &mut web::ServiceConfig) {
     cfg.service(list_routes)
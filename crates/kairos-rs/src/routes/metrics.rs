@@ -4,14 +4,23 @@
 //! Kairos-rs gateway, including request counts, response times, error rates,
 //! histograms, memory usage, per-route statistics, and system health indicators.
 
-use actix_web::{web, HttpResponse, Result};
-use crate::services::http::RouteHandler;
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Result};
+use base64::{engine::general_purpose, Engine};
+use crate::middleware::auth::{validate_jwt_token, JwtConfig};
+use crate::models::settings::{MetricsAuth, MetricsConfig};
+use crate::services::circuit_breaker::CircuitState;
+use crate::services::http::{BackendStatus, RouteHandler};
+use crate::services::metrics_sink::MetricsSink;
 use crate::services::metrics_store::{MetricsStore, AggregationInterval};
+use crate::utils::latency_histogram::LatencyHistogram;
+use crate::utils::sharded_counter::ShardedCounter;
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Thread-safe metrics collector for comprehensive gateway observability.
 /// 
@@ -35,6 +44,12 @@ use std::time::{Duration, Instant};
 /// worker threads. The collector can be safely cloned and shared across
 /// the entire application.
 /// 
+/// The counters bumped by every request, such as `requests_total`, the
+/// byte totals and the response time buckets, are [`ShardedCounter`]s:
+/// each thread increments its own shard, and the shards are summed when the
+/// metrics are read. See `benches/metrics_contention.rs` for the effect
+/// under many threads.
+/// 
 /// # Usage
 /// 
 /// The collector is typically initialized once at application startup
@@ -59,69 +74,330 @@ use std::time::{Duration, Instant};
 #[derive(Debug, Clone)]
 pub struct MetricsCollector {
     /// Total number of HTTP requests processed (counter)
-    pub requests_total: Arc<AtomicU64>,
+    pub requests_total: Arc<ShardedCounter>,
     /// Number of successful HTTP requests (2xx status codes)
-    pub requests_success: Arc<AtomicU64>,
+    pub requests_success: Arc<ShardedCounter>,
     /// Number of failed HTTP requests (4xx, 5xx status codes)
-    pub requests_error: Arc<AtomicU64>,
+    pub requests_error: Arc<ShardedCounter>,
     /// Sum of all response times in milliseconds for average calculation
-    pub response_time_sum: Arc<AtomicU64>,
+    pub response_time_sum: Arc<ShardedCounter>,
     /// Current number of active HTTP connections being processed
     pub active_connections: Arc<AtomicU64>,
     /// Peak number of concurrent connections observed
     pub peak_connections: Arc<AtomicU64>,
     /// Total bytes of requests processed
-    pub request_bytes_total: Arc<AtomicU64>,
+    pub request_bytes_total: Arc<ShardedCounter>,
     /// Total bytes of responses sent
-    pub response_bytes_total: Arc<AtomicU64>,
+    pub response_bytes_total: Arc<ShardedCounter>,
     /// Number of requests with response time < 100ms
-    pub response_time_bucket_100ms: Arc<AtomicU64>,
+    pub response_time_bucket_100ms: Arc<ShardedCounter>,
     /// Number of requests with response time < 500ms
-    pub response_time_bucket_500ms: Arc<AtomicU64>,
+    pub response_time_bucket_500ms: Arc<ShardedCounter>,
     /// Number of requests with response time < 1000ms
-    pub response_time_bucket_1s: Arc<AtomicU64>,
+    pub response_time_bucket_1s: Arc<ShardedCounter>,
     /// Number of requests with response time < 5000ms
-    pub response_time_bucket_5s: Arc<AtomicU64>,
+    pub response_time_bucket_5s: Arc<ShardedCounter>,
     /// Number of requests with response time >= 5000ms
-    pub response_time_bucket_inf: Arc<AtomicU64>,
+    pub response_time_bucket_inf: Arc<ShardedCounter>,
     /// Number of 4xx client errors
-    pub http_4xx_errors: Arc<AtomicU64>,
+    pub http_4xx_errors: Arc<ShardedCounter>,
     /// Number of 5xx server errors
-    pub http_5xx_errors: Arc<AtomicU64>,
+    pub http_5xx_errors: Arc<ShardedCounter>,
     /// Number of timeout errors
     pub timeout_errors: Arc<AtomicU64>,
     /// Number of connection errors
     pub connection_errors: Arc<AtomicU64>,
+    /// Number of requests rejected because a backend's circuit breaker was open
+    pub circuit_open_errors: Arc<AtomicU64>,
+    /// Attempts rejected by an open circuit breaker keyed by upstream (`host:port`)
+    pub circuit_open_rejections: Arc<DashMap<String, u64>>,
+    /// Requests sent to each upstream and how many of them failed, keyed by
+    /// upstream (`host:port`)
+    pub backend_requests: Arc<DashMap<String, BackendRequestStats>>,
+    /// Number of TCP proxy connections currently open to an upstream
+    pub tcp_active_connections: Arc<AtomicU64>,
+    /// Number of TCP proxy connections opened to an upstream
+    pub tcp_connections_total: Arc<AtomicU64>,
+    /// Number of mirrored requests answered by the mirror backend without a 5xx
+    pub mirror_requests_success: Arc<AtomicU64>,
+    /// Number of mirrored requests that failed or got a 5xx from the mirror backend
+    pub mirror_requests_failed: Arc<AtomicU64>,
+    /// Number of upstream responses that failed their route's response schema
+    pub response_schema_violations: Arc<AtomicU64>,
+    /// Number of warnings from validating the configuration in effect
+    pub config_validation_warnings: Arc<AtomicU64>,
+    /// Number of requests currently admitted under `max_concurrent_requests`
+    pub concurrent_requests: Arc<AtomicU64>,
+    /// Number of requests rejected because `max_concurrent_requests` was reached
+    pub requests_shed: Arc<AtomicU64>,
+    /// Response counts indexed by status code (100-999), exported per seen code
+    pub responses_by_status: Arc<[AtomicU64]>,
+    /// Fine-grained response times, for the p50/p95/p99 gauges
+    pub response_time_histogram: Arc<LatencyHistogram>,
+    /// Latest traced request per response time bucket, exported as OpenMetrics exemplars
+    pub latency_exemplars: Arc<Mutex<[Option<Exemplar>; LATENCY_BUCKETS_MS.len() + 1]>>,
+    /// Upper bounds of the request body size histogram, in bytes
+    pub body_size_buckets: Arc<[u64]>,
+    /// Request body size statistics keyed by route `external_path`
+    pub route_body_sizes: Arc<Mutex<HashMap<String, RouteBodyStats>>>,
+    /// Whether `record_request` keeps per-route statistics in `route_metrics`
+    pub collect_per_route: bool,
+    /// Request statistics keyed by the matched route's `external_path`
+    pub route_metrics: Arc<DashMap<String, RouteMetrics>>,
+    /// Latency target statistics of routes with `sla_ms`, keyed by `external_path`
+    pub route_sla: Arc<DashMap<String, RouteSlaStats>>,
+    /// Destination that also receives requests, error counters and gauges
+    /// as they are recorded, such as a StatsD server
+    pub sink: Option<Arc<dyn MetricsSink>>,
     /// Application start time for uptime calculations
     pub start_time: Instant,
 }
 
+/// Trace exemplar attached to a response time bucket in OpenMetrics output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exemplar {
+    /// W3C trace ID of the request
+    pub trace_id: String,
+    /// Observed response time in milliseconds
+    pub value_ms: u64,
+    /// Unix time of the observation in seconds
+    pub timestamp: f64,
+}
+
+/// Request body size statistics of a single route.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteBodyStats {
+    /// Observations per `body_size_buckets` bucket (not cumulative), followed by the `+Inf` bucket
+    pub bucket_counts: Vec<u64>,
+    /// Sum of all observed body sizes in bytes
+    pub sum_bytes: u64,
+    /// Number of observed bodies
+    pub count: u64,
+    /// Number of requests rejected for exceeding the route's `max_body_bytes`
+    pub too_large: u64,
+}
+
+/// Request statistics of a single route.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteMetrics {
+    /// Number of requests matched to the route
+    pub requests: u64,
+    /// Number of those requests that did not succeed
+    pub errors: u64,
+    /// Sum of their response times in milliseconds
+    pub response_time_sum_ms: u64,
+    /// Number of requests on the route currently being handled
+    pub in_flight: u64,
+    /// The route's `tags`, exported as extra labels
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Latency target statistics of a single route with `sla_ms`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteSlaStats {
+    /// Number of requests slower than the route's `sla_ms`
+    pub breaches: u64,
+    /// Whether each of the latest [`SLA_WINDOW_REQUESTS`] requests breached
+    /// the target, oldest first
+    pub recent: VecDeque<bool>,
+    /// Number of breaches in `recent`
+    pub recent_breaches: u64,
+    /// The route's `tags`, exported as extra labels
+    pub tags: BTreeMap<String, String>,
+}
+
+impl RouteSlaStats {
+    /// Returns the share of recent requests that met the target, from 0.0
+    /// to 1.0, or 1.0 before any request.
+    pub fn compliance(&self) -> f64 {
+        if self.recent.is_empty() {
+            1.0
+        } else {
+            1.0 - self.recent_breaches as f64 / self.recent.len() as f64
+        }
+    }
+}
+
+/// Request statistics of a single backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BackendRequestStats {
+    /// Number of attempts sent to the backend
+    pub requests: u64,
+    /// Number of those attempts that failed
+    pub errors: u64,
+}
+
+impl RouteMetrics {
+    /// Returns the average response time in milliseconds.
+    pub fn response_time_avg(&self) -> f64 {
+        if self.requests > 0 {
+            self.response_time_sum_ms as f64 / self.requests as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Gateway-wide metrics at one point in time, served by `/metrics.json`.
+///
+/// Field names match the UI's `MetricsData`, so dashboards and clients can
+/// deserialize it directly instead of parsing Prometheus text.
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsSnapshot {
+    /// When the snapshot was taken (RFC 3339)
+    pub timestamp: String,
+    pub requests_total: u64,
+    pub requests_success: u64,
+    pub requests_error: u64,
+    /// Percentage of successful requests, 100 before any request
+    pub success_rate: f64,
+    /// Average response time in milliseconds
+    pub response_time_avg: f64,
+    /// Sum of all response times in milliseconds
+    pub response_time_sum: u64,
+    /// Median response time in milliseconds
+    pub response_time_p50: f64,
+    /// 95th percentile response time in milliseconds
+    pub response_time_p95: f64,
+    /// 99th percentile response time in milliseconds
+    pub response_time_p99: f64,
+    pub active_connections: u64,
+    pub peak_connections: u64,
+    /// Same as `active_connections`
+    pub requests_in_flight: u64,
+    pub http_4xx_errors: u64,
+    pub http_5xx_errors: u64,
+    pub timeout_errors: u64,
+    pub connection_errors: u64,
+    pub circuit_open_errors: u64,
+    pub response_schema_violations: u64,
+    /// Requests answered within 100ms
+    pub response_time_bucket_100ms: u64,
+    /// Requests answered within 500ms
+    pub response_time_bucket_500ms: u64,
+    /// Requests answered within 1s
+    pub response_time_bucket_1s: u64,
+    /// Requests answered within 5s
+    pub response_time_bucket_5s: u64,
+    /// Requests that took longer than 5s (not cumulative)
+    pub response_time_bucket_inf: u64,
+    pub request_bytes_total: u64,
+    pub response_bytes_total: u64,
+    /// Request and response bytes combined
+    pub data_transferred_bytes: u64,
+    pub uptime_seconds: u64,
+    /// Circuit breaker of every upstream, sorted by upstream
+    pub circuit_breakers: Vec<CircuitBreakerSnapshot>,
+}
+
+/// State of one upstream's circuit breaker in a [`MetricsSnapshot`].
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerSnapshot {
+    /// The upstream (`host:port`) the breaker protects; named to match the
+    /// UI's `CircuitBreakerMetrics`
+    pub route: String,
+    pub state: CircuitState,
+    pub failure_count: u64,
+    pub success_count: u64,
+}
+
+/// Default upper bounds of the request body size histogram, in bytes.
+pub const DEFAULT_BODY_SIZE_BUCKETS: [u64; 5] = [1_024, 16_384, 131_072, 1_048_576, 10_485_760];
+
+/// Number of latest requests per route `kairos_sla_compliance` is computed over.
+pub const SLA_WINDOW_REQUESTS: usize = 1000;
+
+/// Upper bounds of the response time histogram buckets, in milliseconds.
+const LATENCY_BUCKETS_MS: [u64; 4] = [100, 500, 1000, 5000];
+
+/// Content type of the OpenMetrics exposition format.
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Most route paths listed in the `routes` label of `kairos_backend_up`.
+const MAX_BACKEND_ROUTE_LABELS: usize = 5;
+
+/// Range of HTTP status codes tracked by `responses_by_status`.
+const STATUS_CODE_MIN: u16 = 100;
+const STATUS_CODE_MAX: u16 = 999;
+
 impl Default for MetricsCollector {
     fn default() -> Self {
         Self {
-            requests_total: Arc::new(AtomicU64::new(0)),
-            requests_success: Arc::new(AtomicU64::new(0)),
-            requests_error: Arc::new(AtomicU64::new(0)),
-            response_time_sum: Arc::new(AtomicU64::new(0)),
+            requests_total: Arc::new(ShardedCounter::new(0)),
+            requests_success: Arc::new(ShardedCounter::new(0)),
+            requests_error: Arc::new(ShardedCounter::new(0)),
+            response_time_sum: Arc::new(ShardedCounter::new(0)),
             active_connections: Arc::new(AtomicU64::new(0)),
             peak_connections: Arc::new(AtomicU64::new(0)),
-            request_bytes_total: Arc::new(AtomicU64::new(0)),
-            response_bytes_total: Arc::new(AtomicU64::new(0)),
-            response_time_bucket_100ms: Arc::new(AtomicU64::new(0)),
-            response_time_bucket_500ms: Arc::new(AtomicU64::new(0)),
-            response_time_bucket_1s: Arc::new(AtomicU64::new(0)),
-            response_time_bucket_5s: Arc::new(AtomicU64::new(0)),
-            response_time_bucket_inf: Arc::new(AtomicU64::new(0)),
-            http_4xx_errors: Arc::new(AtomicU64::new(0)),
-            http_5xx_errors: Arc::new(AtomicU64::new(0)),
+            request_bytes_total: Arc::new(ShardedCounter::new(0)),
+            response_bytes_total: Arc::new(ShardedCounter::new(0)),
+            response_time_bucket_100ms: Arc::new(ShardedCounter::new(0)),
+            response_time_bucket_500ms: Arc::new(ShardedCounter::new(0)),
+            response_time_bucket_1s: Arc::new(ShardedCounter::new(0)),
+            response_time_bucket_5s: Arc::new(ShardedCounter::new(0)),
+            response_time_bucket_inf: Arc::new(ShardedCounter::new(0)),
+            http_4xx_errors: Arc::new(ShardedCounter::new(0)),
+            http_5xx_errors: Arc::new(ShardedCounter::new(0)),
             timeout_errors: Arc::new(AtomicU64::new(0)),
             connection_errors: Arc::new(AtomicU64::new(0)),
+            circuit_open_errors: Arc::new(AtomicU64::new(0)),
+            circuit_open_rejections: Arc::new(DashMap::new()),
+            backend_requests: Arc::new(DashMap::new()),
+            tcp_active_connections: Arc::new(AtomicU64::new(0)),
+            tcp_connections_total: Arc::new(AtomicU64::new(0)),
+            mirror_requests_success: Arc::new(AtomicU64::new(0)),
+            mirror_requests_failed: Arc::new(AtomicU64::new(0)),
+            response_schema_violations: Arc::new(AtomicU64::new(0)),
+            config_validation_warnings: Arc::new(AtomicU64::new(0)),
+            concurrent_requests: Arc::new(AtomicU64::new(0)),
+            requests_shed: Arc::new(AtomicU64::new(0)),
+            responses_by_status: (STATUS_CODE_MIN..=STATUS_CODE_MAX)
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+            response_time_histogram: Arc::new(LatencyHistogram::new()),
+            latency_exemplars: Arc::new(Mutex::new(Default::default())),
+            body_size_buckets: Arc::new(DEFAULT_BODY_SIZE_BUCKETS),
+            route_body_sizes: Arc::new(Mutex::new(HashMap::new())),
+            collect_per_route: false,
+            route_metrics: Arc::new(DashMap::new()),
+            route_sla: Arc::new(DashMap::new()),
+            sink: None,
             start_time: Instant::now(),
         }
     }
 }
 
 impl MetricsCollector {
+    /// Sets the upper bounds of the request body size histogram, in bytes.
+    ///
+    /// Bounds are sorted and deduplicated; an empty list keeps the defaults.
+    pub fn with_body_size_buckets(mut self, mut buckets: Vec<u64>) -> Self {
+        buckets.sort_unstable();
+        buckets.dedup();
+        if !buckets.is_empty() {
+            self.body_size_buckets = buckets.into();
+        }
+        self
+    }
+
+    /// Enables per-route request statistics.
+    ///
+    /// Routes are keyed by their `external_path` pattern, not the concrete
+    /// request path, so the number of series is bounded by the configuration.
+    pub fn with_per_route_metrics(mut self, enabled: bool) -> Self {
+        self.collect_per_route = enabled;
+        self
+    }
+
+    /// Forwards recorded metrics to `sink` as well.
+    ///
+    /// The in-memory counters are kept either way, so `/metrics` and the
+    /// admin dashboards keep working.
+    pub fn with_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
     /// Records the completion of an HTTP request with detailed timing and status information.
     /// 
     /// This method atomically updates multiple metrics to track request patterns,
@@ -135,14 +411,18 @@ impl MetricsCollector {
     /// * `status_code` - HTTP status code for error categorization
     /// * `request_bytes` - Size of the request in bytes (optional)
     /// * `response_bytes` - Size of the response in bytes (optional)
+    /// * `route` - `external_path` of the matched route, if any
     /// 
     /// # Metrics Updated
     /// 
     /// - Increments `requests_total` counter
     /// - Updates response time histogram buckets
     /// - Categorizes errors by type (4xx, 5xx, timeout, connection)
+    /// - Increments the counter for the exact `status_code`
     /// - Tracks data transfer volumes
     /// - Updates average response time calculation
+    /// - Updates the route's statistics when per-route collection is enabled
+    /// - Forwards the request to the sink, if one is attached
     /// 
     /// # Thread Safety
     /// 
@@ -154,8 +434,13 @@ impl MetricsCollector {
         response_time: Duration, 
         status_code: u16,
         request_bytes: Option<u64>,
-        response_bytes: Option<u64>
+        response_bytes: Option<u64>,
+        route: Option<&str>,
     ) {
+        if let Some(sink) = &self.sink {
+            sink.record_request(route, status_code, response_time);
+        }
+
         // Basic counters
         self.requests_total.fetch_add(1, Ordering::Relaxed);
         self.response_time_sum.fetch_add(response_time.as_millis() as u64, Ordering::Relaxed);
@@ -169,6 +454,7 @@ impl MetricsCollector {
         }
         
         // Update histogram buckets based on response time
+        self.response_time_histogram.record(response_time);
         let response_time_ms = response_time.as_millis() as u64;
         if response_time_ms <= 100 {
             self.response_time_bucket_100ms.fetch_add(1, Ordering::Relaxed);
@@ -185,6 +471,12 @@ impl MetricsCollector {
             self.response_time_bucket_inf.fetch_add(1, Ordering::Relaxed);
         }
         
+        // Per-status-code counter
+        if (STATUS_CODE_MIN..=STATUS_CODE_MAX).contains(&status_code) {
+            self.responses_by_status[(status_code - STATUS_CODE_MIN) as usize]
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        
         // Categorize success/error and track specific error types
         if success {
             self.requests_success.fetch_add(1, Ordering::Relaxed);
@@ -198,36 +490,375 @@ impl MetricsCollector {
                 _ => {} // Other error types handled separately
             }
         }
+
+        if let Some(route) = route.filter(|_| self.collect_per_route) {
+            let update = |stats: &mut RouteMetrics| {
+                stats.requests += 1;
+                stats.response_time_sum_ms += response_time_ms;
+                if !success {
+                    stats.errors += 1;
+                }
+            };
+            // Only the first request of a route allocates its key
+            let updated = self.route_metrics.get_mut(route).map(|mut stats| update(&mut stats));
+            if updated.is_none() {
+                update(&mut self.route_metrics.entry(route.to_string()).or_default());
+            }
+        }
+    }
+
+    /// Reads every gateway-wide counter at once.
+    ///
+    /// Both `/metrics` and `/metrics.json` render from this, so the two
+    /// formats report the same values. Circuit breakers are only listed when
+    /// `route_handler` is given.
+    pub fn snapshot(&self, route_handler: Option<&RouteHandler>) -> MetricsSnapshot {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let requests_total = self.requests_total.load(Ordering::Relaxed);
+        let requests_success = self.requests_success.load(Ordering::Relaxed);
+        let response_time_sum = self.response_time_sum.load(Ordering::Relaxed);
+        let active_connections = load(&self.active_connections);
+        let request_bytes_total = self.request_bytes_total.load(Ordering::Relaxed);
+        let response_bytes_total = self.response_bytes_total.load(Ordering::Relaxed);
+        let [response_time_p50, response_time_p95, response_time_p99] =
+            self.response_time_percentiles();
+
+        let mut circuit_breakers: Vec<CircuitBreakerSnapshot> = route_handler
+            .map(|handler| handler.get_circuit_breaker_states())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(service, (state, failure_count, success_count))| CircuitBreakerSnapshot {
+                route: service,
+                state,
+                failure_count,
+                success_count,
+            })
+            .collect();
+        circuit_breakers.sort_by(|a, b| a.route.cmp(&b.route));
+
+        MetricsSnapshot {
+            timestamp: Utc::now().to_rfc3339(),
+            requests_total,
+            requests_success,
+            requests_error: self.requests_error.load(Ordering::Relaxed),
+            success_rate: if requests_total > 0 {
+                (requests_success as f64 / requests_total as f64) * 100.0
+            } else {
+                100.0
+            },
+            response_time_avg: if requests_total > 0 {
+                response_time_sum as f64 / requests_total as f64
+            } else {
+                0.0
+            },
+            response_time_sum,
+            response_time_p50,
+            response_time_p95,
+            response_time_p99,
+            active_connections,
+            peak_connections: load(&self.peak_connections),
+            requests_in_flight: active_connections,
+            http_4xx_errors: self.http_4xx_errors.load(Ordering::Relaxed),
+            http_5xx_errors: self.http_5xx_errors.load(Ordering::Relaxed),
+            timeout_errors: load(&self.timeout_errors),
+            connection_errors: load(&self.connection_errors),
+            circuit_open_errors: load(&self.circuit_open_errors),
+            response_schema_violations: load(&self.response_schema_violations),
+            response_time_bucket_100ms: self.response_time_bucket_100ms.load(Ordering::Relaxed),
+            response_time_bucket_500ms: self.response_time_bucket_500ms.load(Ordering::Relaxed),
+            response_time_bucket_1s: self.response_time_bucket_1s.load(Ordering::Relaxed),
+            response_time_bucket_5s: self.response_time_bucket_5s.load(Ordering::Relaxed),
+            response_time_bucket_inf: self.response_time_bucket_inf.load(Ordering::Relaxed),
+            request_bytes_total,
+            response_bytes_total,
+            data_transferred_bytes: request_bytes_total + response_bytes_total,
+            uptime_seconds: self.start_time.elapsed().as_secs(),
+            circuit_breakers,
+        }
+    }
+
+    /// Returns the p50, p95 and p99 response times in milliseconds.
+    pub fn response_time_percentiles(&self) -> [f64; 3] {
+        [0.5, 0.95, 0.99].map(|quantile| {
+            self.response_time_histogram
+                .value_at_quantile(quantile)
+                .as_secs_f64()
+                * 1000.0
+        })
+    }
+
+    /// Counts a request matched to `route` as in flight, and keeps the
+    /// route's `tags` for its labels.
+    ///
+    /// Only tracked when per-route collection is enabled. Must be paired with
+    /// `route_request_finished` once the request has been handled, including
+    /// when it is dropped before completing.
+    pub fn route_request_started(&self, route: &str, tags: &BTreeMap<String, String>) {
+        if !self.collect_per_route {
+            return;
+        }
+        let start = |stats: &mut RouteMetrics| {
+            stats.in_flight += 1;
+            // Tags only change with the configuration
+            if stats.tags != *tags {
+                stats.tags = tags.clone();
+            }
+        };
+        let updated = self.route_metrics.get_mut(route).map(|mut stats| start(&mut stats));
+        if updated.is_none() {
+            start(&mut self.route_metrics.entry(route.to_string()).or_default());
+        }
+    }
+
+    /// Checks a request on `route` that took `response_time` against the
+    /// route's latency target `sla`.
+    ///
+    /// Breaches are counted, and forwarded to the sink as `sla_breach`, and
+    /// every request moves the window `kairos_sla_compliance` is computed
+    /// over. Recorded whether or not per-route collection is enabled, since
+    /// only routes with `sla_ms` call it.
+    pub fn record_sla(
+        &self,
+        route: &str,
+        tags: &BTreeMap<String, String>,
+        sla: Duration,
+        response_time: Duration,
+    ) {
+        let breached = response_time > sla;
+        if breached {
+            if let Some(sink) = &self.sink {
+                sink.increment_counter("sla_breach");
+            }
+        }
+        let update = |stats: &mut RouteSlaStats| {
+            if breached {
+                stats.breaches += 1;
+                stats.recent_breaches += 1;
+            }
+            stats.recent.push_back(breached);
+            if stats.recent.len() > SLA_WINDOW_REQUESTS && stats.recent.pop_front() == Some(true) {
+                stats.recent_breaches -= 1;
+            }
+            if stats.tags != *tags {
+                stats.tags = tags.clone();
+            }
+        };
+        let updated = self.route_sla.get_mut(route).map(|mut stats| update(&mut stats));
+        if updated.is_none() {
+            update(&mut self.route_sla.entry(route.to_string()).or_default());
+        }
+    }
+
+    /// Returns latency target statistics for every route with `sla_ms` seen
+    /// so far, in route order.
+    pub fn route_sla(&self) -> Vec<(String, RouteSlaStats)> {
+        let mut stats: Vec<_> = self
+            .route_sla
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    /// Counts a request on `route` as no longer in flight.
+    pub fn route_request_finished(&self, route: &str) {
+        if let Some(mut stats) = self.route_metrics.get_mut(route) {
+            stats.in_flight = stats.in_flight.saturating_sub(1);
+        }
+    }
+
+    /// Returns request statistics for every route seen so far, in route order.
+    pub fn route_metrics(&self) -> Vec<(String, RouteMetrics)> {
+        let mut stats: Vec<_> = self
+            .route_metrics
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+    
+    /// Returns response counts for every status code seen so far, in code order.
+    pub fn status_code_counts(&self) -> Vec<(u16, u64)> {
+        self.responses_by_status
+            .iter()
+            .zip(STATUS_CODE_MIN..=STATUS_CODE_MAX)
+            .map(|(count, code)| (code, count.load(Ordering::Relaxed)))
+            .filter(|(_, count)| *count > 0)
+            .collect()
     }
     
     /// Records a timeout error for requests that exceed the configured timeout.
     /// 
     /// This method is used to track timeout-related failures which help identify
-    /// upstream service performance issues or network latency problems.
+    /// upstream service performance issues or network latency problems. The
+    /// request itself is counted by `record_request`.
     /// 
     /// # Thread Safety
     /// 
     /// Uses atomic operations safe for concurrent access from multiple threads.
-    #[allow(dead_code)] // Used for specific error tracking
     pub fn record_timeout_error(&self) {
         self.timeout_errors.fetch_add(1, Ordering::Relaxed);
-        self.requests_error.fetch_add(1, Ordering::Relaxed);
-        self.requests_total.fetch_add(1, Ordering::Relaxed);
+        self.sink_counter("errors.timeout");
     }
     
     /// Records a connection error for requests that fail to establish connections.
     /// 
     /// This method tracks infrastructure-level failures separate from application
     /// errors to help distinguish between upstream service issues and gateway problems.
+    /// The request itself is counted by `record_request`.
     /// 
     /// # Thread Safety
     /// 
     /// Uses atomic operations safe for concurrent access from multiple threads.
-    #[allow(dead_code)] // Used for specific error tracking
     pub fn record_connection_error(&self) {
         self.connection_errors.fetch_add(1, Ordering::Relaxed);
-        self.requests_error.fetch_add(1, Ordering::Relaxed);
-        self.requests_total.fetch_add(1, Ordering::Relaxed);
+        self.sink_counter("errors.connection");
+    }
+
+    /// Records a request rejected because its backend's circuit breaker was open.
+    ///
+    /// The request itself is counted by `record_request`.
+    pub fn record_circuit_open_error(&self) {
+        self.circuit_open_errors.fetch_add(1, Ordering::Relaxed);
+        self.sink_counter("errors.circuit_open");
+    }
+
+    /// Records an attempt that was not sent to `service` because its circuit
+    /// breaker was open.
+    ///
+    /// Unlike `record_circuit_open_error`, this counts every rejected attempt
+    /// per upstream, including ones retried on another backend or answered
+    /// with the route's circuit-open fallback.
+    pub fn record_circuit_open_rejection(&self, service: &str) {
+        *self
+            .circuit_open_rejections
+            .entry(service.to_string())
+            .or_insert(0) += 1;
+    }
+
+    /// Returns the circuit-open rejections of every upstream, in upstream order.
+    pub fn circuit_open_rejections(&self) -> Vec<(String, u64)> {
+        let mut rejections: Vec<_> = self
+            .circuit_open_rejections
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        rejections.sort();
+        rejections
+    }
+
+    /// Records an attempt sent to the configured backend `service`, failed
+    /// if it got a connection error, a timeout or a 5xx response.
+    ///
+    /// Retries count as separate attempts, each against the backend it was
+    /// sent to.
+    pub fn record_backend_request(&self, service: &str, failed: bool) {
+        let mut stats = self
+            .backend_requests
+            .entry(service.to_string())
+            .or_default();
+        stats.requests += 1;
+        if failed {
+            stats.errors += 1;
+        }
+    }
+
+    /// Returns the request counts of every backend, in backend order.
+    pub fn backend_requests(&self) -> Vec<(String, BackendRequestStats)> {
+        let mut requests: Vec<_> = self
+            .backend_requests
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        requests.sort_by(|a, b| a.0.cmp(&b.0));
+        requests
+    }
+    
+    /// Adds `bytes` to the response bytes total.
+    ///
+    /// Used for streamed responses, whose size is only known once the body
+    /// has been sent and so cannot be passed to `record_request`.
+    pub fn record_response_bytes(&self, bytes: u64) {
+        self.response_bytes_total.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Keeps `trace_id` as the exemplar of the response time bucket `response_time` falls into.
+    ///
+    /// Each bucket holds the most recent traced request; exemplars are only
+    /// exported in OpenMetrics format.
+    pub fn record_exemplar(&self, response_time: Duration, trace_id: &str) {
+        let value_ms = response_time.as_millis() as u64;
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| value_ms <= bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+
+        if let Ok(mut exemplars) = self.latency_exemplars.lock() {
+            exemplars[bucket] = Some(Exemplar {
+                trace_id: trace_id.to_string(),
+                value_ms,
+                timestamp,
+            });
+        }
+    }
+    
+    /// Records the size of a request body received on `route`.
+    pub fn record_body_size(&self, route: &str, bytes: u64) {
+        let bucket = self
+            .body_size_buckets
+            .iter()
+            .position(|&bound| bytes <= bound)
+            .unwrap_or(self.body_size_buckets.len());
+
+        if let Ok(mut routes) = self.route_body_sizes.lock() {
+            let stats = self.route_body_stats_entry(&mut routes, route);
+            stats.bucket_counts[bucket] += 1;
+            stats.sum_bytes += bytes;
+            stats.count += 1;
+        }
+    }
+
+    /// Records a request on `route` rejected for exceeding its body size limit.
+    pub fn record_body_too_large(&self, route: &str) {
+        if let Ok(mut routes) = self.route_body_sizes.lock() {
+            self.route_body_stats_entry(&mut routes, route).too_large += 1;
+        }
+    }
+
+    /// Returns body size statistics for every route seen so far, in route order.
+    pub fn route_body_stats(&self) -> Vec<(String, RouteBodyStats)> {
+        let mut stats: Vec<_> = self
+            .route_body_sizes
+            .lock()
+            .map(|routes| routes.clone().into_iter().collect())
+            .unwrap_or_default();
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        stats
+    }
+
+    fn route_body_stats_entry<'a>(
+        &self,
+        routes: &'a mut HashMap<String, RouteBodyStats>,
+        route: &str,
+    ) -> &'a mut RouteBodyStats {
+        routes.entry(route.to_string()).or_insert_with(|| RouteBodyStats {
+            bucket_counts: vec![0; self.body_size_buckets.len() + 1],
+            ..Default::default()
+        })
+    }
+    
+    /// Records an upstream response that failed its route's response schema.
+    ///
+    /// Counted in both shadow and enforce mode; enforced violations are also
+    /// recorded as 502 responses by `record_request`.
+    pub fn record_response_schema_violation(&self) {
+        self.response_schema_violations.fetch_add(1, Ordering::Relaxed);
     }
     
     /// Increments the active connections counter and updates peak if necessary.
@@ -241,6 +872,7 @@ impl MetricsCollector {
     /// Uses atomic operations safe for concurrent access from multiple threads.
     pub fn increment_connections(&self) {
         let current = self.active_connections.fetch_add(1, Ordering::Relaxed) + 1;
+        self.sink_gauge("active_connections", current);
         
         // Update peak connections if current exceeds previous peak
         let mut peak = self.peak_connections.load(Ordering::Relaxed);
@@ -257,6 +889,51 @@ impl MetricsCollector {
         }
     }
     
+    /// Counts a TCP proxy connection that has been connected to its upstream.
+    ///
+    /// Must be paired with `tcp_connection_closed` once either side closes.
+    pub fn tcp_connection_opened(&self) {
+        self.tcp_connections_total.fetch_add(1, Ordering::Relaxed);
+        self.tcp_active_connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a TCP proxy connection as closed.
+    pub fn tcp_connection_closed(&self) {
+        self.tcp_active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Sets the number of warnings from validating the configuration in
+    /// effect, at startup or after a reload.
+    pub fn set_config_validation_warnings(&self, count: usize) {
+        self.config_validation_warnings
+            .store(count as u64, Ordering::Relaxed);
+        self.sink_gauge("config_validation_warnings", count as u64);
+    }
+
+    /// Sets the number of requests admitted under `max_concurrent_requests`
+    /// that are still being handled.
+    pub fn set_concurrent_requests(&self, count: usize) {
+        self.concurrent_requests.store(count as u64, Ordering::Relaxed);
+        self.sink_gauge("concurrent_requests", count as u64);
+    }
+
+    /// Counts a request rejected because `max_concurrent_requests` was reached.
+    pub fn record_request_shed(&self) {
+        self.requests_shed.fetch_add(1, Ordering::Relaxed);
+        self.sink_counter("requests_shed");
+    }
+
+    /// Counts the outcome of a request copied to a route's mirror backend.
+    pub fn record_mirror_result(&self, success: bool) {
+        let counter = if success {
+            &self.mirror_requests_success
+        } else {
+            &self.mirror_requests_failed
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.sink_counter(if success { "mirror.success" } else { "mirror.failed" });
+    }
+
     /// Decrements the active connections counter.
     /// 
     /// Called when request processing completes to accurately track concurrent load.
@@ -266,7 +943,20 @@ impl MetricsCollector {
     /// 
     /// Uses atomic operations safe for concurrent access from multiple threads.
     pub fn decrement_connections(&self) {
-        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+        let current = self.active_connections.fetch_sub(1, Ordering::Relaxed).saturating_sub(1);
+        self.sink_gauge("active_connections", current);
+    }
+
+    fn sink_counter(&self, name: &str) {
+        if let Some(sink) = &self.sink {
+            sink.increment_counter(name);
+        }
+    }
+
+    fn sink_gauge(&self, name: &str, value: u64) {
+        if let Some(sink) = &self.sink {
+            sink.record_gauge(name, value as f64);
+        }
     }
 }
 
@@ -279,12 +969,15 @@ impl MetricsCollector {
 /// 
 /// # Parameters
 /// 
+/// * `req` - Incoming request, checked for credentials when auth is configured
 /// * `metrics` - Shared MetricsCollector instance containing current statistics
 /// * `route_handler` - Optional RouteHandler for circuit breaker state information
+/// * `metrics_config` - Optional MetricsConfig; when `auth` is set, requests
+///   without matching bearer or basic credentials receive `401 Unauthorized`
 /// 
 /// # Returns
 /// 
-/// * `Ok(HttpResponse)` - Prometheus-formatted metrics as plain text
+/// * `Ok(HttpResponse)` - Prometheus-formatted metrics as plain text, or 401
 /// * `Err(ActixError)` - Internal error (rare, indicates system issues)
 /// 
 /// # Metrics Exposed
@@ -292,23 +985,51 @@ impl MetricsCollector {
 /// - **kairos_requests_total**: Total HTTP requests processed (counter)
 /// - **kairos_requests_success_total**: Successful requests (counter)
 /// - **kairos_requests_error_total**: Failed requests (counter)
+/// - **kairos_responses_total{code}**: Responses per HTTP status code seen (counter)
+/// - **kairos_timeout_errors_total**: Requests failed by an upstream timeout (counter)
+/// - **kairos_connection_errors_total**: Requests failed by an upstream connection error (counter)
+/// - **kairos_circuit_open_errors_total**: Requests rejected by an open circuit breaker (counter)
+/// - **kairos_response_schema_violations_total**: Responses failing route schema validation (counter)
+/// - **kairos_request_body_bytes{route}**: Request body size per route (histogram)
+/// - **kairos_body_too_large_total{route}**: Requests rejected by a route's body size limit (counter)
+/// - **kairos_sla_breach_total{route}**: Requests slower than the route's `sla_ms` (counter)
+/// - **kairos_sla_compliance{route}**: Share of the route's latest requests within its `sla_ms` (gauge)
 /// - **kairos_response_time_avg**: Average response time in milliseconds (gauge)
+/// - **kairos_response_time_p50**, **_p95**, **_p99**: Response time percentiles in milliseconds (gauge)
+/// - **kairos_response_time**: Response time histogram in milliseconds, with `_bucket`, `_sum` and `_count` (histogram)
 /// - **kairos_success_rate**: Success rate as percentage (gauge)
 /// - **kairos_active_connections**: Current active connections (gauge)
 /// - **kairos_uptime_seconds**: Service uptime in seconds (counter)
+/// - **kairos_tcp_active_connections**: Open TCP proxy connections (gauge)
+/// - **kairos_tcp_connections_total**: TCP proxy connections made to an upstream (counter)
+/// - **kairos_mirror_requests_success_total**, **kairos_mirror_requests_failed_total**: Requests copied to mirror backends by outcome (counter)
+/// - **kairos_config_validation_warnings**: Warnings from validating the configuration in effect (gauge)
+/// - **kairos_concurrent_requests**: Requests in flight under `max_concurrent_requests` (gauge)
+/// - **kairos_requests_shed_total**: Requests rejected because `max_concurrent_requests` was reached (counter)
 /// - **kairos_circuit_breaker_state**: Circuit breaker state by service (gauge)
+/// - **kairos_circuit_open_rejections_total{service}**: Attempts not sent to an upstream because its breaker was open (counter)
+/// - **kairos_backend_requests_total{service}**, **kairos_backend_errors_total{service}**: Attempts sent to each backend and failed attempts (counter)
 /// - **kairos_circuit_breaker_failures**: Circuit breaker failure count (counter)
 /// - **kairos_circuit_breaker_successes**: Circuit breaker success count (counter)
+/// - **kairos_requests_total{route}**, **kairos_requests_error_total{route}**: Requests and failed requests by route pattern, with `metrics.collect_per_route` (counter)
+/// - **kairos_route_response_time_avg{route}**: Average response time by route pattern, with `metrics.collect_per_route` (gauge)
+/// - **kairos_route_in_flight{route}**: Requests currently being handled by route pattern, with `metrics.collect_per_route` (gauge)
+/// - **kairos_backend_up{host,port,routes}**: 1 if the backend accepts traffic, 0 if it is down (gauge)
+/// - **kairos_backend_healthy{service}**: 1 if the backend passes its health checks, 0 if not (gauge)
+/// - **kairos_retry_budget_tokens{service}**: Retries left in the upstream's retry budget (gauge)
+/// - **kairos_retries_suppressed_total{service}**: Retries skipped for lack of budget (counter)
 /// 
 /// # Response Format
 /// 
-/// Returns metrics in Prometheus exposition format:
+/// Clients sending `Accept: application/openmetrics-text` get the OpenMetrics
+/// format instead, with response time exemplars and a `# EOF` terminator.
+/// Otherwise metrics are returned in Prometheus exposition format:
 /// ```text
 /// # HELP kairos_requests_total Total number of HTTP requests
 /// # TYPE kairos_requests_total counter
 /// kairos_requests_total 1547
 /// 
-/// # HELP kairos_circuit_breaker_state Circuit breaker state (0=Closed, 1=Open, 2=HalfOpen)
+/// # HELP kairos_circuit_breaker_state Circuit breaker state (0=Closed, 1=Open, 2=HalfOpen, 3=ForcedOpen, 4=ForcedClosed)
 /// # TYPE kairos_circuit_breaker_state gauge
 /// kairos_circuit_breaker_state{service="api.example.com:443"} 0
 /// ```
@@ -327,73 +1048,214 @@ impl MetricsCollector {
 /// - Custom monitoring tools
 /// - Health check systems
 pub async fn metrics_endpoint(
+    req: HttpRequest,
     metrics: web::Data<MetricsCollector>, 
-    route_handler: Option<web::Data<RouteHandler>>
+    route_handler: Option<web::Data<RouteHandler>>,
+    metrics_config: Option<web::Data<MetricsConfig>>
 ) -> Result<HttpResponse> {
-    let total_requests = metrics.requests_total.load(Ordering::Relaxed);
-    let success_requests = metrics.requests_success.load(Ordering::Relaxed);
-    let error_requests = metrics.requests_error.load(Ordering::Relaxed);
-    let response_time_sum = metrics.response_time_sum.load(Ordering::Relaxed);
-    let active_connections = metrics.active_connections.load(Ordering::Relaxed);
-    let peak_connections = metrics.peak_connections.load(Ordering::Relaxed);
-    let request_bytes = metrics.request_bytes_total.load(Ordering::Relaxed);
-    let response_bytes = metrics.response_bytes_total.load(Ordering::Relaxed);
-    let bucket_100ms = metrics.response_time_bucket_100ms.load(Ordering::Relaxed);
-    let bucket_500ms = metrics.response_time_bucket_500ms.load(Ordering::Relaxed);
-    let bucket_1s = metrics.response_time_bucket_1s.load(Ordering::Relaxed);
-    let bucket_5s = metrics.response_time_bucket_5s.load(Ordering::Relaxed);
-    let bucket_inf = metrics.response_time_bucket_inf.load(Ordering::Relaxed);
-    let http_4xx_errors = metrics.http_4xx_errors.load(Ordering::Relaxed);
-    let http_5xx_errors = metrics.http_5xx_errors.load(Ordering::Relaxed);
-    let timeout_errors = metrics.timeout_errors.load(Ordering::Relaxed);
-    let connection_errors = metrics.connection_errors.load(Ordering::Relaxed);
-    let uptime = metrics.start_time.elapsed().as_secs();
-    
-    let avg_response_time = if total_requests > 0 {
-        response_time_sum as f64 / total_requests as f64
-    } else {
-        0.0
-    };
-    
-    let success_rate = if total_requests > 0 {
-        (success_requests as f64 / total_requests as f64) * 100.0
-    } else {
-        100.0
-    };
+    if let Some(rejection) = reject_unauthorized(&req, metrics_config.as_ref().map(|c| c.get_ref())) {
+        return Ok(rejection);
+    }
+
+    if accepts_openmetrics(&req) {
+        return Ok(HttpResponse::Ok()
+            .content_type(OPENMETRICS_CONTENT_TYPE)
+            .body(render_openmetrics(&metrics, route_handler.as_ref().map(|h| h.get_ref()))));
+    }
+
+    let MetricsSnapshot {
+        requests_total: total_requests,
+        requests_success: success_requests,
+        requests_error: error_requests,
+        success_rate,
+        response_time_avg: avg_response_time,
+        response_time_sum,
+        response_time_p50: p50,
+        response_time_p95: p95,
+        response_time_p99: p99,
+        active_connections,
+        peak_connections,
+        http_4xx_errors,
+        http_5xx_errors,
+        timeout_errors,
+        connection_errors,
+        circuit_open_errors,
+        response_schema_violations: schema_violations,
+        response_time_bucket_100ms: bucket_100ms,
+        response_time_bucket_500ms: bucket_500ms,
+        response_time_bucket_1s: bucket_1s,
+        response_time_bucket_5s: bucket_5s,
+        response_time_bucket_inf: bucket_inf,
+        request_bytes_total: request_bytes,
+        response_bytes_total: response_bytes,
+        uptime_seconds: uptime,
+        circuit_breakers,
+        ..
+    } = metrics.snapshot(route_handler.as_ref().map(|h| h.get_ref()));
+
+    // Per-status-code counters, only for codes that have been seen
+    let mut status_code_metrics = String::new();
+    let status_counts = metrics.status_code_counts();
+    if !status_counts.is_empty() {
+        status_code_metrics.push_str("\n# HELP kairos_responses_total Total number of responses by HTTP status code\n");
+        status_code_metrics.push_str("# TYPE kairos_responses_total counter\n");
+        for (code, count) in status_counts {
+            status_code_metrics.push_str(&format!(
+                "kairos_responses_total{{code=\"{}\"}} {}\n",
+                code, count
+            ));
+        }
+    }
+
+    let route_body_metrics = render_route_body_metrics(&metrics, false);
+    let route_sla_metrics = render_route_sla_metrics(&metrics, false);
+
+    // Per-route samples join the gateway-wide request and error families
+    let mut route_request_samples = String::new();
+    let mut route_error_samples = String::new();
+    let mut route_response_time_metrics = String::new();
+    let route_stats = metrics.route_metrics();
+    if !route_stats.is_empty() {
+        route_response_time_metrics.push_str("\n# HELP kairos_route_response_time_avg Average response time in milliseconds by route\n");
+        route_response_time_metrics.push_str("# TYPE kairos_route_response_time_avg gauge\n");
+        for (route, stats) in &route_stats {
+            let labels = route_labels(route, &stats.tags);
+            route_request_samples.push_str(&format!(
+                "\nkairos_requests_total{{{}}} {}",
+                labels, stats.requests
+            ));
+            route_error_samples.push_str(&format!(
+                "\nkairos_requests_error_total{{{}}} {}",
+                labels, stats.errors
+            ));
+            route_response_time_metrics.push_str(&format!(
+                "kairos_route_response_time_avg{{{}}} {:.2}\n",
+                labels,
+                stats.response_time_avg()
+            ));
+        }
+        route_response_time_metrics.push_str("\n# HELP kairos_route_in_flight Number of requests currently being handled by route\n");
+        route_response_time_metrics.push_str("# TYPE kairos_route_in_flight gauge\n");
+        for (route, stats) in &route_stats {
+            route_response_time_metrics.push_str(&format!(
+                "kairos_route_in_flight{{{}}} {}\n",
+                route_labels(route, &stats.tags),
+                stats.in_flight
+            ));
+        }
+    }
 
     // Generate circuit breaker metrics if route handler is available
     let mut circuit_breaker_metrics = String::new();
-    if let Some(handler) = route_handler {
-        let cb_states = handler.get_circuit_breaker_states();
+    if !circuit_breakers.is_empty() {
+        circuit_breaker_metrics.push_str("\n# HELP kairos_circuit_breaker_state Circuit breaker state (0=Closed, 1=Open, 2=HalfOpen, 3=ForcedOpen, 4=ForcedClosed)\n");
+        circuit_breaker_metrics.push_str("# TYPE kairos_circuit_breaker_state gauge\n");
         
-        if !cb_states.is_empty() {
-            circuit_breaker_metrics.push_str("\n# HELP kairos_circuit_breaker_state Circuit breaker state (0=Closed, 1=Open, 2=HalfOpen)\n");
-            circuit_breaker_metrics.push_str("# TYPE kairos_circuit_breaker_state gauge\n");
-            
-            circuit_breaker_metrics.push_str("\n# HELP kairos_circuit_breaker_failures Circuit breaker failure count\n");
-            circuit_breaker_metrics.push_str("# TYPE kairos_circuit_breaker_failures counter\n");
-            
-            circuit_breaker_metrics.push_str("\n# HELP kairos_circuit_breaker_successes Circuit breaker success count\n");
-            circuit_breaker_metrics.push_str("# TYPE kairos_circuit_breaker_successes counter\n");
-            
-            for (service, (state, failures, successes)) in cb_states {
-                let state_value = match state {
-                    crate::services::circuit_breaker::CircuitState::Closed => 0,
-                    crate::services::circuit_breaker::CircuitState::Open => 1,
-                    crate::services::circuit_breaker::CircuitState::HalfOpen => 2,
-                };
-                
-                circuit_breaker_metrics.push_str(&format!(
-                    "kairos_circuit_breaker_state{{service=\"{}\"}} {}\n",
-                    service, state_value
+        circuit_breaker_metrics.push_str("\n# HELP kairos_circuit_breaker_failures Circuit breaker failure count\n");
+        circuit_breaker_metrics.push_str("# TYPE kairos_circuit_breaker_failures counter\n");
+        
+        circuit_breaker_metrics.push_str("\n# HELP kairos_circuit_breaker_successes Circuit breaker success count\n");
+        circuit_breaker_metrics.push_str("# TYPE kairos_circuit_breaker_successes counter\n");
+        
+        for breaker in circuit_breakers {
+            circuit_breaker_metrics.push_str(&format!(
+                "kairos_circuit_breaker_state{{service=\"{}\"}} {}\n",
+                breaker.route, breaker.state as u8
+            ));
+            circuit_breaker_metrics.push_str(&format!(
+                "kairos_circuit_breaker_failures{{service=\"{}\"}} {}\n",
+                breaker.route, breaker.failure_count
+            ));
+            circuit_breaker_metrics.push_str(&format!(
+                "kairos_circuit_breaker_successes{{service=\"{}\"}} {}\n",
+                breaker.route, breaker.success_count
+            ));
+        }
+    }
+
+    let rejections = metrics.circuit_open_rejections();
+    if !rejections.is_empty() {
+        circuit_breaker_metrics.push_str("\n# HELP kairos_circuit_open_rejections_total Attempts not sent to an upstream because its circuit breaker was open\n");
+        circuit_breaker_metrics.push_str("# TYPE kairos_circuit_open_rejections_total counter\n");
+        for (service, count) in &rejections {
+            circuit_breaker_metrics.push_str(&format!(
+                "kairos_circuit_open_rejections_total{{service=\"{}\"}} {}\n",
+                service, count
+            ));
+        }
+    }
+
+    // Load and failures of every backend of a load-balanced pool
+    let mut backend_request_metrics = String::new();
+    let backend_requests = metrics.backend_requests();
+    if !backend_requests.is_empty() {
+        backend_request_metrics.push_str("\n# HELP kairos_backend_requests_total Requests sent to each backend, including retries\n");
+        backend_request_metrics.push_str("# TYPE kairos_backend_requests_total counter\n");
+        for (service, stats) in &backend_requests {
+            backend_request_metrics.push_str(&format!(
+                "kairos_backend_requests_total{{service=\"{}\"}} {}\n",
+                service, stats.requests
+            ));
+        }
+        backend_request_metrics.push_str("\n# HELP kairos_backend_errors_total Requests to each backend that failed with a connection error, timeout or 5xx response\n");
+        backend_request_metrics.push_str("# TYPE kairos_backend_errors_total counter\n");
+        for (service, stats) in &backend_requests {
+            backend_request_metrics.push_str(&format!(
+                "kairos_backend_errors_total{{service=\"{}\"}} {}\n",
+                service, stats.errors
+            ));
+        }
+    }
+
+    // Availability of every backend, for alerting on targets that are down
+    let mut backend_up_metrics = String::new();
+    if let Some(handler) = &route_handler {
+        let statuses = handler.backend_statuses();
+        if !statuses.is_empty() {
+            backend_up_metrics.push_str("\n# HELP kairos_backend_up Whether the backend accepts traffic (1) or is down (0)\n");
+            backend_up_metrics.push_str("# TYPE kairos_backend_up gauge\n");
+            for status in &statuses {
+                backend_up_metrics.push_str(&backend_up_sample(status));
+                backend_up_metrics.push('\n');
+            }
+        }
+
+        let health = handler.backend_health();
+        if !health.is_empty() {
+            backend_up_metrics.push_str("\n# HELP kairos_backend_healthy Whether the backend passes its health checks (1) or not (0)\n");
+            backend_up_metrics.push_str("# TYPE kairos_backend_healthy gauge\n");
+            for (service, healthy) in &health {
+                backend_up_metrics.push_str(&format!(
+                    "kairos_backend_healthy{{service=\"{}\"}} {}\n",
+                    service,
+                    u8::from(*healthy)
                 ));
-                circuit_breaker_metrics.push_str(&format!(
-                    "kairos_circuit_breaker_failures{{service=\"{}\"}} {}\n",
-                    service, failures
+            }
+        }
+    }
+
+    // Retry budgets of upstreams whose routes configure one
+    let mut retry_budget_metrics = String::new();
+    if let Some(handler) = &route_handler {
+        let mut budgets: Vec<_> = handler.get_retry_budgets().into_iter().collect();
+        budgets.sort_by(|a, b| a.0.cmp(&b.0));
+
+        if !budgets.is_empty() {
+            retry_budget_metrics.push_str("\n# HELP kairos_retry_budget_tokens Retries currently allowed by the upstream's retry budget\n");
+            retry_budget_metrics.push_str("# TYPE kairos_retry_budget_tokens gauge\n");
+            for (service, (tokens, _)) in &budgets {
+                retry_budget_metrics.push_str(&format!(
+                    "kairos_retry_budget_tokens{{service=\"{}\"}} {:.2}\n",
+                    service, tokens
                 ));
-                circuit_breaker_metrics.push_str(&format!(
-                    "kairos_circuit_breaker_successes{{service=\"{}\"}} {}\n",
-                    service, successes
+            }
+
+            retry_budget_metrics.push_str("\n# HELP kairos_retries_suppressed_total Retries skipped because the upstream's retry budget was empty\n");
+            retry_budget_metrics.push_str("# TYPE kairos_retries_suppressed_total counter\n");
+            for (service, (_, suppressed)) in &budgets {
+                retry_budget_metrics.push_str(&format!(
+                    "kairos_retries_suppressed_total{{service=\"{}\"}} {}\n",
+                    service, suppressed
                 ));
             }
         }
@@ -402,7 +1264,7 @@ pub async fn metrics_endpoint(
     let metrics_text = format!(
         r#"# HELP kairos_requests_total Total number of HTTP requests
 # TYPE kairos_requests_total counter
-kairos_requests_total {}
+kairos_requests_total {}{}
 
 # HELP kairos_requests_success_total Total number of successful HTTP requests
 # TYPE kairos_requests_success_total counter
@@ -410,7 +1272,7 @@ kairos_requests_success_total {}
 
 # HELP kairos_requests_error_total Total number of failed HTTP requests
 # TYPE kairos_requests_error_total counter
-kairos_requests_error_total {}
+kairos_requests_error_total {}{}
 
 # HELP kairos_http_4xx_errors_total Total number of 4xx client errors
 # TYPE kairos_http_4xx_errors_total counter
@@ -428,17 +1290,39 @@ kairos_timeout_errors_total {}
 # TYPE kairos_connection_errors_total counter
 kairos_connection_errors_total {}
 
+# HELP kairos_circuit_open_errors_total Total number of requests rejected by an open circuit breaker
+# TYPE kairos_circuit_open_errors_total counter
+kairos_circuit_open_errors_total {}
+
+# HELP kairos_response_schema_violations_total Total number of upstream responses that failed schema validation
+# TYPE kairos_response_schema_violations_total counter
+kairos_response_schema_violations_total {}
+
 # HELP kairos_response_time_avg Average response time in milliseconds
 # TYPE kairos_response_time_avg gauge
 kairos_response_time_avg {:.2}
 
-# HELP kairos_response_time_bucket Response time histogram buckets
-# TYPE kairos_response_time_bucket histogram
+# HELP kairos_response_time_p50 Median response time in milliseconds
+# TYPE kairos_response_time_p50 gauge
+kairos_response_time_p50 {:.2}
+
+# HELP kairos_response_time_p95 95th percentile response time in milliseconds
+# TYPE kairos_response_time_p95 gauge
+kairos_response_time_p95 {:.2}
+
+# HELP kairos_response_time_p99 99th percentile response time in milliseconds
+# TYPE kairos_response_time_p99 gauge
+kairos_response_time_p99 {:.2}
+
+# HELP kairos_response_time Response time histogram in milliseconds
+# TYPE kairos_response_time histogram
 kairos_response_time_bucket{{le="100"}} {}
 kairos_response_time_bucket{{le="500"}} {}
 kairos_response_time_bucket{{le="1000"}} {}
 kairos_response_time_bucket{{le="5000"}} {}
 kairos_response_time_bucket{{le="+Inf"}} {}
+kairos_response_time_sum {}
+kairos_response_time_count {}
 
 # HELP kairos_request_bytes_total Total bytes received in requests
 # TYPE kairos_request_bytes_total counter
@@ -460,30 +1344,81 @@ kairos_active_connections {}
 # TYPE kairos_peak_connections gauge
 kairos_peak_connections {}
 
+# HELP kairos_tcp_active_connections Current number of open TCP proxy connections
+# TYPE kairos_tcp_active_connections gauge
+kairos_tcp_active_connections {}
+
+# HELP kairos_tcp_connections_total Total number of TCP proxy connections made to an upstream
+# TYPE kairos_tcp_connections_total counter
+kairos_tcp_connections_total {}
+
+# HELP kairos_mirror_requests_success_total Total number of mirrored requests answered without a 5xx
+# TYPE kairos_mirror_requests_success_total counter
+kairos_mirror_requests_success_total {}
+
+# HELP kairos_mirror_requests_failed_total Total number of mirrored requests that failed or got a 5xx
+# TYPE kairos_mirror_requests_failed_total counter
+kairos_mirror_requests_failed_total {}
+
+# HELP kairos_config_validation_warnings Number of warnings from validating the configuration in effect
+# TYPE kairos_config_validation_warnings gauge
+kairos_config_validation_warnings {}
+
+# HELP kairos_concurrent_requests Number of requests in flight under max_concurrent_requests
+# TYPE kairos_concurrent_requests gauge
+kairos_concurrent_requests {}
+
+# HELP kairos_requests_shed_total Total number of requests rejected because max_concurrent_requests was reached
+# TYPE kairos_requests_shed_total counter
+kairos_requests_shed_total {}
+
 # HELP kairos_uptime_seconds Service uptime in seconds
 # TYPE kairos_uptime_seconds counter
-kairos_uptime_seconds {}{}
+kairos_uptime_seconds {}{}{}{}{}{}{}{}{}
 "#,
         total_requests,
+        route_request_samples,
         success_requests,
         error_requests,
+        route_error_samples,
         http_4xx_errors,
         http_5xx_errors,
         timeout_errors,
         connection_errors,
+        circuit_open_errors,
+        schema_violations,
         avg_response_time,
+        p50,
+        p95,
+        p99,
         bucket_100ms,
         bucket_500ms,
         bucket_1s,
         bucket_5s,
-        bucket_inf,
+        bucket_5s + bucket_inf,
+        response_time_sum,
+        bucket_5s + bucket_inf,
         request_bytes,
         response_bytes,
         success_rate,
         active_connections,
         peak_connections,
+        metrics.tcp_active_connections.load(Ordering::Relaxed),
+        metrics.tcp_connections_total.load(Ordering::Relaxed),
+        metrics.mirror_requests_success.load(Ordering::Relaxed),
+        metrics.mirror_requests_failed.load(Ordering::Relaxed),
+        metrics.config_validation_warnings.load(Ordering::Relaxed),
+        metrics.concurrent_requests.load(Ordering::Relaxed),
+        metrics.requests_shed.load(Ordering::Relaxed),
         uptime,
-        circuit_breaker_metrics
+        status_code_metrics,
+        route_body_metrics,
+        route_sla_metrics,
+        route_response_time_metrics,
+        circuit_breaker_metrics,
+        backend_request_metrics,
+        backend_up_metrics,
+        retry_budget_metrics
     );
 
     Ok(HttpResponse::Ok()
@@ -491,6 +1426,446 @@ kairos_uptime_seconds {}{}
         .body(metrics_text))
 }
 
+/// Whether the request's `Accept` header asks for OpenMetrics.
+fn accepts_openmetrics(req: &HttpRequest) -> bool {
+    req.headers()
+        .get_all(header::ACCEPT)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_range| {
+            let mut parts = media_range.split(';').map(str::trim);
+            parts.next() == Some("application/openmetrics-text")
+                && !parts.any(|param| matches!(param, "q=0" | "q=0.0" | "q=0.00" | "q=0.000"))
+        })
+}
+
+/// Formats the labels of a per-route sample: `route` followed by the
+/// route's tags, with values escaped for the exposition formats.
+fn route_labels(route: &str, tags: &BTreeMap<String, String>) -> String {
+    let mut labels = format!("route=\"{}\"", route);
+    for (name, value) in tags {
+        let value = value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        labels.push_str(&format!(",{}=\"{}\"", name, value));
+    }
+    labels
+}
+
+/// Extracts the trace ID from a W3C `traceparent` header value.
+///
+/// Returns `None` for malformed values and the all-zero (invalid) trace ID.
+pub fn trace_id_from_traceparent(value: &str) -> Option<&str> {
+    let mut parts = value.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let parent_id = parts.next()?;
+    let flags = parts.next()?;
+
+    let is_hex = |s: &str, len: usize| {
+        s.len() == len && s.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+    let valid = is_hex(version, 2)
+        && version != "ff"
+        && is_hex(trace_id, 32)
+        && trace_id.bytes().any(|b| b != b'0')
+        && is_hex(parent_id, 16)
+        && is_hex(flags, 2);
+    valid.then_some(trace_id)
+}
+
+/// Renders the collected metrics in OpenMetrics text format.
+///
+/// Sample names match the Prometheus output; counter families drop their
+/// `_total` suffix as OpenMetrics requires, and counts that can reset, such
+/// as circuit breaker counters and uptime, are typed as gauges.
+fn render_openmetrics(metrics: &MetricsCollector, route_handler: Option<&RouteHandler>) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+
+    let family = |out: &mut String, name: &str, kind: &str, help: &str| {
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+    };
+
+    let counters = [
+        ("kairos_requests", "Total number of HTTP requests", metrics.requests_total.load(Ordering::Relaxed)),
+        ("kairos_requests_success", "Total number of successful HTTP requests", metrics.requests_success.load(Ordering::Relaxed)),
+        ("kairos_requests_error", "Total number of failed HTTP requests", metrics.requests_error.load(Ordering::Relaxed)),
+        ("kairos_http_4xx_errors", "Total number of 4xx client errors", metrics.http_4xx_errors.load(Ordering::Relaxed)),
+        ("kairos_http_5xx_errors", "Total number of 5xx server errors", metrics.http_5xx_errors.load(Ordering::Relaxed)),
+        ("kairos_timeout_errors", "Total number of timeout errors", metrics.timeout_errors.load(Ordering::Relaxed)),
+        ("kairos_connection_errors", "Total number of connection errors", metrics.connection_errors.load(Ordering::Relaxed)),
+        ("kairos_circuit_open_errors", "Total number of requests rejected by an open circuit breaker", metrics.circuit_open_errors.load(Ordering::Relaxed)),
+        ("kairos_response_schema_violations", "Total number of upstream responses that failed schema validation", metrics.response_schema_violations.load(Ordering::Relaxed)),
+        ("kairos_request_bytes", "Total bytes received in requests", metrics.request_bytes_total.load(Ordering::Relaxed)),
+        ("kairos_response_bytes", "Total bytes sent in responses", metrics.response_bytes_total.load(Ordering::Relaxed)),
+        ("kairos_tcp_connections", "Total number of TCP proxy connections made to an upstream", metrics.tcp_connections_total.load(Ordering::Relaxed)),
+        ("kairos_mirror_requests_success", "Total number of mirrored requests answered without a 5xx", metrics.mirror_requests_success.load(Ordering::Relaxed)),
+        ("kairos_mirror_requests_failed", "Total number of mirrored requests that failed or got a 5xx", metrics.mirror_requests_failed.load(Ordering::Relaxed)),
+        ("kairos_requests_shed", "Total number of requests rejected because max_concurrent_requests was reached", metrics.requests_shed.load(Ordering::Relaxed)),
+    ];
+    let route_stats = metrics.route_metrics();
+    for (name, help, count) in counters {
+        family(&mut out, name, "counter", help);
+        let _ = writeln!(out, "{}_total {}", name, count);
+        for (route, stats) in &route_stats {
+            match name {
+                "kairos_requests" => {
+                    let _ = writeln!(out, "kairos_requests_total{{{}}} {}", route_labels(route, &stats.tags), stats.requests);
+                }
+                "kairos_requests_error" => {
+                    let _ = writeln!(out, "kairos_requests_error_total{{{}}} {}", route_labels(route, &stats.tags), stats.errors);
+                }
+                _ => break,
+            }
+        }
+    }
+
+    let status_counts = metrics.status_code_counts();
+    if !status_counts.is_empty() {
+        family(&mut out, "kairos_responses", "counter", "Total number of responses by HTTP status code");
+        for (code, count) in status_counts {
+            let _ = writeln!(out, "kairos_responses_total{{code=\"{}\"}} {}", code, count);
+        }
+    }
+
+    // Buckets are cumulative; the last one counts every observation
+    let buckets = [
+        metrics.response_time_bucket_100ms.load(Ordering::Relaxed),
+        metrics.response_time_bucket_500ms.load(Ordering::Relaxed),
+        metrics.response_time_bucket_1s.load(Ordering::Relaxed),
+        metrics.response_time_bucket_5s.load(Ordering::Relaxed),
+        metrics.response_time_bucket_5s.load(Ordering::Relaxed) + metrics.response_time_bucket_inf.load(Ordering::Relaxed),
+    ];
+    let exemplars = metrics
+        .latency_exemplars
+        .lock()
+        .map(|exemplars| exemplars.clone())
+        .unwrap_or_default();
+    family(&mut out, "kairos_response_time", "histogram", "Response time histogram in milliseconds");
+    for (i, count) in buckets.iter().enumerate() {
+        let le = LATENCY_BUCKETS_MS
+            .get(i)
+            .map(|bound| bound.to_string())
+            .unwrap_or_else(|| "+Inf".to_string());
+        let _ = write!(out, "kairos_response_time_bucket{{le=\"{}\"}} {}", le, count);
+        if let Some(exemplar) = &exemplars[i] {
+            let _ = write!(
+                out,
+                " # {{trace_id=\"{}\"}} {} {:.3}",
+                exemplar.trace_id, exemplar.value_ms, exemplar.timestamp
+            );
+        }
+        out.push('\n');
+    }
+    let _ = writeln!(out, "kairos_response_time_count {}", buckets[buckets.len() - 1]);
+    let _ = writeln!(out, "kairos_response_time_sum {}", metrics.response_time_sum.load(Ordering::Relaxed));
+
+    out.push_str(&render_route_body_metrics(metrics, true));
+    out.push_str(&render_route_sla_metrics(metrics, true));
+
+    let total_requests = metrics.requests_total.load(Ordering::Relaxed);
+    let success_rate = if total_requests > 0 {
+        (metrics.requests_success.load(Ordering::Relaxed) as f64 / total_requests as f64) * 100.0
+    } else {
+        100.0
+    };
+    let avg_response_time = if total_requests > 0 {
+        metrics.response_time_sum.load(Ordering::Relaxed) as f64 / total_requests as f64
+    } else {
+        0.0
+    };
+    let [p50, p95, p99] = metrics.response_time_percentiles();
+    let gauges = [
+        ("kairos_response_time_avg", "Average response time in milliseconds", format!("{:.2}", avg_response_time)),
+        ("kairos_response_time_p50", "Median response time in milliseconds", format!("{:.2}", p50)),
+        ("kairos_response_time_p95", "95th percentile response time in milliseconds", format!("{:.2}", p95)),
+        ("kairos_response_time_p99", "99th percentile response time in milliseconds", format!("{:.2}", p99)),
+        ("kairos_success_rate", "Success rate percentage", format!("{:.2}", success_rate)),
+        ("kairos_active_connections", "Current number of active connections", load(&metrics.active_connections).to_string()),
+        ("kairos_peak_connections", "Peak number of concurrent connections", load(&metrics.peak_connections).to_string()),
+        ("kairos_tcp_active_connections", "Current number of open TCP proxy connections", load(&metrics.tcp_active_connections).to_string()),
+        ("kairos_config_validation_warnings", "Number of warnings from validating the configuration in effect", load(&metrics.config_validation_warnings).to_string()),
+        ("kairos_concurrent_requests", "Number of requests in flight under max_concurrent_requests", load(&metrics.concurrent_requests).to_string()),
+        ("kairos_uptime_seconds", "Service uptime in seconds", metrics.start_time.elapsed().as_secs().to_string()),
+    ];
+    for (name, help, value) in gauges {
+        family(&mut out, name, "gauge", help);
+        let _ = writeln!(out, "{} {}", name, value);
+    }
+
+    if !route_stats.is_empty() {
+        family(&mut out, "kairos_route_response_time_avg", "gauge", "Average response time in milliseconds by route");
+        for (route, stats) in &route_stats {
+            let _ = writeln!(out, "kairos_route_response_time_avg{{{}}} {:.2}", route_labels(route, &stats.tags), stats.response_time_avg());
+        }
+        family(&mut out, "kairos_route_in_flight", "gauge", "Number of requests currently being handled by route");
+        for (route, stats) in &route_stats {
+            let _ = writeln!(out, "kairos_route_in_flight{{{}}} {}", route_labels(route, &stats.tags), stats.in_flight);
+        }
+    }
+
+    let rejections = metrics.circuit_open_rejections();
+    if !rejections.is_empty() {
+        family(&mut out, "kairos_circuit_open_rejections", "counter", "Attempts not sent to an upstream because its circuit breaker was open");
+        for (service, count) in &rejections {
+            let _ = writeln!(out, "kairos_circuit_open_rejections_total{{service=\"{}\"}} {}", service, count);
+        }
+    }
+
+    let backend_requests = metrics.backend_requests();
+    if !backend_requests.is_empty() {
+        family(&mut out, "kairos_backend_requests", "counter", "Requests sent to each backend, including retries");
+        for (service, stats) in &backend_requests {
+            let _ = writeln!(out, "kairos_backend_requests_total{{service=\"{}\"}} {}", service, stats.requests);
+        }
+        family(&mut out, "kairos_backend_errors", "counter", "Requests to each backend that failed with a connection error, timeout or 5xx response");
+        for (service, stats) in &backend_requests {
+            let _ = writeln!(out, "kairos_backend_errors_total{{service=\"{}\"}} {}", service, stats.errors);
+        }
+    }
+
+    if let Some(handler) = route_handler {
+        let mut cb_states: Vec<_> = handler.get_circuit_breaker_states().into_iter().collect();
+        cb_states.sort_by(|a, b| a.0.cmp(&b.0));
+        if !cb_states.is_empty() {
+            let families = [
+                ("kairos_circuit_breaker_state", "Circuit breaker state (0=Closed, 1=Open, 2=HalfOpen, 3=ForcedOpen, 4=ForcedClosed)"),
+                ("kairos_circuit_breaker_failures", "Circuit breaker failure count"),
+                ("kairos_circuit_breaker_successes", "Circuit breaker success count"),
+            ];
+            for (index, (name, help)) in families.into_iter().enumerate() {
+                family(&mut out, name, "gauge", help);
+                for (service, (state, failures, successes)) in &cb_states {
+                    let value = match index {
+                        0 => match state {
+                            crate::services::circuit_breaker::CircuitState::Closed => 0,
+                            crate::services::circuit_breaker::CircuitState::Open => 1,
+                            crate::services::circuit_breaker::CircuitState::HalfOpen => 2,
+                            crate::services::circuit_breaker::CircuitState::ForcedOpen => 3,
+                            crate::services::circuit_breaker::CircuitState::ForcedClosed => 4,
+                        },
+                        1 => *failures,
+                        _ => *successes,
+                    };
+                    let _ = writeln!(out, "{}{{service=\"{}\"}} {}", name, service, value);
+                }
+            }
+        }
+
+        let statuses = handler.backend_statuses();
+        if !statuses.is_empty() {
+            family(&mut out, "kairos_backend_up", "gauge", "Whether the backend accepts traffic (1) or is down (0)");
+            for status in &statuses {
+                let _ = writeln!(out, "{}", backend_up_sample(status));
+            }
+        }
+
+        let health = handler.backend_health();
+        if !health.is_empty() {
+            family(&mut out, "kairos_backend_healthy", "gauge", "Whether the backend passes its health checks (1) or not (0)");
+            for (service, healthy) in &health {
+                let _ = writeln!(out, "kairos_backend_healthy{{service=\"{}\"}} {}", service, u8::from(*healthy));
+            }
+        }
+
+        let mut budgets: Vec<_> = handler.get_retry_budgets().into_iter().collect();
+        budgets.sort_by(|a, b| a.0.cmp(&b.0));
+        if !budgets.is_empty() {
+            family(&mut out, "kairos_retry_budget_tokens", "gauge", "Retries currently allowed by the upstream's retry budget");
+            for (service, (tokens, _)) in &budgets {
+                let _ = writeln!(out, "kairos_retry_budget_tokens{{service=\"{}\"}} {:.2}", service, tokens);
+            }
+            family(&mut out, "kairos_retries_suppressed", "counter", "Retries skipped because the upstream's retry budget was empty");
+            for (service, (_, suppressed)) in &budgets {
+                let _ = writeln!(out, "kairos_retries_suppressed_total{{service=\"{}\"}} {}", service, suppressed);
+            }
+        }
+    }
+
+    out.push_str("# EOF\n");
+    out
+}
+
+/// Formats the `kairos_backend_up` sample of one backend.
+///
+/// The `routes` label lists at most [`MAX_BACKEND_ROUTE_LABELS`] paths, so
+/// backends shared by many routes do not produce unbounded label values.
+fn backend_up_sample(status: &BackendStatus) -> String {
+    let mut routes = status
+        .routes
+        .iter()
+        .take(MAX_BACKEND_ROUTE_LABELS)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(",");
+    if status.routes.len() > MAX_BACKEND_ROUTE_LABELS {
+        routes.push_str(&format!(",+{} more", status.routes.len() - MAX_BACKEND_ROUTE_LABELS));
+    }
+
+    format!(
+        "kairos_backend_up{{host=\"{}\",port=\"{}\",routes=\"{}\"}} {}",
+        status.host,
+        status.port,
+        routes,
+        u8::from(status.up)
+    )
+}
+
+/// Renders the per-route request body size histogram and rejection counter.
+///
+/// Prometheus output separates the families with blank lines; OpenMetrics
+/// output puts `# TYPE` before `# HELP` and names counter families without
+/// their `_total` suffix.
+fn render_route_body_metrics(metrics: &MetricsCollector, openmetrics: bool) -> String {
+    use std::fmt::Write;
+
+    let stats = metrics.route_body_stats();
+    let mut out = String::new();
+    if stats.is_empty() {
+        return out;
+    }
+
+    let family = |out: &mut String, name: &str, kind: &str, help: &str| {
+        if openmetrics {
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+        } else {
+            let _ = writeln!(out, "\n# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        }
+    };
+
+    family(&mut out, "kairos_request_body_bytes", "histogram", "Request body size in bytes by route");
+    for (route, route_stats) in &stats {
+        let mut cumulative = 0;
+        for (i, count) in route_stats.bucket_counts.iter().enumerate() {
+            cumulative += count;
+            let le = metrics
+                .body_size_buckets
+                .get(i)
+                .map(|bound| bound.to_string())
+                .unwrap_or_else(|| "+Inf".to_string());
+            let _ = writeln!(
+                out,
+                "kairos_request_body_bytes_bucket{{route=\"{}\",le=\"{}\"}} {}",
+                route, le, cumulative
+            );
+        }
+        let _ = writeln!(out, "kairos_request_body_bytes_count{{route=\"{}\"}} {}", route, route_stats.count);
+        let _ = writeln!(out, "kairos_request_body_bytes_sum{{route=\"{}\"}} {}", route, route_stats.sum_bytes);
+    }
+
+    let counter = if openmetrics { "kairos_body_too_large" } else { "kairos_body_too_large_total" };
+    family(&mut out, counter, "counter", "Requests rejected for exceeding the route body size limit");
+    for (route, route_stats) in &stats {
+        let _ = writeln!(
+            out,
+            "kairos_body_too_large_total{{route=\"{}\"}} {}",
+            route, route_stats.too_large
+        );
+    }
+
+    out
+}
+
+/// Renders the breach counter and compliance gauge of routes with `sla_ms`,
+/// in the same layout as [`render_route_body_metrics`].
+fn render_route_sla_metrics(metrics: &MetricsCollector, openmetrics: bool) -> String {
+    use std::fmt::Write;
+
+    let stats = metrics.route_sla();
+    let mut out = String::new();
+    if stats.is_empty() {
+        return out;
+    }
+
+    let family = |out: &mut String, name: &str, kind: &str, help: &str| {
+        if openmetrics {
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+        } else {
+            let _ = writeln!(out, "\n# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        }
+    };
+
+    let counter = if openmetrics { "kairos_sla_breach" } else { "kairos_sla_breach_total" };
+    family(&mut out, counter, "counter", "Requests slower than the route's sla_ms");
+    for (route, route_stats) in &stats {
+        let _ = writeln!(
+            out,
+            "kairos_sla_breach_total{{{}}} {}",
+            route_labels(route, &route_stats.tags),
+            route_stats.breaches
+        );
+    }
+
+    family(
+        &mut out,
+        "kairos_sla_compliance",
+        "gauge",
+        &format!("Share of the route's last {} requests within its sla_ms", SLA_WINDOW_REQUESTS),
+    );
+    for (route, route_stats) in &stats {
+        let _ = writeln!(
+            out,
+            "kairos_sla_compliance{{{}}} {:.4}",
+            route_labels(route, &route_stats.tags),
+            route_stats.compliance()
+        );
+    }
+
+    out
+}
+
+/// Checks the request's `Authorization` header against the configured
+/// metrics credentials.
+///
+/// JWT auth validates against the [`JwtConfig`] in the app data and rejects
+/// every request if there is none.
+fn is_metrics_request_authorized(req: &HttpRequest, auth: &MetricsAuth) -> bool {
+    let Some(value) = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+
+    match auth {
+        MetricsAuth::Bearer { token } => value
+            .strip_prefix("Bearer ")
+            .is_some_and(|provided| constant_time_eq(provided.as_bytes(), token.as_bytes())),
+        MetricsAuth::Basic { username, password } => value
+            .strip_prefix("Basic ")
+            .and_then(|encoded| general_purpose::STANDARD.decode(encoded).ok())
+            .is_some_and(|decoded| {
+                let expected = format!("{}:{}", username, password);
+                constant_time_eq(&decoded, expected.as_bytes())
+            }),
+        MetricsAuth::Jwt => {
+            let jwt_config = req.app_data::<web::Data<JwtConfig>>();
+            match (value.strip_prefix("Bearer "), jwt_config) {
+                (Some(token), Some(config)) => validate_jwt_token(token, config).is_ok(),
+                _ => false,
+            }
+        }
+    }
+}
+
+/// Compares two byte slices without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// Query parameters for historical metrics.
 #[derive(Debug, Deserialize)]
 pub struct HistoricalMetricsQuery {
@@ -539,11 +1914,66 @@ pub async fn get_historical_metrics(
     }
 }
 
+/// Returns the `401 Unauthorized` response for a metrics request lacking the
+/// configured credentials, or `None` if it may proceed.
+fn reject_unauthorized(req: &HttpRequest, metrics_config: Option<&MetricsConfig>) -> Option<HttpResponse> {
+    let auth = metrics_config.and_then(|c| c.auth.as_ref())?;
+    if is_metrics_request_authorized(req, auth) {
+        return None;
+    }
+
+    log::warn!("Rejected unauthenticated metrics request");
+    let challenge = match auth {
+        MetricsAuth::Bearer { .. } | MetricsAuth::Jwt => "Bearer realm=\"metrics\"",
+        MetricsAuth::Basic { .. } => "Basic realm=\"metrics\"",
+    };
+    Some(HttpResponse::Unauthorized()
+        .insert_header((header::WWW_AUTHENTICATE, challenge))
+        .json(serde_json::json!({
+            "error": "Metrics endpoint requires authentication",
+            "type": "authentication_error",
+            "timestamp": Utc::now().to_rfc3339()
+        })))
+}
+
+/// Serves the gateway-wide metrics as a JSON [`MetricsSnapshot`].
+///
+/// Carries the same values as `/metrics`, read through the same
+/// [`MetricsCollector::snapshot`], for clients that would otherwise have to
+/// parse Prometheus text. Protected by the same `metrics.auth` credentials.
+///
+/// # Response Format
+///
+/// ```json
+/// {
+///   "timestamp": "2024-03-15T10:30:00Z",
+///   "requests_total": 1547,
+///   "success_rate": 99.2,
+///   "response_time_avg": 12.5,
+///   "circuit_breakers": [
+///     { "route": "api.example.com:443", "state": "Closed", "failure_count": 0, "success_count": 12 }
+///   ]
+/// }
+/// ```
+pub async fn metrics_json_endpoint(
+    req: HttpRequest,
+    metrics: web::Data<MetricsCollector>,
+    route_handler: Option<web::Data<RouteHandler>>,
+    metrics_config: Option<web::Data<MetricsConfig>>,
+) -> Result<HttpResponse> {
+    if let Some(rejection) = reject_unauthorized(&req, metrics_config.as_ref().map(|c| c.get_ref())) {
+        return Ok(rejection);
+    }
+
+    Ok(HttpResponse::Ok().json(metrics.snapshot(route_handler.as_ref().map(|h| h.get_ref()))))
+}
+
 /// Configures the metrics endpoint route for Actix Web application.
 /// 
 /// This function registers the `/metrics` endpoint that exposes Prometheus-compatible
-/// metrics for monitoring and observability. It should be called during application
-/// setup to enable metrics collection.
+/// metrics for monitoring and observability, and `/metrics.json` with the same
+/// values as JSON. It should be called during application setup to enable
+/// metrics collection.
 /// 
 /// # Parameters
 /// 
@@ -576,6 +2006,7 @@ pub async fn get_historical_metrics(
 /// The endpoint automatically accesses the collector to provide real-time metrics.
 pub fn configure_metrics(cfg: &mut web::ServiceConfig) {
     cfg.route("/metrics", web::get().to(metrics_endpoint))
+       .route("/metrics.json", web::get().to(metrics_json_endpoint))
        .route("/api/metrics/list", web::get().to(list_metrics))
        .route("/api/metrics/history", web::get().to(get_historical_metrics));
 }
\ No newline at end of file
@@ -8,6 +8,8 @@
 //! 
 //! - [`health`] - Health check endpoints for monitoring and Kubernetes probes
 //! - [`http`] - HTTP proxy route configuration and request handling
+//! - [`https_redirect`] - Plain HTTP listener that redirects to HTTPS
+//! - [`root`] - Configurable landing response for `GET /`
 //! - [`websocket`] - WebSocket connection handling and upgrades (future feature)
 //! 
 //! # Route Architecture
@@ -88,8 +90,10 @@ pub mod auth_http;
 pub mod config_reload;
 pub mod health;
 pub mod http;
+pub mod https_redirect;
 pub mod management;
 pub mod metrics;
+pub mod root;
 pub mod websocket;
 pub mod websocket_admin;
 pub mod ftp;
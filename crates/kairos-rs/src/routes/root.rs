@@ -0,0 +1,70 @@
+//! Landing response for the gateway root.
+//!
+//! A bare `GET /` is usually the first request someone sends to a new
+//! gateway. Unless configured otherwise it answers with the gateway name and
+//! version instead of falling through to the proxy.
+
+use crate::models::settings::RootResponse;
+use actix_web::{guard, http::header, web, HttpResponse};
+use serde_json::json;
+
+/// Registers the handler for `GET /` and `HEAD /` described by `response`.
+///
+/// Must be configured before the proxy's catch-all route, which would
+/// otherwise match `/` first. With [`RootResponse::Proxy`] nothing is
+/// registered, and other methods on `/` are always proxied.
+///
+/// # Examples
+///
+/// ```rust
+/// use actix_web::App;
+/// use kairos_rs::models::settings::RootResponse;
+/// use kairos_rs::routes::root::configure_root;
+///
+/// let app = App::new().configure(|cfg| configure_root(cfg, RootResponse::default()));
+/// ```
+pub fn configure_root(cfg: &mut web::ServiceConfig, response: RootResponse) {
+    if response == RootResponse::Proxy {
+        return;
+    }
+
+    cfg.service(
+        web::resource("/")
+            .guard(guard::Any(guard::Get()).or(guard::Head()))
+            .to(move || {
+                let response = response.clone();
+                async move { root_response(&response) }
+            }),
+    );
+}
+
+/// Builds the configured root response.
+fn root_response(response: &RootResponse) -> HttpResponse {
+    match response {
+        RootResponse::Info { name, docs_url } => {
+            let mut body = json!({
+                "service": name.as_deref().unwrap_or("kairos-rs"),
+                "version": env!("CARGO_PKG_VERSION"),
+            });
+            if let Some(docs_url) = docs_url {
+                body["docs"] = json!(docs_url);
+            }
+            HttpResponse::Ok().json(body)
+        }
+        RootResponse::Redirect {
+            location,
+            permanent,
+        } => {
+            let mut builder = if *permanent {
+                HttpResponse::MovedPermanently()
+            } else {
+                HttpResponse::Found()
+            };
+            builder
+                .insert_header((header::LOCATION, location.as_str()))
+                .finish()
+        }
+        // Not registered by `configure_root`
+        RootResponse::Proxy => HttpResponse::NotFound().finish(),
+    }
+}
@@ -14,6 +14,7 @@ use std::sync::atomic::Ordering;
 /// 
 /// - **Server -> Client**: JSON object containing current metrics snapshot (every 1s)
 /// - **Client -> Server**: Ping/Pong for keepalive
+#[allow(clippy::collapsible_match)] // Keeps the pong send out of the match guard
 pub async fn admin_metrics_ws(
     req: HttpRequest,
     stream: web::Payload,
@@ -4,11 +4,13 @@
 //! upstream services from cascading failures and provide fast failure responses
 //! when services are unavailable.
 
+use crate::models::router::CircuitBreakerSettings;
 use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use log::{warn, info, debug};
+use serde::Serialize;
 
 /// State of a circuit breaker.
 ///
@@ -17,6 +19,11 @@ use log::{warn, info, debug};
 /// * `Closed` - Normal operation, all requests pass through
 /// * `Open` - Circuit tripped, requests fail fast without executing
 /// * `HalfOpen` - Testing recovery, limited requests allowed through
+/// * `ForcedOpen` - Opened by an operator, failing fast until released
+/// * `ForcedClosed` - Closed by an operator, ignoring failures until released
+///
+/// Forced states are sticky: automatic transitions leave them alone until
+/// [`CircuitBreaker::release`] hands the breaker back to automatic control.
 ///
 /// # Examples
 ///
@@ -28,9 +35,11 @@ use log::{warn, info, debug};
 ///     CircuitState::Closed => println!("Healthy"),
 ///     CircuitState::Open => println!("Degraded"),
 ///     CircuitState::HalfOpen => println!("Recovering"),
+///     CircuitState::ForcedOpen => println!("In maintenance"),
+///     CircuitState::ForcedClosed => println!("Pinned healthy"),
 /// }
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum CircuitState {
     /// Normal operation - requests pass through
     Closed = 0,
@@ -38,6 +47,22 @@ pub enum CircuitState {
     Open = 1,
     /// Testing if service is back
     HalfOpen = 2,
+    /// Held open by an operator
+    ForcedOpen = 3,
+    /// Held closed by an operator
+    ForcedClosed = 4,
+}
+
+impl CircuitState {
+    /// Returns whether an operator pinned the breaker in this state.
+    pub fn is_forced(self) -> bool {
+        matches!(self, CircuitState::ForcedOpen | CircuitState::ForcedClosed)
+    }
+
+    /// Returns whether requests are rejected in this state.
+    pub fn is_open(self) -> bool {
+        matches!(self, CircuitState::Open | CircuitState::ForcedOpen)
+    }
 }
 
 impl From<u8> for CircuitState {
@@ -46,6 +71,8 @@ impl From<u8> for CircuitState {
             0 => CircuitState::Closed,
             1 => CircuitState::Open,
             2 => CircuitState::HalfOpen,
+            3 => CircuitState::ForcedOpen,
+            4 => CircuitState::ForcedClosed,
             _ => CircuitState::Closed,
         }
     }
@@ -63,6 +90,7 @@ impl From<u8> for CircuitState {
 /// * `success_threshold` - Number of consecutive successes to close the circuit (default: 3)
 /// * `timeout` - Request timeout before considering operation failed (default: 60s)
 /// * `reset_timeout` - Time to wait before transitioning from Open to HalfOpen (default: 30s)
+/// * `warmup` - Time after creation during which failures are not counted (default: none)
 /// 
 /// # Usage
 /// 
@@ -79,15 +107,17 @@ impl From<u8> for CircuitState {
 ///     success_threshold: 5,  // More conservative recovery
 ///     timeout: Duration::from_secs(30),
 ///     reset_timeout: Duration::from_secs(60),
+///     warmup: Duration::ZERO,
 /// };
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CircuitBreakerConfig {
     pub failure_threshold: u64,
     pub success_threshold: u64,
     #[allow(dead_code)] // Intended for request timeout integration
     pub timeout: Duration,
     pub reset_timeout: Duration,
+    pub warmup: Duration,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -97,6 +127,36 @@ impl Default for CircuitBreakerConfig {
             success_threshold: 3,
             timeout: Duration::from_secs(60),
             reset_timeout: Duration::from_secs(30),
+            warmup: Duration::ZERO,
+        }
+    }
+}
+
+impl From<&CircuitBreakerSettings> for CircuitBreakerConfig {
+    fn from(settings: &CircuitBreakerSettings) -> Self {
+        Self {
+            failure_threshold: settings.failure_threshold,
+            success_threshold: settings.success_threshold,
+            reset_timeout: Duration::from_secs(settings.reset_timeout_secs),
+            warmup: Duration::from_secs(settings.warmup_secs),
+            ..Default::default()
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Combines two configurations, keeping the stricter value of each field.
+    ///
+    /// The result trips on the fewest failures, starts counting them soonest,
+    /// and waits longest and for the most successes before closing again. Used when one backend is shared
+    /// by routes with different breaker settings.
+    pub fn most_conservative(&self, other: &Self) -> Self {
+        Self {
+            failure_threshold: self.failure_threshold.min(other.failure_threshold),
+            success_threshold: self.success_threshold.max(other.success_threshold),
+            timeout: self.timeout.min(other.timeout),
+            reset_timeout: self.reset_timeout.max(other.reset_timeout),
+            warmup: self.warmup.min(other.warmup),
         }
     }
 }
@@ -157,6 +217,7 @@ pub struct CircuitBreaker {
     failure_count: AtomicU64,
     success_count: AtomicU64,
     last_failure_time: RwLock<Option<Instant>>,
+    created: Instant,
     name: String,
 }
 
@@ -187,6 +248,7 @@ impl CircuitBreaker {
             failure_count: AtomicU64::new(0),
             success_count: AtomicU64::new(0),
             last_failure_time: RwLock::new(None),
+            created: Instant::now(),
             name,
         })
     }
@@ -223,6 +285,22 @@ impl CircuitBreaker {
     /// # }
     /// ```
     pub async fn call<F, T, E>(&self, operation: F) -> Result<T, CircuitBreakerError<E>>
+    where
+        F: std::future::Future<Output = Result<T, E>>,
+    {
+        self.call_with(operation, |_| true).await
+    }
+
+    /// Executes an operation like [`call`](Self::call), counting only the
+    /// errors for which `is_failure` returns `true` against the circuit.
+    ///
+    /// Other errors are returned as `OperationFailed` without changing the
+    /// circuit's state.
+    pub async fn call_with<F, T, E>(
+        &self,
+        operation: F,
+        is_failure: impl Fn(&E) -> bool,
+    ) -> Result<T, CircuitBreakerError<E>>
     where
         F: std::future::Future<Output = Result<T, E>>,
     {
@@ -239,7 +317,9 @@ impl CircuitBreaker {
                 Ok(result)
             }
             Err(error) => {
-                self.on_failure().await;
+                if is_failure(&error) {
+                    self.on_failure().await;
+                }
                 Err(CircuitBreakerError::OperationFailed(error))
             }
         }
@@ -263,7 +343,8 @@ impl CircuitBreaker {
                     false
                 }
             }
-            CircuitState::HalfOpen => false,
+            CircuitState::HalfOpen | CircuitState::ForcedClosed => false,
+            CircuitState::ForcedOpen => true,
         }
     }
 
@@ -285,6 +366,7 @@ impl CircuitBreaker {
                 // This shouldn't happen, but handle gracefully
                 debug!("Unexpected success in open state for circuit {}", self.name);
             }
+            CircuitState::ForcedOpen | CircuitState::ForcedClosed => {}
         }
     }
 
@@ -292,6 +374,13 @@ impl CircuitBreaker {
         let current_state = CircuitState::from(self.state.load(Ordering::Relaxed));
         
         match current_state {
+            CircuitState::Closed if self.created.elapsed() < self.config.warmup => {
+                // Backends may fail while they warm up; do not let that trip the breaker
+                info!(
+                    "Circuit breaker {} ignoring failure during {:?} warmup",
+                    self.name, self.config.warmup
+                );
+            }
             CircuitState::Closed => {
                 let failure_count = self.failure_count.fetch_add(1, Ordering::Relaxed) + 1;
                 if failure_count >= self.config.failure_threshold {
@@ -306,11 +395,24 @@ impl CircuitBreaker {
                 // Update last failure time
                 *self.last_failure_time.write().await = Some(Instant::now());
             }
+            CircuitState::ForcedOpen | CircuitState::ForcedClosed => {}
         }
     }
 
+    /// Moves to `state` unless an operator forced a state in the meantime,
+    /// returning whether it did.
+    fn transition(&self, state: CircuitState) -> bool {
+        self.state
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                (!CircuitState::from(current).is_forced()).then_some(state as u8)
+            })
+            .is_ok()
+    }
+
     async fn transition_to_open(&self) {
-        self.state.store(CircuitState::Open as u8, Ordering::Relaxed);
+        if !self.transition(CircuitState::Open) {
+            return;
+        }
         *self.last_failure_time.write().await = Some(Instant::now());
         self.success_count.store(0, Ordering::Relaxed);
         
@@ -318,25 +420,111 @@ impl CircuitBreaker {
     }
 
     async fn transition_to_half_open(&self) {
-        self.state.store(CircuitState::HalfOpen as u8, Ordering::Relaxed);
+        if !self.transition(CircuitState::HalfOpen) {
+            return;
+        }
         self.success_count.store(0, Ordering::Relaxed);
         
         info!("Circuit breaker {} transitioned to half-open", self.name);
     }
 
     async fn transition_to_closed(&self) {
-        self.state.store(CircuitState::Closed as u8, Ordering::Relaxed);
+        if !self.transition(CircuitState::Closed) {
+            return;
+        }
         self.failure_count.store(0, Ordering::Relaxed);
         self.success_count.store(0, Ordering::Relaxed);
         
         info!("Circuit breaker {} closed - service recovered", self.name);
     }
 
+    /// Forces the circuit back to Closed and clears its counters.
+    ///
+    /// Used to resume traffic to a backend known to have recovered without
+    /// waiting for the reset timeout and half-open probing. A forced state
+    /// is cleared as well, returning the breaker to automatic control.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kairos_rs::services::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+    /// # async fn example() {
+    /// # let breaker = CircuitBreaker::new("test".to_string(), CircuitBreakerConfig::default());
+    /// breaker.reset().await;
+    /// assert_eq!(breaker.get_state(), CircuitState::Closed);
+    /// # }
+    /// ```
+    pub async fn reset(&self) {
+        self.state.store(CircuitState::Closed as u8, Ordering::Relaxed);
+        self.failure_count.store(0, Ordering::Relaxed);
+        self.success_count.store(0, Ordering::Relaxed);
+        *self.last_failure_time.write().await = None;
+
+        info!("Circuit breaker {} manually reset to closed", self.name);
+    }
+
+    /// Holds the circuit open until [`release`](Self::release) is called.
+    ///
+    /// Requests fail fast as if the backend were failing, and the reset
+    /// timeout does not let test traffic through. Used to drain traffic from
+    /// a backend before planned maintenance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kairos_rs::services::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+    /// # async fn example() {
+    /// # let breaker = CircuitBreaker::new("test".to_string(), CircuitBreakerConfig::default());
+    /// breaker.force_open().await;
+    /// assert_eq!(breaker.get_state(), CircuitState::ForcedOpen);
+    /// breaker.release().await;
+    /// assert_eq!(breaker.get_state(), CircuitState::HalfOpen);
+    /// # }
+    /// ```
+    pub async fn force_open(&self) {
+        self.state.store(CircuitState::ForcedOpen as u8, Ordering::Relaxed);
+        self.failure_count.store(0, Ordering::Relaxed);
+        self.success_count.store(0, Ordering::Relaxed);
+
+        warn!("Circuit breaker {} forced open", self.name);
+    }
+
+    /// Holds the circuit closed until [`release`](Self::release) is called.
+    ///
+    /// Requests are always forwarded and failures do not open the circuit.
+    pub async fn force_closed(&self) {
+        self.state.store(CircuitState::ForcedClosed as u8, Ordering::Relaxed);
+        self.failure_count.store(0, Ordering::Relaxed);
+        self.success_count.store(0, Ordering::Relaxed);
+        *self.last_failure_time.write().await = None;
+
+        warn!("Circuit breaker {} forced closed", self.name);
+    }
+
+    /// Returns a forced circuit to automatic control.
+    ///
+    /// A circuit forced open moves to HalfOpen, so the backend has to pass
+    /// the usual recovery probing before receiving all traffic again. A
+    /// circuit forced closed moves to Closed with cleared counters. Other
+    /// states are left unchanged.
+    pub async fn release(&self) {
+        let released = match self.get_state() {
+            CircuitState::ForcedOpen => CircuitState::HalfOpen,
+            CircuitState::ForcedClosed => CircuitState::Closed,
+            _ => return,
+        };
+        self.state.store(released as u8, Ordering::Relaxed);
+        self.failure_count.store(0, Ordering::Relaxed);
+        self.success_count.store(0, Ordering::Relaxed);
+
+        info!("Circuit breaker {} released to automatic control", self.name);
+    }
+
     /// Gets the current state of the circuit breaker.
     ///
     /// # Returns
     ///
-    /// Current `CircuitState` (Closed, Open, or HalfOpen)
+    /// Current `CircuitState` (Closed, Open, HalfOpen, or a forced state)
     ///
     /// # Examples
     ///
@@ -347,6 +535,7 @@ impl CircuitBreaker {
     ///     CircuitState::Closed => println!("Operating normally"),
     ///     CircuitState::Open => println!("Failing fast"),
     ///     CircuitState::HalfOpen => println!("Testing recovery"),
+    ///     CircuitState::ForcedOpen | CircuitState::ForcedClosed => println!("Operator override"),
     /// }
     /// ```
     pub fn get_state(&self) -> CircuitState {
@@ -386,6 +575,11 @@ impl CircuitBreaker {
     pub fn get_success_count(&self) -> u64 {
         self.success_count.load(Ordering::Relaxed)
     }
+
+    /// Gets the configuration this breaker was created with.
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
 }
 
 /// Errors that can occur when using a circuit breaker.
@@ -0,0 +1,209 @@
+//! DNS-based backend discovery.
+//!
+//! Routes with `dns_discovery` get their backends from an A/AAAA or SRV
+//! record instead of a fixed list, so the gateway can point at services whose
+//! addresses change, such as Kubernetes headless services.
+//!
+//! A single background task resolves every discovering route once per its
+//! `ttl_secs` and feeds the results into the [`RouteHandler`]. Failed or empty
+//! resolutions keep the previously discovered backends.
+
+use crate::models::error::GatewayError;
+use crate::models::router::{Backend, DnsDiscovery, DnsRecordType};
+use crate::services::http::RouteHandler;
+use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+use hickory_resolver::TokioAsyncResolver;
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use tokio::time::{Duration, Instant};
+
+/// How often the discovery task checks for routes due for resolution.
+const DISCOVERY_TICK: Duration = Duration::from_secs(1);
+
+/// Resolves [`DnsDiscovery`] records into backends.
+#[derive(Clone)]
+pub struct DnsDiscoveryResolver {
+    resolver: TokioAsyncResolver,
+}
+
+impl DnsDiscoveryResolver {
+    /// Creates a resolver from the system configuration (`/etc/resolv.conf`
+    /// and the hosts file), falling back to public name servers if it cannot
+    /// be read.
+    pub fn from_system_conf() -> Self {
+        let resolver = TokioAsyncResolver::tokio_from_system_conf().unwrap_or_else(|e| {
+            warn!("Cannot read system DNS configuration, using defaults: {}", e);
+            TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default())
+        });
+        Self { resolver }
+    }
+
+    /// Resolves `discovery` into a sorted list of backends.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GatewayError::Upstream`] if the lookup fails or finds no records.
+    pub async fn resolve(&self, discovery: &DnsDiscovery) -> Result<Vec<Backend>, GatewayError> {
+        let lookup_error = |message: String| GatewayError::Upstream {
+            message,
+            url: discovery.name.clone(),
+            status: None,
+        };
+
+        let backends = match discovery.record_type {
+            DnsRecordType::A => {
+                let lookup = self
+                    .resolver
+                    .lookup_ip(discovery.name.as_str())
+                    .await
+                    .map_err(|e| lookup_error(format!("DNS lookup failed: {}", e)))?;
+                backends_from_ips(discovery, lookup.iter())
+            }
+            DnsRecordType::Srv => {
+                let lookup = self
+                    .resolver
+                    .srv_lookup(discovery.name.as_str())
+                    .await
+                    .map_err(|e| lookup_error(format!("DNS SRV lookup failed: {}", e)))?;
+                let records = lookup.iter().map(|srv| {
+                    (srv.priority(), srv.weight(), srv.port(), srv.target().to_utf8())
+                });
+                backends_from_srv(discovery, records)
+            }
+        };
+
+        if backends.is_empty() {
+            return Err(lookup_error("DNS lookup returned no records".to_string()));
+        }
+        Ok(backends)
+    }
+}
+
+/// Builds one backend per address on the configured port.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::models::router::DnsDiscovery;
+/// use kairos_rs::services::discovery::backends_from_ips;
+///
+/// let discovery: DnsDiscovery = serde_json::from_value(serde_json::json!({
+///     "name": "users.default.svc.cluster.local",
+///     "port": 8080
+/// })).unwrap();
+/// let ips = ["10.0.0.2".parse().unwrap(), "fd00::1".parse().unwrap()];
+///
+/// let backends = backends_from_ips(&discovery, ips);
+/// assert_eq!(backends[0].host, "http://10.0.0.2");
+/// assert_eq!(backends[1].host, "http://[fd00::1]");
+/// assert_eq!(backends[1].port, 8080);
+/// ```
+pub fn backends_from_ips(
+    discovery: &DnsDiscovery,
+    ips: impl IntoIterator<Item = IpAddr>,
+) -> Vec<Backend> {
+    let port = discovery.port.unwrap_or_default();
+    let mut backends: Vec<Backend> = ips
+        .into_iter()
+        .map(|ip| {
+            let host = match ip {
+                IpAddr::V4(ip) => format!("{}://{}", discovery.scheme, ip),
+                IpAddr::V6(ip) => format!("{}://[{}]", discovery.scheme, ip),
+            };
+            discovered_backend(host, port, 1)
+        })
+        .collect();
+    sort_and_dedup(&mut backends);
+    backends
+}
+
+/// Builds one backend per SRV target of the lowest priority.
+///
+/// Records are given as `(priority, weight, port, target)`. The configured
+/// port, if any, overrides the record's port, and a weight of 0 counts as 1.
+pub fn backends_from_srv(
+    discovery: &DnsDiscovery,
+    records: impl IntoIterator<Item = (u16, u16, u16, String)>,
+) -> Vec<Backend> {
+    let records: Vec<_> = records.into_iter().collect();
+    let Some(priority) = records.iter().map(|(priority, ..)| *priority).min() else {
+        return Vec::new();
+    };
+
+    let mut backends: Vec<Backend> = records
+        .into_iter()
+        .filter(|(record_priority, ..)| *record_priority == priority)
+        .map(|(_, weight, port, target)| {
+            let host = format!("{}://{}", discovery.scheme, target.trim_end_matches('.'));
+            discovered_backend(host, discovery.port.unwrap_or(port), u32::from(weight.max(1)))
+        })
+        .collect();
+    sort_and_dedup(&mut backends);
+    backends
+}
+
+fn discovered_backend(host: String, port: u16, weight: u32) -> Backend {
+    Backend {
+        host,
+        port,
+        weight,
+        health_check_path: None,
+        timeout_secs: None,
+    }
+}
+
+/// Sorts backends so unchanged record sets compare equal across lookups.
+fn sort_and_dedup(backends: &mut Vec<Backend>) {
+    backends.sort_by(|a, b| (&a.host, a.port).cmp(&(&b.host, b.port)));
+    backends.dedup_by(|a, b| a.host == b.host && a.port == b.port);
+}
+
+/// Spawns the background task that keeps DNS-discovered backends up to date.
+///
+/// Routes are resolved immediately and then every `ttl_secs`. Routes added,
+/// removed or changed by a reload are picked up on the next tick.
+pub fn spawn_dns_discovery(
+    handler: RouteHandler,
+    resolver: DnsDiscoveryResolver,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut next_resolution: HashMap<String, (DnsDiscovery, Instant)> = HashMap::new();
+        let mut interval = tokio::time::interval(DISCOVERY_TICK);
+
+        loop {
+            interval.tick().await;
+
+            let routes = handler.dns_discovery_routes();
+            next_resolution.retain(|external_path, (discovery, _)| {
+                routes.iter().any(|(path, d)| path == external_path && d == discovery)
+            });
+
+            for (external_path, discovery) in routes {
+                let now = Instant::now();
+                if next_resolution
+                    .get(&external_path)
+                    .is_some_and(|(_, due)| *due > now)
+                {
+                    continue;
+                }
+                next_resolution.insert(
+                    external_path.clone(),
+                    (discovery.clone(), now + Duration::from_secs(discovery.ttl_secs)),
+                );
+
+                match resolver.resolve(&discovery).await {
+                    Ok(backends) => {
+                        if !handler.set_discovered_backends(&external_path, &discovery, backends) {
+                            debug!("Backends of {} unchanged after DNS discovery", external_path);
+                        }
+                    }
+                    Err(e) => warn!(
+                        "DNS discovery of {} for route {} failed, keeping previous backends: {}",
+                        discovery.name, external_path, e
+                    ),
+                }
+            }
+        }
+    })
+}
@@ -110,6 +110,7 @@ impl DnsHandler {
     ///     port: 53,
     ///     weight: 1,
     ///     health_check_path: None,
+    ///     timeout_secs: None,
     /// };
     ///
     /// let query = vec![/* DNS query bytes */];
@@ -182,6 +183,7 @@ impl DnsHandler {
         .await
         .map_err(|_| GatewayError::Timeout {
             timeout: self.timeout_seconds,
+            message: None,
         })?
         .map_err(|e| GatewayError::Upstream {
             message: format!("Failed to send DNS query: {}", e),
@@ -198,6 +200,7 @@ impl DnsHandler {
         .await
         .map_err(|_| GatewayError::Timeout {
             timeout: self.timeout_seconds,
+            message: None,
         })?
         .map_err(|e| GatewayError::Upstream {
             message: format!("Failed to receive DNS response: {}", e),
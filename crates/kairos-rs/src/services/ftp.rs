@@ -96,6 +96,7 @@ impl FtpHandler {
     ///     port: 21,
     ///     weight: 1,
     ///     health_check_path: None,
+    ///     timeout_secs: None,
     /// };
     ///
     /// let files = handler.list_directory(
@@ -168,6 +169,7 @@ impl FtpHandler {
     ///     port: 21,
     ///     weight: 1,
     ///     health_check_path: None,
+    ///     timeout_secs: None,
     /// };
     ///
     /// let content = handler.retrieve_file(
@@ -236,6 +238,7 @@ impl FtpHandler {
     ///     port: 21,
     ///     weight: 1,
     ///     health_check_path: None,
+    ///     timeout_secs: None,
     /// };
     ///
     /// let content = b"Hello, FTP!";
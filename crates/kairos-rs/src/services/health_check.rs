@@ -0,0 +1,155 @@
+//! Background health checking of route backends.
+//!
+//! Routes with `health_check` have every backend with a `health_check_path`
+//! probed on an interval. A backend that fails `unhealthy_threshold` probes in
+//! a row is taken out of load balancer rotation until it passes
+//! `healthy_threshold` probes in a row. If every backend of a route is down,
+//! requests are sent to all of them anyway rather than failing outright.
+//!
+//! A single background task probes every checked route once per its
+//! `interval_secs`, like DNS discovery.
+
+use crate::models::router::HealthCheckSettings;
+use crate::services::http::RouteHandler;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+/// How often the health check task looks for routes due for probing.
+const HEALTH_CHECK_TICK: Duration = Duration::from_secs(1);
+
+/// Health of one backend, as decided by consecutive probe results.
+///
+/// Backends start healthy, so traffic flows before the first probe.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::models::router::HealthCheckSettings;
+/// use kairos_rs::services::health_check::BackendHealth;
+///
+/// let settings = HealthCheckSettings {
+///     unhealthy_threshold: 2,
+///     healthy_threshold: 1,
+///     ..Default::default()
+/// };
+/// let health = BackendHealth::new();
+///
+/// assert_eq!(health.record(false, &settings), None);
+/// assert_eq!(health.record(false, &settings), Some(false));
+/// assert!(!health.is_healthy());
+///
+/// assert_eq!(health.record(true, &settings), Some(true));
+/// assert!(health.is_healthy());
+/// ```
+#[derive(Debug)]
+pub struct BackendHealth {
+    state: Mutex<HealthState>,
+}
+
+#[derive(Debug)]
+struct HealthState {
+    healthy: bool,
+    consecutive_failures: u32,
+    consecutive_successes: u32,
+}
+
+impl BackendHealth {
+    /// Creates a healthy backend state.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(HealthState {
+                healthy: true,
+                consecutive_failures: 0,
+                consecutive_successes: 0,
+            }),
+        }
+    }
+
+    /// Records a probe result.
+    ///
+    /// # Returns
+    ///
+    /// The new health if the probe changed it, `None` otherwise
+    pub fn record(&self, success: bool, settings: &HealthCheckSettings) -> Option<bool> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if success {
+            state.consecutive_failures = 0;
+            state.consecutive_successes = state.consecutive_successes.saturating_add(1);
+            if !state.healthy && state.consecutive_successes >= settings.healthy_threshold {
+                state.healthy = true;
+                return Some(true);
+            }
+        } else {
+            state.consecutive_successes = 0;
+            state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+            if state.healthy && state.consecutive_failures >= settings.unhealthy_threshold {
+                state.healthy = false;
+                return Some(false);
+            }
+        }
+        None
+    }
+
+    /// Returns whether the backend is in rotation.
+    pub fn is_healthy(&self) -> bool {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).healthy
+    }
+}
+
+impl Default for BackendHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Spawns the background task that probes the backends of routes with
+/// `health_check`.
+///
+/// Routes are probed immediately and then every `interval_secs`. Routes
+/// added, removed or changed by a reload are picked up on the next tick.
+pub fn spawn_health_checks(handler: RouteHandler) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut next_check: HashMap<String, (HealthCheckSettings, Instant)> = HashMap::new();
+        let mut interval = tokio::time::interval(HEALTH_CHECK_TICK);
+
+        loop {
+            interval.tick().await;
+
+            let routes = handler.health_check_routes();
+            next_check.retain(|external_path, (settings, _)| {
+                routes.iter().any(|(path, s)| path == external_path && s == settings)
+            });
+
+            let now = Instant::now();
+            let due: Vec<(String, HealthCheckSettings)> = routes
+                .into_iter()
+                .filter(|(external_path, _)| {
+                    next_check
+                        .get(external_path)
+                        .is_none_or(|(_, due)| *due <= now)
+                })
+                .collect();
+            for (external_path, settings) in &due {
+                next_check.insert(
+                    external_path.clone(),
+                    (settings.clone(), now + Duration::from_secs(settings.interval_secs)),
+                );
+            }
+
+            let checks = due
+                .iter()
+                .map(|(external_path, settings)| handler.check_backend_health(external_path, settings));
+            for transitions in futures::future::join_all(checks).await {
+                for (service, healthy) in transitions {
+                    if healthy {
+                        info!("Backend {} passed its health checks, back in rotation", service);
+                    } else {
+                        warn!("Backend {} failed its health checks, out of rotation", service);
+                    }
+                }
+            }
+        }
+    })
+}
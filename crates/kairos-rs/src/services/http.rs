@@ -1,26 +1,61 @@
+use crate::middleware::transform::{RequestTransformer, ResponseTransformer};
 use crate::models::error::GatewayError;
-use crate::models::router::{AiRoutingStrategy, Router};
-use crate::routes::metrics::MetricsCollector;
+use crate::models::router::{
+    AcceptEncodingMode, AiRoutingStrategy, Backend, DnsDiscovery, FixedResponse, HashKey, HealthCheckSettings,
+    LoadBalancingStrategy, RequestStartHeader, ResponseSchemaMode, Router, TrailingSlash,
+};
+use crate::models::settings::{EmptyPoolStatus, ForwardedHeadersSettings, StreamingSettings};
+use crate::routes::metrics::{trace_id_from_traceparent, MetricsCollector};
 use crate::services::ai::AiService;
-use crate::services::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerError};
+use crate::services::circuit_breaker::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerError, CircuitState,
+};
+use crate::services::health_check::BackendHealth;
 use crate::services::load_balancer::{LoadBalancer, LoadBalancerFactory};
+use crate::services::response_schema::ResponseSchema;
+use crate::services::retry_budget::RetryBudget;
+use crate::services::webhook_signature::WebhookVerifier;
 use crate::utils::path::format_route;
-use crate::utils::route_matcher::RouteMatcher;
+use crate::utils::route_matcher::{RouteMatchError, RouteMatcher};
 
 use actix_web::{
-    http::{Method as ActixMethod, StatusCode},
-    web, Error as ActixError, HttpRequest, HttpResponse,
+    body::{BodySize, BoxBody, MessageBody, SizedStream},
+    http::{header, Method as ActixMethod, StatusCode},
+    web, Error as ActixError, FromRequest, HttpRequest, HttpResponse,
 };
-use log::{debug, info, warn};
+use arc_swap::ArcSwap;
+use ipnet::IpNet;
+use log::{debug, error, info, warn};
 use reqwest::{
     header::HeaderMap as ReqwestHeaderMap, header::HeaderName, header::HeaderValue, Client,
     Method as ReqwestMethod,
 };
-use std::collections::HashMap;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 use tokio::time::{sleep, timeout, Duration};
 
+/// Header asking the gateway to skip circuit breakers for a request. Only
+/// honored from peers allowed by [`RouteHandler::with_circuit_breaker_bypass`]
+/// and never forwarded upstream.
+pub const BYPASS_BREAKER_HEADER: &str = "x-kairos-bypass-breaker";
+
+/// Header naming the upstream path to forward a request to instead of the
+/// route's internal path. Only honored from peers allowed by
+/// [`RouteHandler::with_debug_overrides`] and never forwarded upstream.
+pub const OVERRIDE_PATH_HEADER: &str = "x-kairos-override-path";
+
+/// Upstream response header naming a path the gateway should serve instead,
+/// on routes with `x_accel_redirect` enabled.
+const ACCEL_REDIRECT_HEADER: &str = "x-accel-redirect";
+
+/// Chunks of a streamed request body buffered between the client and the
+/// upstream connection.
+const UPLOAD_CHANNEL_CAPACITY: usize = 8;
+
 /// High-performance HTTP request handler for the kairos-rs gateway.
 ///
 /// The `RouteHandler` is responsible for processing incoming HTTP requests,
@@ -41,6 +76,8 @@ use tokio::time::{sleep, timeout, Duration};
 /// - **Connection Pooling**: Reuses HTTP connections for better performance
 /// - **Timeout Management**: Configurable request timeouts prevent hanging requests
 /// - **Header Optimization**: Efficient header conversion and filtering
+/// - **Response Streaming**: Large, chunked and `text/event-stream` upstream bodies are
+///   streamed, not buffered
 /// - **Route Matching**: Supports both static and dynamic (parameterized) routes
 /// - **Thread Safety**: Safe to clone and share across multiple workers
 ///
@@ -55,7 +92,7 @@ use tokio::time::{sleep, timeout, Duration};
 ///
 /// ```rust
 /// use kairos_rs::services::http::RouteHandler;
-/// use kairos_rs::models::router::{Router, Backend, Protocol};
+/// use kairos_rs::models::router::{Router, Backend};
 ///
 /// let routes = vec![
 ///     Router {
@@ -64,19 +101,14 @@ use tokio::time::{sleep, timeout, Duration};
 ///         external_path: "/api/users/{id}".to_string(),
 ///         internal_path: "/v1/user/{id}".to_string(),
 ///         methods: vec!["GET".to_string(), "PUT".to_string()],
-///         auth_required: false,
 ///         backends: Some(vec![Backend {
 ///             host: "http://backend".to_string(),
 ///             port: 8080,
 ///             weight: 1,
 ///             health_check_path: None,
+///             timeout_secs: None,
 ///         }]),
-///         load_balancing_strategy: Default::default(),
-///         retry: None,
-///         protocol: Protocol::Http,
-///         request_transformation: None,
-///         response_transformation: None,
-///         ai_policy: None,
+///         ..Default::default()
 ///     }
 /// ];
 ///
@@ -86,16 +118,456 @@ use tokio::time::{sleep, timeout, Duration};
 pub struct RouteHandler {
     /// HTTP client with connection pooling and optimized settings
     client: Client,
-    /// Thread-safe route matcher for path resolution
-    route_matcher: Arc<RouteMatcher>,
-    /// Request timeout in seconds
+    /// Route-derived state, swapped as a whole when routes are reloaded
+    routes: Arc<ArcSwap<RouteTable>>,
+    /// Default request timeout in seconds (overridable per backend)
     timeout_seconds: u64,
-    /// Circuit breakers for upstream services (keyed by host:port)
-    circuit_breakers: Arc<HashMap<String, Arc<CircuitBreaker>>>,
-    /// Load balancers for each route (keyed by external_path)
-    load_balancers: Arc<HashMap<String, Arc<dyn LoadBalancer>>>,
     /// AI Service for intelligent routing
     ai_service: Option<Arc<AiService>>,
+    /// Response size in bytes above which upstream bodies are streamed
+    streaming_threshold_bytes: u64,
+    /// Largest request body in bytes copied to a route's mirror backend
+    mirror_body_limit_bytes: u64,
+    /// Client-facing message for upstream timeouts, replacing the default
+    timeout_message: Option<String>,
+    /// Status returned for routes whose backend pool is empty
+    empty_pool_status: EmptyPoolStatus,
+    /// Whether routes' `fault_injection` settings are applied
+    fault_injection_enabled: bool,
+    /// `X-Forwarded-*` headers added to upstream requests, if enabled
+    forwarded_headers: Option<ForwardedHeadersSettings>,
+    /// Headers set on every upstream request before route transformations
+    default_upstream_headers: Vec<(header::HeaderName, header::HeaderValue)>,
+    /// Peers whose `X-Kairos-Bypass-Breaker` header is honored
+    breaker_bypass_networks: Vec<IpNet>,
+    /// Peers whose `X-Kairos-Override-Path` header is honored
+    override_path_networks: Vec<IpNet>,
+    /// Request start header for routes that do not configure their own
+    request_start_header: Option<RequestStartHeader>,
+    /// Whether proxy routes forward traffic; false while warming up
+    ready: Arc<AtomicBool>,
+    /// End of the warmup grace period, if a warmup was configured
+    warmup_deadline: Option<Instant>,
+}
+
+/// Builds a [`RouteHandler`] with a customized upstream HTTP client.
+///
+/// [`RouteHandler::new`] uses a client keeping up to 32 idle connections per
+/// host for 30 seconds, resolving names with the system resolver and
+/// honoring the usual proxy environment variables. The builder can change
+/// the pool, add a connect timeout, a DNS resolver or proxies, or take a
+/// fully configured client instead, for example with custom TLS roots or
+/// client certificates.
+///
+/// `timeout_seconds` remains the default per-attempt timeout applied by the
+/// handler; backends' `timeout_secs` still override it. Everything else is
+/// configured on the built handler with the `with_*` methods.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::services::http::RouteHandler;
+/// use std::time::Duration;
+///
+/// let handler = RouteHandler::builder(vec![], 30)
+///     .connect_timeout(Duration::from_secs(2))
+///     .pool_max_idle_per_host(64)
+///     .proxy(reqwest::Proxy::http("http://egress.internal:3128").unwrap())
+///     .build()
+///     .unwrap();
+/// ```
+pub struct RouteHandlerBuilder {
+    routes: Vec<Router>,
+    timeout_seconds: u64,
+    client: Option<Client>,
+    connect_timeout: Option<Duration>,
+    pool_idle_timeout: Duration,
+    pool_max_idle_per_host: usize,
+    dns_resolver: Option<Arc<dyn reqwest::dns::Resolve>>,
+    proxies: Vec<reqwest::Proxy>,
+    trailing_slash: TrailingSlash,
+}
+
+impl RouteHandlerBuilder {
+    /// Starts a builder with the same client settings as [`RouteHandler::new`].
+    pub fn new(routes: Vec<Router>, timeout_seconds: u64) -> Self {
+        Self {
+            routes,
+            timeout_seconds,
+            client: None,
+            connect_timeout: None,
+            pool_idle_timeout: Duration::from_secs(30),
+            pool_max_idle_per_host: 32,
+            dns_resolver: None,
+            proxies: Vec::new(),
+            trailing_slash: TrailingSlash::default(),
+        }
+    }
+
+    /// Uses `client` for upstream requests as is.
+    ///
+    /// The other client settings of the builder are then ignored. The client
+    /// should be built with `.no_gzip().no_brotli()`, so backend responses
+    /// are relayed with the encoding they were sent with.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Limits how long connecting to a backend may take, within the
+    /// attempt's timeout. Unlimited by default.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Closes idle pooled connections after `timeout` (default: 30 seconds).
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = timeout;
+        self
+    }
+
+    /// Keeps at most `max` idle connections per backend (default: 32).
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = max;
+        self
+    }
+
+    /// Resolves backend host names with `resolver` instead of the system
+    /// resolver.
+    pub fn dns_resolver<R: reqwest::dns::Resolve + 'static>(mut self, resolver: Arc<R>) -> Self {
+        self.dns_resolver = Some(resolver);
+        self
+    }
+
+    /// Sends upstream requests through `proxy`. Can be called several
+    /// times; the first proxy matching a request is used.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Sets how request paths differing from a route only by a trailing
+    /// slash are matched; see [`TrailingSlash`]. Kept across route reloads.
+    pub fn trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    /// Builds the handler.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the HTTP client cannot be created or a route cannot be
+    /// compiled.
+    pub fn build(self) -> Result<RouteHandler, String> {
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                // Bodies are relayed as the backend encoded them; compressing
+                // for the client is left to the backend or the gateway's
+                // Compress middleware
+                let mut builder = Client::builder()
+                    .no_gzip()
+                    .no_brotli()
+                    .pool_idle_timeout(self.pool_idle_timeout)
+                    .pool_max_idle_per_host(self.pool_max_idle_per_host);
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(resolver) = self.dns_resolver {
+                    builder = builder.dns_resolver2(resolver);
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                builder
+                    .build()
+                    .map_err(|e| format!("Failed to create HTTP client: {}", e))?
+            }
+        };
+        RouteHandler::from_client(client, self.routes, self.timeout_seconds, self.trailing_slash)
+    }
+}
+
+/// Availability of one backend, as reported by [`RouteHandler::backend_statuses`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackendStatus {
+    /// Backend host, including the scheme
+    pub host: String,
+    /// Backend port
+    pub port: u16,
+    /// External paths of the routes using this backend
+    pub routes: Vec<String>,
+    /// Whether the backend is accepting traffic
+    pub up: bool,
+}
+
+/// How a dynamic route is matched, as reported by [`RouteHandler::route_debug`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RouteDebugInfo {
+    /// External path pattern of the route
+    pub external_path: String,
+    /// Regular expression the pattern was compiled to
+    pub regex: String,
+    /// Parameter names, in the order they appear in the pattern
+    pub param_names: Vec<String>,
+    /// Request paths that matched the route
+    pub hits: u64,
+    /// Request paths tried against the route without matching
+    pub misses: u64,
+}
+
+/// Result of probing a backend, as reported by [`RouteHandler::probe_upstreams`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpstreamHealth {
+    /// The backend has no `health_check_path`, so it was not probed
+    Unknown,
+    /// The backend's health check path was requested
+    Probed {
+        /// Whether a 2xx response arrived within the probe timeout
+        healthy: bool,
+        /// Status code of the response, if one arrived
+        status_code: Option<u16>,
+        /// Time until the response, error or timeout
+        latency_ms: u64,
+    },
+}
+
+/// Everything the handler derives from the route list.
+///
+/// Kept behind a single [`ArcSwap`] so a route reload replaces matcher, load
+/// balancers and circuit breakers at once. Requests load the table once and
+/// finish with it, even if a reload happens while they are in flight.
+struct RouteTable {
+    /// Routes as configured, before discovered backends are applied
+    routes: Vec<Router>,
+    /// Backends found by DNS discovery and the discovery settings that found
+    /// them (keyed by external_path)
+    discovered_backends: HashMap<String, (DnsDiscovery, Vec<Backend>)>,
+    /// Route matcher for path resolution
+    route_matcher: RouteMatcher,
+    /// Circuit breakers for upstream services (keyed by host:port)
+    circuit_breakers: HashMap<String, Arc<CircuitBreaker>>,
+    /// Every distinct backend and the external paths of the routes using it
+    /// (keyed by host:port)
+    backend_routes: BTreeMap<String, (Backend, Vec<String>)>,
+    /// Retry budgets for upstream services of routes with a
+    /// `retry_budget_ratio` (keyed by host:port)
+    retry_budgets: HashMap<String, Arc<RetryBudget>>,
+    /// Health check state of backends probed by a route's `health_check`
+    /// (keyed by host:port)
+    backend_health: HashMap<String, Arc<BackendHealth>>,
+    /// Load balancers for each route (keyed by external_path)
+    load_balancers: HashMap<String, Arc<dyn LoadBalancer>>,
+    /// Request transformers with pre-compiled patterns (keyed by external_path)
+    request_transformers: HashMap<String, Arc<RequestTransformer>>,
+    /// Response transformers with pre-compiled patterns (keyed by external_path)
+    response_transformers: HashMap<String, Arc<ResponseTransformer>>,
+    /// Compiled response schemas (keyed by external_path)
+    response_schemas: HashMap<String, Arc<ResponseSchema>>,
+    /// Webhook signature verifiers with resolved secrets (keyed by external_path)
+    webhook_verifiers: HashMap<String, Arc<WebhookVerifier>>,
+    /// Health check URL of every distinct backend, used to warm the connection pool
+    warmup_urls: Vec<String>,
+}
+
+impl RouteTable {
+    /// Compiles `routes` into a route table.
+    ///
+    /// Circuit breakers from `previous` are carried over for backends whose
+    /// breaker configuration did not change, so their state survives a reload.
+    /// The trailing slash mode of `previous` is kept.
+    /// Retry budgets and health check state are carried over for backends
+    /// that still have them.
+    /// Entries of `discovered_backends` replace the backends of routes whose
+    /// DNS discovery settings still match; the others are dropped.
+    fn build(
+        routes: &[Router],
+        previous: Option<&RouteTable>,
+        mut discovered_backends: HashMap<String, (DnsDiscovery, Vec<Backend>)>,
+    ) -> Result<Self, String> {
+        discovered_backends.retain(|external_path, (discovery, _)| {
+            routes.iter().any(|route| {
+                &route.external_path == external_path
+                    && route.dns_discovery.as_ref() == Some(discovery)
+            })
+        });
+        let configured_routes = routes;
+        let routes: Vec<Router> = routes
+            .iter()
+            .map(|route| match discovered_backends.get(&route.external_path) {
+                Some((_, backends)) => Router {
+                    backends: Some(backends.clone()),
+                    ..route.clone()
+                },
+                None => route.clone(),
+            })
+            .collect();
+        let routes = routes.as_slice();
+
+        let mut route_matcher = RouteMatcher::new(routes.to_vec()).map_err(|e| e.to_string())?;
+        if let Some(previous) = previous {
+            route_matcher.inherit_stats(&previous.route_matcher);
+            route_matcher = route_matcher.with_trailing_slash(previous.route_matcher.trailing_slash());
+        }
+
+        // Circuit breaker config per unique backend, merged across routes
+        let mut breaker_configs: HashMap<String, CircuitBreakerConfig> = HashMap::new();
+        let mut load_balancers = HashMap::new();
+        let mut request_transformers = HashMap::new();
+        let mut response_transformers = HashMap::new();
+        let mut response_schemas = HashMap::new();
+        let mut webhook_verifiers = HashMap::new();
+        let mut retry_budgets = HashMap::new();
+        let mut backend_health = HashMap::new();
+        let mut backend_routes: BTreeMap<String, (Backend, Vec<String>)> = BTreeMap::new();
+        let mut warmup_urls = Vec::new();
+        let mut seen_backends = HashSet::new();
+
+        for route in routes {
+            // Get all backends for this route
+            let backends = route.get_backends();
+
+            // Collect circuit breaker config for each backend; shared backends
+            // keep the most conservative settings of all their routes
+            let route_config = route
+                .circuit_breaker
+                .as_ref()
+                .map(CircuitBreakerConfig::from)
+                .unwrap_or_default();
+            for backend in &backends {
+                let service_key = format!("{}:{}", backend.host, backend.port);
+                if seen_backends.insert(service_key.clone()) {
+                    let probe_path = backend.health_check_path.as_deref().unwrap_or("/");
+                    warmup_urls.push(format_route(&backend.host, &backend.port, probe_path));
+                }
+                backend_routes
+                    .entry(service_key.clone())
+                    .or_insert_with(|| (backend.clone(), Vec::new()))
+                    .1
+                    .push(route.external_path.clone());
+                let has_retry_budget = route
+                    .retry
+                    .as_ref()
+                    .is_some_and(|retry| retry.retry_budget_ratio.is_some());
+                if has_retry_budget {
+                    retry_budgets.entry(service_key.clone()).or_insert_with(|| {
+                        previous
+                            .and_then(|table| table.retry_budgets.get(&service_key))
+                            .cloned()
+                            .unwrap_or_else(|| Arc::new(RetryBudget::new()))
+                    });
+                }
+                if route.health_check.is_some() && backend.health_check_path.is_some() {
+                    backend_health.entry(service_key.clone()).or_insert_with(|| {
+                        previous
+                            .and_then(|table| table.backend_health.get(&service_key))
+                            .cloned()
+                            .unwrap_or_else(|| Arc::new(BackendHealth::new()))
+                    });
+                }
+                breaker_configs
+                    .entry(service_key)
+                    .and_modify(|config| *config = config.most_conservative(&route_config))
+                    .or_insert_with(|| route_config.clone());
+            }
+
+            // Create load balancer for this route if multiple backends
+            if backends.len() > 1 {
+                let balancer = LoadBalancerFactory::create(&route.load_balancing_strategy);
+                load_balancers.insert(route.external_path.clone(), balancer);
+                info!(
+                    "Created {:?} load balancer for route {} with {} backends",
+                    route.load_balancing_strategy,
+                    route.external_path,
+                    backends.len()
+                );
+            }
+
+            // Compile transformation patterns once per route
+            if let Some(transformation) = &route.request_transformation {
+                let transformer = RequestTransformer::new(transformation.clone());
+                request_transformers.insert(route.external_path.clone(), Arc::new(transformer));
+            }
+            if let Some(transformation) = &route.response_transformation {
+                let transformer = ResponseTransformer::new(transformation.clone());
+                response_transformers.insert(route.external_path.clone(), Arc::new(transformer));
+            }
+
+            // Compile the response schema once; unreadable schemas are reported
+            // by config validation, so the route is simply left unchecked here
+            if let Some(path) = &route.response_schema {
+                match ResponseSchema::load(path) {
+                    Ok(schema) => {
+                        response_schemas.insert(route.external_path.clone(), Arc::new(schema));
+                    }
+                    Err(e) => error!("Response schema disabled for {}: {}", route.external_path, e),
+                }
+            }
+
+            // Unlike a schema, a verifier cannot be skipped without letting
+            // unsigned requests through, so an unresolvable secret fails the build
+            if let Some(config) = &route.webhook_verify {
+                let verifier = WebhookVerifier::new(config).map_err(|e| {
+                    format!("Webhook verification of {}: {}", route.external_path, e)
+                })?;
+                webhook_verifiers.insert(route.external_path.clone(), Arc::new(verifier));
+            }
+        }
+
+        let circuit_breakers = breaker_configs
+            .into_iter()
+            .map(|(service_key, config)| {
+                let existing = previous
+                    .and_then(|table| table.circuit_breakers.get(&service_key))
+                    .filter(|breaker| breaker.config() == &config);
+                let breaker = match existing {
+                    Some(breaker) => breaker.clone(),
+                    None => CircuitBreaker::new(service_key.clone(), config),
+                };
+                (service_key, breaker)
+            })
+            .collect();
+
+        Ok(Self {
+            routes: configured_routes.to_vec(),
+            discovered_backends,
+            route_matcher,
+            circuit_breakers,
+            backend_routes,
+            retry_budgets,
+            backend_health,
+            load_balancers,
+            request_transformers,
+            response_transformers,
+            response_schemas,
+            webhook_verifiers,
+            warmup_urls,
+        })
+    }
+
+    /// Returns the backends that have not failed their health checks.
+    ///
+    /// If every backend is down, all of them are returned, so requests are
+    /// still attempted rather than rejected.
+    fn available_backends(&self, backends: &[Backend]) -> Vec<Backend> {
+        let healthy: Vec<Backend> = backends
+            .iter()
+            .filter(|backend| {
+                let service_key = format!("{}:{}", backend.host, backend.port);
+                self.backend_health
+                    .get(&service_key)
+                    .is_none_or(|health| health.is_healthy())
+            })
+            .cloned()
+            .collect();
+        if healthy.is_empty() {
+            backends.to_vec()
+        } else {
+            healthy
+        }
+    }
 }
 
 impl RouteHandler {
@@ -121,6 +593,9 @@ impl RouteHandler {
     /// - **Pool Size**: Up to 32 idle connections per host
     /// - **Connection Reuse**: Automatic connection pooling
     ///
+    /// Use [`RouteHandler::builder`] to change these, add a DNS resolver or a
+    /// proxy, or supply a client of your own.
+    ///
     /// # Route Compilation
     ///
     /// All routes are pre-compiled into an optimized matcher that:
@@ -136,11 +611,14 @@ impl RouteHandler {
     /// - **Reset Timeout**: 30 seconds before transitioning from open to half-open
     /// - **Service Isolation**: Each upstream service has independent protection
     ///
+    /// Routes can override these defaults with `circuit_breaker`. A backend shared
+    /// by several routes uses the most conservative of their settings.
+    ///
     /// # Examples
     ///
     /// ```rust
     /// use kairos_rs::services::http::RouteHandler;
-    /// use kairos_rs::models::router::{Router, Backend, Protocol};
+    /// use kairos_rs::models::router::{Router, Backend};
     ///
     /// let routes = vec![
     ///     Router {
@@ -149,19 +627,14 @@ impl RouteHandler {
     ///         external_path: "/auth/login".to_string(),
     ///         internal_path: "/authenticate".to_string(),
     ///         methods: vec!["POST".to_string()],
-    ///         auth_required: false,
     ///         backends: Some(vec![Backend {
     ///             host: "http://auth-service".to_string(),
     ///             port: 8080,
     ///             weight: 1,
     ///             health_check_path: None,
+    ///             timeout_secs: None,
     ///         }]),
-    ///         load_balancing_strategy: Default::default(),
-    ///         retry: None,
-    ///         protocol: Protocol::Http,
-    ///         request_transformation: None,
-    ///         response_transformation: None,
-    ///         ai_policy: None,
+    ///         ..Default::default()
     ///     },
     ///     Router {
     ///         host: Some("http://user-service".to_string()),
@@ -169,19 +642,14 @@ impl RouteHandler {
     ///         external_path: "/users/{id}".to_string(),
     ///         internal_path: "/api/v1/user/{id}".to_string(),
     ///         methods: vec!["GET".to_string(), "PUT".to_string(), "DELETE".to_string()],
-    ///         auth_required: false,
     ///         backends: Some(vec![Backend {
     ///             host: "http://user-service".to_string(),
     ///             port: 8080,
     ///             weight: 1,
     ///             health_check_path: None,
+    ///             timeout_secs: None,
     ///         }]),
-    ///         load_balancing_strategy: Default::default(),
-    ///         retry: None,
-    ///         protocol: Protocol::Http,
-    ///         request_transformation: None,
-    ///         response_transformation: None,
-    ///         ai_policy: None,
+    ///         ..Default::default()
     ///     }
     /// ];
     ///
@@ -199,55 +667,50 @@ impl RouteHandler {
     /// The returned handler is safe to clone and share across multiple worker threads.
     /// All internal state is either immutable or thread-safe.
     pub fn new(routes: Vec<Router>, timeout_seconds: u64) -> Self {
-        let client = Client::builder()
-            .pool_idle_timeout(Duration::from_secs(30))
-            .pool_max_idle_per_host(32)
+        RouteHandlerBuilder::new(routes, timeout_seconds)
             .build()
-            .expect("Failed to create HTTP client");
-
-        let route_matcher =
-            Arc::new(RouteMatcher::new(routes.clone()).expect("Failed to create route matcher"));
-
-        // Create circuit breakers for each unique backend
-        let mut circuit_breakers = HashMap::new();
-        let mut load_balancers = HashMap::new();
-
-        for route in &routes {
-            // Get all backends for this route
-            let backends = route.get_backends();
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
 
-            // Create circuit breakers for each backend
-            for backend in &backends {
-                let service_key = format!("{}:{}", backend.host, backend.port);
-                circuit_breakers
-                    .entry(service_key.clone())
-                    .or_insert_with(|| {
-                        let config = CircuitBreakerConfig::default();
-                        CircuitBreaker::new(service_key, config)
-                    });
-            }
+    /// Returns a builder for a handler with a customized HTTP client.
+    ///
+    /// See [`RouteHandlerBuilder`].
+    pub fn builder(routes: Vec<Router>, timeout_seconds: u64) -> RouteHandlerBuilder {
+        RouteHandlerBuilder::new(routes, timeout_seconds)
+    }
 
-            // Create load balancer for this route if multiple backends
-            if backends.len() > 1 {
-                let balancer = LoadBalancerFactory::create(&route.load_balancing_strategy);
-                load_balancers.insert(route.external_path.clone(), balancer);
-                info!(
-                    "Created {:?} load balancer for route {} with {} backends",
-                    route.load_balancing_strategy,
-                    route.external_path,
-                    backends.len()
-                );
-            }
-        }
+    /// Creates a handler around an already built client.
+    fn from_client(
+        client: Client,
+        routes: Vec<Router>,
+        timeout_seconds: u64,
+        trailing_slash: TrailingSlash,
+    ) -> Result<Self, String> {
+        let table = RouteTable::build(&routes, None, HashMap::new())
+            .map_err(|e| format!("Failed to create route matcher: {}", e))?;
+        let table = RouteTable {
+            route_matcher: table.route_matcher.with_trailing_slash(trailing_slash),
+            ..table
+        };
 
-        Self {
+        Ok(Self {
             client,
-            route_matcher,
+            routes: Arc::new(ArcSwap::from_pointee(table)),
             timeout_seconds,
-            circuit_breakers: Arc::new(circuit_breakers),
-            load_balancers: Arc::new(load_balancers),
             ai_service: None,
-        }
+            streaming_threshold_bytes: StreamingSettings::default().threshold_bytes,
+            mirror_body_limit_bytes: StreamingSettings::default().mirror_body_limit_bytes,
+            timeout_message: None,
+            empty_pool_status: EmptyPoolStatus::default(),
+            fault_injection_enabled: false,
+            forwarded_headers: None,
+            default_upstream_headers: Vec::new(),
+            breaker_bypass_networks: Vec::new(),
+            override_path_networks: Vec::new(),
+            request_start_header: None,
+            ready: Arc::new(AtomicBool::new(true)),
+            warmup_deadline: None,
+        })
     }
 
     /// Attaches an AI service to the route handler.
@@ -256,6 +719,390 @@ impl RouteHandler {
         self
     }
 
+    /// Sets the response size above which upstream bodies are streamed.
+    ///
+    /// Responses with an unknown length (chunked) are always streamed; responses
+    /// with a `Content-Length` at or below `threshold_bytes` are buffered.
+    pub fn with_streaming_threshold(mut self, threshold_bytes: u64) -> Self {
+        self.streaming_threshold_bytes = threshold_bytes;
+        self
+    }
+
+    /// Sets the largest request body that is copied to a route's mirror backend.
+    ///
+    /// The buffered request body is shared by the primary and mirror requests;
+    /// bodies above `limit_bytes` are sent to the primary backend only.
+    pub fn with_mirror_body_limit(mut self, limit_bytes: u64) -> Self {
+        self.mirror_body_limit_bytes = limit_bytes;
+        self
+    }
+
+    /// Sets the message returned with `504 Gateway Timeout` when a backend
+    /// does not answer in time.
+    ///
+    /// The default message only states the timeout in seconds; neither
+    /// reveals which backend timed out.
+    pub fn with_timeout_message(mut self, message: impl Into<String>) -> Self {
+        self.timeout_message = Some(message.into());
+        self
+    }
+
+    /// Sets the status returned when a route has no backends left.
+    ///
+    /// Defaults to `503 Service Unavailable` with a `Retry-After` header;
+    /// `BadGateway` reports the empty pool like other upstream failures.
+    pub fn with_empty_pool_status(mut self, status: EmptyPoolStatus) -> Self {
+        self.empty_pool_status = status;
+        self
+    }
+
+    /// Applies routes' `fault_injection` settings for chaos testing.
+    ///
+    /// Disabled by default, so faults configured on routes are ignored
+    /// unless the gateway explicitly opts in.
+    pub fn with_fault_injection(mut self, enabled: bool) -> Self {
+        self.fault_injection_enabled = enabled;
+        self
+    }
+
+    /// Adds `X-Forwarded-Proto`, `X-Forwarded-Host` and `X-Forwarded-Port`
+    /// to upstream requests.
+    ///
+    /// Values the client sent for these headers are replaced, so backends
+    /// always see what the gateway observed or was configured with.
+    pub fn with_forwarded_headers(mut self, settings: ForwardedHeadersSettings) -> Self {
+        self.forwarded_headers = Some(settings);
+        self
+    }
+
+    /// Sets `headers` on every upstream request, replacing client-sent
+    /// values of the same name.
+    ///
+    /// They are set before route request transformations, which can still
+    /// override or remove them. Pairs that are not valid HTTP headers are
+    /// skipped; [`Settings::resolved_default_upstream_headers`] rejects them.
+    ///
+    /// [`Settings::resolved_default_upstream_headers`]: crate::models::settings::Settings::resolved_default_upstream_headers
+    pub fn with_default_upstream_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.default_upstream_headers = headers
+            .into_iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    header::HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    header::HeaderValue::from_str(&value).ok()?,
+                ))
+            })
+            .collect();
+        self
+    }
+
+    /// Lets requests carrying [`BYPASS_BREAKER_HEADER`] from `trusted`
+    /// networks skip circuit breakers.
+    ///
+    /// Such requests are sent even while a backend's breaker is open and
+    /// their outcome is not counted by the breaker, so monitors can probe
+    /// the backend's real state. Only the address of the direct connection
+    /// is checked, as forwarding headers can be forged.
+    pub fn with_circuit_breaker_bypass(mut self, trusted: Vec<IpNet>) -> Self {
+        self.breaker_bypass_networks = trusted;
+        self
+    }
+
+    /// Returns whether `req` asks to bypass circuit breakers and comes from
+    /// a trusted peer.
+    fn bypasses_circuit_breaker(&self, req: &HttpRequest) -> bool {
+        !self.breaker_bypass_networks.is_empty()
+            && req.headers().contains_key(BYPASS_BREAKER_HEADER)
+            && req.peer_addr().is_some_and(|peer| {
+                self.breaker_bypass_networks
+                    .iter()
+                    .any(|network| network.contains(&peer.ip()))
+            })
+    }
+
+    /// Lets requests carrying [`OVERRIDE_PATH_HEADER`] from `trusted`
+    /// networks choose the upstream path.
+    ///
+    /// The header's value replaces the route's internal path, after any
+    /// path transformation; the client's query string is still appended.
+    /// Route matching, authentication and the other route settings are
+    /// unchanged. Only the address of the direct connection is checked.
+    pub fn with_debug_overrides(mut self, trusted: Vec<IpNet>) -> Self {
+        self.override_path_networks = trusted;
+        self
+    }
+
+    /// Adds the time the gateway received each request to forwarded
+    /// requests, for routes without their own `request_start_header`.
+    pub fn with_request_start_header(mut self, header: RequestStartHeader) -> Self {
+        self.request_start_header = Some(header);
+        self
+    }
+
+    /// Returns the name of the request start header to add to requests
+    /// forwarded by `route`, if it is enabled.
+    fn request_start_header_name<'a>(&'a self, route: &'a Router) -> Option<&'a str> {
+        let header = route
+            .request_start_header
+            .as_ref()
+            .or(self.request_start_header.as_ref())?;
+        if !header.enabled {
+            return None;
+        }
+        let name = header
+            .name
+            .as_deref()
+            .or_else(|| self.request_start_header.as_ref()?.name.as_deref());
+        Some(name.unwrap_or(RequestStartHeader::DEFAULT_NAME))
+    }
+
+    /// Returns the upstream path `req` asks for, if it comes from a trusted
+    /// peer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the requested path does not start with `/`.
+    fn override_path(&self, req: &HttpRequest) -> Result<Option<String>, GatewayError> {
+        if self.override_path_networks.is_empty() {
+            return Ok(None);
+        }
+        let Some(value) = req.headers().get(OVERRIDE_PATH_HEADER) else {
+            return Ok(None);
+        };
+        let trusted = req.peer_addr().is_some_and(|peer| {
+            self.override_path_networks
+                .iter()
+                .any(|network| network.contains(&peer.ip()))
+        });
+        if !trusted {
+            return Ok(None);
+        }
+        match value.to_str() {
+            Ok(path) if path.starts_with('/') => Ok(Some(path.to_string())),
+            _ => Err(GatewayError::BadRequest {
+                reason: format!("{} must be a path starting with '/'", OVERRIDE_PATH_HEADER),
+            }),
+        }
+    }
+
+    /// Holds proxy traffic back for up to `grace_period` after startup.
+    ///
+    /// Until [`finish_warmup`](Self::finish_warmup) is called, or
+    /// [`warm_up`](Self::warm_up) completes, proxy requests are answered with
+    /// `503 Service Unavailable` and a `Retry-After` header.
+    pub fn with_warmup(mut self, grace_period: Duration) -> Self {
+        self.ready = Arc::new(AtomicBool::new(false));
+        self.warmup_deadline = Some(Instant::now() + grace_period);
+        self
+    }
+
+    /// Returns whether proxy routes are forwarding traffic.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// Ends the warmup and starts forwarding proxy traffic.
+    pub fn finish_warmup(&self) {
+        if !self.ready.swap(true, Ordering::AcqRel) {
+            info!("Warmup finished, proxy routes are now serving traffic");
+        }
+    }
+
+    /// Runs the startup warmup and flips readiness when it ends.
+    ///
+    /// With `warm_connections`, every backend's health check path (or `/`) is
+    /// requested once so pooled connections are open before real traffic
+    /// arrives; readiness flips once all backends answered or the grace
+    /// period ran out. Otherwise the full grace period is waited out.
+    pub async fn warm_up(&self, warm_connections: bool) {
+        let Some(deadline) = self.warmup_deadline else {
+            self.finish_warmup();
+            return;
+        };
+        let deadline = tokio::time::Instant::from_std(deadline);
+
+        if warm_connections {
+            let table = self.routes.load_full();
+            let probes = table.warmup_urls.iter().map(|url| {
+                let request = self.client.get(url).send();
+                async move {
+                    if let Err(e) = request.await {
+                        debug!("Warmup request to {} failed: {}", url, e);
+                    }
+                }
+            });
+            if tokio::time::timeout_at(deadline, futures::future::join_all(probes))
+                .await
+                .is_err()
+            {
+                warn!("Warmup grace period ended before all backends answered");
+            }
+        } else {
+            tokio::time::sleep_until(deadline).await;
+        }
+
+        self.finish_warmup();
+    }
+
+    /// Validates a buffered 2xx response body against the route's schema.
+    ///
+    /// Violations are logged and counted; in enforce mode they also turn the
+    /// response into a `502 Bad Gateway`. Violation details are kept out of
+    /// the client-facing error.
+    fn check_response_schema(
+        &self,
+        req: &HttpRequest,
+        route: &Router,
+        schema: &ResponseSchema,
+        body: &[u8],
+        target_url: &str,
+        status_code: u16,
+    ) -> Result<(), GatewayError> {
+        let Err(violations) = schema.validate(body) else {
+            return Ok(());
+        };
+
+        warn!(
+            "Response from {} violates the schema of route {} ({:?} mode): {}",
+            target_url,
+            route.external_path,
+            route.response_schema_mode,
+            violations.join("; ")
+        );
+        if let Some(metrics) = req.app_data::<web::Data<MetricsCollector>>() {
+            metrics.record_response_schema_violation();
+        }
+
+        match route.response_schema_mode {
+            ResponseSchemaMode::Shadow => Ok(()),
+            ResponseSchemaMode::Enforce => Err(GatewayError::Upstream {
+                message: "Response failed schema validation".to_string(),
+                url: target_url.to_string(),
+                status: Some(status_code),
+            }),
+        }
+    }
+
+    /// Sends a fire-and-forget copy of the request to a mirror backend.
+    ///
+    /// Mirror responses and errors are logged and counted in `metrics`, and
+    /// otherwise ignored, so the mirror can never affect the client response.
+    fn spawn_mirror(
+        &self,
+        metrics: Option<web::Data<MetricsCollector>>,
+        mirror: &Backend,
+        internal_path: &str,
+        method: ReqwestMethod,
+        headers: ReqwestHeaderMap,
+        body: &web::Bytes,
+    ) {
+        let mirror_url = format_route(&mirror.host, &mirror.port, internal_path);
+
+        if body.len() as u64 > self.mirror_body_limit_bytes {
+            info!(
+                "Skipping mirror to {}: body of {} bytes exceeds mirror limit of {} bytes",
+                mirror_url,
+                body.len(),
+                self.mirror_body_limit_bytes
+            );
+            return;
+        }
+
+        let timeout_seconds = mirror.timeout_secs.unwrap_or(self.timeout_seconds);
+        let mirror_req = self
+            .client
+            .request(method, &mirror_url)
+            .body(body.clone())
+            .headers(headers)
+            .timeout(Duration::from_secs(timeout_seconds));
+
+        tokio::spawn(async move {
+            let success = match mirror_req.send().await {
+                Ok(resp) => {
+                    debug!("Mirror {} responded with {}", mirror_url, resp.status());
+                    !resp.status().is_server_error()
+                }
+                Err(e) => {
+                    debug!("Mirror request to {} failed: {}", mirror_url, e);
+                    false
+                }
+            };
+            if let Some(metrics) = metrics {
+                metrics.record_mirror_result(success);
+            }
+        });
+    }
+
+    /// Serves the target of an upstream `X-Accel-Redirect` instead of the
+    /// response that named it.
+    ///
+    /// The target path is fetched from the same backend with `GET`, or `HEAD`
+    /// for `HEAD` requests, and the original request headers. Responses
+    /// without the header are returned unchanged, and only one redirect is
+    /// followed.
+    async fn follow_accel_redirect(
+        &self,
+        response: reqwest::Response,
+        backend: &Backend,
+        method: &ReqwestMethod,
+        headers: &ReqwestHeaderMap,
+        timeout_seconds: u64,
+    ) -> Result<reqwest::Response, GatewayError> {
+        let Some(target) = response.headers().get(ACCEL_REDIRECT_HEADER) else {
+            return Ok(response);
+        };
+        let path = match target.to_str() {
+            Ok(path) if path.starts_with('/') => path.to_string(),
+            _ => {
+                return Err(GatewayError::Upstream {
+                    message: "X-Accel-Redirect must name a path starting with '/'".to_string(),
+                    url: response.url().to_string(),
+                    status: Some(response.status().as_u16()),
+                })
+            }
+        };
+        let target_url = format_route(&backend.host, &backend.port, &path);
+        debug!("Following X-Accel-Redirect to {}", target_url);
+
+        // The redirected request has no body, so the original's framing is dropped
+        let mut headers = headers.clone();
+        for name in ["content-length", "content-type", "transfer-encoding"] {
+            headers.remove(name);
+        }
+        let method = if *method == ReqwestMethod::HEAD {
+            ReqwestMethod::HEAD
+        } else {
+            ReqwestMethod::GET
+        };
+
+        let request = self.client.request(method, &target_url).headers(headers);
+        match timeout(Duration::from_secs(timeout_seconds), request.send()).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(e)) => Err(GatewayError::Upstream {
+                message: e.to_string(),
+                url: target_url,
+                status: None,
+            }),
+            Err(_) => Err(GatewayError::Timeout {
+                timeout: timeout_seconds,
+                message: self.timeout_message.clone(),
+            }),
+        }
+    }
+
+    /// Returns whether an upstream body should be streamed instead of buffered.
+    fn should_stream(&self, method: &ActixMethod, content_length: Option<u64>) -> bool {
+        // HEAD responses advertise a length but carry no body
+        if *method == ActixMethod::HEAD {
+            return false;
+        }
+
+        match content_length {
+            Some(length) => length > self.streaming_threshold_bytes,
+            None => true,
+        }
+    }
+
     /// Processes an incoming HTTP request and forwards it to the appropriate upstream service.
     ///
     /// This is the core request processing method that handles route matching,
@@ -318,6 +1165,7 @@ impl RouteHandler {
     /// # Timeout Management
     ///
     /// - **Request Timeout**: Configurable per-instance timeout for upstream requests
+    /// - **Backend Timeout**: `Backend::timeout_secs` overrides the instance timeout for that backend
     /// - **Connection Pooling**: Automatic connection reuse reduces latency
     /// - **Circuit Breaking**: Failed requests don't block subsequent requests
     ///
@@ -365,7 +1213,26 @@ impl RouteHandler {
         req: HttpRequest,
         body: web::Bytes,
     ) -> Result<HttpResponse, ActixError> {
+        self.handle(req, RequestBody::Buffered(body)).await
+    }
+
+    /// Handles a request whose body has not been read yet.
+    ///
+    /// Routes for which [`Router::streams_request_body`] holds forward the
+    /// body to the backend as it arrives. Other routes buffer it first, within
+    /// the limit of the app's `PayloadConfig`, and behave like
+    /// [`handle_request`](Self::handle_request).
+    pub async fn handle_payload(
+        &self,
+        req: HttpRequest,
+        payload: web::Payload,
+    ) -> Result<HttpResponse, ActixError> {
+        self.handle(req, RequestBody::Payload(payload)).await
+    }
+
+    async fn handle(&self, req: HttpRequest, body: RequestBody) -> Result<HttpResponse, ActixError> {
         let start_time = Instant::now();
+        let received_at = SystemTime::now();
 
         // Get metrics collector from app data if available
         let metrics = req.app_data::<web::Data<MetricsCollector>>().cloned();
@@ -375,89 +1242,398 @@ impl RouteHandler {
             metrics.increment_connections();
         }
 
-        let result = self.handle_request_internal(req, body).await;
+        // Traced requests become exemplars of the response time histogram
+        let trace_id = req
+            .headers()
+            .get("traceparent")
+            .and_then(|value| value.to_str().ok())
+            .and_then(trace_id_from_traceparent)
+            .map(str::to_string);
+
+        let mut request_bytes = match &body {
+            RequestBody::Buffered(body) => Some(body.len() as u64),
+            RequestBody::Payload(_) => None,
+        };
+        let mut matched = None;
+        let mut result = self
+            .handle_request_internal(req, body, received_at, &mut matched, &mut request_bytes)
+            .await;
+        let matched_route = matched.as_ref().map(|matched| matched.external_path.as_str());
 
         // Record metrics
         if let Some(ref metrics) = metrics {
             let duration = start_time.elapsed();
+            if let Some(trace_id) = &trace_id {
+                metrics.record_exemplar(duration, trace_id);
+            }
             match &result {
                 Ok(resp) => {
                     let success = resp.status().is_success();
                     let status_code = resp.status().as_u16();
-                    metrics.record_request(success, duration, status_code, None, None);
+                    let response_bytes = match resp.body().size() {
+                        BodySize::None => Some(0),
+                        BodySize::Sized(length) => Some(length),
+                        BodySize::Stream => None,
+                    };
+                    metrics.record_request(
+                        success,
+                        duration,
+                        status_code,
+                        request_bytes,
+                        response_bytes,
+                        matched_route,
+                    );
+                }
+                Err(e) => {
+                    match e.as_error::<GatewayError>() {
+                        Some(GatewayError::Timeout { .. }) => metrics.record_timeout_error(),
+                        Some(GatewayError::Connection { .. }) => metrics.record_connection_error(),
+                        Some(GatewayError::CircuitOpen { .. }) => metrics.record_circuit_open_error(),
+                        _ => {}
+                    }
+                    // Gateway errors carry the status code they render as
+                    let status_code = e.as_response_error().status_code().as_u16();
+                    metrics.record_request(
+                        false,
+                        duration,
+                        status_code,
+                        request_bytes,
+                        None,
+                        matched_route,
+                    );
                 }
-                Err(_) => {
-                    // For errors, we don't have a specific status code, so use 500
-                    metrics.record_request(false, duration, 500, None, None);
+            }
+            if let Some(matched) = &matched {
+                if let Some((sla, tags)) = &matched.sla {
+                    metrics.record_sla(&matched.external_path, tags, *sla, duration);
                 }
             }
             metrics.decrement_connections();
         }
+        // Dropping the request before this point releases it as well
+        if let Some(matched) = &mut matched {
+            matched.in_flight.take();
+        }
+
+        // Streamed bodies are counted as they are sent, sized ones up front
+        let large_response = matched.and_then(|matched| matched.large_response);
+        if let Ok(resp) = result {
+            result = Ok(match resp.body().size() {
+                BodySize::Stream if metrics.is_some() || large_response.is_some() => resp
+                    .map_body(|_, body| {
+                        BoxBody::new(CountingBody {
+                            body,
+                            bytes: 0,
+                            metrics,
+                            large_response,
+                        })
+                    }),
+                BodySize::Sized(length) => {
+                    if let Some(large) = &large_response {
+                        large.check(length);
+                    }
+                    resp
+                }
+                _ => resp,
+            });
+        }
 
         result
     }
 
+    /// Forwards the request received at `received_at`, setting `matched` to
+    /// the route it matched and `request_bytes` to the size of the body once
+    /// it is known, for metrics and response size warnings.
     async fn handle_request_internal(
         &self,
         req: HttpRequest,
-        body: web::Bytes,
+        body: RequestBody,
+        received_at: SystemTime,
+        matched: &mut Option<MatchedRoute>,
+        request_bytes: &mut Option<u64>,
     ) -> Result<HttpResponse, ActixError> {
-        let path = req.path().to_string();
+        // Several Host headers are ambiguous between hops and can be used to
+        // smuggle requests past host-based checks
+        if req.headers().get_all(header::HOST).nth(1).is_some() {
+            return Err(GatewayError::BadRequest {
+                reason: "Multiple Host headers".to_string(),
+            }
+            .into());
+        }
+
+        // Absolute-form targets (`GET http://host/path`) match on their path only
+        let path = req.uri().path().to_string();
         let method = req.method().clone();
 
         // Convert Actix method to Reqwest method
         let reqwest_method = self.parse_method(&method);
 
-        // Convert headers
-        let reqwest_headers = self.build_headers_optimized(req.headers());
+        // Pin the current route table for the whole request
+        let table = self.routes.load_full();
 
         // Find matching route using the new pattern matching function
-        let (route, transformed_internal_path) =
-            self.route_matcher.find_match(&path).map_err(|e| match e {
-                crate::utils::route_matcher::RouteMatchError::NoMatch { path } => {
-                    GatewayError::RouteNotFound { path }
-                }
-                _ => GatewayError::Config {
+        let (route, transformed_internal_path) = match table.route_matcher.find_match(&path) {
+            Ok(found) => found,
+            Err(RouteMatchError::TrailingSlashRedirect { location }) => {
+                let location = match req.uri().query() {
+                    Some(query) => format!("{}?{}", location, query),
+                    None => location,
+                };
+                return Ok(HttpResponse::MovedPermanently()
+                    .insert_header((header::LOCATION, location))
+                    .finish());
+            }
+            Err(RouteMatchError::NoMatch { path }) => {
+                return Err(GatewayError::RouteNotFound { path }.into());
+            }
+            Err(e) => {
+                return Err(GatewayError::Config {
                     message: e.to_string(),
                     route: path.clone(),
-                },
-            })?;
+                }
+                .into());
+            }
+        };
+        let tag_fields = log_tag_fields(&route.tags);
+        *matched = Some(MatchedRoute {
+            external_path: route.external_path.clone(),
+            large_response: route.warn_response_bytes.map(|limit| LargeResponseWarning {
+                path: path.clone(),
+                limit,
+                tag_fields: tag_fields.clone(),
+            }),
+            sla: route
+                .sla_ms
+                .map(|ms| (Duration::from_millis(ms), route.tags.clone())),
+            in_flight: req
+                .app_data::<web::Data<MetricsCollector>>()
+                .map(|metrics| RouteInFlight::start(metrics.clone(), &route.external_path, &route.tags)),
+        });
+
+        // Answer OPTIONS from the route's methods unless the route forwards
+        // it, so backends that do not handle preflights are not asked to
+        if method == ActixMethod::OPTIONS && !route.methods.iter().any(|m| m == "OPTIONS") {
+            return Ok(options_response(&route.methods, &req));
+        }
+
+        // Validate method is allowed
+        if !route.methods.iter().any(|m| m == method.as_str()) {
+            return Err(GatewayError::MethodNotAllowed {
+                method: method.to_string(),
+                path: path.clone(),
+            }
+            .into());
+        }
+
+        // Stream the body to the backend if the route allows it, otherwise read it all
+        let (body, mut upload) = match body {
+            RequestBody::Buffered(body) => (body, None),
+            RequestBody::Payload(payload) if route.streams_request_body() => {
+                *request_bytes = declared_length(&req).map(|length| length as u64);
+                (web::Bytes::new(), Some(Upload::start(payload, route.max_body_bytes)))
+            }
+            RequestBody::Payload(payload) => {
+                let body = web::Bytes::from_request(&req, &mut payload.into_inner()).await?;
+                *request_bytes = Some(body.len() as u64);
+                (body, None)
+            }
+        };
+        let upload_overflow = upload.as_ref().map(|upload| upload.overflow.clone());
+
+        // Check webhook signatures over the raw body, before it is transformed
+        if let Some(verifier) = table.webhook_verifiers.get(&route.external_path) {
+            if let Err(reason) = verifier.verify(req.headers(), &body) {
+                warn!("Rejected webhook on {}: {}{}", route.external_path, reason, tag_fields);
+                return Err(GatewayError::Unauthorized { reason }.into());
+            }
+        }
+
+        // Apply the route's request transformation to headers, body, path and query.
+        // The body limit below still applies to the body as the client sent it.
+        let transformer = table
+            .request_transformers
+            .get(&route.external_path)
+            .filter(|transformer| transformer.applies_to(&path, method.as_str(), req.headers()));
+        let mut upstream_body = body.clone();
+        let mut headers = Cow::Borrowed(req.headers());
+        if !self.default_upstream_headers.is_empty() {
+            let headers = headers.to_mut();
+            for (name, value) in &self.default_upstream_headers {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+        if let Some(transformer) = transformer {
+            let headers = headers.to_mut();
+            transformer.transform_headers(headers);
+            if let Some(rewritten) = transformer.transform_body(headers, &body) {
+                upstream_body = web::Bytes::from(rewritten);
+            }
+        }
+        let mut reqwest_headers = self.build_headers_optimized(&headers);
+        if let Some(settings) = &self.forwarded_headers {
+            add_forwarded_headers(&mut reqwest_headers, &req, settings);
+        }
+        reqwest_headers.remove(BYPASS_BREAKER_HEADER);
+        reqwest_headers.remove(OVERRIDE_PATH_HEADER);
+        if route.accept_encoding == Some(AcceptEncodingMode::Strip) {
+            // An explicit value also keeps the client from negotiating
+            // compression and decompressing on its own
+            reqwest_headers.insert(
+                reqwest::header::ACCEPT_ENCODING,
+                HeaderValue::from_static("identity"),
+            );
+        }
+        if let Some(name) = self.request_start_header_name(&route) {
+            // Names are checked when the configuration is validated
+            if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+                let millis = received_at
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                reqwest_headers.insert(name, HeaderValue::from(millis));
+            }
+        }
+        let bypass_breaker = self.bypasses_circuit_breaker(&req);
+        let mut transformed_internal_path = match transformer {
+            Some(transformer) => transformer.transform_path(&transformed_internal_path),
+            None => transformed_internal_path,
+        };
+        if let Some(path) = self.override_path(&req)? {
+            warn!(
+                "Overriding upstream path of {} {} with {} (debug_overrides)",
+                req.method(),
+                req.path(),
+                path
+            );
+            transformed_internal_path = path;
+        }
+        let query = upstream_query(req.query_string(), transformer.map(Arc::as_ref));
+        if !query.is_empty() {
+            // Internal paths may already carry a query, e.g. `/users?id={id:encoded}`
+            let separator = if transformed_internal_path.contains('?') { '&' } else { '?' };
+            transformed_internal_path =
+                format!("{}{}{}", transformed_internal_path, separator, query);
+        }
+
+        // Hold proxy traffic back until the warmup has finished
+        if !self.is_ready() {
+            let retry_after = self
+                .warmup_deadline
+                .map(|deadline| deadline.saturating_duration_since(Instant::now()).as_secs())
+                .unwrap_or(0)
+                .max(1);
+            return Err(GatewayError::WarmingUp { retry_after }.into());
+        }
+
+        // Enforce the route's body size limit before doing any upstream work.
+        // Streamed bodies are checked by their declared length here and by the
+        // bytes actually received while they are forwarded.
+        let metrics = req.app_data::<web::Data<MetricsCollector>>();
+        let body_size = match &upload {
+            Some(_) => declared_length(&req),
+            None => Some(body.len()),
+        };
+        if let (Some(metrics), Some(size)) = (metrics, body_size) {
+            metrics.record_body_size(&route.external_path, size as u64);
+        }
+        if let (Some(limit), Some(size)) = (route.max_body_bytes, body_size) {
+            if size > limit {
+                if let Some(metrics) = metrics {
+                    metrics.record_body_too_large(&route.external_path);
+                }
+                return Err(GatewayError::PayloadTooLarge {
+                    size,
+                    limit,
+                }
+                .into());
+            }
+        }
 
-        // Validate method is allowed
-        if !route.methods.iter().any(|m| m == method.as_str()) {
-            return Err(GatewayError::MethodNotAllowed {
-                method: method.to_string(),
-                path: path.clone(),
+        // Chaos testing: drop, abort or slow down the request before any upstream work
+        if let Some(fault) = route.fault_injection.as_ref().filter(|_| self.fault_injection_enabled) {
+            use rand::Rng;
+            let mut rng = rand::thread_rng();
+            let drop_connection = rng.gen_bool(fault.drop_rate);
+            let abort = !drop_connection && rng.gen_bool(fault.abort_rate);
+            let delay =
+                !drop_connection && !abort && fault.delay_ms > 0 && rng.gen_bool(fault.delay_rate);
+            drop(rng);
+
+            if drop_connection {
+                debug!("Fault injection dropping connection of request to {}", route.external_path);
+                return Ok(dropped_connection_response());
+            }
+            if abort {
+                debug!("Fault injection aborted request to {}", route.external_path);
+                return Err(GatewayError::FaultInjected {
+                    path: path.clone(),
+                    status: fault.abort_status,
+                }
+                .into());
+            }
+            if delay {
+                debug!(
+                    "Fault injection delaying request to {} by {}ms",
+                    route.external_path, fault.delay_ms
+                );
+                sleep(Duration::from_millis(fault.delay_ms)).await;
             }
-            .into());
         }
 
         // Get all backends for this route
         let backends = route.get_backends();
         if backends.is_empty() {
-            return Err(GatewayError::Config {
-                message: "No backends configured for route".to_string(),
-                route: path.clone(),
+            return Err(GatewayError::NoBackends {
+                route: route.external_path.clone(),
+                status: self.empty_pool_status,
             }
             .into());
         }
 
-        // Get client IP for IP hash load balancing
+        // Backends failing their health checks are left out of rotation
+        let candidates = table.available_backends(&backends);
+
+        // Hash-based strategies route on the client IP, or on the route's
+        // consistent hash key when the request carries it
         let client_ip = req
             .connection_info()
             .realip_remote_addr()
             .map(|s| s.to_string());
+        let hash_key = match &route.load_balancing_strategy {
+            LoadBalancingStrategy::ConsistentHash(key) => {
+                consistent_hash_key(key, &req, &path, &table.route_matcher, &route)
+            }
+            _ => None,
+        }
+        .or(client_ip);
 
-        // Try with retry logic if configured
+        // Try with retry logic if configured. Failed requests are only retried
+        // for idempotent methods unless the route opts in to retrying the rest;
+        // requests rejected by an open circuit never reached a backend and may
+        // always move on to the next one.
         let retry_config = route.retry.clone();
+        let retries_method = retry_config
+            .as_ref()
+            .is_some_and(|c| c.retries_method(method.as_str()));
         let max_attempts = retry_config
             .as_ref()
             .map(|c| c.max_retries + 1)
             .unwrap_or(1);
 
-        // AI-Powered Routing Logic
-        let ai_backend_index = if let Some(policy) = &route.ai_policy {
-            if policy.enabled && self.ai_service.is_some() {
+        // Body routing picks the backend from a JSON field of the client's body
+        let body_backend_index = route
+            .body_routing
+            .as_ref()
+            .and_then(|routing| routing.backend_index(&body));
+        if let Some(idx) = body_backend_index {
+            debug!("Body routing selected backend index {} for {}", idx, route.external_path);
+        }
+
+        // AI-Powered Routing Logic, unless body routing already decided
+        let selected_backend_index = if body_backend_index.is_some() {
+            body_backend_index
+        } else if let Some(policy) = &route.ai_policy {
+            if let (true, Some(ai_service)) = (policy.enabled, self.ai_service.as_ref()) {
                 if let AiRoutingStrategy::ContentAnalysis { model } = &policy.strategy {
                     // Prepare request summary for AI
                     let headers_summary = req
@@ -504,7 +1680,6 @@ impl RouteHandler {
                         method, path, headers_summary, body_preview
                     );
 
-                    let ai_service = self.ai_service.as_ref().unwrap();
                     match ai_service
                         .predict_backend(
                             &req_info,
@@ -533,91 +1708,189 @@ impl RouteHandler {
             None
         };
 
+        // Tee the buffered body to the mirror backend, if configured
+        if let Some(mirror) = &route.mirror_to {
+            self.spawn_mirror(
+                req.app_data::<web::Data<MetricsCollector>>().cloned(),
+                mirror,
+                &transformed_internal_path,
+                reqwest_method.clone(),
+                reqwest_headers.clone(),
+                &upstream_body,
+            );
+        }
+
         for attempt in 0..max_attempts {
-            // Select backend using load balancing strategy or AI decision
-            let backend = if let Some(idx) = ai_backend_index {
-                // Use AI selection if valid
+            // Select backend using load balancing strategy, body routing or AI decision
+            let backend = if let Some(idx) = selected_backend_index {
+                // Use the selection if valid
                 if let Some(b) = backends.get(idx) {
                     b.clone()
                 } else {
-                    warn!("AI selected invalid backend index: {}", idx);
+                    warn!("Selected invalid backend index: {}", idx);
                     // Fallback to standard load balancing
-                    if backends.len() == 1 {
-                        backends[0].clone()
+                    if candidates.len() == 1 {
+                        candidates[0].clone()
                     } else if let Some(load_balancer) =
-                        self.load_balancers.get(&route.external_path)
+                        table.load_balancers.get(&route.external_path)
                     {
                         load_balancer
-                            .select_backend(&backends, client_ip.as_deref())
+                            .select_backend(&candidates, hash_key.as_deref())
                             .ok_or_else(|| GatewayError::Config {
                                 message: "Load balancer failed to select backend".to_string(),
                                 route: path.clone(),
                             })?
                     } else {
                         // random fallback if no LB found
-                        backends[0].clone()
+                        candidates[0].clone()
                     }
                 }
-            } else if backends.len() == 1 {
-                backends[0].clone()
-            } else if let Some(load_balancer) = self.load_balancers.get(&route.external_path) {
+            } else if candidates.len() == 1 {
+                candidates[0].clone()
+            } else if let Some(load_balancer) = table.load_balancers.get(&route.external_path) {
                 load_balancer
-                    .select_backend(&backends, client_ip.as_deref())
+                    .select_backend(&candidates, hash_key.as_deref())
                     .ok_or_else(|| GatewayError::Config {
                         message: "Load balancer failed to select backend".to_string(),
                         route: path.clone(),
                     })?
             } else {
                 // Fallback to first backend if no load balancer
-                backends[0].clone()
+                candidates[0].clone()
             };
 
             let target_url = format_route(&backend.host, &backend.port, &transformed_internal_path);
 
             if attempt > 0 {
-                warn!("Retry attempt {} for {}", attempt, target_url);
+                warn!("Retry attempt {} for {}{}", attempt, target_url, tag_fields);
             } else {
-                debug!("Forwarding request to: {}", target_url);
+                debug!("Forwarding request to: {}{}", target_url, tag_fields);
             }
 
             // Get circuit breaker for this backend
             let service_key = format!("{}:{}", backend.host, backend.port);
             let circuit_breaker =
-                self.circuit_breakers
+                table.circuit_breakers
                     .get(&service_key)
                     .ok_or_else(|| GatewayError::Config {
                         message: format!("No circuit breaker found for backend: {}", service_key),
                         route: path.clone(),
                     })?;
 
-            // Prepare request
+            // Original requests refill the backend's retry budget, retries draw from it
+            let retry_budget = table.retry_budgets.get(&service_key);
+            if attempt == 0 {
+                let ratio = retry_config.as_ref().and_then(|c| c.retry_budget_ratio);
+                if let (Some(budget), Some(ratio)) = (retry_budget, ratio) {
+                    budget.deposit(ratio);
+                }
+            }
+            let retry_budget_allows = || match retry_budget {
+                Some(budget) if !budget.try_withdraw() => {
+                    warn!(
+                        "Retry budget for {} exhausted, not retrying{}",
+                        service_key, tag_fields
+                    );
+                    false
+                }
+                _ => true,
+            };
+
+            // Per-backend timeout takes precedence over the gateway-wide default
+            let timeout_seconds = backend.timeout_secs.unwrap_or(self.timeout_seconds);
+
+            // Prepare request, telling the backend how long this attempt may take
+            let mut attempt_headers = reqwest_headers.clone();
+            if let Some(deadline) = &route.deadline_header {
+                let value = deadline.value(Duration::from_secs(timeout_seconds));
+                if let (Ok(name), Ok(value)) = (
+                    HeaderName::from_bytes(deadline.name.as_bytes()),
+                    HeaderValue::from_str(&value),
+                ) {
+                    attempt_headers.insert(name, value);
+                }
+            }
+            if route.debug_logging {
+                let body = upload.is_none().then_some(&upstream_body[..]);
+                log_forwarded_request(&req, &target_url, &attempt_headers, body, &tag_fields);
+            }
+            let request_body = match upload.take() {
+                Some(upload) => reqwest::Body::wrap_stream(upload.chunks),
+                None => reqwest::Body::from(upstream_body.clone()),
+            };
             let forwarded_req = self
                 .client
                 .request(reqwest_method.clone(), &target_url)
-                .body(body.clone())
-                .headers(reqwest_headers.clone());
-
-            // Execute request with timeout and circuit breaker protection
-            let result = circuit_breaker
-                .call(async {
-                    match timeout(
-                        Duration::from_secs(self.timeout_seconds),
-                        forwarded_req.send(),
-                    )
+                .body(request_body)
+                .headers(attempt_headers);
+
+            // Execute request with timeout and circuit breaker protection. The call
+            // resolves once response headers arrive, so long-lived streams such as
+            // Server-Sent Events count as successes without waiting for the body.
+            // The time until then is the response time reported to the load balancer.
+            let attempt_start = Instant::now();
+            let send = async {
+                match timeout(Duration::from_secs(timeout_seconds), forwarded_req.send()).await {
+                    Ok(Ok(resp)) => Ok(resp),
+                    // The client sent more than the route allows while the body was streamed
+                    Ok(Err(_)) if upload_overflow.as_ref().is_some_and(|o| o.load(Ordering::Relaxed) > 0) => {
+                        Err(GatewayError::PayloadTooLarge {
+                            size: upload_overflow.as_ref().map_or(0, |o| o.load(Ordering::Relaxed)),
+                            limit: route.max_body_bytes.unwrap_or_default(),
+                        })
+                    }
+                    Ok(Err(e)) if e.is_connect() => Err(GatewayError::Connection {
+                        message: e.to_string(),
+                        url: target_url.clone(),
+                    }),
+                    Ok(Err(e)) => Err(GatewayError::Upstream {
+                        message: e.to_string(),
+                        url: target_url.clone(),
+                        status: None,
+                    }),
+                    Err(_) => Err(GatewayError::Timeout {
+                        timeout: timeout_seconds,
+                        message: self.timeout_message.clone(),
+                    }),
+                }
+            };
+            // Trusted probes skip the breaker and leave its counters untouched,
+            // as do oversized uploads, which are the client's fault
+            let result = if bypass_breaker {
+                send.await.map_err(CircuitBreakerError::OperationFailed)
+            } else {
+                circuit_breaker
+                    .call_with(send, |e| !matches!(e, GatewayError::PayloadTooLarge { .. }))
                     .await
-                    {
-                        Ok(Ok(resp)) => Ok(resp),
-                        Ok(Err(e)) => Err(GatewayError::Upstream {
-                            message: e.to_string(),
-                            url: target_url.clone(),
-                            status: None,
-                        }),
-                        Err(_) => Err(GatewayError::Timeout {
-                            timeout: self.timeout_seconds,
-                        }),
+            };
+
+            // Per-backend load and failures; attempts stopped by an open
+            // circuit or cut off for an oversized upload say nothing about it
+            if let Some(metrics) = req.app_data::<web::Data<MetricsCollector>>() {
+                match &result {
+                    Ok(response) => metrics
+                        .record_backend_request(&service_key, response.status().is_server_error()),
+                    Err(CircuitBreakerError::OperationFailed(GatewayError::PayloadTooLarge {
+                        ..
+                    }))
+                    | Err(CircuitBreakerError::CircuitOpen) => {}
+                    Err(CircuitBreakerError::OperationFailed(_)) => {
+                        metrics.record_backend_request(&service_key, true)
                     }
-                })
-                .await;
+                }
+            }
+
+            if route.debug_logging {
+                match &result {
+                    Ok(response) => debug!(
+                        "{} responded with {}{}",
+                        target_url,
+                        response.status(),
+                        tag_fields
+                    ),
+                    Err(e) => debug!("Request to {} failed: {}{}", target_url, e, tag_fields),
+                }
+            }
 
             match result {
                 Ok(response) => {
@@ -626,14 +1899,17 @@ impl RouteHandler {
                     // Check if we should retry based on status code
                     if let Some(retry_cfg) = &retry_config {
                         if retry_cfg.retry_on_status_codes.contains(&status_code)
+                            && retries_method
                             && attempt < max_attempts - 1
+                            && retry_budget_allows()
                         {
                             warn!(
-                                "Retryable status {} from {}, attempt {}/{}",
+                                "Retryable status {} from {}, attempt {}/{}{}",
                                 status_code,
                                 target_url,
                                 attempt + 1,
-                                max_attempts
+                                max_attempts,
+                                tag_fields
                             );
 
                             // Exponential backoff
@@ -644,28 +1920,114 @@ impl RouteHandler {
                     }
 
                     // Success - record and return response
-                    if let Some(lb) = self.load_balancers.get(&route.external_path) {
-                        lb.record_success(&backend);
+                    if let Some(lb) = table.load_balancers.get(&route.external_path) {
+                        lb.record_success(&backend, attempt_start.elapsed());
                     }
 
-                    // Convert upstream response to HttpResponse
-                    let mut builder =
-                        HttpResponse::build(StatusCode::from_u16(status_code).unwrap());
+                    let response = if route.x_accel_redirect {
+                        self.follow_accel_redirect(
+                            response,
+                            &backend,
+                            &reqwest_method,
+                            &reqwest_headers,
+                            timeout_seconds,
+                        )
+                        .await?
+                    } else {
+                        response
+                    };
+                    let status_code = response.status().as_u16();
 
-                    // Forward headers with proper conversion
+                    // Forward headers with proper conversion; framing headers are
+                    // recomputed by Actix from the body we hand it
+                    const SKIP_RESPONSE_HEADERS: &[&str] =
+                        &["connection", "content-length", "transfer-encoding"];
+                    let mut headers = header::HeaderMap::with_capacity(response.headers().len());
                     for (key, value) in response.headers() {
-                        if !key.as_str().starts_with("connection") {
-                            if let Ok(header_value) =
-                                actix_web::http::header::HeaderValue::from_bytes(value.as_bytes())
-                            {
-                                builder.insert_header((key.as_str(), header_value));
+                        let skip = SKIP_RESPONSE_HEADERS.contains(&key.as_str())
+                            || (route.x_accel_redirect && key == ACCEL_REDIRECT_HEADER);
+                        if !skip {
+                            if let (Ok(name), Ok(value)) = (
+                                header::HeaderName::from_bytes(key.as_ref()),
+                                header::HeaderValue::from_bytes(value.as_bytes()),
+                            ) {
+                                headers.append(name, value);
                             }
                         }
                     }
 
+                    // Apply the route's response transformation to status and headers
+                    let mut status = StatusCode::from_u16(status_code).unwrap();
+                    if let Some(transformer) = table.response_transformers.get(&route.external_path)
+                    {
+                        transformer.transform_headers(&mut headers);
+                        status = transformer.transform_status_code(
+                            status,
+                            &path,
+                            method.as_str(),
+                            req.headers(),
+                        );
+                    }
+
+                    // Convert upstream response to HttpResponse
+                    let mut builder = HttpResponse::build(status);
+                    for (name, value) in headers {
+                        builder.append_header((name, value));
+                    }
+
+                    // Server-Sent Events never complete, so they must always be streamed
+                    let is_event_stream = response
+                        .headers()
+                        .get(reqwest::header::CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .is_some_and(|ct| ct.starts_with("text/event-stream"));
+                    let passthrough =
+                        route.accept_encoding == Some(AcceptEncodingMode::Passthrough);
+                    if (is_event_stream || passthrough)
+                        && !response.headers().contains_key("content-encoding")
+                    {
+                        // An explicit encoding makes the Compress middleware pass
+                        // the body through instead of buffering or compressing it
+                        builder.insert_header(("content-encoding", "identity"));
+                    }
+
+                    // Responses checked against a schema have to be buffered
+                    let schema = table
+                        .response_schemas
+                        .get(&route.external_path)
+                        .filter(|_| response.status().is_success() && !is_event_stream);
+
+                    // Stream large or unknown-length bodies instead of buffering them
+                    let content_length = response.content_length();
+                    if is_event_stream
+                        || (schema.is_none() && self.should_stream(&method, content_length))
+                    {
+                        debug!(
+                            "Streaming response from {} (content-length: {:?})",
+                            target_url, content_length
+                        );
+                        let stream = response.bytes_stream();
+                        return Ok(match content_length {
+                            Some(length) => builder.body(SizedStream::new(length, stream)),
+                            None => builder.streaming(stream),
+                        });
+                    }
+
                     // Handle the response body
                     match response.bytes().await {
-                        Ok(bytes) => return Ok(builder.body(bytes)),
+                        Ok(bytes) => {
+                            if let Some(schema) = schema {
+                                self.check_response_schema(
+                                    &req,
+                                    &route,
+                                    schema,
+                                    &bytes,
+                                    &target_url,
+                                    status_code,
+                                )?;
+                            }
+                            return Ok(builder.body(bytes));
+                        }
                         Err(e) => {
                             return Err(GatewayError::Upstream {
                                 message: e.to_string(),
@@ -678,32 +2040,56 @@ impl RouteHandler {
                 }
                 Err(CircuitBreakerError::CircuitOpen) => {
                     // Circuit is open, try next backend or fail
-                    warn!("Circuit breaker open for {}", service_key);
+                    warn!("Circuit breaker open for {}{}", service_key, tag_fields);
+                    if let Some(metrics) = req.app_data::<web::Data<MetricsCollector>>() {
+                        metrics.record_circuit_open_rejection(&service_key);
+                    }
 
                     if backends.len() > 1 && attempt < max_attempts - 1 {
                         // Try another backend
                         continue;
                     }
 
+                    if let Some(fallback) = &route.circuit_open_fallback {
+                        debug!(
+                            "Serving circuit-open fallback for {} ({})",
+                            route.external_path, service_key
+                        );
+                        return Ok(fixed_response(fallback));
+                    }
+
                     return Err(GatewayError::CircuitOpen {
                         service: service_key,
                     }
                     .into());
                 }
+                Err(CircuitBreakerError::OperationFailed(
+                    gateway_error @ GatewayError::PayloadTooLarge { .. },
+                )) => {
+                    if let Some(metrics) = req.app_data::<web::Data<MetricsCollector>>() {
+                        metrics.record_body_too_large(&route.external_path);
+                    }
+                    return Err(gateway_error.into());
+                }
                 Err(CircuitBreakerError::OperationFailed(gateway_error)) => {
                     // Request failed, record failure
-                    if let Some(lb) = self.load_balancers.get(&route.external_path) {
+                    if let Some(lb) = table.load_balancers.get(&route.external_path) {
                         lb.record_failure(&backend);
                     }
 
                     // Check if we should retry
                     if let Some(retry_cfg) = &retry_config {
-                        if retry_cfg.retry_on_connection_error && attempt < max_attempts - 1 {
+                        if retry_cfg.retry_on_connection_error
+                            && retries_method
+                            && attempt < max_attempts - 1
+                            && retry_budget_allows()
+                        {
                             warn!(
-                                "Connection error to {}, retrying (attempt {}/{})",
+                                "Connection error to {}, retrying (attempt {}/{}){}",
                                 target_url,
                                 attempt + 1,
-                                max_attempts
+                                max_attempts,
+                                tag_fields
                             );
 
                             // Exponential backoff
@@ -748,6 +2134,8 @@ impl RouteHandler {
     /// - `connection` - Connection management headers
     /// - `upgrade` - Protocol upgrade headers  
     /// - `proxy-connection` - Proxy-specific connection headers
+    /// - `transfer-encoding` - Hop-by-hop framing; buffered bodies are sent
+    ///   with a computed `Content-Length` instead
     ///
     /// ## Preserved Headers
     /// - `authorization` - Authentication credentials
@@ -755,6 +2143,7 @@ impl RouteHandler {
     /// - `content-length` - Request body size
     /// - `accept` - Response format preferences
     /// - Custom application headers
+    /// - Every value of repeated headers, such as several `Accept` lines
     ///
     /// ## Added Headers
     /// - `user-agent` - Default "kairos-rs/0.2.0" if not present
@@ -809,7 +2198,13 @@ impl RouteHandler {
         let mut reqwest_headers = ReqwestHeaderMap::with_capacity(original_headers.len());
 
         // Skip problematic headers more efficiently
-        const SKIP_HEADERS: &[&str] = &["host", "connection", "upgrade", "proxy-connection"];
+        const SKIP_HEADERS: &[&str] = &[
+            "host",
+            "connection",
+            "upgrade",
+            "proxy-connection",
+            "transfer-encoding",
+        ];
 
         for (key, value) in original_headers {
             let key_str = key.as_str().to_lowercase();
@@ -822,7 +2217,7 @@ impl RouteHandler {
                 HeaderName::from_bytes(key.as_ref()),
                 HeaderValue::from_bytes(value.as_bytes()),
             ) {
-                reqwest_headers.insert(header_name, header_value);
+                reqwest_headers.append(header_name, header_value);
             }
         }
 
@@ -931,6 +2326,7 @@ impl RouteHandler {
     /// - **Closed**: Normal operation, requests are forwarded
     /// - **Open**: Circuit is open, requests fail fast
     /// - **HalfOpen**: Testing recovery, limited requests allowed
+    /// - **ForcedOpen** / **ForcedClosed**: Held in place by an operator
     ///
     /// # Examples
     ///
@@ -955,7 +2351,9 @@ impl RouteHandler {
     pub fn get_circuit_breaker_states(
         &self,
     ) -> HashMap<String, (crate::services::circuit_breaker::CircuitState, u64, u64)> {
-        self.circuit_breakers
+        self.routes
+            .load()
+            .circuit_breakers
             .iter()
             .map(|(service, breaker)| {
                 let state = breaker.get_state();
@@ -965,4 +2363,731 @@ impl RouteHandler {
             })
             .collect()
     }
+
+    /// Returns whether each backend is currently available.
+    ///
+    /// A backend counts as down while its circuit breaker is open or it is
+    /// failing its health checks. Backends are sorted by `host:port`, and each
+    /// lists the external paths of the routes using it.
+    pub fn backend_statuses(&self) -> Vec<BackendStatus> {
+        let table = self.routes.load();
+        table
+            .backend_routes
+            .iter()
+            .map(|(service_key, (backend, routes))| BackendStatus {
+                host: backend.host.clone(),
+                port: backend.port,
+                routes: routes.clone(),
+                up: table
+                    .circuit_breakers
+                    .get(service_key)
+                    .is_none_or(|breaker| !breaker.get_state().is_open())
+                    && table
+                        .backend_health
+                        .get(service_key)
+                        .is_none_or(|health| health.is_healthy()),
+            })
+            .collect()
+    }
+
+    /// Requests the health check path of every backend that has one.
+    ///
+    /// Probes reuse the pooled client and run concurrently, each bounded by
+    /// `probe_timeout`, so the call returns within roughly `probe_timeout`
+    /// however many backends there are. Results are keyed by `host:port`;
+    /// backends without a `health_check_path` are reported as
+    /// [`UpstreamHealth::Unknown`].
+    pub async fn probe_upstreams(&self, probe_timeout: Duration) -> BTreeMap<String, UpstreamHealth> {
+        let table = self.routes.load_full();
+        let probes = table.backend_routes.iter().map(|(service_key, (backend, _))| {
+            let url = backend
+                .health_check_path
+                .as_deref()
+                .map(|path| format_route(&backend.host, &backend.port, path));
+            async move {
+                let Some(url) = url else {
+                    return (service_key.clone(), UpstreamHealth::Unknown);
+                };
+
+                let start = Instant::now();
+                let status = self.probe(&url, probe_timeout).await;
+                let health = UpstreamHealth::Probed {
+                    healthy: status.is_some_and(|status| status.is_success()),
+                    status_code: status.map(|status| status.as_u16()),
+                    latency_ms: start.elapsed().as_millis() as u64,
+                };
+                (service_key.clone(), health)
+            }
+        });
+
+        futures::future::join_all(probes).await.into_iter().collect()
+    }
+
+    /// Requests `url` and returns the response status, or `None` if the
+    /// request failed or took longer than `probe_timeout`.
+    async fn probe(&self, url: &str, probe_timeout: Duration) -> Option<reqwest::StatusCode> {
+        match timeout(probe_timeout, self.client.get(url).send()).await {
+            Ok(Ok(response)) => Some(response.status()),
+            Ok(Err(e)) => {
+                debug!("Health probe to {} failed: {}", url, e);
+                None
+            }
+            Err(_) => {
+                debug!("Health probe to {} timed out", url);
+                None
+            }
+        }
+    }
+
+    /// Requests `url` and returns whether the response passes the health
+    /// check `settings` within their `timeout_secs`.
+    ///
+    /// The body is only read when `expected_body_contains` is set.
+    async fn health_probe(&self, url: &str, settings: &HealthCheckSettings) -> bool {
+        let probe = async {
+            let response = self.client.get(url).send().await?;
+            let status = response.status();
+            let status_ok = match settings.expected_status {
+                Some(expected) => status.as_u16() == expected,
+                None => status.is_success(),
+            };
+            if !status_ok {
+                debug!("Health probe to {} returned {}", url, status);
+                return Ok(false);
+            }
+            let Some(expected) = settings.expected_body_contains.as_deref() else {
+                return Ok(true);
+            };
+            let body = response.text().await?;
+            if !body.contains(expected) {
+                debug!("Health probe to {} returned a body without {:?}", url, expected);
+                return Ok(false);
+            }
+            Ok::<_, reqwest::Error>(true)
+        };
+
+        match timeout(Duration::from_secs(settings.timeout_secs), probe).await {
+            Ok(Ok(passed)) => passed,
+            Ok(Err(e)) => {
+                debug!("Health probe to {} failed: {}", url, e);
+                false
+            }
+            Err(_) => {
+                debug!("Health probe to {} timed out", url);
+                false
+            }
+        }
+    }
+
+    /// Returns the compiled regex, parameter names and match counters of the
+    /// dynamic route with `external_path`.
+    ///
+    /// Returns `None` if no dynamic route has that path; static routes are
+    /// matched by exact lookup and have no regex.
+    pub fn route_debug(&self, external_path: &str) -> Option<RouteDebugInfo> {
+        let table = self.routes.load();
+        let route = table.route_matcher.dynamic_route(external_path)?;
+        Some(RouteDebugInfo {
+            external_path: route.router.external_path.clone(),
+            regex: route.regex.as_str().to_string(),
+            param_names: route.param_names.clone(),
+            hits: route.stats.hits(),
+            misses: route.stats.misses(),
+        })
+    }
+
+    /// Returns the external path and health check settings of every route
+    /// with `health_check`.
+    pub fn health_check_routes(&self) -> Vec<(String, HealthCheckSettings)> {
+        self.routes
+            .load()
+            .routes
+            .iter()
+            .filter_map(|route| {
+                let settings = route.health_check.clone()?;
+                Some((route.external_path.clone(), settings))
+            })
+            .collect()
+    }
+
+    /// Probes the health check path of every backend of the route at
+    /// `external_path` and records the results.
+    ///
+    /// Probes run concurrently, each bounded by the settings' `timeout_secs`.
+    ///
+    /// # Returns
+    ///
+    /// The backends (as `host:port`) whose health changed, with their new health
+    pub async fn check_backend_health(
+        &self,
+        external_path: &str,
+        settings: &HealthCheckSettings,
+    ) -> Vec<(String, bool)> {
+        let table = self.routes.load_full();
+        let probes = table
+            .backend_routes
+            .iter()
+            .filter(|(_, (_, routes))| routes.iter().any(|route| route == external_path))
+            .filter_map(|(service_key, (backend, _))| {
+                let health = table.backend_health.get(service_key)?;
+                let path = backend.health_check_path.as_deref()?;
+                let url = format_route(&backend.host, &backend.port, path);
+                Some(async move {
+                    let success = self.health_probe(&url, settings).await;
+                    health
+                        .record(success, settings)
+                        .map(|healthy| (service_key.clone(), healthy))
+                })
+            });
+
+        futures::future::join_all(probes)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Returns whether each health-checked backend is in rotation, keyed by
+    /// `host:port`.
+    pub fn backend_health(&self) -> BTreeMap<String, bool> {
+        self.routes
+            .load()
+            .backend_health
+            .iter()
+            .map(|(service, health)| (service.clone(), health.is_healthy()))
+            .collect()
+    }
+
+    /// Returns the retry budget of every upstream service that has one.
+    ///
+    /// Keys are `host:port`; values are the tokens left and the number of
+    /// retries suppressed because the budget was empty.
+    pub fn get_retry_budgets(&self) -> HashMap<String, (f64, u64)> {
+        self.routes
+            .load()
+            .retry_budgets
+            .iter()
+            .map(|(service, budget)| (service.clone(), (budget.tokens(), budget.suppressed_count())))
+            .collect()
+    }
+
+    /// Forces the circuit breaker for `service` (a `host:port` key) closed.
+    ///
+    /// Returns `false` if no circuit breaker exists for `service`.
+    pub async fn reset_circuit_breaker(&self, service: &str) -> bool {
+        match self.routes.load().circuit_breakers.get(service) {
+            Some(breaker) => {
+                breaker.reset().await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Holds the circuit breaker for `service` open, ignoring automatic
+    /// transitions until [`release_circuit_breaker`](Self::release_circuit_breaker).
+    ///
+    /// Returns `false` if no circuit breaker exists for `service`.
+    pub async fn force_circuit_breaker_open(&self, service: &str) -> bool {
+        match self.routes.load().circuit_breakers.get(service) {
+            Some(breaker) => {
+                breaker.force_open().await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Holds the circuit breaker for `service` closed, ignoring automatic
+    /// transitions until [`release_circuit_breaker`](Self::release_circuit_breaker).
+    ///
+    /// Returns `false` if no circuit breaker exists for `service`.
+    pub async fn force_circuit_breaker_closed(&self, service: &str) -> bool {
+        match self.routes.load().circuit_breakers.get(service) {
+            Some(breaker) => {
+                breaker.force_closed().await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the circuit breaker for `service` to automatic control,
+    /// reporting the state it resumes from.
+    ///
+    /// Returns `None` if no circuit breaker exists for `service`.
+    pub async fn release_circuit_breaker(&self, service: &str) -> Option<CircuitState> {
+        let breaker = self.routes.load().circuit_breakers.get(service)?.clone();
+        breaker.release().await;
+        Some(breaker.get_state())
+    }
+
+    /// Replaces the handler's routes without touching anything else.
+    ///
+    /// Only route-derived state is rebuilt: the route matcher, load balancers,
+    /// response schemas and circuit breakers. Breakers of backends whose
+    /// configuration is unchanged keep their state. Middleware state such as
+    /// rate limit counters lives outside the handler and is unaffected.
+    /// Requests already in flight finish with the previous routes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the routes cannot be compiled; the current routes
+    /// stay in place.
+    pub fn reload_routes(&self, routes: Vec<Router>) -> Result<(), String> {
+        let mut result = Ok(());
+        self.routes.rcu(|current| {
+            match RouteTable::build(&routes, Some(current), current.discovered_backends.clone()) {
+                Ok(table) => {
+                    result = Ok(());
+                    Arc::new(table)
+                }
+                Err(e) => {
+                    result = Err(e);
+                    Arc::clone(current)
+                }
+            }
+        });
+        result?;
+        info!("Reloaded {} routes", routes.len());
+        Ok(())
+    }
+
+    /// Returns the external path and DNS discovery settings of every route
+    /// that discovers its backends.
+    pub fn dns_discovery_routes(&self) -> Vec<(String, DnsDiscovery)> {
+        self.routes
+            .load()
+            .routes
+            .iter()
+            .filter_map(|route| {
+                let discovery = route.dns_discovery.clone()?;
+                Some((route.external_path.clone(), discovery))
+            })
+            .collect()
+    }
+
+    /// Replaces the backends of a DNS-discovered route.
+    ///
+    /// The update is ignored if the route no longer exists or its discovery
+    /// settings changed since `discovery` was read, so a resolution cannot
+    /// overwrite a concurrent route reload.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the route's backends changed
+    pub fn set_discovered_backends(
+        &self,
+        external_path: &str,
+        discovery: &DnsDiscovery,
+        backends: Vec<Backend>,
+    ) -> bool {
+        let mut changed = false;
+        self.routes.rcu(|current| {
+            changed = false;
+            let is_current = current.routes.iter().any(|route| {
+                route.external_path == external_path
+                    && route.dns_discovery.as_ref() == Some(discovery)
+            });
+            let unchanged = current
+                .discovered_backends
+                .get(external_path)
+                .is_some_and(|(_, known)| *known == backends);
+            if !is_current || unchanged {
+                return Arc::clone(current);
+            }
+
+            let mut discovered_backends = current.discovered_backends.clone();
+            discovered_backends.insert(
+                external_path.to_string(),
+                (discovery.clone(), backends.clone()),
+            );
+            match RouteTable::build(&current.routes, Some(current), discovered_backends) {
+                Ok(table) => {
+                    changed = true;
+                    Arc::new(table)
+                }
+                Err(e) => {
+                    error!("Failed to apply discovered backends for {}: {}", external_path, e);
+                    Arc::clone(current)
+                }
+            }
+        });
+        if changed {
+            info!(
+                "Discovered {} backends for route {}",
+                backends.len(),
+                external_path
+            );
+        }
+        changed
+    }
+}
+
+/// Body of a request as the handler received it.
+enum RequestBody {
+    /// Body already read by the `web::Bytes` extractor
+    Buffered(web::Bytes),
+    /// Body still to be read from the client
+    Payload(web::Payload),
+}
+
+/// Request body forwarded to the backend while the client is still sending it.
+///
+/// Actix payloads cannot leave the worker thread, so a local task copies the
+/// chunks into a channel the HTTP client reads from.
+struct Upload {
+    chunks: futures::channel::mpsc::Receiver<Result<web::Bytes, std::io::Error>>,
+    /// Bytes received when the body went over the route's limit, 0 until then
+    overflow: Arc<AtomicUsize>,
+}
+
+impl Upload {
+    /// Starts copying `payload`, failing the body once more than `limit`
+    /// bytes have arrived.
+    fn start(mut payload: web::Payload, limit: Option<usize>) -> Self {
+        use futures::{SinkExt, StreamExt};
+
+        let (mut sender, chunks) = futures::channel::mpsc::channel(UPLOAD_CHANNEL_CAPACITY);
+        let overflow = Arc::new(AtomicUsize::new(0));
+        let task_overflow = overflow.clone();
+        actix_web::rt::spawn(async move {
+            let mut received = 0;
+            while let Some(chunk) = payload.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => {
+                        received += chunk.len();
+                        match limit {
+                            Some(limit) if received > limit => {
+                                task_overflow.store(received, Ordering::Relaxed);
+                                Err(std::io::Error::other(format!(
+                                    "request body exceeds {} bytes",
+                                    limit
+                                )))
+                            }
+                            _ => Ok(chunk),
+                        }
+                    }
+                    Err(e) => Err(std::io::Error::other(e.to_string())),
+                };
+                let failed = chunk.is_err();
+                // The upstream request is gone once the receiver is dropped
+                if sender.send(chunk).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Upload { chunks, overflow }
+    }
+}
+
+/// Returns the body length the client declared in `Content-Length`.
+fn declared_length(req: &HttpRequest) -> Option<usize> {
+    req.headers()
+        .get(header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// The route a request matched, as far as [`RouteHandler::handle`] needs it
+/// once the request has been forwarded.
+struct MatchedRoute {
+    external_path: String,
+    /// Set if the route has `warn_response_bytes`
+    large_response: Option<LargeResponseWarning>,
+    /// The route's `sla_ms` and tags, if it has a latency target
+    sla: Option<(Duration, BTreeMap<String, String>)>,
+    /// Counts the request as in flight on the route until dropped
+    in_flight: Option<RouteInFlight>,
+}
+
+/// A request counted as in flight on its route.
+///
+/// Finishes the request when dropped, so it is also counted out when the
+/// client disconnects and the request future is dropped mid-flight.
+struct RouteInFlight {
+    metrics: web::Data<MetricsCollector>,
+    route: String,
+}
+
+impl RouteInFlight {
+    fn start(metrics: web::Data<MetricsCollector>, route: &str, tags: &BTreeMap<String, String>) -> Self {
+        metrics.route_request_started(route, tags);
+        Self {
+            metrics,
+            route: route.to_string(),
+        }
+    }
+}
+
+impl Drop for RouteInFlight {
+    fn drop(&mut self) {
+        self.metrics.route_request_finished(&self.route);
+    }
+}
+
+/// Warning for responses over a route's `warn_response_bytes`.
+struct LargeResponseWarning {
+    /// Request path the response answered
+    path: String,
+    limit: u64,
+    /// The route's tags, as formatted by [`log_tag_fields`]
+    tag_fields: String,
+}
+
+impl LargeResponseWarning {
+    /// Logs a warning if a response of `bytes` is over the limit.
+    fn check(&self, bytes: u64) {
+        if bytes > self.limit {
+            warn!(
+                "Response to {} was {} bytes, over warn_response_bytes ({}){}",
+                self.path, bytes, self.limit, self.tag_fields
+            );
+        }
+    }
+}
+
+/// Response body wrapper that records how many bytes were sent once the
+/// body is dropped, whether it completed or the client went away.
+struct CountingBody {
+    body: BoxBody,
+    bytes: u64,
+    metrics: Option<web::Data<MetricsCollector>>,
+    large_response: Option<LargeResponseWarning>,
+}
+
+impl MessageBody for CountingBody {
+    type Error = <BoxBody as MessageBody>::Error;
+
+    fn size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<web::Bytes, Self::Error>>> {
+        let poll = std::pin::Pin::new(&mut self.body).poll_next(cx);
+        if let std::task::Poll::Ready(Some(Ok(chunk))) = &poll {
+            self.bytes += chunk.len() as u64;
+        }
+        poll
+    }
+}
+
+impl Drop for CountingBody {
+    fn drop(&mut self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_response_bytes(self.bytes);
+        }
+        if let Some(large) = &self.large_response {
+            large.check(self.bytes);
+        }
+    }
+}
+
+/// Sets the `X-Forwarded-*` headers describing the external request.
+///
+/// The scheme and host come from the request itself: the scheme of an
+/// absolute target, or the listener's TLS setting, and the `Host` header.
+/// Forwarding headers sent by the client are not trusted, so it cannot claim
+/// another scheme or host. The port comes from the `Host` header, falling
+/// back to the scheme's default. Configured values take precedence.
+fn add_forwarded_headers(
+    headers: &mut ReqwestHeaderMap,
+    req: &HttpRequest,
+    settings: &ForwardedHeadersSettings,
+) {
+    let scheme = settings
+        .scheme
+        .as_deref()
+        .or_else(|| req.uri().scheme_str())
+        .unwrap_or(if req.app_config().secure() { "https" } else { "http" });
+    let host = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .or_else(|| req.uri().authority().map(|authority| authority.as_str()))
+        .unwrap_or_else(|| req.app_config().host());
+    // The port the gateway listens on, which clients may leave out of `Host`
+    let port = settings
+        .port
+        .unwrap_or_else(|| req.app_config().local_addr().port());
+
+    for (name, value) in [
+        ("x-forwarded-proto", scheme.to_string()),
+        ("x-forwarded-host", host.to_string()),
+        ("x-forwarded-port", port.to_string()),
+    ] {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(HeaderName::from_static(name), value);
+        }
+    }
+}
+
+/// Returns the consistent hash `key` of the request, if it carries one.
+fn consistent_hash_key(
+    key: &HashKey,
+    req: &HttpRequest,
+    path: &str,
+    route_matcher: &RouteMatcher,
+    route: &Router,
+) -> Option<String> {
+    match key {
+        HashKey::Header(name) => req
+            .headers()
+            .get(name.as_str())
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+        HashKey::PathParam(name) => route_matcher
+            .dynamic_route(&route.external_path)?
+            .param(path, name),
+    }
+}
+
+/// Longest body snippet logged for routes with `debug_logging`
+const DEBUG_BODY_SNIPPET_BYTES: usize = 1024;
+
+/// Headers whose values are never logged by `debug_logging`
+const REDACTED_HEADERS: &[&str] = &["authorization", "proxy-authorization"];
+
+/// Logs a request forwarded on a route with `debug_logging` at DEBUG.
+///
+/// The body is `None` when it is streamed to the backend and so is not
+/// available to log.
+fn log_forwarded_request(
+    req: &HttpRequest,
+    target_url: &str,
+    headers: &ReqwestHeaderMap,
+    body: Option<&[u8]>,
+    tag_fields: &str,
+) {
+    if !log::log_enabled!(log::Level::Debug) {
+        return;
+    }
+    let headers = headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                "[redacted]".into()
+            } else {
+                String::from_utf8_lossy(value.as_bytes())
+            };
+            format!("{}: {}", name, value)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let body = match body {
+        None => "(streamed)".to_string(),
+        Some(body) if body.len() > DEBUG_BODY_SNIPPET_BYTES => format!(
+            "{}... ({} bytes)",
+            String::from_utf8_lossy(&body[..DEBUG_BODY_SNIPPET_BYTES]),
+            body.len()
+        ),
+        Some(body) => String::from_utf8_lossy(body).into_owned(),
+    };
+    debug!(
+        "{} {} {:?} forwarded to {} with headers [{}] body {:?}{}",
+        req.method(),
+        req.uri(),
+        req.version(),
+        target_url,
+        headers,
+        body,
+        tag_fields
+    );
+}
+
+/// Formats a route's tags for the log lines of requests it matched, as
+/// ` [team=payments tier=critical]`, or nothing for an untagged route.
+fn log_tag_fields(tags: &BTreeMap<String, String>) -> String {
+    if tags.is_empty() {
+        return String::new();
+    }
+    let fields = tags
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(" [{}]", fields)
+}
+
+/// Builds the query string forwarded upstream.
+///
+/// The client's query is passed through unchanged unless the route transforms
+/// query parameters; transformed parameters are re-encoded in the client's
+/// order, with repeated names kept. A query that cannot be parsed is forwarded
+/// unchanged.
+fn upstream_query(query: &str, transformer: Option<&RequestTransformer>) -> String {
+    let Some(transformer) = transformer.filter(|t| t.transforms_query_params()) else {
+        return query.to_string();
+    };
+
+    let Ok(mut pairs) = serde_urlencoded::from_str::<Vec<(String, String)>>(query) else {
+        warn!("Forwarding unparsable query string without transforming it");
+        return query.to_string();
+    };
+    transformer.transform_query_pairs(&mut pairs);
+    serde_urlencoded::to_string(pairs).unwrap_or_else(|_| query.to_string())
+}
+
+/// Builds the `204 No Content` answer to an `OPTIONS` request for a route
+/// allowing `methods`.
+///
+/// `Allow` lists the methods plus `OPTIONS`. CORS preflights, which carry
+/// `Access-Control-Request-Method`, also get `Access-Control-Allow-Methods`;
+/// as a route-specific header it takes precedence over the generic one added
+/// by the CORS headers middleware, which only fills in missing headers.
+fn options_response(methods: &[String], req: &HttpRequest) -> HttpResponse {
+    let mut allowed: Vec<&str> = methods.iter().map(String::as_str).collect();
+    allowed.push("OPTIONS");
+    let allowed = allowed.join(", ");
+
+    let mut builder = HttpResponse::NoContent();
+    builder.insert_header((header::ALLOW, allowed.as_str()));
+    if req.headers().contains_key(header::ACCESS_CONTROL_REQUEST_METHOD) {
+        builder.insert_header((header::ACCESS_CONTROL_ALLOW_METHODS, allowed.as_str()));
+    }
+    builder.finish()
+}
+
+/// Builds a response that makes the server close the connection before
+/// anything is written, for fault injection's `drop_rate`.
+///
+/// The body fails on its first read, so the status line never reaches the
+/// client. The status is only seen by the gateway's own metrics.
+fn dropped_connection_response() -> HttpResponse {
+    let body = futures::stream::once(async {
+        Err::<web::Bytes, _>(std::io::Error::new(
+            std::io::ErrorKind::ConnectionAborted,
+            "connection dropped by fault injection",
+        ))
+    });
+    HttpResponse::ServiceUnavailable().streaming(body)
+}
+
+/// Builds the HTTP response for a configured [`FixedResponse`].
+fn fixed_response(fixed: &FixedResponse) -> HttpResponse {
+    let status = StatusCode::from_u16(fixed.status).unwrap_or(StatusCode::OK);
+    let mut builder = HttpResponse::build(status);
+
+    let body = match &fixed.body {
+        Some(serde_json::Value::String(text)) => {
+            builder.content_type("text/plain; charset=utf-8");
+            text.clone()
+        }
+        Some(value) => {
+            builder.content_type("application/json");
+            value.to_string()
+        }
+        None => String::new(),
+    };
+
+    for (name, value) in &fixed.headers {
+        builder.insert_header((name.as_str(), value.as_str()));
+    }
+
+    builder.body(body)
 }
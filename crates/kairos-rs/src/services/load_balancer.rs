@@ -9,6 +9,7 @@ use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::hash::{Hash, Hasher};
 use std::collections::hash_map::DefaultHasher;
+use std::time::{Duration, Instant};
 
 /// Load balancer trait for selecting backends.
 /// 
@@ -20,16 +21,18 @@ pub trait LoadBalancer: Send + Sync {
     /// # Parameters
     /// 
     /// * `backends` - Available backend servers
-    /// * `client_ip` - Optional client IP for IP hash strategies
+    /// * `hash_key` - Optional key for hash-based strategies: the client IP,
+    ///   or the request's consistent hash key
     /// 
     /// # Returns
     /// 
     /// The selected backend or None if no backends are available
-    fn select_backend(&self, backends: &[Backend], client_ip: Option<&str>) -> Option<Backend>;
+    fn select_backend(&self, backends: &[Backend], hash_key: Option<&str>) -> Option<Backend>;
     
-    /// Records a successful request to a backend.
-    /// Used for strategies that track connection counts or health.
-    fn record_success(&self, backend: &Backend);
+    /// Records a successful request to a backend and how long the backend
+    /// took to respond.
+    /// Used for strategies that track connection counts, latency or health.
+    fn record_success(&self, backend: &Backend, response_time: Duration);
     
     /// Records a failed request to a backend.
     /// Used for strategies that track connection counts or health.
@@ -69,7 +72,7 @@ impl LoadBalancer for RoundRobinBalancer {
         Some(backends[index].clone())
     }
     
-    fn record_success(&self, _backend: &Backend) {
+    fn record_success(&self, _backend: &Backend, _response_time: Duration) {
         // No-op for round-robin
     }
     
@@ -161,7 +164,7 @@ impl LoadBalancer for LeastConnectionsBalancer {
             .cloned()
     }
     
-    fn record_success(&self, backend: &Backend) {
+    fn record_success(&self, backend: &Backend, _response_time: Duration) {
         let key = Self::get_backend_key(backend);
         let mut connections = self.connections.write().unwrap();
         
@@ -215,7 +218,7 @@ impl LoadBalancer for RandomBalancer {
         Some(backends[index].clone())
     }
     
-    fn record_success(&self, _backend: &Backend) {
+    fn record_success(&self, _backend: &Backend, _response_time: Duration) {
         // No-op for random
     }
     
@@ -311,7 +314,7 @@ impl LoadBalancer for WeightedBalancer {
         Some(weighted_list[index].clone())
     }
     
-    fn record_success(&self, _backend: &Backend) {
+    fn record_success(&self, _backend: &Backend, _response_time: Duration) {
         // No-op for weighted
     }
     
@@ -361,7 +364,7 @@ impl LoadBalancer for IpHashBalancer {
         }
     }
     
-    fn record_success(&self, _backend: &Backend) {
+    fn record_success(&self, _backend: &Backend, _response_time: Duration) {
         // No-op for IP hash
     }
     
@@ -370,6 +373,294 @@ impl LoadBalancer for IpHashBalancer {
     }
 }
 
+/// Least-latency load balancer.
+///
+/// Routes requests to the backend with the lowest exponentially-weighted
+/// moving average (EWMA) of recent response times.
+///
+/// # Algorithm
+///
+/// 1. Each successful response updates the backend's average:
+///    `ewma = α × sample + (1 - α) × ewma`, with α = 0.3
+/// 2. Failures count as a sample of [`LATENCY_FAILURE_PENALTY`], so a backend
+///    that fails fast is not mistaken for a fast one
+/// 3. Backends without samples start at 0, so new backends are tried first
+/// 4. Averages halve every [`LATENCY_DECAY_HALF_LIFE`] without a sample, so a
+///    backend left out after a slow response or a failure is tried again
+///    once its average drops below the others', and keeps traffic if it has
+///    recovered
+///
+/// # Concurrency
+///
+/// Averages live in an `RwLock<HashMap>`; selection takes the read lock and
+/// only recording takes the write lock.
+///
+/// # Example
+///
+/// ```text
+/// Current averages:
+/// - Backend A: 120 ms
+/// - Backend B:  35 ms
+/// - Backend C: (no samples yet)
+///
+/// Next request → Backend C (untried), then → Backend B (35 ms)
+/// About 20 s later without samples, A has decayed below B's 35 ms → Backend A
+/// ```
+#[derive(Debug)]
+pub struct LeastLatencyBalancer {
+    /// Response time EWMA per backend (host:port), in milliseconds, and when
+    /// it was last sampled
+    latencies: RwLock<HashMap<String, (f64, Instant)>>,
+    /// Time without samples after which an average has halved
+    decay_half_life: Duration,
+}
+
+/// Weight of the newest sample in the response time average.
+const LATENCY_EWMA_ALPHA: f64 = 0.3;
+
+/// Response time recorded for a failed request.
+pub const LATENCY_FAILURE_PENALTY: Duration = Duration::from_secs(1);
+
+/// Time without samples after which a backend's response time average has
+/// halved.
+pub const LATENCY_DECAY_HALF_LIFE: Duration = Duration::from_secs(10);
+
+impl LeastLatencyBalancer {
+    pub fn new() -> Self {
+        Self::with_decay_half_life(LATENCY_DECAY_HALF_LIFE)
+    }
+
+    /// Creates a balancer whose averages halve every `half_life` without
+    /// samples, instead of [`LATENCY_DECAY_HALF_LIFE`].
+    pub fn with_decay_half_life(half_life: Duration) -> Self {
+        Self {
+            latencies: RwLock::new(HashMap::new()),
+            decay_half_life: half_life,
+        }
+    }
+
+    /// Creates a unique key for a backend (host:port).
+    fn get_backend_key(backend: &Backend) -> String {
+        format!("{}:{}", backend.host, backend.port)
+    }
+
+    /// Returns `ewma` decayed by the time since it was sampled at `sampled_at`.
+    fn decayed(&self, (ewma, sampled_at): (f64, Instant), now: Instant) -> f64 {
+        let half_lives = now.duration_since(sampled_at).as_secs_f64()
+            / self.decay_half_life.as_secs_f64().max(f64::MIN_POSITIVE);
+        ewma * 0.5f64.powf(half_lives)
+    }
+
+    /// Returns the response time average of a backend in milliseconds, as
+    /// currently used for selection.
+    /// Returns 0 if the backend has no samples yet.
+    pub fn average_latency_ms(&self, backend: &Backend) -> f64 {
+        let latencies = self.latencies.read().unwrap();
+        latencies
+            .get(&Self::get_backend_key(backend))
+            .map_or(0.0, |entry| self.decayed(*entry, Instant::now()))
+    }
+
+    fn record_sample(&self, backend: &Backend, response_time: Duration) {
+        let sample = response_time.as_secs_f64() * 1000.0;
+        let now = Instant::now();
+        let mut latencies = self.latencies.write().unwrap();
+
+        let ewma = match latencies.get(&Self::get_backend_key(backend)) {
+            Some(entry) => {
+                LATENCY_EWMA_ALPHA * sample + (1.0 - LATENCY_EWMA_ALPHA) * self.decayed(*entry, now)
+            }
+            None => sample,
+        };
+        latencies.insert(Self::get_backend_key(backend), (ewma, now));
+    }
+}
+
+impl Default for LeastLatencyBalancer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoadBalancer for LeastLatencyBalancer {
+    fn select_backend(&self, backends: &[Backend], _client_ip: Option<&str>) -> Option<Backend> {
+        let latencies = self.latencies.read().unwrap();
+        let now = Instant::now();
+
+        backends
+            .iter()
+            .min_by(|a, b| {
+                let latency = |backend| {
+                    latencies
+                        .get(&Self::get_backend_key(backend))
+                        .map_or(0.0, |entry| self.decayed(*entry, now))
+                };
+                latency(a).total_cmp(&latency(b))
+            })
+            .cloned()
+    }
+
+    fn record_success(&self, backend: &Backend, response_time: Duration) {
+        self.record_sample(backend, response_time);
+    }
+
+    fn record_failure(&self, backend: &Backend) {
+        self.record_sample(backend, LATENCY_FAILURE_PENALTY);
+    }
+}
+
+/// Consistent hash load balancer.
+///
+/// Places every backend on a hash ring at [`CONSISTENT_HASH_VIRTUAL_NODES`]
+/// points and routes each key to the first backend point at or after the
+/// key's hash. Adding or removing one of N backends only moves the keys
+/// between it and its neighbours, about 1/N of them, while the rest keep
+/// their backend. Requests without a key are spread round-robin.
+///
+/// Keys and backends are hashed with a fixed algorithm, FNV-1a, so every
+/// replica and every build of the gateway routes a key to the same backend.
+///
+/// # Example
+///
+/// ```rust
+/// use kairos_rs::models::router::Backend;
+/// use kairos_rs::services::load_balancer::{ConsistentHashBalancer, LoadBalancer};
+///
+/// let backend = |port| Backend {
+///     host: "http://127.0.0.1".to_string(),
+///     port,
+///     weight: 1,
+///     health_check_path: None,
+///     timeout_secs: None,
+/// };
+/// let balancer = ConsistentHashBalancer::new();
+/// let backends = vec![backend(8080), backend(8081)];
+///
+/// let first = balancer.select_backend(&backends, Some("tenant-a")).unwrap();
+/// let again = balancer.select_backend(&backends, Some("tenant-a")).unwrap();
+/// assert_eq!(first.port, again.port);
+/// ```
+#[derive(Debug)]
+pub struct ConsistentHashBalancer {
+    /// Ring for the last backend set seen, rebuilt when the set changes
+    ring: RwLock<HashRing>,
+    /// Picks backends for requests without a key
+    keyless: RoundRobinBalancer,
+}
+
+/// Points each backend occupies on the hash ring.
+pub const CONSISTENT_HASH_VIRTUAL_NODES: usize = 160;
+
+#[derive(Debug, Default)]
+struct HashRing {
+    /// Sorted `host:port` keys of the backends the ring was built for
+    backends: Vec<String>,
+    /// Sorted ring points and the backend key each belongs to
+    points: Vec<(u64, String)>,
+}
+
+impl HashRing {
+    fn build(mut backends: Vec<String>) -> Self {
+        backends.sort();
+        let mut points: Vec<(u64, String)> = backends
+            .iter()
+            .flat_map(|backend| {
+                (0..CONSISTENT_HASH_VIRTUAL_NODES)
+                    .map(move |node| (hash_str(&format!("{}#{}", backend, node)), backend.clone()))
+            })
+            .collect();
+        points.sort();
+        Self { backends, points }
+    }
+
+    fn lookup(&self, key: &str) -> Option<&str> {
+        let hash = hash_str(key);
+        let index = self.points.partition_point(|(point, _)| *point < hash);
+        self.points
+            .get(index)
+            .or_else(|| self.points.first())
+            .map(|(_, backend)| backend.as_str())
+    }
+}
+
+/// 64-bit FNV-1a offset basis
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// 64-bit FNV-1a prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes `value` with 64-bit FNV-1a, finalized with MurmurHash3's `fmix64`.
+///
+/// Unlike `DefaultHasher`, whose algorithm may change between Rust
+/// releases, the result is fixed, so ring positions do not move when the
+/// gateway is rebuilt. FNV-1a alone barely changes the high bits for keys
+/// that differ only in their last bytes, such as `tenant-1` and `tenant-2`,
+/// which would bunch them up on the ring; the finalizer spreads them out.
+fn hash_str(value: &str) -> u64 {
+    let mut hash = value.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+impl ConsistentHashBalancer {
+    pub fn new() -> Self {
+        Self {
+            ring: RwLock::new(HashRing::default()),
+            keyless: RoundRobinBalancer::new(),
+        }
+    }
+
+    /// Creates a unique key for a backend (host:port).
+    fn get_backend_key(backend: &Backend) -> String {
+        format!("{}:{}", backend.host, backend.port)
+    }
+}
+
+impl Default for ConsistentHashBalancer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoadBalancer for ConsistentHashBalancer {
+    fn select_backend(&self, backends: &[Backend], hash_key: Option<&str>) -> Option<Backend> {
+        let Some(key) = hash_key else {
+            return self.keyless.select_backend(backends, None);
+        };
+
+        let mut keys: Vec<String> = backends.iter().map(Self::get_backend_key).collect();
+        keys.sort();
+        let selected = {
+            let ring = self.ring.read().unwrap();
+            if ring.backends == keys {
+                ring.lookup(key).map(str::to_string)
+            } else {
+                drop(ring);
+                let mut ring = self.ring.write().unwrap();
+                *ring = HashRing::build(keys);
+                ring.lookup(key).map(str::to_string)
+            }
+        }?;
+
+        backends
+            .iter()
+            .find(|backend| Self::get_backend_key(backend) == selected)
+            .cloned()
+    }
+
+    fn record_success(&self, _backend: &Backend, _response_time: Duration) {
+        // No-op for consistent hash
+    }
+
+    fn record_failure(&self, _backend: &Backend) {
+        // No-op for consistent hash
+    }
+}
+
 /// Factory for creating load balancers based on strategy.
 pub struct LoadBalancerFactory;
 
@@ -392,6 +683,12 @@ impl LoadBalancerFactory {
             LoadBalancingStrategy::IpHash => {
                 Arc::new(IpHashBalancer::new())
             }
+            LoadBalancingStrategy::LeastLatency => {
+                Arc::new(LeastLatencyBalancer::new())
+            }
+            LoadBalancingStrategy::ConsistentHash(_) => {
+                Arc::new(ConsistentHashBalancer::new())
+            }
         }
     }
 }
@@ -0,0 +1,116 @@
+//! Pluggable destinations for gateway metrics.
+//!
+//! [`MetricsCollector`](crate::routes::metrics::MetricsCollector) always keeps
+//! its in-memory counters, which `/metrics` renders for Prometheus. Setups that
+//! push metrics instead of having them scraped attach a [`MetricsSink`], which
+//! receives every request and gauge change as it is recorded.
+
+use log::debug;
+use std::fmt;
+use std::io;
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+/// Receiver of metrics recorded by the gateway.
+///
+/// Methods are called on the request path, so implementations must not
+/// block; dropping a metric is better than delaying a request.
+pub trait MetricsSink: Send + Sync + fmt::Debug {
+    /// Records a completed request, with the `external_path` of the route it
+    /// matched, if any.
+    fn record_request(&self, route: Option<&str>, status_code: u16, response_time: Duration);
+
+    /// Adds one to the counter `name`, such as `errors.timeout`.
+    fn increment_counter(&self, name: &str);
+
+    /// Sets the gauge `name`, such as `active_connections`, to `value`.
+    fn record_gauge(&self, name: &str, value: f64);
+}
+
+/// Sends metrics to a StatsD server as UDP datagrams.
+///
+/// Each request is sent as one datagram holding a `requests` counter, a
+/// `responses.<status>` counter and a `response_time` timing. With `tags`,
+/// request metrics carry DogStatsD `route` and `status` tags, for Datadog
+/// agents; plain StatsD servers do not understand tags.
+///
+/// Sending never blocks: datagrams that cannot be sent right away, or are
+/// lost on the way, are dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::services::metrics_sink::{MetricsSink, StatsdSink};
+/// use std::time::Duration;
+///
+/// let sink = StatsdSink::connect("127.0.0.1:8125", "kairos", false).unwrap();
+/// sink.record_request(Some("/api/users/{id}"), 200, Duration::from_millis(12));
+/// ```
+#[derive(Debug)]
+pub struct StatsdSink {
+    socket: UdpSocket,
+    prefix: String,
+    tags: bool,
+}
+
+impl StatsdSink {
+    /// Creates a sink sending to `address` (`host:port`), prefixing metric
+    /// names with `prefix` unless it is empty.
+    pub fn connect(address: &str, prefix: impl Into<String>, tags: bool) -> io::Result<Self> {
+        let target = address.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("StatsD address {} did not resolve", address),
+            )
+        })?;
+        let socket = UdpSocket::bind(if target.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" })?;
+        socket.connect(target)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            prefix: prefix.into(),
+            tags,
+        })
+    }
+
+    fn name(&self, metric: &str) -> String {
+        if self.prefix.is_empty() {
+            metric.to_string()
+        } else {
+            format!("{}.{}", self.prefix, metric)
+        }
+    }
+
+    fn send(&self, payload: &str) {
+        if let Err(e) = self.socket.send(payload.as_bytes()) {
+            debug!("Dropped StatsD datagram: {}", e);
+        }
+    }
+}
+
+impl MetricsSink for StatsdSink {
+    fn record_request(&self, route: Option<&str>, status_code: u16, response_time: Duration) {
+        let tags = if self.tags {
+            // Commas and pipes would end the tag list
+            let route = route.unwrap_or("none").replace([',', '|'], "_");
+            format!("|#route:{},status:{}", route, status_code)
+        } else {
+            String::new()
+        };
+        self.send(&format!(
+            "{}:1|c{tags}\n{}:1|c{tags}\n{}:{}|ms{tags}",
+            self.name("requests"),
+            self.name(&format!("responses.{}", status_code)),
+            self.name("response_time"),
+            response_time.as_millis(),
+        ));
+    }
+
+    fn increment_counter(&self, name: &str) {
+        self.send(&format!("{}:1|c", self.name(name)));
+    }
+
+    fn record_gauge(&self, name: &str, value: f64) {
+        self.send(&format!("{}:{}|g", self.name(name), value));
+    }
+}
@@ -8,6 +8,13 @@
 //! # Module Organization
 //!
 //! - [`http`] - HTTP request handling and upstream service communication
+//! - [`discovery`] - DNS-based discovery of route backends
+//! - [`health_check`] - Background health checks that take failing backends out of rotation
+//! - [`metrics_sink`] - Destinations receiving metrics as they are recorded, such as StatsD
+//! - [`response_schema`] - JSON Schema validation of upstream responses
+//! - [`retry_budget`] - Per-upstream budgets that keep retries from piling up
+//! - [`tcp_proxy`] - Plain TCP passthrough for non-HTTP services
+//! - [`webhook_signature`] - HMAC verification of signed webhook bodies
 //!
 //! # Architecture
 //!
@@ -45,7 +52,7 @@
 //!
 //! ```rust
 //! use kairos_rs::services::http::RouteHandler;
-//! use kairos_rs::models::router::{Router, Backend, Protocol};
+//! use kairos_rs::models::router::{Router, Backend};
 //! use actix_web::{web, HttpRequest};
 //!
 //! // Create a route handler
@@ -56,19 +63,14 @@
 //!         external_path: "/api/users/{id}".to_string(),
 //!         internal_path: "/v1/user/{id}".to_string(),
 //!         methods: vec!["GET".to_string()],
-//!         auth_required: false,
 //!         backends: Some(vec![Backend {
 //!             host: "http://backend".to_string(),
 //!             port: 8080,
 //!             weight: 1,
 //!             health_check_path: None,
+//!             timeout_secs: None,
 //!         }]),
-//!         load_balancing_strategy: Default::default(),
-//!         retry: None,
-//!         protocol: Protocol::Http,
-//!         request_transformation: None,
-//!         response_transformation: None,
-//!         ai_policy: None,
+//!         ..Default::default()
 //!     }
 //! ];
 //!
@@ -80,10 +82,17 @@
 
 pub mod ai;
 pub mod circuit_breaker;
+pub mod discovery;
 pub mod dns;
 pub mod ftp;
+pub mod health_check;
 pub mod http;
 pub mod load_balancer;
+pub mod metrics_sink;
 pub mod metrics_store;
+pub mod response_schema;
+pub mod retry_budget;
+pub mod tcp_proxy;
+pub mod webhook_signature;
 pub mod websocket;
 pub mod websocket_metrics;
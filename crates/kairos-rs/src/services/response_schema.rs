@@ -0,0 +1,52 @@
+//! JSON Schema validation of upstream response bodies.
+//!
+//! Routes can declare a `response_schema` file that upstream responses are
+//! checked against. Depending on the route's `response_schema_mode`,
+//! violations are either only reported (shadow) or rejected with a 502.
+
+use jsonschema::JSONSchema;
+use std::path::Path;
+
+/// Maximum number of violation messages reported for a single response.
+const MAX_REPORTED_VIOLATIONS: usize = 5;
+
+/// A compiled JSON Schema loaded from a route's `response_schema` file.
+pub struct ResponseSchema {
+    schema: JSONSchema,
+}
+
+impl ResponseSchema {
+    /// Reads and compiles the JSON Schema at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a descriptive message if the file cannot be read, is not valid
+    /// JSON, or is not a valid JSON Schema.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read response schema {}: {}", path.display(), e))?;
+        let value: serde_json::Value = serde_json::from_str(&source)
+            .map_err(|e| format!("Response schema {} is not valid JSON: {}", path.display(), e))?;
+        let schema = JSONSchema::compile(&value).map_err(|e| {
+            format!("Response schema {} is not a valid JSON Schema: {}", path.display(), e)
+        })?;
+
+        Ok(Self { schema })
+    }
+
+    /// Validates a response body against the schema.
+    ///
+    /// Bodies that are not JSON count as a violation. On failure, returns up to
+    /// a handful of violation messages including the offending instance path.
+    pub fn validate(&self, body: &[u8]) -> Result<(), Vec<String>> {
+        let instance: serde_json::Value = serde_json::from_slice(body)
+            .map_err(|e| vec![format!("response body is not valid JSON: {}", e)])?;
+
+        self.schema.validate(&instance).map_err(|errors| {
+            errors
+                .take(MAX_REPORTED_VIOLATIONS)
+                .map(|e| format!("{} (at '{}')", e, e.instance_path))
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,91 @@
+//! Retry budgets for upstream services.
+//!
+//! Retrying every failed request independently multiplies the load on an
+//! upstream that is already struggling. A retry budget is a token bucket per
+//! upstream service (`host:port`): every request forwarded to the service
+//! deposits a fraction of a token, and every retry withdraws a whole one. Once
+//! the bucket is empty, failures are returned to the client without retrying
+//! until enough regular traffic has refilled it.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Maximum number of tokens a budget can hold.
+///
+/// Budgets start full, so a service that has seen no traffic yet can still
+/// retry a short burst of failures.
+pub const RETRY_BUDGET_CAPACITY: f64 = 10.0;
+
+/// Token bucket limiting retries to one upstream service.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::services::retry_budget::{RetryBudget, RETRY_BUDGET_CAPACITY};
+///
+/// let budget = RetryBudget::new();
+/// assert_eq!(budget.tokens(), RETRY_BUDGET_CAPACITY);
+///
+/// // Drain the budget
+/// while budget.try_withdraw() {}
+/// assert_eq!(budget.suppressed_count(), 1);
+///
+/// // With a ratio of 0.1, ten requests pay for one retry
+/// for _ in 0..10 {
+///     budget.deposit(0.1);
+/// }
+/// assert!(budget.try_withdraw());
+/// ```
+#[derive(Debug)]
+pub struct RetryBudget {
+    tokens: Mutex<f64>,
+    suppressed: AtomicU64,
+}
+
+impl RetryBudget {
+    /// Creates a full budget.
+    pub fn new() -> Self {
+        Self {
+            tokens: Mutex::new(RETRY_BUDGET_CAPACITY),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Credits `ratio` tokens for a request forwarded to the service.
+    pub fn deposit(&self, ratio: f64) {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        *tokens = (*tokens + ratio).min(RETRY_BUDGET_CAPACITY);
+    }
+
+    /// Takes one token for a retry.
+    ///
+    /// Returns `false`, and counts the retry as suppressed, if the budget has
+    /// less than a whole token left.
+    pub fn try_withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        // Tolerate rounding errors from summing fractional deposits
+        if *tokens >= 1.0 - 1e-9 {
+            *tokens = (*tokens - 1.0).max(0.0);
+            true
+        } else {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            false
+        }
+    }
+
+    /// Returns the tokens currently available.
+    pub fn tokens(&self) -> f64 {
+        *self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns how many retries were suppressed because the budget was empty.
+    pub fn suppressed_count(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,169 @@
+//! Plain TCP passthrough for non-HTTP services.
+//!
+//! Each configured `tcp_proxies` entry gets its own listener. Accepted
+//! connections are connected to an upstream picked by the entry's load
+//! balancer, and bytes are copied both ways until either side closes. Every
+//! upstream has a circuit breaker that counts failed connection attempts, so
+//! an upstream refusing connections is skipped until its breaker resets.
+//!
+//! The gateway does not look at the bytes it forwards; TLS, if any, is
+//! between the client and the upstream.
+
+use crate::models::router::Backend;
+use crate::models::settings::TcpProxySettings;
+use crate::routes::metrics::MetricsCollector;
+use crate::services::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerError};
+use crate::services::load_balancer::{LoadBalancer, LoadBalancerFactory};
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{timeout, Duration, Instant};
+
+/// One TCP proxy listener with its upstreams, balancer and circuit breakers.
+struct TcpProxy {
+    upstreams: Vec<Backend>,
+    balancer: Arc<dyn LoadBalancer>,
+    breakers: HashMap<String, Arc<CircuitBreaker>>,
+    connect_timeout: Duration,
+    metrics: MetricsCollector,
+}
+
+impl TcpProxy {
+    fn new(settings: &TcpProxySettings, metrics: MetricsCollector) -> Self {
+        let upstreams = settings.upstreams();
+        let breaker_config = settings
+            .circuit_breaker
+            .as_ref()
+            .map(CircuitBreakerConfig::from)
+            .unwrap_or_default();
+        let breakers = upstreams
+            .iter()
+            .map(|upstream| {
+                let key = upstream_address(upstream);
+                let breaker = CircuitBreaker::new(format!("tcp:{}", key), breaker_config.clone());
+                (key, breaker)
+            })
+            .collect();
+
+        Self {
+            upstreams,
+            balancer: LoadBalancerFactory::create(&settings.load_balancing_strategy),
+            breakers,
+            connect_timeout: Duration::from_secs(settings.connect_timeout_secs),
+            metrics,
+        }
+    }
+
+    /// Connects `inbound` to an upstream and copies bytes until either side closes.
+    async fn handle(&self, mut inbound: TcpStream, peer: SocketAddr) {
+        let client_ip = peer.ip().to_string();
+        let Some(upstream) = self.balancer.select_backend(&self.upstreams, Some(&client_ip)) else {
+            warn!("TCP proxy has no upstream for connection from {}", peer);
+            return;
+        };
+        let address = upstream_address(&upstream);
+        let Some(breaker) = self.breakers.get(&address) else {
+            return;
+        };
+
+        let started = Instant::now();
+        let connect = breaker
+            .call(async {
+                timeout(self.connect_timeout, TcpStream::connect(&address))
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(io::Error::new(io::ErrorKind::TimedOut, "connect timed out"))
+                    })
+            })
+            .await;
+        let mut outbound = match connect {
+            Ok(outbound) => outbound,
+            Err(CircuitBreakerError::CircuitOpen) => {
+                debug!("Circuit breaker of TCP upstream {} is open, closing connection from {}", address, peer);
+                return;
+            }
+            Err(CircuitBreakerError::OperationFailed(e)) => {
+                self.balancer.record_failure(&upstream);
+                warn!("TCP proxy cannot connect to {} for {}: {}", address, peer, e);
+                return;
+            }
+        };
+
+        self.metrics.tcp_connection_opened();
+        match tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await {
+            Ok((sent, received)) => debug!(
+                "TCP connection from {} to {} closed after {} bytes sent, {} received",
+                peer, address, sent, received
+            ),
+            Err(e) => debug!("TCP connection from {} to {} failed: {}", peer, address, e),
+        }
+        self.metrics.tcp_connection_closed();
+        self.balancer.record_success(&upstream, started.elapsed());
+    }
+}
+
+/// Returns the `host:port` an upstream is connected to.
+fn upstream_address(upstream: &Backend) -> String {
+    format!("{}:{}", upstream.host, upstream.port)
+}
+
+/// Binds `host` on the proxy's `listen_port` and spawns the task accepting
+/// connections on it.
+///
+/// Returns the bound address, which has the actual port when `listen_port`
+/// is 0, and the accept task.
+///
+/// # Errors
+///
+/// Returns the error of binding the listener, for example when the port is
+/// already in use.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use kairos_rs::models::settings::TcpProxySettings;
+/// use kairos_rs::routes::metrics::MetricsCollector;
+/// use kairos_rs::services::tcp_proxy::spawn_tcp_proxy;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let settings: TcpProxySettings = serde_json::from_str(r#"{
+///     "listen_port": 15432,
+///     "upstream_host": "db.internal",
+///     "upstream_port": 5432
+/// }"#).unwrap();
+///
+/// let (address, _task) = spawn_tcp_proxy("0.0.0.0", &settings, MetricsCollector::default()).await?;
+/// println!("Forwarding {} to db.internal:5432", address);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn spawn_tcp_proxy(
+    host: &str,
+    settings: &TcpProxySettings,
+    metrics: MetricsCollector,
+) -> io::Result<(SocketAddr, tokio::task::JoinHandle<()>)> {
+    let listener = TcpListener::bind((host, settings.listen_port)).await?;
+    let address = listener.local_addr()?;
+    let proxy = Arc::new(TcpProxy::new(settings, metrics));
+
+    let task = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((inbound, peer)) => {
+                    let proxy = proxy.clone();
+                    tokio::spawn(async move { proxy.handle(inbound, peer).await });
+                }
+                Err(e) => {
+                    // Usually out of file descriptors; give connections time to close
+                    warn!("TCP proxy on {} failed to accept a connection: {}", address, e);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            }
+        }
+    });
+
+    Ok((address, task))
+}
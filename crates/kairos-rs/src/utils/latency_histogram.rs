@@ -0,0 +1,167 @@
+//! Lock-free latency histogram for percentile estimation.
+//!
+//! Values are recorded in microseconds into log-linear buckets, in the style
+//! of HDR histograms: each power of two is split into 32 equal sub-buckets,
+//! so any recorded value is reported within about 3% of its true value while
+//! the whole histogram stays a fixed array of 1024 counters.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Values below this are counted exactly, one bucket per microsecond.
+const LINEAR_BUCKETS: u64 = 64;
+
+/// Sub-buckets per power of two above `LINEAR_BUCKETS`.
+const SUB_BUCKETS: u64 = LINEAR_BUCKETS / 2;
+
+/// Largest recorded value in microseconds (about 19 hours); larger values
+/// are clamped to it.
+const MAX_VALUE_MICROS: u64 = (1 << 36) - 1;
+
+/// Total number of buckets needed to cover `MAX_VALUE_MICROS`.
+const BUCKET_COUNT: usize = bucket_index(MAX_VALUE_MICROS) + 1;
+
+/// Returns the bucket counting `value` microseconds.
+const fn bucket_index(value: u64) -> usize {
+    if value < LINEAR_BUCKETS {
+        return value as usize;
+    }
+    let shift = (63 - value.leading_zeros() as u64) - (SUB_BUCKETS.trailing_zeros() as u64);
+    (LINEAR_BUCKETS + (shift - 1) * SUB_BUCKETS + ((value >> shift) - SUB_BUCKETS)) as usize
+}
+
+/// Returns the largest value in microseconds counted by bucket `index`.
+const fn bucket_upper_bound(index: usize) -> u64 {
+    let index = index as u64;
+    if index < LINEAR_BUCKETS {
+        return index;
+    }
+    let shift = (index - LINEAR_BUCKETS) / SUB_BUCKETS + 1;
+    let sub_bucket = (index - LINEAR_BUCKETS) % SUB_BUCKETS + SUB_BUCKETS;
+    ((sub_bucket + 1) << shift) - 1
+}
+
+/// Thread-safe histogram of response times.
+///
+/// Recording is a single atomic increment, so it can be shared by every
+/// request handler without locking.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::utils::latency_histogram::LatencyHistogram;
+/// use std::time::Duration;
+///
+/// let histogram = LatencyHistogram::new();
+/// for ms in 1..=100 {
+///     histogram.record(Duration::from_millis(ms));
+/// }
+///
+/// assert_eq!(histogram.count(), 100);
+/// let p99 = histogram.value_at_quantile(0.99).as_secs_f64() * 1000.0;
+/// assert!((p99 - 99.0).abs() < 99.0 * 0.04);
+/// ```
+#[derive(Debug)]
+pub struct LatencyHistogram {
+    counts: Box<[AtomicU64]>,
+}
+
+impl LatencyHistogram {
+    /// Creates an empty histogram.
+    pub fn new() -> Self {
+        Self {
+            counts: (0..BUCKET_COUNT).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    /// Records one observation.
+    pub fn record(&self, value: Duration) {
+        let micros = u64::try_from(value.as_micros())
+            .unwrap_or(MAX_VALUE_MICROS)
+            .min(MAX_VALUE_MICROS);
+        self.counts[bucket_index(micros)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the number of recorded observations.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().map(|count| count.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Returns the value below which `quantile` (between 0 and 1) of the
+    /// observations fall, or zero if nothing has been recorded.
+    ///
+    /// The result is the upper bound of the bucket holding that observation,
+    /// so it never understates the latency.
+    pub fn value_at_quantile(&self, quantile: f64) -> Duration {
+        let counts: Vec<u64> = self
+            .counts
+            .iter()
+            .map(|count| count.load(Ordering::Relaxed))
+            .collect();
+        let total: u64 = counts.iter().sum();
+        if total == 0 {
+            return Duration::ZERO;
+        }
+
+        let rank = ((quantile.clamp(0.0, 1.0) * total as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, count) in counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_micros(bucket_upper_bound(index));
+            }
+        }
+        Duration::from_micros(MAX_VALUE_MICROS)
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets_cover_their_values() {
+        for value in [0, 1, 63, 64, 65, 100, 1_000, 123_456, 5_000_000, MAX_VALUE_MICROS] {
+            let index = bucket_index(value);
+            assert!(index < BUCKET_COUNT);
+            assert!(bucket_upper_bound(index) >= value);
+            if index > 0 {
+                assert!(bucket_upper_bound(index - 1) < value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_relative_error_is_bounded() {
+        for value in [100, 999, 10_000, 250_000, 7_654_321] {
+            let upper = bucket_upper_bound(bucket_index(value));
+            assert!((upper - value) as f64 / value as f64 <= 1.0 / SUB_BUCKETS as f64);
+        }
+    }
+
+    #[test]
+    fn test_quantiles_reflect_the_tail() {
+        let histogram = LatencyHistogram::new();
+        for _ in 0..98 {
+            histogram.record(Duration::from_millis(10));
+        }
+        histogram.record(Duration::from_millis(2_000));
+        histogram.record(Duration::from_millis(2_000));
+
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        assert!((ms(histogram.value_at_quantile(0.5)) - 10.0).abs() < 0.5);
+        assert!((ms(histogram.value_at_quantile(0.95)) - 10.0).abs() < 0.5);
+        assert!((ms(histogram.value_at_quantile(0.99)) - 2_000.0).abs() < 2_000.0 * 0.04);
+    }
+
+    #[test]
+    fn test_empty_histogram_reports_zero() {
+        assert_eq!(LatencyHistogram::new().value_at_quantile(0.99), Duration::ZERO);
+    }
+}
@@ -7,8 +7,10 @@
 //! 
 //! # Module Organization
 //! 
+//! - [`latency_histogram`] - Lock-free response time histogram for percentiles
 //! - [`path`] - URL and path manipulation utilities for request forwarding
 //! - [`route_matcher`] - High-performance route matching with regex compilation
+//! - [`sharded_counter`] - Per-thread sharded counters for hot metrics
 //! 
 //! # Performance Focus
 //! 
@@ -35,7 +37,7 @@
 //! 
 //! ```rust
 //! use kairos_rs::utils::{path::format_route, route_matcher::RouteMatcher};
-//! use kairos_rs::models::router::{Router, Backend, Protocol};
+//! use kairos_rs::models::router::{Router, Backend};
 //! 
 //! // URL formatting
 //! let url = format_route("http://backend", &8080, "/api/users/123");
@@ -49,19 +51,14 @@
 //!         external_path: "/users/{id}".to_string(),
 //!         internal_path: "/v1/user/{id}".to_string(),
 //!         methods: vec!["GET".to_string()],
-//!         auth_required: false,
 //!         backends: Some(vec![Backend {
 //!             host: "http://localhost".to_string(),
 //!             port: 8080,
 //!             weight: 1,
 //!             health_check_path: None,
+//!             timeout_secs: None,
 //!         }]),
-//!         load_balancing_strategy: Default::default(),
-//!         retry: None,
-//!         protocol: Protocol::Http,
-//!         request_transformation: None,
-//!         response_transformation: None,
-//!         ai_policy: None,
+//!         ..Default::default()
 //!     }
 //! ];
 //! let matcher = RouteMatcher::new(routes)?;
@@ -69,5 +66,7 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+pub mod latency_histogram;
 pub mod path;
 pub mod route_matcher;
+pub mod sharded_counter;
@@ -48,4 +48,66 @@ pub fn format_route(host: &str, port: &u16, internal_path: &str) -> String {
         port,
         internal_path
     )
-}
\ No newline at end of file
+}
+/// Decodes `%XX` escapes in a URL component.
+///
+/// Malformed escapes are kept as they are, and decoded bytes that are not
+/// valid UTF-8 are replaced with `U+FFFD`.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::utils::path::percent_decode;
+///
+/// assert_eq!(percent_decode("a%20b%2Fc"), "a b/c");
+/// assert_eq!(percent_decode("100%"), "100%");
+/// ```
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Percent-encodes everything except unreserved characters
+/// (`A-Z a-z 0-9 - . _ ~`), so the result is safe in both a path segment and
+/// a query parameter.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::utils::path::percent_encode;
+///
+/// assert_eq!(percent_encode("a b/c&d=é"), "a%20b%2Fc%26d%3D%C3%A9");
+/// assert_eq!(percent_encode("user-1.2_x~"), "user-1.2_x~");
+/// ```
+pub fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
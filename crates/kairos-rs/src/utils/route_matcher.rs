@@ -1,6 +1,9 @@
-use crate::models::router::Router;
+use crate::models::router::{Router, TrailingSlash};
+use crate::utils::path::{percent_decode, percent_encode};
 use ahash::HashMap as AHashMap;
+use log::warn;
 use regex::Regex;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -27,6 +30,19 @@ pub enum RouteMatchError {
     #[error("Regex compilation failed: {0}")]
     RegexError(String),
 
+    /// Two routes match exactly the same request paths.
+    ///
+    /// Routes are selected by path before their methods are checked, so only
+    /// one of them could ever handle requests. Routes that differ only in
+    /// their methods have to be merged into one route.
+    #[error("Duplicate route: {path} matches the same paths as {existing}")]
+    DuplicateRoute {
+        /// The route defined later
+        path: String,
+        /// The route defined first
+        existing: String,
+    },
+
     /// No configured route matches the requested path.
     ///
     /// This occurs during request processing when the incoming path
@@ -36,6 +52,56 @@ pub enum RouteMatchError {
         /// The requested path that couldn't be matched
         path: String,
     },
+
+    /// The requested path only matches a route with its trailing slash
+    /// added or removed, and the matcher redirects such paths.
+    ///
+    /// Only returned in [`TrailingSlash::Redirect`] mode.
+    #[error("Path should be requested as {location}")]
+    TrailingSlashRedirect {
+        /// The path in the form the route is configured with
+        location: String,
+    },
+}
+
+/// How a captured path parameter is written into `internal_path`.
+///
+/// Selected per placeholder with a suffix: `{id}` keeps the value as the
+/// client sent it, `{id:raw}` percent-decodes it, and `{id:encoded}`
+/// percent-encodes every reserved character, which makes it safe in both a
+/// path segment and a query parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamEncoding {
+    /// Value as received in the request path (default)
+    AsReceived,
+    /// Percent-decoded value
+    Raw,
+    /// Value with every character but `A-Z a-z 0-9 - . _ ~` percent-encoded
+    Encoded,
+}
+
+impl ParamEncoding {
+    /// Splits the inside of an `internal_path` placeholder into parameter name
+    /// and encoding, e.g. `id:encoded`.
+    ///
+    /// Returns `None` for an unknown encoding.
+    pub fn parse_placeholder(placeholder: &str) -> Option<(&str, Self)> {
+        match placeholder.split_once(':') {
+            None => Some((placeholder, Self::AsReceived)),
+            Some((name, "raw")) => Some((name, Self::Raw)),
+            Some((name, "encoded")) => Some((name, Self::Encoded)),
+            Some(_) => None,
+        }
+    }
+
+    /// Applies the encoding to a value captured from the request path.
+    pub fn apply(self, captured: &str) -> String {
+        match self {
+            Self::AsReceived => captured.to_string(),
+            Self::Raw => percent_decode(captured),
+            Self::Encoded => percent_encode(&percent_decode(captured)),
+        }
+    }
 }
 
 /// A pre-compiled route pattern optimized for high-performance matching.
@@ -64,6 +130,41 @@ pub struct CompiledRoute {
     pub regex: Arc<Regex>,
     /// Ordered list of parameter names extracted from the pattern
     pub param_names: Vec<String>,
+    /// How often request paths matched or were tried against this route
+    pub stats: Arc<RouteMatchStats>,
+}
+
+impl CompiledRoute {
+    /// Returns the value of parameter `name` in `request_path`, or `None` if
+    /// the path does not match the route or the route has no such parameter.
+    pub fn param(&self, request_path: &str, name: &str) -> Option<String> {
+        let index = self.param_names.iter().position(|param| param == name)?;
+        let captures = self.regex.captures(request_path)?;
+        captures.get(index + 1).map(|value| value.as_str().to_string())
+    }
+}
+
+/// Match counters of a dynamic route.
+///
+/// A hit is a request path that matched the route. A miss is a request path
+/// that was tried against the route's regex without matching, which happens
+/// for every path that ends up matching a less specific route, or none.
+#[derive(Debug, Default)]
+pub struct RouteMatchStats {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl RouteMatchStats {
+    /// Returns how many request paths matched the route.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns how many request paths were tried against the route without matching.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
 }
 
 /// High-performance route matcher with optimized lookup strategies.
@@ -82,14 +183,15 @@ pub struct CompiledRoute {
 ///
 /// # Thread Safety
 ///
-/// All fields are immutable after construction, making the matcher safe to
-/// share across multiple worker threads without synchronization overhead.
+/// All fields are immutable after construction, apart from atomic match
+/// counters, making the matcher safe to share across multiple worker threads
+/// without locking.
 ///
 /// # Examples
 ///
 /// ```rust
 /// use kairos_rs::utils::route_matcher::RouteMatcher;
-/// use kairos_rs::models::router::{Router, Backend, Protocol};
+/// use kairos_rs::models::router::{Router, Backend};
 ///
 /// let routes = vec![
 ///     Router {
@@ -98,19 +200,14 @@ pub struct CompiledRoute {
 ///         external_path: "/users".to_string(),          // Static route
 ///         internal_path: "/v1/users".to_string(),
 ///         methods: vec!["GET".to_string()],
-///         auth_required: false,
 ///         backends: Some(vec![Backend {
 ///             host: "http://api".to_string(),
 ///             port: 8080,
 ///             weight: 1,
 ///             health_check_path: None,
+///             timeout_secs: None,
 ///         }]),
-///         load_balancing_strategy: Default::default(),
-///         retry: None,
-///         protocol: Protocol::Http,
-///         request_transformation: None,
-///         response_transformation: None,
-///         ai_policy: None,
+///         ..Default::default()
 ///     },
 ///     Router {
 ///         host: Some("http://api".to_string()),
@@ -118,19 +215,14 @@ pub struct CompiledRoute {
 ///         external_path: "/users/{id}".to_string(),     // Dynamic route
 ///         internal_path: "/v1/user/{id}".to_string(),
 ///         methods: vec!["GET".to_string()],
-///         auth_required: false,
 ///         backends: Some(vec![Backend {
 ///             host: "http://api".to_string(),
 ///             port: 8080,
 ///             weight: 1,
 ///             health_check_path: None,
+///             timeout_secs: None,
 ///         }]),
-///         load_balancing_strategy: Default::default(),
-///         retry: None,
-///         protocol: Protocol::Http,
-///         request_transformation: None,
-///         response_transformation: None,
-///         ai_policy: None,
+///         ..Default::default()
 ///     },
 /// ];
 ///
@@ -149,6 +241,8 @@ pub struct RouteMatcher {
     static_routes: AHashMap<String, Router>,
     /// Vector of compiled dynamic routes sorted by specificity (most specific first)
     dynamic_routes: Vec<CompiledRoute>,
+    /// How paths differing from a route only by a trailing slash are matched
+    trailing_slash: TrailingSlash,
 }
 
 impl RouteMatcher {
@@ -190,7 +284,7 @@ impl RouteMatcher {
     ///
     /// ```rust
     /// use kairos_rs::utils::route_matcher::RouteMatcher;
-    /// use kairos_rs::models::router::{Router, Backend, Protocol};
+    /// use kairos_rs::models::router::{Router, Backend};
     ///
     /// let routes = vec![
     ///     Router {
@@ -199,19 +293,14 @@ impl RouteMatcher {
     ///         external_path: "/health".to_string(),        // Static
     ///         internal_path: "/status".to_string(),
     ///         methods: vec!["GET".to_string()],
-    ///         auth_required: false,
     ///         backends: Some(vec![Backend {
     ///             host: "http://localhost".to_string(),
     ///             port: 8080,
     ///             weight: 1,
     ///             health_check_path: None,
+    ///             timeout_secs: None,
     ///         }]),
-    ///         load_balancing_strategy: Default::default(),
-    ///         retry: None,
-    ///         protocol: Protocol::Http,
-    ///         request_transformation: None,
-    ///         response_transformation: None,
-    ///         ai_policy: None,
+    ///         ..Default::default()
     ///     },
     ///     Router {
     ///         host: Some("http://localhost".to_string()),
@@ -219,19 +308,14 @@ impl RouteMatcher {
     ///         external_path: "/users/{id}".to_string(),    // Dynamic
     ///         internal_path: "/v1/user/{id}".to_string(),
     ///         methods: vec!["GET".to_string()],
-    ///         auth_required: false,
     ///         backends: Some(vec![Backend {
     ///             host: "http://localhost".to_string(),
     ///             port: 8080,
     ///             weight: 1,
     ///             health_check_path: None,
+    ///             timeout_secs: None,
     ///         }]),
-    ///         load_balancing_strategy: Default::default(),
-    ///         retry: None,
-    ///         protocol: Protocol::Http,
-    ///         request_transformation: None,
-    ///         response_transformation: None,
-    ///         ai_policy: None,
+    ///         ..Default::default()
     ///     },
     /// ];
     ///
@@ -245,30 +329,128 @@ impl RouteMatcher {
     /// - Malformed parameter syntax (e.g., `{unclosed` or `{empty}`)
     /// - Invalid parameter names (non-alphanumeric characters)
     /// - Regex compilation failures
+    ///
+    /// Returns `RouteMatchError::DuplicateRoute` if two static routes have
+    /// the same path, or two dynamic routes the same pattern apart from their
+    /// parameter names, like `/users/{id}` and `/users/{user_id}`. A static
+    /// route that a dynamic one would also match is not a duplicate: static
+    /// routes are always tried first.
     pub fn new(routes: Vec<Router>) -> Result<Self, RouteMatchError> {
-        let mut static_routes = AHashMap::default();
-        let mut dynamic_routes = Vec::with_capacity(routes.len());
+        Self::build(routes, false)
+    }
+
+    /// Creates a route matcher that tolerates duplicate routes.
+    ///
+    /// Like [`RouteMatcher::new`], but a route duplicating an earlier one
+    /// replaces it with a warning instead of failing, so the route defined
+    /// last wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RouteMatchError::InvalidPattern` or `RouteMatchError::RegexError`
+    /// for routes that cannot be compiled.
+    pub fn new_lenient(routes: Vec<Router>) -> Result<Self, RouteMatchError> {
+        Self::build(routes, true)
+    }
+
+    fn build(routes: Vec<Router>, lenient: bool) -> Result<Self, RouteMatchError> {
+        let mut static_routes: AHashMap<String, Router> = AHashMap::default();
+        let mut dynamic_routes: Vec<CompiledRoute> = Vec::with_capacity(routes.len());
+        // Index into `dynamic_routes` by regex, which ignores parameter names
+        let mut dynamic_patterns: AHashMap<String, usize> = AHashMap::default();
 
         for route in routes {
             if route.external_path.contains('{') {
                 // Dynamic route
                 let compiled = Self::compile_route(route)?;
-                dynamic_routes.push(compiled);
+                match dynamic_patterns.get(compiled.regex.as_str()) {
+                    Some(&index) => {
+                        Self::check_duplicate(
+                            &compiled.router.external_path,
+                            &dynamic_routes[index].router.external_path,
+                            lenient,
+                        )?;
+                        dynamic_routes[index] = compiled;
+                    }
+                    None => {
+                        dynamic_patterns.insert(compiled.regex.as_str().to_string(), dynamic_routes.len());
+                        dynamic_routes.push(compiled);
+                    }
+                }
             } else {
                 // Static route
+                if static_routes.contains_key(&route.external_path) {
+                    Self::check_duplicate(&route.external_path, &route.external_path, lenient)?;
+                }
                 static_routes.insert(route.external_path.clone(), route);
             }
         }
 
         // Sort dynamic routes by specificity (more parameters = more specific)
+        #[allow(clippy::unnecessary_sort_by)] // Descending order reads plainer as a comparator
         dynamic_routes.sort_by(|a, b| b.param_names.len().cmp(&a.param_names.len()));
 
         Ok(Self {
             static_routes,
             dynamic_routes,
+            trailing_slash: TrailingSlash::default(),
         })
     }
 
+    /// Sets how paths differing from a route only by a trailing slash are
+    /// matched. Matching is strict by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use kairos_rs::models::router::{Router, TrailingSlash};
+    /// use kairos_rs::utils::route_matcher::{RouteMatchError, RouteMatcher};
+    ///
+    /// let route: Router = serde_json::from_value(serde_json::json!({
+    ///     "host": "http://localhost",
+    ///     "port": 8080,
+    ///     "external_path": "/users",
+    ///     "internal_path": "/v1/users",
+    ///     "methods": ["GET"]
+    /// }))?;
+    ///
+    /// let matcher = RouteMatcher::new(vec![route.clone()])?;
+    /// assert!(matcher.find_match("/users/").is_err());
+    ///
+    /// let matcher = RouteMatcher::new(vec![route.clone()])?.with_trailing_slash(TrailingSlash::Ignore);
+    /// assert_eq!(matcher.find_match("/users/")?.1, "/v1/users");
+    ///
+    /// let matcher = RouteMatcher::new(vec![route])?.with_trailing_slash(TrailingSlash::Redirect);
+    /// assert_eq!(
+    ///     matcher.find_match("/users/").unwrap_err(),
+    ///     RouteMatchError::TrailingSlashRedirect { location: "/users".to_string() }
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    /// Returns how paths differing from a route only by a trailing slash are
+    /// matched.
+    pub fn trailing_slash(&self) -> TrailingSlash {
+        self.trailing_slash
+    }
+
+    /// Fails on a duplicate route, or only warns about it when `lenient`.
+    fn check_duplicate(path: &str, existing: &str, lenient: bool) -> Result<(), RouteMatchError> {
+        let error = RouteMatchError::DuplicateRoute {
+            path: path.to_string(),
+            existing: existing.to_string(),
+        };
+        if !lenient {
+            return Err(error);
+        }
+        warn!("{}; using the route defined last", error);
+        Ok(())
+    }
+
     /// Finds a matching route for the given request path and returns the transformed internal path.
     ///
     /// This method implements a two-phase matching strategy optimized for performance:
@@ -284,6 +466,9 @@ impl RouteMatcher {
     /// - `Ok((Router, String))` - Tuple containing the matched router configuration
     ///   and the transformed internal path with parameters substituted
     /// - `Err(RouteMatchError::NoMatch)` - No route matches the given path
+    /// - `Err(RouteMatchError::TrailingSlashRedirect)` - In
+    ///   [`TrailingSlash::Redirect`] mode, a route only matches the path with
+    ///   its trailing slash added or removed
     ///
     /// # Matching Algorithm
     ///
@@ -291,6 +476,9 @@ impl RouteMatcher {
     /// 2. **Dynamic Route Check**: If no static match, tries each dynamic route in specificity order
     /// 3. **Parameter Extraction**: For dynamic matches, extracts parameter values from the path
     /// 4. **Path Transformation**: Substitutes parameters into the internal path template
+    /// 5. **Trailing Slash**: Unless matching is [`TrailingSlash::Strict`], a path
+    ///    without any match is tried again with its trailing slash added or
+    ///    removed, so an exact match always takes precedence
     ///
     /// # Path Transformation Examples
     ///
@@ -311,7 +499,7 @@ impl RouteMatcher {
     ///
     /// ```rust
     /// # use kairos_rs::utils::route_matcher::RouteMatcher;
-    /// # use kairos_rs::models::router::{Router, Backend, Protocol};
+    /// # use kairos_rs::models::router::{Router, Backend};
     /// # let routes = vec![
     /// #     Router {
     /// #         host: Some("http://localhost".to_string()),
@@ -319,19 +507,14 @@ impl RouteMatcher {
     /// #         external_path: "/health".to_string(),
     /// #         internal_path: "/status".to_string(),
     /// #         methods: vec!["GET".to_string()],
-    /// #         auth_required: false,
     /// #         backends: Some(vec![Backend {
     /// #             host: "http://localhost".to_string(),
     /// #             port: 8080,
     /// #             weight: 1,
     /// #             health_check_path: None,
+    /// #             timeout_secs: None,
     /// #         }]),
-    /// #         load_balancing_strategy: Default::default(),
-    /// #         retry: None,
-    /// #         protocol: Protocol::Http,
-    /// #         request_transformation: None,
-    /// #         response_transformation: None,
-    /// #         ai_policy: None,
+    /// #         ..Default::default()
     /// #     },
     /// #     Router {
     /// #         host: Some("http://localhost".to_string()),
@@ -339,19 +522,14 @@ impl RouteMatcher {
     /// #         external_path: "/users/{id}".to_string(),
     /// #         internal_path: "/v1/user/{id}".to_string(),
     /// #         methods: vec!["GET".to_string()],
-    /// #         auth_required: false,
     /// #         backends: Some(vec![Backend {
     /// #             host: "http://localhost".to_string(),
     /// #             port: 8080,
     /// #             weight: 1,
     /// #             health_check_path: None,
+    /// #             timeout_secs: None,
     /// #         }]),
-    /// #         load_balancing_strategy: Default::default(),
-    /// #         retry: None,
-    /// #         protocol: Protocol::Http,
-    /// #         request_transformation: None,
-    /// #         response_transformation: None,
-    /// #         ai_policy: None,
+    /// #         ..Default::default()
     /// #     }
     /// # ];
     /// # let matcher = RouteMatcher::new(routes)?;
@@ -369,23 +547,27 @@ impl RouteMatcher {
     ///
     /// # Thread Safety
     ///
-    /// This method is safe to call concurrently from multiple threads as it only
-    /// reads immutable data structures and doesn't modify the matcher state.
+    /// This method is safe to call concurrently from multiple threads. Apart
+    /// from the atomic match counters of dynamic routes, it only reads
+    /// immutable data structures.
     pub fn find_match(&self, request_path: &str) -> Result<(Router, String), RouteMatchError> {
-        // First, try static routes (O(1) lookup)
-        if let Some(route) = self.static_routes.get(request_path) {
-            return Ok((route.clone(), route.internal_path.clone()));
+        if let Some(found) = self.lookup(request_path) {
+            return Ok(found);
         }
 
-        // Then, try dynamic routes
-        for compiled_route in &self.dynamic_routes {
-            if let Some(captures) = compiled_route.regex.captures(request_path) {
-                let transformed_path = self.transform_internal_path(
-                    &compiled_route.router.internal_path,
-                    &compiled_route.param_names,
-                    &captures,
-                );
-                return Ok((compiled_route.router.clone(), transformed_path));
+        if self.trailing_slash != TrailingSlash::Strict {
+            let alternate = match request_path.strip_suffix('/') {
+                Some(trimmed) if !trimmed.is_empty() => Some(trimmed.to_string()),
+                Some(_) => None,
+                None => Some(format!("{}/", request_path)),
+            };
+            if let Some(alternate) = alternate {
+                if let Some(found) = self.lookup(&alternate) {
+                    if self.trailing_slash == TrailingSlash::Redirect {
+                        return Err(RouteMatchError::TrailingSlashRedirect { location: alternate });
+                    }
+                    return Ok(found);
+                }
             }
         }
 
@@ -394,6 +576,53 @@ impl RouteMatcher {
         })
     }
 
+    /// Returns the route matching `request_path` exactly and the transformed
+    /// internal path.
+    fn lookup(&self, request_path: &str) -> Option<(Router, String)> {
+        // First, try static routes (O(1) lookup)
+        if let Some(route) = self.static_routes.get(request_path) {
+            return Some((route.clone(), route.internal_path.clone()));
+        }
+
+        // Then, try dynamic routes
+        for compiled_route in &self.dynamic_routes {
+            let Some(captures) = compiled_route.regex.captures(request_path) else {
+                compiled_route.stats.misses.fetch_add(1, Ordering::Relaxed);
+                continue;
+            };
+            compiled_route.stats.hits.fetch_add(1, Ordering::Relaxed);
+            let transformed_path = self.transform_internal_path(
+                &compiled_route.router.internal_path,
+                &compiled_route.param_names,
+                &captures,
+            );
+            return Some((compiled_route.router.clone(), transformed_path));
+        }
+
+        None
+    }
+
+    /// Returns the compiled form of the dynamic route with `external_path`.
+    ///
+    /// Static routes are matched by exact lookup and have no compiled form.
+    pub fn dynamic_route(&self, external_path: &str) -> Option<&CompiledRoute> {
+        self.dynamic_routes
+            .iter()
+            .find(|route| route.router.external_path == external_path)
+    }
+
+    /// Carries match counters over from `previous` for dynamic routes whose
+    /// pattern is unchanged, so a reload does not reset them.
+    pub fn inherit_stats(&mut self, previous: &RouteMatcher) {
+        for route in &mut self.dynamic_routes {
+            if let Some(old) = previous.dynamic_route(&route.router.external_path) {
+                if old.regex.as_str() == route.regex.as_str() {
+                    route.stats = old.stats.clone();
+                }
+            }
+        }
+    }
+
     /// Compiles a route pattern into a regex and extracts parameter names
     fn compile_route(route: Router) -> Result<CompiledRoute, RouteMatchError> {
         let param_names = Self::extract_parameter_names(&route.external_path);
@@ -406,6 +635,7 @@ impl RouteMatcher {
             router: route,
             regex: Arc::new(regex),
             param_names,
+            stats: Arc::new(RouteMatchStats::default()),
         })
     }
 
@@ -483,21 +713,39 @@ impl RouteMatcher {
     }
 
     /// Transforms the internal path by replacing parameters with captured values
+    ///
+    /// Placeholders may carry a [`ParamEncoding`] suffix; placeholders that do
+    /// not name a captured parameter are left unchanged.
     fn transform_internal_path(
         &self,
         internal_pattern: &str,
         param_names: &[String],
         captures: &regex::Captures,
     ) -> String {
-        let mut result = internal_pattern.to_string();
+        let mut result = String::with_capacity(internal_pattern.len());
+        let mut rest = internal_pattern;
+
+        while let Some(open) = rest.find('{') {
+            result.push_str(&rest[..open]);
+            let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+                rest = &rest[open..];
+                break;
+            };
 
-        for (i, param_name) in param_names.iter().enumerate() {
-            if let Some(capture) = captures.get(i + 1) {
-                let placeholder = format!("{{{}}}", param_name);
-                result = result.replace(&placeholder, capture.as_str());
+            let placeholder = &rest[open + 1..close];
+            let value = ParamEncoding::parse_placeholder(placeholder).and_then(|(name, encoding)| {
+                let index = param_names.iter().position(|param| param == name)?;
+                let capture = captures.get(index + 1)?;
+                Some(encoding.apply(capture.as_str()))
+            });
+            match value {
+                Some(value) => result.push_str(&value),
+                None => result.push_str(&rest[open..=close]),
             }
+            rest = &rest[close + 1..];
         }
 
+        result.push_str(rest);
         result
     }
 }
@@ -0,0 +1,164 @@
+//! Contention-free counter for hot metrics.
+//!
+//! Every request bumps a handful of gateway-wide counters. With a single
+//! atomic per counter, all worker threads write the same cache line, which
+//! bounces between cores and serializes them under high concurrency. A
+//! [`ShardedCounter`] spreads the increments over per-thread shards, each on
+//! its own cache line, and only sums the shards when the value is read,
+//! which happens on scrape.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Upper bound on the number of shards, whatever the core count.
+const MAX_SHARDS: usize = 64;
+
+/// Source of the shard index assigned to each thread on first use.
+static NEXT_THREAD_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Shard index of the current thread, assigned round-robin.
+    static THREAD_INDEX: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Returns the current thread's index, assigning one on first use.
+fn thread_index() -> usize {
+    THREAD_INDEX.with(|index| match index.get() {
+        Some(index) => index,
+        None => {
+            let assigned = NEXT_THREAD_INDEX.fetch_add(1, Ordering::Relaxed);
+            index.set(Some(assigned));
+            assigned
+        }
+    })
+}
+
+/// One shard, padded to its own cache line so shards written by different
+/// cores never share one.
+#[derive(Debug, Default)]
+#[repr(align(128))]
+struct Shard(AtomicU64);
+
+/// Monotonic counter sharded across threads.
+///
+/// Exposes the subset of the [`AtomicU64`] API the metrics use, so it is a
+/// drop-in replacement for gateway-wide counters: [`fetch_add`](Self::fetch_add)
+/// is a single uncontended atomic add on the calling thread's shard, and
+/// [`load`](Self::load) sums every shard. A read may miss increments racing
+/// with it, like a read of any counter updated concurrently.
+///
+/// # Examples
+///
+/// ```rust
+/// use kairos_rs::utils::sharded_counter::ShardedCounter;
+/// use std::sync::atomic::Ordering;
+/// use std::sync::Arc;
+///
+/// let counter = Arc::new(ShardedCounter::new(0));
+/// let threads: Vec<_> = (0..4)
+///     .map(|_| {
+///         let counter = counter.clone();
+///         std::thread::spawn(move || {
+///             for _ in 0..1000 {
+///                 counter.fetch_add(1, Ordering::Relaxed);
+///             }
+///         })
+///     })
+///     .collect();
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+///
+/// assert_eq!(counter.load(Ordering::Relaxed), 4000);
+/// ```
+#[derive(Debug)]
+pub struct ShardedCounter {
+    shards: Box<[Shard]>,
+}
+
+impl ShardedCounter {
+    /// Creates a counter starting at `value`, with one shard per core up to
+    /// a fixed maximum.
+    pub fn new(value: u64) -> Self {
+        let cores = std::thread::available_parallelism().map_or(8, |cores| cores.get());
+        Self::with_shards(value, cores)
+    }
+
+    /// Creates a counter starting at `value` with `shards` shards, rounded
+    /// up to a power of two and capped.
+    pub fn with_shards(value: u64, shards: usize) -> Self {
+        let shards = shards.clamp(1, MAX_SHARDS).next_power_of_two();
+        let counter = Self {
+            shards: (0..shards).map(|_| Shard::default()).collect(),
+        };
+        counter.shards[0].0.store(value, Ordering::Relaxed);
+        counter
+    }
+
+    /// Adds `value` to the counter.
+    ///
+    /// Unlike [`AtomicU64::fetch_add`], the previous value is not returned,
+    /// since computing it would mean reading every shard.
+    pub fn fetch_add(&self, value: u64, order: Ordering) {
+        let shard = thread_index() & (self.shards.len() - 1);
+        self.shards[shard].0.fetch_add(value, order);
+    }
+
+    /// Returns the sum of all shards.
+    pub fn load(&self, order: Ordering) -> u64 {
+        self.shards
+            .iter()
+            .fold(0u64, |sum, shard| sum.wrapping_add(shard.0.load(order)))
+    }
+
+    /// Sets the counter to `value`.
+    ///
+    /// Increments racing with the reset may be kept or lost.
+    pub fn store(&self, value: u64, order: Ordering) {
+        for shard in &self.shards[1..] {
+            shard.0.store(0, order);
+        }
+        self.shards[0].0.store(value, order);
+    }
+}
+
+impl Default for ShardedCounter {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_shard_count_is_a_capped_power_of_two() {
+        assert_eq!(ShardedCounter::with_shards(0, 0).shards.len(), 1);
+        assert_eq!(ShardedCounter::with_shards(0, 6).shards.len(), 8);
+        assert_eq!(ShardedCounter::with_shards(0, 1000).shards.len(), MAX_SHARDS);
+    }
+
+    #[test]
+    fn test_concurrent_increments_are_all_counted() {
+        let counter = Arc::new(ShardedCounter::with_shards(5, 4));
+        let threads: Vec<_> = (0..16)
+            .map(|_| {
+                let counter = counter.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10_000 {
+                        counter.fetch_add(2, Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(counter.load(Ordering::Relaxed), 5 + 16 * 10_000 * 2);
+
+        counter.store(7, Ordering::Relaxed);
+        assert_eq!(counter.load(Ordering::Relaxed), 7);
+    }
+}
@@ -0,0 +1,114 @@
+//! X-Accel-Redirect tests
+//!
+//! Verifies that routes with `x_accel_redirect` serve the resource named by
+//! an upstream `X-Accel-Redirect` header instead of the response carrying it,
+//! and that other routes pass the header through untouched.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream whose `/download` answers with an
+/// `X-Accel-Redirect` to `/protected/report.txt`, and whose protected file
+/// reports the method it was fetched with.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new()
+            .route(
+                "/download",
+                web::to(|| async {
+                    HttpResponse::Ok()
+                        .insert_header(("X-Accel-Redirect", "/protected/report.txt"))
+                        .body("access granted")
+                }),
+            )
+            .route(
+                "/broken",
+                web::to(|| async {
+                    HttpResponse::Ok()
+                        .insert_header(("X-Accel-Redirect", "protected/report.txt"))
+                        .finish()
+                }),
+            )
+            .route(
+                "/protected/report.txt",
+                web::to(|req: HttpRequest| async move {
+                    HttpResponse::Ok()
+                        .content_type("text/plain")
+                        .body(format!("report fetched with {}", req.method()))
+                }),
+            )
+    })
+}
+
+fn route(port: u16, internal_path: &str, x_accel_redirect: bool) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/files".to_string(),
+        internal_path: internal_path.to_string(),
+        methods: vec!["GET".to_string(), "POST".to_string()],
+        x_accel_redirect,
+        ..Default::default()
+    }
+}
+
+async fn call(route: Router, req: test::TestRequest) -> actix_web::dev::ServiceResponse {
+    let handler = RouteHandler::new(vec![route], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+    test::call_service(&app, req.uri("/files").to_request()).await
+}
+
+#[actix_web::test]
+async fn test_redirect_target_is_served_instead() {
+    let route = route(spawn_upstream(), "/download", true);
+    let resp = call(route, test::TestRequest::get()).await;
+
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("X-Accel-Redirect").is_none());
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "text/plain");
+    assert_eq!(test::read_body(resp).await, "report fetched with GET");
+}
+
+#[actix_web::test]
+async fn test_redirect_target_is_fetched_with_get() {
+    let route = route(spawn_upstream(), "/download", true);
+    let req = test::TestRequest::post()
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload(r#"{"id": 7}"#);
+    let resp = call(route, req).await;
+
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "report fetched with GET");
+}
+
+#[actix_web::test]
+async fn test_header_passes_through_when_disabled() {
+    let route = route(spawn_upstream(), "/download", false);
+    let resp = call(route, test::TestRequest::get()).await;
+
+    assert_eq!(
+        resp.headers().get("X-Accel-Redirect").unwrap(),
+        "/protected/report.txt"
+    );
+    assert_eq!(test::read_body(resp).await, "access granted");
+}
+
+#[actix_web::test]
+async fn test_relative_redirect_target_is_rejected() {
+    let route = route(spawn_upstream(), "/broken", true);
+    let resp = call(route, test::TestRequest::get()).await;
+
+    assert_eq!(resp.status(), 502);
+}
@@ -0,0 +1,185 @@
+//! Accept-Encoding negotiation tests
+//!
+//! Verifies that routes with `accept_encoding: strip` ask the backend for
+//! uncompressed responses and leave compression to the gateway, that routes
+//! with `accept_encoding: passthrough` forward the client's header and are
+//! never compressed by the gateway, that compressed backend responses are
+//! relayed byte for byte, and that routes without a mode keep
+//! forwarding the header with the gateway compressing plain responses.
+
+mod common;
+
+use actix_web::{middleware::Compress, test, web, App, HttpRequest, HttpResponse, HttpServer};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use kairos_rs::models::router::{AcceptEncodingMode, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+/// Uncompressed body served by the mock upstream, large enough to be worth
+/// compressing.
+fn payload() -> String {
+    "kairos gateway payload ".repeat(200)
+}
+
+/// Starts a mock upstream that never compresses and reports the
+/// `Accept-Encoding` it received in `X-Seen-Accept-Encoding`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let seen = req
+                .headers()
+                .get("accept-encoding")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("none")
+                .to_string();
+            HttpResponse::Ok()
+                .insert_header(("X-Seen-Accept-Encoding", seen))
+                .content_type("text/plain")
+                .body(payload())
+        }))
+    })
+}
+
+/// Gzip-compressed [`payload`].
+fn gzipped_payload() -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(payload().as_bytes()).unwrap();
+    encoder.finish().unwrap()
+}
+
+/// Starts a mock upstream that always answers with [`gzipped_payload`] and
+/// `Content-Encoding: gzip`.
+fn spawn_gzip_upstream() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = HttpServer::new(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let seen = req
+                .headers()
+                .get("accept-encoding")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("none")
+                .to_string();
+            HttpResponse::Ok()
+                .insert_header(("X-Seen-Accept-Encoding", seen))
+                .insert_header(("Content-Encoding", "gzip"))
+                .content_type("text/plain")
+                .body(gzipped_payload())
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+    actix_web::rt::spawn(server);
+    port
+}
+
+fn route(port: u16, accept_encoding: Option<AcceptEncodingMode>) -> Router {
+    let mut route: Router = serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": "/api/report",
+        "internal_path": "/report",
+        "methods": ["GET"]
+    }))
+    .unwrap();
+    route.accept_encoding = accept_encoding;
+    route
+}
+
+/// Requests `/api/report` with `Accept-Encoding: gzip` through the Compress
+/// middleware, returning the upstream's view of the header, the response's
+/// `Content-Encoding` and its body.
+async fn fetch(accept_encoding: Option<AcceptEncodingMode>) -> (String, Option<String>, Vec<u8>) {
+    fetch_from(spawn_upstream(), accept_encoding).await
+}
+
+/// Like [`fetch`], against the upstream listening on `port`.
+async fn fetch_from(
+    port: u16,
+    accept_encoding: Option<AcceptEncodingMode>,
+) -> (String, Option<String>, Vec<u8>) {
+    let handler = RouteHandler::new(vec![route(port, accept_encoding)], 5);
+    let app = test::init_service(
+        App::new()
+            .wrap(Compress::default())
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/report")
+        .insert_header(("Accept-Encoding", "gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let seen = resp
+        .headers()
+        .get("x-seen-accept-encoding")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    let encoding = resp
+        .headers()
+        .get("content-encoding")
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = test::read_body(resp).await.to_vec();
+    (seen, encoding, body)
+}
+
+#[actix_web::test]
+async fn test_strip_leaves_compression_to_gateway() {
+    let (seen, encoding, body) = fetch(Some(AcceptEncodingMode::Strip)).await;
+    assert_eq!(seen, "identity");
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    assert!(body.len() < payload().len());
+}
+
+#[actix_web::test]
+async fn test_passthrough_skips_gateway_compression() {
+    let (seen, encoding, body) = fetch(Some(AcceptEncodingMode::Passthrough)).await;
+    assert_eq!(seen, "gzip");
+    assert_eq!(encoding.as_deref(), Some("identity"));
+    assert_eq!(body, payload().as_bytes());
+}
+
+#[actix_web::test]
+async fn test_passthrough_relays_compressed_body_unchanged() {
+    let (seen, encoding, body) =
+        fetch_from(spawn_gzip_upstream(), Some(AcceptEncodingMode::Passthrough)).await;
+    assert_eq!(seen, "gzip");
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    assert_eq!(body, gzipped_payload());
+
+    let mut decoded = String::new();
+    GzDecoder::new(body.as_slice()).read_to_string(&mut decoded).unwrap();
+    assert_eq!(decoded, payload());
+}
+
+#[actix_web::test]
+async fn test_default_forwards_header_and_compresses_plain_responses() {
+    let (seen, encoding, _) = fetch(None).await;
+    assert_eq!(seen, "gzip");
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+}
+
+#[actix_web::test]
+async fn test_accept_encoding_mode_parses() {
+    let parse = |mode: &str| {
+        serde_json::from_value::<Router>(serde_json::json!({
+            "host": "http://127.0.0.1",
+            "port": 8080,
+            "external_path": "/api",
+            "internal_path": "/api",
+            "methods": ["GET"],
+            "accept_encoding": mode
+        }))
+        .map(|route| route.accept_encoding)
+    };
+    assert_eq!(parse("strip").unwrap(), Some(AcceptEncodingMode::Strip));
+    assert_eq!(parse("passthrough").unwrap(), Some(AcceptEncodingMode::Passthrough));
+    assert!(parse("gzip").is_err());
+}
@@ -0,0 +1,116 @@
+//! Backend pool tests
+//!
+//! Verifies that routes referencing a shared `backend_pools` entry are
+//! resolved to its backends at load time and that unknown pool names fail
+//! validation.
+
+use kairos_rs::config::validation::ConfigValidator;
+use kairos_rs::models::settings::Settings;
+
+fn settings(routers: serde_json::Value) -> Settings {
+    serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "backend_pools": {
+            "users": [
+                { "host": "https://users-a.internal", "port": 8443 },
+                { "host": "https://users-b.internal", "port": 8443, "weight": 2 }
+            ]
+        },
+        "routers": routers
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_routes_share_pool_backends() {
+    let mut settings = settings(serde_json::json!([
+        {
+            "backend_pool": "users",
+            "external_path": "/api/users",
+            "internal_path": "/users",
+            "methods": ["GET"]
+        },
+        {
+            "backend_pool": "users",
+            "external_path": "/api/users/{id}",
+            "internal_path": "/users/{id}",
+            "methods": ["GET", "PUT"]
+        }
+    ]));
+
+    ConfigValidator::resolve_backend_pools(&mut settings).unwrap();
+
+    for router in &settings.routers {
+        let backends = router.get_backends();
+        let hosts: Vec<_> = backends.iter().map(|b| (b.host.as_str(), b.weight)).collect();
+        assert_eq!(
+            hosts,
+            [("https://users-a.internal", 1), ("https://users-b.internal", 2)]
+        );
+        assert!(router.validate().is_ok());
+    }
+    assert!(settings.validate().is_ok());
+    assert!(ConfigValidator::validate_comprehensive(&settings).is_valid);
+}
+
+#[test]
+fn test_pool_replaces_inline_backends() {
+    let mut settings = settings(serde_json::json!([{
+        "backend_pool": "users",
+        "backends": [{ "host": "https://legacy.internal", "port": 443 }],
+        "external_path": "/api/users",
+        "internal_path": "/users",
+        "methods": ["GET"]
+    }]));
+
+    ConfigValidator::resolve_backend_pools(&mut settings).unwrap();
+
+    assert_eq!(settings.routers[0].get_backends().len(), 2);
+    assert_eq!(settings.routers[0].get_backends()[0].host, "https://users-a.internal");
+}
+
+#[test]
+fn test_unknown_pool_fails_validation() {
+    let mut settings = settings(serde_json::json!([
+        {
+            "backend_pool": "users",
+            "external_path": "/api/users",
+            "internal_path": "/users",
+            "methods": ["GET"]
+        },
+        {
+            "backend_pool": "orders",
+            "external_path": "/api/orders",
+            "internal_path": "/orders",
+            "methods": ["GET"]
+        }
+    ]));
+
+    let result = ConfigValidator::validate_comprehensive(&settings);
+    assert!(!result.is_valid);
+    assert_eq!(result.errors.len(), 1);
+    assert!(result.errors[0].contains("Unknown backend pool 'orders'"));
+
+    let error = ConfigValidator::resolve_backend_pools(&mut settings).unwrap_err();
+    assert!(error.contains("/api/orders") && error.contains("'orders'"));
+    // Known pools are still resolved
+    assert_eq!(settings.routers[0].get_backends().len(), 2);
+    assert!(settings.validate().is_err());
+}
+
+#[test]
+fn test_unused_pool_is_reported() {
+    let settings = settings(serde_json::json!([{
+        "backends": [{ "host": "https://orders.internal", "port": 443 }],
+        "external_path": "/api/orders",
+        "internal_path": "/orders",
+        "methods": ["GET"]
+    }]));
+
+    let result = ConfigValidator::validate_comprehensive(&settings);
+    assert!(result.is_valid);
+    assert!(result
+        .recommendations
+        .iter()
+        .any(|r| r.contains("Backend pool 'users'")));
+}
@@ -0,0 +1,109 @@
+//! Per-backend request metrics tests
+//!
+//! Verifies that every attempt sent to a backend of a load-balanced pool is
+//! counted against that backend, that connection errors and 5xx responses
+//! are counted as its errors, and that both counters are exported.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::routes::metrics::{self, BackendRequestStats, MetricsCollector};
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+
+/// Starts a mock upstream answering every request with `status`.
+fn spawn_upstream(status: u16) -> u16 {
+    common::spawn_server(move || {
+        App::new().default_service(web::to(move || async move {
+            HttpResponse::build(actix_web::http::StatusCode::from_u16(status).unwrap()).finish()
+        }))
+    })
+}
+
+/// Returns a port nothing listens on.
+fn unused_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+#[actix_web::test]
+async fn test_requests_and_errors_are_counted_per_backend() {
+    let ports = [spawn_upstream(200), spawn_upstream(500), unused_port()];
+    let backends: Vec<_> = ports
+        .iter()
+        .map(|port| serde_json::json!({"host": "http://127.0.0.1", "port": port}))
+        .collect();
+    let route: Router = serde_json::from_value(serde_json::json!({
+        "backends": backends,
+        "external_path": "/api/items",
+        "internal_path": "/items",
+        "methods": ["GET"],
+        "load_balancing_strategy": "round_robin"
+    }))
+    .unwrap();
+    let handler = RouteHandler::new(vec![route], 5);
+    let collector = MetricsCollector::default();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for _ in 0..6 {
+        let req = test::TestRequest::get().uri("/api/items").to_request();
+        test::call_service(&app, req).await;
+    }
+
+    let services: Vec<_> = ports
+        .iter()
+        .map(|port| format!("http://127.0.0.1:{}", port))
+        .collect();
+    let mut expected = vec![
+        (services[0].clone(), BackendRequestStats { requests: 2, errors: 0 }),
+        (services[1].clone(), BackendRequestStats { requests: 2, errors: 2 }),
+        (services[2].clone(), BackendRequestStats { requests: 2, errors: 2 }),
+    ];
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(collector.backend_requests(), expected);
+
+    for accept in ["text/plain", "application/openmetrics-text"] {
+        let req = test::TestRequest::get()
+            .uri("/metrics")
+            .insert_header(("Accept", accept))
+            .to_request();
+        let body = test::read_body(test::call_service(&app, req).await).await;
+        let text = String::from_utf8_lossy(&body);
+        for (service, stats) in &expected {
+            let requests = format!(
+                "kairos_backend_requests_total{{service=\"{}\"}} {}",
+                service, stats.requests
+            );
+            let errors = format!(
+                "kairos_backend_errors_total{{service=\"{}\"}} {}",
+                service, stats.errors
+            );
+            assert!(text.contains(&requests), "{text}");
+            assert!(text.contains(&errors), "{text}");
+        }
+    }
+}
+
+#[actix_web::test]
+async fn test_backend_metrics_absent_before_any_request() {
+    let collector = MetricsCollector::default();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    assert!(collector.backend_requests().is_empty());
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    assert!(!String::from_utf8_lossy(&body).contains("kairos_backend_requests_total"));
+}
@@ -0,0 +1,153 @@
+//! Per-backend timeout tests
+//!
+//! Verifies that `Backend::timeout_secs` overrides the gateway-wide timeout
+//! for the selected backend, so backends on the same route can have
+//! different tolerances for slow responses.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+use std::time::Duration;
+
+/// Starts a mock upstream that answers every request after `delay`.
+fn spawn_slow_upstream(delay: Duration) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = HttpServer::new(move || {
+        App::new().default_service(web::to(move || async move {
+            tokio::time::sleep(delay).await;
+            HttpResponse::Ok().body("slow response")
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    port
+}
+
+fn backend(port: u16, timeout_secs: Option<u64>) -> Backend {
+    Backend {
+        host: "http://127.0.0.1".to_string(),
+        port,
+        weight: 1,
+        health_check_path: None,
+        timeout_secs,
+    }
+}
+
+fn route(backends: Vec<Backend>) -> Router {
+    Router {
+        backends: Some(backends),
+        external_path: "/archive".to_string(),
+        internal_path: "/archive".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_backend_timeout_overrides_global_timeout_per_backend() {
+    let fast_port = spawn_slow_upstream(Duration::from_millis(1500));
+    let archive_port = spawn_slow_upstream(Duration::from_millis(1500));
+
+    // Round robin selects the fast backend first, then the archival one
+    let routes = vec![route(vec![
+        backend(fast_port, Some(1)),
+        backend(archive_port, Some(5)),
+    ])];
+    let handler = RouteHandler::new(routes, 30);
+
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    // The fast backend trips its 1s timeout even though the global timeout is 30s
+    let req = test::TestRequest::get().uri("/archive").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 504, "fast backend should time out");
+
+    // The archival backend on the same route tolerates the same delay
+    let req = test::TestRequest::get().uri("/archive").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200, "archival backend should allow the longer wait");
+
+    let body = test::read_body(resp).await;
+    assert_eq!(body, "slow response");
+}
+
+#[actix_web::test]
+async fn test_backend_without_timeout_uses_global_timeout() {
+    let port = spawn_slow_upstream(Duration::from_millis(1500));
+
+    let routes = vec![route(vec![backend(port, None)])];
+    let handler = RouteHandler::new(routes, 1);
+
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/archive").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 504);
+}
+
+#[actix_web::test]
+async fn test_backend_zero_timeout_is_rejected() {
+    let invalid = backend(8080, Some(0));
+    assert!(invalid.validate().is_err());
+
+    let valid = backend(8080, Some(10));
+    assert!(valid.validate().is_ok());
+}
+
+#[actix_web::test]
+async fn test_timeout_response_body() {
+    let port = spawn_slow_upstream(Duration::from_millis(1500));
+
+    let routes = vec![route(vec![backend(port, Some(1))])];
+    let handler = RouteHandler::new(routes, 30)
+        .with_timeout_message("The service took too long to respond");
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/archive").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 504);
+    let request_id = resp.headers().get("x-request-id").unwrap().to_str().unwrap().to_string();
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "The service took too long to respond");
+    assert_eq!(body["type"], "timeout");
+    assert_eq!(body["request_id"], request_id.as_str());
+    assert!(uuid::Uuid::parse_str(&request_id).is_ok());
+}
+
+#[actix_web::test]
+async fn test_default_timeout_message_hides_backend() {
+    let port = spawn_slow_upstream(Duration::from_millis(1500));
+
+    let routes = vec![route(vec![backend(port, None)])];
+    let handler = RouteHandler::new(routes, 1);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/archive").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 504);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "Request timeout after 1s");
+    assert!(!body["error"].as_str().unwrap().contains("127.0.0.1"));
+}
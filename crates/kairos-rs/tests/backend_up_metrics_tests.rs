@@ -0,0 +1,137 @@
+//! Backend availability metrics tests
+//!
+//! Verifies that `kairos_backend_up` reports 0 for backends whose circuit
+//! breaker is open and 1 for healthy ones, labelled with the routes using them.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{Backend, CircuitBreakerSettings, Router};
+use kairos_rs::routes::{http, metrics};
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+
+/// Starts a mock upstream that answers every request with `ok`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Ok().body("ok") }))
+    })
+}
+
+/// Returns a local port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn backend(port: u16) -> Backend {
+    Backend {
+        host: "http://127.0.0.1".to_string(),
+        port,
+        weight: 1,
+        health_check_path: None,
+        timeout_secs: None,
+    }
+}
+
+fn route(path: &str, backends: Vec<Backend>) -> Router {
+    Router {
+        backends: Some(backends),
+        external_path: path.to_string(),
+        internal_path: path.to_string(),
+        methods: vec!["GET".to_string()],
+        circuit_breaker: Some(CircuitBreakerSettings {
+            failure_threshold: 1,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_down_backend_reports_zero() {
+    let healthy = spawn_upstream();
+    let down = unused_port();
+    let routes = vec![
+        route("/api/users", vec![backend(healthy), backend(down)]),
+        route("/api/orders", vec![backend(healthy)]),
+    ];
+    let handler = RouteHandler::new(routes, 5);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(handler.clone()))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    // Round robin sends one of these to the backend that is down
+    for _ in 0..2 {
+        let req = test::TestRequest::get().uri("/api/users").to_request();
+        test::call_service(&app, req).await;
+    }
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let text = String::from_utf8_lossy(&body);
+    assert!(text.contains("# TYPE kairos_backend_up gauge"), "{text}");
+    assert!(
+        text.contains(&format!(
+            "kairos_backend_up{{host=\"http://127.0.0.1\",port=\"{}\",routes=\"/api/users,/api/orders\"}} 1",
+            healthy
+        )),
+        "{text}"
+    );
+    assert!(
+        text.contains(&format!(
+            "kairos_backend_up{{host=\"http://127.0.0.1\",port=\"{}\",routes=\"/api/users\"}} 0",
+            down
+        )),
+        "{text}"
+    );
+
+    // OpenMetrics output carries the same samples
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Accept", "application/openmetrics-text"))
+        .to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let text = String::from_utf8_lossy(&body);
+    assert!(
+        text.contains(&format!("port=\"{}\",routes=\"/api/users\"}} 0", down)),
+        "{text}"
+    );
+}
+
+#[actix_web::test]
+async fn test_route_label_is_bounded() {
+    let port = unused_port();
+    let routes: Vec<Router> = (0..7)
+        .map(|i| route(&format!("/api/r{}", i), vec![backend(port)]))
+        .collect();
+    let handler = RouteHandler::new(routes, 5);
+
+    let statuses = handler.backend_statuses();
+    assert_eq!(statuses.len(), 1);
+    assert_eq!(statuses[0].routes.len(), 7);
+    assert!(statuses[0].up);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(handler))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let text = String::from_utf8_lossy(&body);
+    assert!(
+        text.contains("routes=\"/api/r0,/api/r1,/api/r2,/api/r3,/api/r4,+2 more\"} 1"),
+        "{text}"
+    );
+}
@@ -0,0 +1,141 @@
+//! Body routing tests
+//!
+//! Verifies that routes with `body_routing` send requests to the backend
+//! mapped to a JSON body field, fall back to load balancing for unmatched,
+//! oversized or non-JSON bodies, and that the configuration is validated.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{BodyRouting, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream that answers every request with `name`.
+fn spawn_upstream(name: &'static str) -> u16 {
+    common::spawn_server(move || {
+        App::new().default_service(web::to(move || async move { HttpResponse::Ok().body(name) }))
+    })
+}
+
+fn route(ports: &[u16], max_body_bytes: usize) -> Router {
+    let backends: Vec<_> = ports
+        .iter()
+        .map(|port| serde_json::json!({"host": "http://127.0.0.1", "port": port}))
+        .collect();
+    serde_json::from_value(serde_json::json!({
+        "backends": backends,
+        "external_path": "/webhooks",
+        "internal_path": "/events",
+        "methods": ["POST"],
+        "load_balancing_strategy": "round_robin",
+        "body_routing": {
+            "field": "$.event_type",
+            "backends": {"invoice.created": 0, "payment.succeeded": 1},
+            "max_body_bytes": max_body_bytes
+        }
+    }))
+    .unwrap()
+}
+
+#[actix_web::test]
+async fn test_requests_follow_body_field() {
+    let ports = [spawn_upstream("invoices"), spawn_upstream("payments")];
+    let handler = RouteHandler::new(vec![route(&ports, 1024)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for (event, expected) in [
+        ("payment.succeeded", "payments"),
+        ("payment.succeeded", "payments"),
+        ("invoice.created", "invoices"),
+        ("invoice.created", "invoices"),
+    ] {
+        let req = test::TestRequest::post()
+            .uri("/webhooks")
+            .set_json(serde_json::json!({"event_type": event, "id": 7}))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(test::read_body(resp).await, expected, "{}", event);
+    }
+}
+
+#[actix_web::test]
+async fn test_unrouted_bodies_are_load_balanced() {
+    let ports = [spawn_upstream("invoices"), spawn_upstream("payments")];
+    let handler = RouteHandler::new(vec![route(&ports, 64)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let oversized = serde_json::json!({"event_type": "payment.succeeded", "padding": "x".repeat(100)});
+    let bodies = [
+        serde_json::json!({"event_type": "customer.deleted"}).to_string(),
+        "event_type=payment.succeeded".to_string(),
+        oversized.to_string(),
+        oversized.to_string(),
+    ];
+
+    let mut served = Vec::new();
+    for body in bodies {
+        let req = test::TestRequest::post()
+            .uri("/webhooks")
+            .set_payload(body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        served.push(test::read_body(resp).await);
+    }
+    served.sort();
+    assert_eq!(served, vec!["invoices", "invoices", "payments", "payments"]);
+}
+
+#[actix_web::test]
+async fn test_body_routing_field_paths() {
+    let routing = |field: &str| BodyRouting {
+        field: field.to_string(),
+        backends: [("2".to_string(), 1), ("true".to_string(), 0)].into_iter().collect(),
+        max_body_bytes: 1024,
+    };
+    let body = br#"{"data": {"items": [{"version": 1}, {"version": 2}], "live": true}}"#;
+
+    assert_eq!(routing("$.data.items[1].version").backend_index(body), Some(1));
+    assert_eq!(routing("$.data.live").backend_index(body), Some(0));
+    assert_eq!(routing("$.data.items[0].version").backend_index(body), None);
+    assert_eq!(routing("$.data.items[5].version").backend_index(body), None);
+    assert_eq!(routing("$.data").backend_index(body), None);
+}
+
+#[actix_web::test]
+async fn test_body_routing_validation() {
+    let routing: BodyRouting = serde_json::from_value(serde_json::json!({
+        "field": "$.event_type",
+        "backends": {"invoice.created": 0, "payment.succeeded": 1}
+    }))
+    .unwrap();
+    assert_eq!(routing.max_body_bytes, 65536);
+    assert!(routing.validate(Some(2)).is_ok());
+    assert!(routing.validate(None).is_ok());
+
+    let err = routing.validate(Some(1)).unwrap_err();
+    assert!(err.contains("maps to backend 1"), "{err}");
+
+    for field in ["event_type", "$.", "$.items[x]", "$.items[0", "$..type"] {
+        let invalid = BodyRouting {
+            field: field.to_string(),
+            ..routing.clone()
+        };
+        assert!(invalid.validate(None).is_err(), "{field}");
+    }
+
+    let mut router = route(&[8080], 1024);
+    let err = router.validate().unwrap_err();
+    assert!(err.contains("Body routing validation failed"), "{err}");
+
+    router.body_routing.as_mut().unwrap().backends.remove("payment.succeeded");
+    assert!(router.validate().is_ok());
+}
@@ -0,0 +1,82 @@
+//! Panic recovery tests
+//!
+//! Verifies that `CatchPanic` answers a request whose handler panics with a
+//! 500 carrying a request ID instead of dropping the connection, keeps the
+//! panic message out of the response, and leaves the server able to serve
+//! further requests.
+
+use actix_web::{http::StatusCode, test, web, App, HttpResponse, HttpServer};
+use kairos_rs::middleware::catch_panic::CatchPanic;
+use std::net::TcpListener;
+
+async fn panicking() -> HttpResponse {
+    panic!("transform received unexpected input");
+}
+
+fn app() -> App<
+    impl actix_web::dev::ServiceFactory<
+        actix_web::dev::ServiceRequest,
+        Config = (),
+        Response = actix_web::dev::ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .wrap(CatchPanic)
+        .route("/panic", web::get().to(panicking))
+        .route("/ok", web::get().to(HttpResponse::Ok))
+}
+
+#[actix_web::test]
+async fn test_panic_becomes_internal_error() {
+    let app = test::init_service(app()).await;
+
+    let req = test::TestRequest::get().uri("/panic").to_request();
+    let err = test::try_call_service(&app, req)
+        .await
+        .expect_err("panic should surface as an error");
+    let resp = err.error_response();
+    assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let header_id = resp
+        .headers()
+        .get("X-Request-ID")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .expect("response should carry a request ID");
+
+    let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["type"], "internal");
+    assert_eq!(body["request_id"], header_id.as_str());
+    assert!(!body.to_string().contains("unexpected input"), "{body}");
+
+    let req = test::TestRequest::get().uri("/ok").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_client_gets_500_instead_of_connection_reset() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = HttpServer::new(app).listen(listener).unwrap().workers(1).run();
+    actix_web::rt::spawn(server);
+
+    let client = reqwest::Client::new();
+    for _ in 0..3 {
+        let resp = client
+            .get(format!("http://127.0.0.1:{}/panic", port))
+            .send()
+            .await
+            .expect("panicking handler should still answer");
+        assert_eq!(resp.status(), 500);
+    }
+
+    // The single worker survived the panics
+    let resp = client
+        .get(format!("http://127.0.0.1:{}/ok", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+}
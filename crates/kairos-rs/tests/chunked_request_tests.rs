@@ -0,0 +1,165 @@
+//! Chunked request body tests
+//!
+//! Verifies that a chunked upload without `Content-Length` reaches a buffering
+//! route's backend with a computed `Content-Length` and without the client's
+//! `Transfer-Encoding`, so backends that require a length accept it.
+
+use actix_web::{App, HttpServer};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc;
+
+/// Request head and body received by the raw upstream.
+struct Received {
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Received {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Starts a raw HTTP/1.1 upstream that, like some legacy servers, answers
+/// `411 Length Required` unless the request carries `Content-Length`, and
+/// reports each request it reads.
+fn spawn_length_requiring_upstream() -> (u16, mpsc::Receiver<Received>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let tx = tx.clone();
+            std::thread::spawn(move || serve(stream, tx));
+        }
+    });
+    (port, rx)
+}
+
+fn serve(mut stream: TcpStream, tx: mpsc::Sender<Received>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 {
+            return;
+        }
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once(':').unwrap();
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+        let length = headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse::<usize>().ok());
+        let response = match length {
+            Some(length) => {
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let len = body.len();
+                tx.send(Received { headers, body }).unwrap();
+                let len = len.to_string();
+                format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}", len.len(), len)
+            }
+            None => {
+                tx.send(Received { headers, body: Vec::new() }).unwrap();
+                "HTTP/1.1 411 Length Required\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                    .to_string()
+            }
+        };
+        stream.write_all(response.as_bytes()).unwrap();
+        if length.is_none() {
+            return;
+        }
+    }
+}
+
+/// Starts the gateway on a real listener, so the client's chunked framing
+/// goes through Actix's HTTP/1.1 decoder.
+fn spawn_gateway(upstream_port: u16) -> u16 {
+    let route: Router = serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": upstream_port}],
+        "external_path": "/upload",
+        "internal_path": "/upload",
+        "methods": ["POST"]
+    }))
+    .unwrap();
+    let handler = RouteHandler::new(vec![route], 5);
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = HttpServer::new(move || {
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone()))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+    actix_web::rt::spawn(server);
+    port
+}
+
+#[actix_web::test]
+async fn test_chunked_upload_forwarded_with_content_length() {
+    let (upstream_port, received) = spawn_length_requiring_upstream();
+    let gateway_port = spawn_gateway(upstream_port);
+
+    let chunks = futures::stream::iter(
+        ["first chunk, ", "second chunk, ", "last chunk"]
+            .map(|chunk| Ok::<_, std::io::Error>(chunk.as_bytes().to_vec())),
+    );
+    let resp = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{}/upload", gateway_port))
+        .body(reqwest::Body::wrap_stream(chunks))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "37");
+
+    let request = received.recv().unwrap();
+    assert_eq!(request.header("content-length"), Some("37"));
+    assert_eq!(request.header("transfer-encoding"), None);
+    assert_eq!(request.body, b"first chunk, second chunk, last chunk");
+}
+
+#[actix_web::test]
+async fn test_client_transfer_encoding_not_forwarded_verbatim() {
+    let (upstream_port, received) = spawn_length_requiring_upstream();
+    let gateway_port = spawn_gateway(upstream_port);
+
+    // Hand-written chunked request, so the client's exact headers are known
+    let mut stream = TcpStream::connect(("127.0.0.1", gateway_port)).unwrap();
+    stream
+        .write_all(
+            b"POST /upload HTTP/1.1\r\nhost: gateway\r\ntransfer-encoding: chunked\r\n\
+              connection: close\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n",
+        )
+        .unwrap();
+    let response = actix_web::rt::task::spawn_blocking(move || {
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    })
+    .await
+    .unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+    let request = received.recv().unwrap();
+    assert_eq!(request.header("content-length"), Some("11"));
+    assert_eq!(request.header("transfer-encoding"), None);
+    assert_eq!(request.body, b"hello world");
+}
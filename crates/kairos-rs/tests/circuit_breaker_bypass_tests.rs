@@ -0,0 +1,159 @@
+//! Circuit breaker bypass tests
+//!
+//! Verifies that requests carrying `X-Kairos-Bypass-Breaker` from a trusted
+//! peer reach the backend while its circuit breaker is open, that the header
+//! is ignored from other peers, and that bypassed requests are not counted
+//! by the breaker.
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse, HttpServer};
+use kairos_rs::models::router::{Backend, CircuitBreakerSettings, Router};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::http;
+use kairos_rs::services::circuit_breaker::CircuitState;
+use kairos_rs::services::http::{RouteHandler, BYPASS_BREAKER_HEADER};
+use serde_json::json;
+use std::net::TcpListener;
+
+/// Starts a mock upstream on `port` that reports whether it received the
+/// bypass header.
+fn spawn_upstream(port: u16) {
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+
+    let server = HttpServer::new(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let forwarded = req.headers().contains_key(BYPASS_BREAKER_HEADER);
+            HttpResponse::Ok().body(format!("bypass header forwarded: {}", forwarded))
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+}
+
+/// Returns a port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn route(port: u16, failure_threshold: u64) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: Some(1),
+        }]),
+        external_path: "/api/status".to_string(),
+        internal_path: "/status".to_string(),
+        methods: vec!["GET".to_string()],
+        circuit_breaker: Some(CircuitBreakerSettings {
+            failure_threshold,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn handler(port: u16, failure_threshold: u64) -> RouteHandler {
+    RouteHandler::new(vec![route(port, failure_threshold)], 5)
+        .with_circuit_breaker_bypass(vec!["10.0.0.0/8".parse().unwrap()])
+}
+
+/// Builds a probe from `peer`, with or without the bypass header.
+fn probe(peer: &str, bypass: bool) -> test::TestRequest {
+    let mut req = test::TestRequest::get()
+        .uri("/api/status")
+        .peer_addr(peer.parse().unwrap());
+    if bypass {
+        req = req.insert_header((BYPASS_BREAKER_HEADER, "1"));
+    }
+    req
+}
+
+fn breaker(handler: &RouteHandler, port: u16) -> (CircuitState, u64, u64) {
+    handler.get_circuit_breaker_states()[&format!("http://127.0.0.1:{}", port)]
+}
+
+#[actix_web::test]
+async fn test_trusted_probe_reaches_backend_behind_open_breaker() {
+    let port = unused_port();
+    let handler = handler(port, 1);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    // The refused connection opens the breaker, then the backend comes up
+    let resp = test::call_service(&app, probe("192.168.1.5:4000", false).to_request()).await;
+    assert_eq!(resp.status(), 502);
+    assert_eq!(breaker(&handler, port).0, CircuitState::Open);
+    spawn_upstream(port);
+
+    let resp = test::call_service(&app, probe("192.168.1.5:4000", false).to_request()).await;
+    assert_eq!(resp.status(), 503);
+
+    let resp = test::call_service(&app, probe("10.1.2.3:4000", true).to_request()).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "bypass header forwarded: false");
+
+    // The successful probe does not close the breaker or count as a success
+    assert_eq!(breaker(&handler, port), (CircuitState::Open, 1, 0));
+}
+
+#[actix_web::test]
+async fn test_bypass_header_from_untrusted_peer_is_ignored() {
+    let port = unused_port();
+    let handler = handler(port, 1);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let resp = test::call_service(&app, probe("192.168.1.5:4000", true).to_request()).await;
+    assert_eq!(resp.status(), 502);
+    spawn_upstream(port);
+
+    let resp = test::call_service(&app, probe("192.168.1.5:4000", true).to_request()).await;
+    assert_eq!(resp.status(), 503);
+}
+
+#[actix_web::test]
+async fn test_failed_probes_are_not_counted_by_breaker() {
+    let port = unused_port();
+    let handler = handler(port, 2);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for _ in 0..3 {
+        let resp = test::call_service(&app, probe("10.1.2.3:4000", true).to_request()).await;
+        assert_eq!(resp.status(), 502);
+    }
+    assert_eq!(breaker(&handler, port), (CircuitState::Closed, 0, 0));
+}
+
+#[actix_web::test]
+async fn test_trusted_proxies_are_validated() {
+    let settings = |trusted_proxies: serde_json::Value| -> Settings {
+        serde_json::from_value(json!({
+            "version": 1,
+            "circuit_breaker_bypass": { "trusted_proxies": trusted_proxies },
+            "routers": []
+        }))
+        .unwrap()
+    };
+
+    assert!(settings(json!(["10.0.0.0/8", "192.168.1.20", "fd00::/8"])).validate().is_ok());
+    assert!(settings(json!(["10.0.0.0/33"])).validate().is_err());
+    assert!(settings(json!(["monitor.internal"])).validate().is_err());
+    assert!(settings(json!([])).validate().is_err());
+}
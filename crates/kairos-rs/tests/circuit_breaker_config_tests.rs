@@ -0,0 +1,131 @@
+//! Per-route circuit breaker configuration tests
+//!
+//! Verifies that `Router::circuit_breaker` tunes the breaker of the route's
+//! backends, that shared backends take the most conservative settings and
+//! that zero thresholds are rejected.
+
+use actix_web::{test, App};
+use kairos_rs::models::router::{Backend, CircuitBreakerSettings, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::circuit_breaker::{CircuitBreakerConfig, CircuitState};
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+use std::time::Duration;
+
+/// Returns a local port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn route(port: u16, path: &str, circuit_breaker: Option<CircuitBreakerSettings>) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: path.to_string(),
+        internal_path: path.to_string(),
+        methods: vec!["GET".to_string()],
+        circuit_breaker,
+        ..Default::default()
+    }
+}
+
+fn settings(failure_threshold: u64) -> CircuitBreakerSettings {
+    CircuitBreakerSettings {
+        failure_threshold,
+        ..Default::default()
+    }
+}
+
+/// Sends failing requests to `path` and returns how many it took to open the circuit.
+async fn failures_until_open(routes: Vec<Router>, path: &str, service: &str) -> usize {
+    let handler = RouteHandler::new(routes, 1);
+    let control = handler.clone();
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    for attempt in 1..=10 {
+        let req = test::TestRequest::get().uri(path).to_request();
+        test::call_service(&app, req).await;
+        if control.get_circuit_breaker_states()[service].0 == CircuitState::Open {
+            return attempt;
+        }
+    }
+    panic!("circuit never opened");
+}
+
+#[actix_web::test]
+async fn test_route_failure_threshold_is_used() {
+    let port = unused_port();
+    let service = format!("http://127.0.0.1:{}", port);
+
+    let attempts =
+        failures_until_open(vec![route(port, "/analytics", Some(settings(8)))], "/analytics", &service)
+            .await;
+    assert_eq!(attempts, 8);
+
+    let attempts =
+        failures_until_open(vec![route(port, "/analytics", None)], "/analytics", &service).await;
+    assert_eq!(attempts, 5, "routes without settings keep the default");
+}
+
+#[actix_web::test]
+async fn test_shared_backend_uses_most_conservative_settings() {
+    let port = unused_port();
+    let service = format!("http://127.0.0.1:{}", port);
+    let routes = vec![
+        route(port, "/analytics", Some(settings(8))),
+        route(port, "/payments", Some(settings(2))),
+    ];
+
+    // The lenient route still trips at the stricter threshold
+    let attempts = failures_until_open(routes, "/analytics", &service).await;
+    assert_eq!(attempts, 2);
+}
+
+#[actix_web::test]
+async fn test_most_conservative_merge() {
+    let lenient = CircuitBreakerConfig::from(&CircuitBreakerSettings {
+        failure_threshold: 10,
+        success_threshold: 1,
+        reset_timeout_secs: 60,
+        warmup_secs: 120,
+    });
+    let strict = CircuitBreakerConfig::from(&CircuitBreakerSettings {
+        failure_threshold: 2,
+        success_threshold: 5,
+        reset_timeout_secs: 15,
+        warmup_secs: 30,
+    });
+
+    let merged = lenient.most_conservative(&strict);
+    assert_eq!(merged.failure_threshold, 2);
+    assert_eq!(merged.success_threshold, 5);
+    assert_eq!(merged.reset_timeout, Duration::from_secs(60));
+    assert_eq!(merged.warmup, Duration::from_secs(30));
+}
+
+#[actix_web::test]
+async fn test_zero_thresholds_are_rejected() {
+    let defaults: CircuitBreakerSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(defaults, CircuitBreakerSettings::default());
+    assert!(route(8080, "/analytics", Some(defaults)).validate().is_ok());
+
+    assert!(route(8080, "/analytics", Some(settings(0))).validate().is_err());
+
+    let zero_success = CircuitBreakerSettings {
+        success_threshold: 0,
+        ..Default::default()
+    };
+    assert!(route(8080, "/analytics", Some(zero_success)).validate().is_err());
+}
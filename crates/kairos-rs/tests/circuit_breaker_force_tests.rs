@@ -0,0 +1,218 @@
+//! Circuit breaker forced state tests
+//!
+//! Verifies that `POST /admin/circuit-breakers/{service}/open` and `/close`
+//! pin a breaker in place regardless of backend results and the reset
+//! timeout, that `/auto` hands it back to automatic control, that forced
+//! states have their own metric values, and that the endpoints return 404
+//! for unknown services, require a JWT and are not served at all without JWT
+//! settings.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::Router;
+use kairos_rs::models::settings::{JwtSettings, Settings};
+use kairos_rs::routes::{http, management};
+use kairos_rs::services::circuit_breaker::CircuitState;
+use kairos_rs::services::http::RouteHandler;
+use serde_json::json;
+use std::net::TcpListener;
+use std::time::Duration;
+
+/// Returns a local port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// Starts a mock backend that always answers `200 OK`.
+fn spawn_backend() -> u16 {
+    common::spawn_server(|| App::new().default_service(web::to(HttpResponse::Ok)))
+}
+
+/// Route to `port` whose breaker trips after two failures, probes after one
+/// second and closes after one success.
+fn route(port: u16) -> Router {
+    serde_json::from_value(json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": "/api/orders",
+        "internal_path": "/orders",
+        "methods": ["GET"],
+        "circuit_breaker": {
+            "failure_threshold": 2,
+            "success_threshold": 1,
+            "reset_timeout_secs": 1
+        }
+    }))
+    .unwrap()
+}
+
+fn settings(jwt: Option<JwtSettings>) -> Settings {
+    let mut settings: Settings =
+        serde_json::from_value(json!({"version": 1, "routers": []})).unwrap();
+    settings.jwt = jwt;
+    settings
+}
+
+fn admin_uri(service: &str, action: &str) -> String {
+    let encoded = service.replace(':', "%3A").replace('/', "%2F");
+    format!("/admin/circuit-breakers/{}/{}", encoded, action)
+}
+
+/// Authenticated request to apply `action` to the breaker of `service`.
+fn admin_request(service: &str, action: &str) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri(&admin_uri(service, action))
+        .insert_header(("Authorization", common::bearer_token()))
+}
+
+fn state(handler: &RouteHandler, service: &str) -> CircuitState {
+    handler.get_circuit_breaker_states()[service].0
+}
+
+macro_rules! app {
+    ($handler:expr, $settings:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(web::Data::new($handler.clone()))
+                .configure(|cfg| management::configure_admin(cfg, &$settings))
+                .configure(|cfg| http::configure_route(cfg, $handler.clone())),
+        )
+        .await
+    };
+}
+
+#[actix_web::test]
+async fn test_force_open_stops_traffic_until_auto() {
+    let port = spawn_backend();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port)], 1);
+    let settings = settings(Some(common::jwt_settings()));
+    let app = app!(handler, settings);
+
+    let req = admin_request(&service, "open").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["state"], "forced_open");
+
+    // The backend is healthy, yet requests fail fast, even after the reset
+    // timeout would have let a probe through
+    for _ in 0..2 {
+        let req = test::TestRequest::get().uri("/api/orders").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 503);
+        assert_eq!(state(&handler, &service), CircuitState::ForcedOpen);
+        actix_web::rt::time::sleep(Duration::from_millis(1100)).await;
+    }
+    assert_eq!(state(&handler, &service) as u8, 3);
+    assert!(!handler.backend_statuses()[0].up);
+
+    let req = admin_request(&service, "auto").to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["state"], "half_open");
+
+    // One successful probe closes the breaker again
+    let req = test::TestRequest::get().uri("/api/orders").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+    assert_eq!(state(&handler, &service), CircuitState::Closed);
+}
+
+#[actix_web::test]
+async fn test_force_close_ignores_failures_until_auto() {
+    let port = unused_port();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port)], 1);
+    let settings = settings(Some(common::jwt_settings()));
+    let app = app!(handler, settings);
+
+    let req = admin_request(&service, "close").to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["state"], "forced_closed");
+
+    // Every request reaches the dead backend instead of failing fast
+    for _ in 0..5 {
+        let req = test::TestRequest::get().uri("/api/orders").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 502);
+    }
+    let (state_after, failures, _) = handler.get_circuit_breaker_states()[&service];
+    assert_eq!(state_after, CircuitState::ForcedClosed);
+    assert_eq!(state_after as u8, 4);
+    assert_eq!(failures, 0);
+
+    let req = admin_request(&service, "auto").to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["state"], "closed");
+
+    // Failures count again once released
+    for _ in 0..2 {
+        let req = test::TestRequest::get().uri("/api/orders").to_request();
+        test::call_service(&app, req).await;
+    }
+    assert_eq!(state(&handler, &service), CircuitState::Open);
+}
+
+#[actix_web::test]
+async fn test_reset_clears_forced_state() {
+    let port = spawn_backend();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port)], 1);
+    let settings = settings(Some(common::jwt_settings()));
+    let app = app!(handler, settings);
+
+    let req = admin_request(&service, "open").to_request();
+    test::call_service(&app, req).await;
+    let req = admin_request(&service, "reset").to_request();
+    test::call_service(&app, req).await;
+    assert_eq!(state(&handler, &service), CircuitState::Closed);
+
+    let req = test::TestRequest::get().uri("/api/orders").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_unknown_service_returns_404() {
+    let handler = RouteHandler::new(vec![route(unused_port())], 1);
+    let settings = settings(Some(common::jwt_settings()));
+    let app = app!(handler, settings);
+
+    for action in ["open", "close", "auto"] {
+        let req = admin_request("http://unknown:8080", action).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404, "{action}");
+    }
+}
+
+#[actix_web::test]
+async fn test_endpoints_require_jwt_when_configured() {
+    let port = unused_port();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port)], 1);
+    let settings = settings(Some(common::jwt_settings()));
+    let app = app!(handler, settings);
+
+    for action in ["open", "close", "auto"] {
+        let req = test::TestRequest::post().uri(&admin_uri(&service, action)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 401, "{action}");
+    }
+    assert_eq!(state(&handler, &service), CircuitState::Closed);
+}
+
+#[actix_web::test]
+async fn test_endpoints_not_served_without_jwt() {
+    let port = spawn_backend();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port)], 1);
+    let settings = settings(None);
+    let app = app!(handler, settings);
+
+    for action in ["open", "close", "auto", "reset"] {
+        let req = admin_request(&service, action).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404, "{action}");
+    }
+    assert_eq!(state(&handler, &service), CircuitState::Closed);
+
+    let req = test::TestRequest::get().uri("/api/orders").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
@@ -2,7 +2,6 @@ use actix_web::{test, web, App};
 use kairos_rs::routes::{http, metrics};
 use kairos_rs::services::http::RouteHandler;
 use kairos_rs::models::router::{Router, Backend};
-use kairos_rs::models::router::Protocol;
 use std::time::Duration;
 
 #[actix_web::test]
@@ -15,21 +14,16 @@ async fn test_circuit_breaker_integration() {
             external_path: "/api/test".to_string(),
             internal_path: "/test".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
             backends: Some(vec![
                 Backend {
                     host: "http://non-existent-service".to_string(),
                     port: 9999,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }
             ]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }
     ];
     let route_handler = RouteHandler::new(routes, 5); // 5 second timeout
@@ -100,21 +94,16 @@ async fn test_multiple_service_circuit_breakers() {
             external_path: "/api/service-a".to_string(),
             internal_path: "/test".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
             backends: Some(vec![
                 Backend {
                     host: "http://service-a".to_string(),
                     port: 8001,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }
             ]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         },
         Router {
             host: Some("http://service-b".to_string()),
@@ -122,21 +111,16 @@ async fn test_multiple_service_circuit_breakers() {
             external_path: "/api/service-b".to_string(),
             internal_path: "/test".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
             backends: Some(vec![
                 Backend {
                     host: "http://service-b".to_string(),
                     port: 8002,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }
             ]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }
     ];
     let route_handler = RouteHandler::new(routes, 5);
@@ -0,0 +1,158 @@
+//! Circuit breaker manual reset tests
+//!
+//! Verifies that `POST /admin/circuit-breakers/{service}/reset` force-closes
+//! an open breaker, returns 404 for unknown services and requires a JWT.
+
+mod common;
+
+use actix_web::{test, web, App};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::{http, management};
+use kairos_rs::services::circuit_breaker::CircuitState;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+
+/// Returns a local port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/orders".to_string(),
+        internal_path: "/orders".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+fn settings() -> Settings {
+    Settings {
+        version: 1,
+        jwt: Some(common::jwt_settings()),
+        rate_limit: None,
+        disable_rate_limiting: false,
+        ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
+        routers: vec![],
+    }
+}
+
+fn reset_uri(service: &str) -> String {
+    let encoded = service.replace(':', "%3A").replace('/', "%2F");
+    format!("/admin/circuit-breakers/{}/reset", encoded)
+}
+
+#[actix_web::test]
+async fn test_reset_closes_open_breaker() {
+    let port = unused_port();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port)], 1);
+    let control = handler.clone();
+    let settings = settings();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(handler.clone()))
+            .configure(|cfg| management::configure_admin(cfg, &settings))
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    // Trip the breaker with connection failures
+    for _ in 0..5 {
+        let req = test::TestRequest::get().uri("/api/orders").to_request();
+        test::call_service(&app, req).await;
+    }
+    let (state, _, _) = control.get_circuit_breaker_states()[&service];
+    assert_eq!(state, CircuitState::Open);
+
+    let req = test::TestRequest::post()
+        .uri(&reset_uri(&service))
+        .insert_header(("Authorization", common::bearer_token()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["service"], service);
+    assert_eq!(body["state"], "closed");
+
+    let (state, failures, successes) = control.get_circuit_breaker_states()[&service];
+    assert_eq!(state, CircuitState::Closed);
+    assert_eq!(failures, 0);
+    assert_eq!(successes, 0);
+}
+
+#[actix_web::test]
+async fn test_reset_unknown_service_returns_404() {
+    let handler = RouteHandler::new(vec![route(unused_port())], 1);
+    let settings = settings();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(handler))
+            .configure(|cfg| management::configure_admin(cfg, &settings)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri(&reset_uri("http://unknown:8080"))
+        .insert_header(("Authorization", common::bearer_token()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn test_reset_requires_jwt() {
+    let port = unused_port();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port)], 1);
+    let settings = settings();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(handler))
+            .configure(|cfg| management::configure_admin(cfg, &settings)),
+    )
+    .await;
+
+    let req = test::TestRequest::post().uri(&reset_uri(&service)).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+
+    let req = test::TestRequest::post()
+        .uri(&reset_uri(&service))
+        .insert_header(("Authorization", common::bearer_token()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+}
@@ -8,6 +8,7 @@ async fn test_circuit_breaker_closed_state() {
         success_threshold: 2,
         timeout: Duration::from_secs(1),
         reset_timeout: Duration::from_secs(1),
+        warmup: Duration::ZERO,
     };
     
     let cb = CircuitBreaker::new("test".to_string(), config);
@@ -28,6 +29,7 @@ async fn test_circuit_breaker_opens_on_failures() {
         success_threshold: 2,
         timeout: Duration::from_secs(1),
         reset_timeout: Duration::from_secs(1),
+        warmup: Duration::ZERO,
     };
     
     let cb = CircuitBreaker::new("test".to_string(), config);
@@ -54,6 +56,7 @@ async fn test_circuit_breaker_half_open_recovery() {
         success_threshold: 2,
         timeout: Duration::from_secs(1),
         reset_timeout: Duration::from_millis(100),
+        warmup: Duration::ZERO,
     };
     
     let cb = CircuitBreaker::new("test".to_string(), config);
@@ -74,3 +77,32 @@ async fn test_circuit_breaker_half_open_recovery() {
     assert!(result.is_ok());
     assert_eq!(cb.get_state(), CircuitState::Closed);
 }
+
+#[tokio::test]
+async fn test_circuit_breaker_ignores_failures_during_warmup() {
+    let config = CircuitBreakerConfig {
+        failure_threshold: 2,
+        success_threshold: 1,
+        timeout: Duration::from_secs(1),
+        reset_timeout: Duration::from_secs(1),
+        warmup: Duration::from_millis(200),
+    };
+    
+    let cb = CircuitBreaker::new("test".to_string(), config);
+    
+    // Failures while the backend warms up are not counted
+    for _ in 0..5 {
+        let result = cb.call(async { Err::<i32, &str>("warming up") }).await;
+        assert!(matches!(result, Err(CircuitBreakerError::OperationFailed(_))));
+    }
+    assert_eq!(cb.get_state(), CircuitState::Closed);
+    assert_eq!(cb.get_failure_count(), 0);
+    
+    sleep(Duration::from_millis(250)).await;
+    
+    // After the warmup, the usual threshold applies
+    let _ = cb.call(async { Err::<i32, &str>("error") }).await;
+    assert_eq!(cb.get_state(), CircuitState::Closed);
+    let _ = cb.call(async { Err::<i32, &str>("error") }).await;
+    assert_eq!(cb.get_state(), CircuitState::Open);
+}
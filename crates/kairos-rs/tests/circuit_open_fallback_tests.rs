@@ -0,0 +1,102 @@
+//! Circuit-open fallback tests
+//!
+//! Verifies that a route with `circuit_open_fallback` serves the configured
+//! response while its backend's circuit is open, and that routes without one
+//! keep returning the standard 503.
+
+use actix_web::{test, App};
+use kairos_rs::models::router::{Backend, FixedResponse, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::collections::HashMap;
+use std::net::TcpListener;
+
+/// Returns a local port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn route(port: u16, path: &str, fallback: Option<FixedResponse>) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: path.to_string(),
+        internal_path: path.to_string(),
+        methods: vec!["GET".to_string()],
+        circuit_open_fallback: fallback,
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_fallback_served_only_while_circuit_open() {
+    let port = unused_port();
+    let fallback = FixedResponse {
+        status: 200,
+        headers: HashMap::from([("Cache-Control".to_string(), "no-store".to_string())]),
+        body: Some(serde_json::json!({"items": [], "degraded": true})),
+    };
+    let handler = RouteHandler::new(
+        vec![
+            route(port, "/catalog", Some(fallback)),
+            route(port, "/orders", None),
+        ],
+        1,
+    );
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    // While the circuit is closed, failures surface as upstream errors
+    let req = test::TestRequest::get().uri("/catalog").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 502);
+
+    // Trip the shared backend's breaker
+    for _ in 0..4 {
+        let req = test::TestRequest::get().uri("/orders").to_request();
+        test::call_service(&app, req).await;
+    }
+
+    let req = test::TestRequest::get().uri("/catalog").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("cache-control").unwrap(), "no-store");
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/json");
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body, serde_json::json!({"items": [], "degraded": true}));
+
+    let req = test::TestRequest::get().uri("/orders").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 503);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["type"], "circuit_open");
+}
+
+#[actix_web::test]
+async fn test_fallback_deserializes_with_defaults() {
+    let fallback: FixedResponse =
+        serde_json::from_str(r#"{"body": "Temporarily unavailable"}"#).unwrap();
+    assert_eq!(fallback.status, 200);
+    assert!(fallback.headers.is_empty());
+    assert!(fallback.validate().is_ok());
+
+    let invalid = FixedResponse {
+        status: 42,
+        ..fallback
+    };
+    let mut router = route(8080, "/catalog", Some(invalid));
+    assert!(router.validate().is_err());
+
+    router.circuit_open_fallback = None;
+    assert!(router.validate().is_ok());
+}
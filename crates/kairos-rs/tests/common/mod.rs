@@ -0,0 +1,72 @@
+//! Helpers shared by the integration tests.
+
+// Each test binary compiles this module and uses only some of it
+#![allow(dead_code)]
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::{App, Error, HttpServer};
+use kairos_rs::middleware::auth::{create_test_token, Claims};
+use kairos_rs::models::settings::JwtSettings;
+use std::net::TcpListener;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Secret of [`jwt_settings`]
+pub const JWT_SECRET: &str = "test-secret-key-that-is-long-enough-for-security-requirements";
+
+/// Serves the app built by `app` on a free local port with a single worker,
+/// and returns the port.
+///
+/// Used to start mock upstreams for requests proxied by the gateway.
+pub fn spawn_server<F, T, B>(app: F) -> u16
+where
+    F: Fn() -> App<T> + Send + Clone + 'static,
+    T: ServiceFactory<
+            ServiceRequest,
+            Config = (),
+            Response = ServiceResponse<B>,
+            Error = Error,
+            InitError = (),
+        > + 'static,
+    B: MessageBody + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = HttpServer::new(app)
+        .listen(listener)
+        .unwrap()
+        .workers(1)
+        .run();
+
+    actix_web::rt::spawn(server);
+    port
+}
+
+/// JWT settings signed with [`JWT_SECRET`], without issuer, audience or
+/// required claims.
+pub fn jwt_settings() -> JwtSettings {
+    JwtSettings {
+        secret: JWT_SECRET.to_string(),
+        issuer: None,
+        audience: None,
+        required_claims: vec![],
+    }
+}
+
+/// Returns an `Authorization` header value accepted with [`jwt_settings`].
+pub fn bearer_token() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as usize;
+    let claims = Claims {
+        sub: "operator".to_string(),
+        exp: now + 3600,
+        iat: now,
+        iss: None,
+        aud: None,
+        roles: None,
+    };
+    format!("Bearer {}", create_test_token(claims, JWT_SECRET).unwrap())
+}
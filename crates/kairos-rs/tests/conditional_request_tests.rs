@@ -0,0 +1,90 @@
+//! Conditional request tests
+//!
+//! Verifies that `If-None-Match` and `If-Modified-Since` reach the backend
+//! and that its validators and `304 Not Modified` responses reach the client
+//! unchanged, so clients can revalidate through the gateway.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+const ETAG: &str = "\"v1\"";
+const LAST_MODIFIED: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+/// Starts a mock upstream serving a document with an ETag and Last-Modified
+/// date, answering matching conditional requests with `304`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let header = |name| req.headers().get(name).and_then(|v| v.to_str().ok());
+            let not_modified = header("if-none-match") == Some(ETAG)
+                || (header("if-none-match").is_none()
+                    && header("if-modified-since") == Some(LAST_MODIFIED));
+            let mut resp = if not_modified {
+                HttpResponse::NotModified()
+            } else {
+                HttpResponse::Ok()
+            };
+            resp.insert_header(("ETag", ETAG))
+                .insert_header(("Last-Modified", LAST_MODIFIED));
+            if not_modified {
+                resp.finish()
+            } else {
+                resp.body("document")
+            }
+        }))
+    })
+}
+
+fn route(port: u16) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": "/api/document",
+        "internal_path": "/document",
+        "methods": ["GET"]
+    }))
+    .unwrap()
+}
+
+#[actix_web::test]
+async fn test_validators_and_not_modified_pass_through() {
+    let handler = RouteHandler::new(vec![route(spawn_upstream())], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/document").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers().get("etag").unwrap(), ETAG);
+    assert_eq!(resp.headers().get("last-modified").unwrap(), LAST_MODIFIED);
+    assert_eq!(test::read_body(resp).await, "document");
+
+    let conditional = [
+        ("If-None-Match", ETAG),
+        ("If-Modified-Since", LAST_MODIFIED),
+    ];
+    for (name, value) in conditional {
+        let req = test::TestRequest::get()
+            .uri("/api/document")
+            .insert_header((name, value))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 304, "{name}");
+        assert_eq!(resp.headers().get("etag").unwrap(), ETAG);
+        assert!(test::read_body(resp).await.is_empty());
+    }
+
+    // A stale validator gets the full document
+    let req = test::TestRequest::get()
+        .uri("/api/document")
+        .insert_header(("If-None-Match", "\"v0\""))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "document");
+}
@@ -4,10 +4,14 @@
 //! hot reload functionality, ensuring configuration changes are properly detected
 //! and applied without requiring application restarts.
 
-use kairos_rs::config::hot_reload::ConfigWatcher;
-use kairos_rs::models::router::Protocol;
+use actix_web::{test, web, App};
+use kairos_rs::config::hot_reload::{record_validation_warnings, ConfigWatcher};
+use kairos_rs::config::validation::ConfigValidator;
 use kairos_rs::models::router::{Backend, Router};
 use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::metrics::{self, MetricsCollector};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 use std::io::Write;
 use tempfile::NamedTempFile;
 
@@ -16,26 +20,39 @@ fn create_test_settings() -> Settings {
         version: 1,
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![Router {
             host: Some("http://localhost".to_string()),
             port: Some(3000),
             external_path: "/test".to_string(),
             internal_path: "/test".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
             backends: Some(vec![Backend {
                 host: "http://localhost".to_string(),
                 port: 3000,
                 weight: 1,
                 health_check_path: None,
+                timeout_secs: None,
             }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }],
     }
 }
@@ -152,3 +169,59 @@ async fn test_config_version_tracking() {
         assert_eq!(updated_config.version, 2u64);
     }
 }
+
+#[actix_web::test]
+async fn test_validation_warnings_gauge_follows_reloads() {
+    let settings = create_test_settings();
+    let baseline = ConfigValidator::validate_comprehensive(&settings).warnings.len();
+
+    // Disabling rate limiting adds a warning
+    let mut warned = settings.clone();
+    warned.disable_rate_limiting = true;
+    let mut temp_file = NamedTempFile::new().unwrap();
+    temp_file
+        .write_all(serde_json::to_string(&warned).unwrap().as_bytes())
+        .unwrap();
+    temp_file.flush().unwrap();
+
+    let collector = MetricsCollector::default();
+    collector.set_config_validation_warnings(baseline);
+    let watcher = ConfigWatcher::new(settings, temp_file.path().to_string_lossy().to_string());
+    actix_web::rt::spawn(record_validation_warnings(
+        watcher.subscribe(),
+        collector.clone(),
+    ));
+
+    let update = watcher.manual_reload().await.unwrap();
+    assert_eq!(update.validation_warnings, baseline + 1);
+
+    let mut gauge = 0;
+    for _ in 0..50 {
+        gauge = collector.config_validation_warnings.load(Ordering::Relaxed);
+        if gauge == (baseline + 1) as u64 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(gauge, (baseline + 1) as u64);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+    for accept in ["text/plain", "application/openmetrics-text"] {
+        let req = test::TestRequest::get()
+            .uri("/metrics")
+            .insert_header(("Accept", accept))
+            .to_request();
+        let body = test::read_body(test::call_service(&app, req).await).await;
+        let text = String::from_utf8_lossy(&body);
+        assert!(text.contains("# TYPE kairos_config_validation_warnings gauge"), "{text}");
+        assert!(
+            text.contains(&format!("kairos_config_validation_warnings {}", baseline + 1)),
+            "{text}"
+        );
+    }
+}
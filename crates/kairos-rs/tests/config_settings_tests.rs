@@ -5,7 +5,6 @@
 //! security validation, and error reporting.
 
 use kairos_rs::config::settings::load_settings;
-use kairos_rs::models::router::Protocol;
 use kairos_rs::models::router::{Backend, Router};
 use kairos_rs::models::settings::Settings;
 use once_cell::sync::Lazy;
@@ -21,26 +20,39 @@ fn create_test_settings() -> Settings {
         version: 1,
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![Router {
             host: Some("http://localhost".to_string()),
             port: Some(3000),
             external_path: "/api/test".to_string(),
             internal_path: "/test".to_string(),
             methods: vec!["GET".to_string(), "POST".to_string()],
-            auth_required: false,
             backends: Some(vec![Backend {
                 host: "http://localhost".to_string(),
                 port: 3000,
                 weight: 1,
                 health_check_path: None,
+                timeout_secs: None,
             }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }],
     }
 }
@@ -151,6 +163,64 @@ fn test_load_settings_malformed_structure() {
     assert!(result.unwrap_err().to_string().contains("Invalid JSON"));
 }
 
+#[test]
+fn test_load_settings_trailing_comma_reports_location() {
+    let _lock = ENV_MUTEX.lock().unwrap();
+    let mut temp_file = NamedTempFile::new().unwrap();
+    temp_file
+        .write_all(b"{\n  \"version\": 1,\n  \"routers\": [],\n}")
+        .unwrap();
+    temp_file.flush().unwrap();
+
+    env::set_var("KAIROS_CONFIG_PATH", temp_file.path());
+
+    let result = load_settings();
+
+    env::remove_var("KAIROS_CONFIG_PATH");
+
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("line 4, column 1"), "{}", error);
+    assert!(error.contains("trailing comma"), "{}", error);
+}
+
+#[test]
+fn test_load_settings_wrong_type_reports_route_field() {
+    let _lock = ENV_MUTEX.lock().unwrap();
+    let mut temp_file = NamedTempFile::new().unwrap();
+    let config = r#"{
+  "version": 1,
+  "routers": [
+    {
+      "host": "http://localhost",
+      "port": 3000,
+      "external_path": "/api/users",
+      "internal_path": "/users",
+      "methods": ["GET"]
+    },
+    {
+      "host": "http://localhost",
+      "port": "3001",
+      "external_path": "/api/orders",
+      "internal_path": "/orders",
+      "methods": ["GET"]
+    }
+  ]
+}"#;
+    temp_file.write_all(config.as_bytes()).unwrap();
+    temp_file.flush().unwrap();
+
+    env::set_var("KAIROS_CONFIG_PATH", temp_file.path());
+
+    let result = load_settings();
+
+    env::remove_var("KAIROS_CONFIG_PATH");
+
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("line 13"), "{}", error);
+    assert!(error.contains("`routers[1].port`"), "{}", error);
+    assert!(error.contains("invalid type"), "{}", error);
+}
+
 #[test]
 fn test_load_settings_path_traversal_protection() {
     let _lock = ENV_MUTEX.lock().unwrap();
@@ -201,7 +271,25 @@ fn test_load_settings_complex_configuration() {
         version: 2,
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![
             Router {
                 host: Some("https://api.example.com".to_string()),
@@ -209,19 +297,14 @@ fn test_load_settings_complex_configuration() {
                 external_path: "/api/v1/users/{id}".to_string(),
                 internal_path: "/users/{id}".to_string(),
                 methods: vec!["GET".to_string(), "PUT".to_string(), "DELETE".to_string()],
-                auth_required: false,
                 backends: Some(vec![Backend {
                     host: "https://api.example.com".to_string(),
                     port: 443,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }]),
-                load_balancing_strategy: Default::default(),
-                retry: None,
-                protocol: Protocol::Http,
-                request_transformation: None,
-                response_transformation: None,
-                ai_policy: None,
+                ..Default::default()
             },
             Router {
                 host: Some("http://internal-service".to_string()),
@@ -229,19 +312,14 @@ fn test_load_settings_complex_configuration() {
                 external_path: "/internal/{service}/{action}".to_string(),
                 internal_path: "/{service}/{action}".to_string(),
                 methods: vec!["POST".to_string()],
-                auth_required: false,
                 backends: Some(vec![Backend {
                     host: "http://internal-service".to_string(),
                     port: 8080,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }]),
-                load_balancing_strategy: Default::default(),
-                retry: None,
-                protocol: Protocol::Http,
-                request_transformation: None,
-                response_transformation: None,
-                ai_policy: None,
+                ..Default::default()
             },
             Router {
                 host: Some("https://auth.example.com".to_string()),
@@ -249,19 +327,14 @@ fn test_load_settings_complex_configuration() {
                 external_path: "/auth/login".to_string(),
                 internal_path: "/v2/auth/login".to_string(),
                 methods: vec!["POST".to_string()],
-                auth_required: false,
                 backends: Some(vec![Backend {
                     host: "https://auth.example.com".to_string(),
                     port: 443,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }]),
-                load_balancing_strategy: Default::default(),
-                retry: None,
-                protocol: Protocol::Http,
-                request_transformation: None,
-                response_transformation: None,
-                ai_policy: None,
+                ..Default::default()
             },
         ],
     };
@@ -299,7 +372,25 @@ fn test_load_settings_empty_routers() {
         version: 1,
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![],
     };
 
@@ -326,26 +417,39 @@ fn test_load_settings_unicode_content() {
         version: 1,
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![Router {
             host: Some("https://测试.example.com".to_string()),
             port: Some(443),
             external_path: "/api/用户/{id}".to_string(),
             internal_path: "/users/{id}".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
             backends: Some(vec![Backend {
                 host: "https://测试.example.com".to_string(),
                 port: 443,
                 weight: 1,
                 health_check_path: None,
+                timeout_secs: None,
             }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }],
     };
 
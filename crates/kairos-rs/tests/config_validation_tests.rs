@@ -5,7 +5,6 @@
 //! recommendations, and detailed error reporting.
 
 use kairos_rs::config::validation::{ConfigValidator, ValidationResult};
-use kairos_rs::models::router::Protocol;
 use kairos_rs::models::router::{Backend, Router};
 use kairos_rs::models::settings::Settings;
 
@@ -16,19 +15,14 @@ fn create_test_router(host: &str, external_path: &str, methods: Vec<&str>) -> Ro
         external_path: external_path.to_string(),
         internal_path: "/test".to_string(),
         methods: methods.iter().map(|s| s.to_string()).collect(),
-        auth_required: false,
         backends: Some(vec![Backend {
             host: host.to_string(),
             port: 80,
             weight: 1,
             health_check_path: None,
+            timeout_secs: None,
         }]),
-        load_balancing_strategy: Default::default(),
-        retry: None,
-        protocol: Protocol::Http,
-        request_transformation: None,
-        response_transformation: None,
-        ai_policy: None,
+        ..Default::default()
     }
 }
 
@@ -37,7 +31,25 @@ fn test_empty_configuration() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![],
     };
@@ -55,7 +67,25 @@ fn test_security_warnings() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![create_test_router(
             "http://example.com",
@@ -76,7 +106,25 @@ fn test_performance_warnings() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![create_test_router(
             "https://example.com",
@@ -136,7 +184,25 @@ fn test_localhost_http_warnings() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![
             create_test_router("http://localhost:3000", "/api/test", vec!["GET"]),
@@ -153,7 +219,25 @@ fn test_overly_permissive_methods() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![create_test_router(
             "https://example.com",
@@ -174,7 +258,25 @@ fn test_path_traversal_detection() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![Router {
             host: Some("https://example.com".to_string()),
@@ -182,19 +284,14 @@ fn test_path_traversal_detection() {
             external_path: "/api/../admin".to_string(),
             internal_path: "/test".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
             backends: Some(vec![Backend {
                 host: "https://example.com".to_string(),
                 port: 443,
                 weight: 1,
                 health_check_path: None,
+                timeout_secs: None,
             }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }],
     };
 
@@ -211,7 +308,25 @@ fn test_duplicate_route_paths() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![
             create_test_router("https://example.com", "/api/test", vec!["GET"]),
@@ -227,12 +342,65 @@ fn test_duplicate_route_paths() {
         .any(|e| e.contains("Duplicate route path")));
 }
 
+#[test]
+fn test_duplicate_route_conflicts() {
+    let validate = |routers: Vec<Router>| {
+        let mut settings: Settings = serde_json::from_str(r#"{"version": 1, "routers": []}"#).unwrap();
+        settings.routers = routers;
+        ConfigValidator::validate_comprehensive(&settings)
+    };
+
+    // Exact duplicate with overlapping methods
+    let result = validate(vec![
+        create_test_router("https://example.com", "/api/test", vec!["GET", "POST"]),
+        create_test_router("https://other.com", "/api/test", vec!["POST", "PUT"]),
+    ]);
+    assert!(result.errors.iter().any(|e| e.contains(
+        "Duplicate route path detected: /api/test matches the same paths as /api/test (both handle POST)"
+    )), "{:?}", result.errors);
+
+    // Dynamic patterns differing only in parameter names
+    let result = validate(vec![
+        create_test_router("https://example.com", "/api/users/{id}", vec!["GET"]),
+        create_test_router("https://example.com", "/api/users/{user_id}", vec!["DELETE"]),
+    ]);
+    assert!(result.errors.iter().any(|e| e.contains(
+        "/api/users/{user_id} matches the same paths as /api/users/{id} (merge them"
+    )), "{:?}", result.errors);
+
+    // Static routes win over dynamic ones, so they only conflict potentially
+    let result = validate(vec![
+        create_test_router("https://example.com", "/api/users/{id}", vec!["GET"]),
+        create_test_router("https://example.com", "/api/users/me", vec!["GET"]),
+    ]);
+    assert!(!result.errors.iter().any(|e| e.contains("Duplicate")), "{:?}", result.errors);
+    assert!(result.warnings.iter().any(|w| w.contains("Potential route conflict")));
+}
+
 #[test]
 fn test_mixed_http_https_warnings() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![
             create_test_router("http://example.com", "/api/insecure", vec!["GET"]),
@@ -257,7 +425,25 @@ fn test_all_http_routes_warning() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![
             create_test_router("http://example1.com", "/api/test1", vec!["GET"]),
@@ -286,7 +472,25 @@ fn test_high_dynamic_routes_warning() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers,
     };
@@ -303,7 +507,25 @@ fn test_static_route_recommendation() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![
             create_test_router("https://example.com", "/api/{id}", vec!["GET"]),
@@ -323,7 +545,25 @@ fn test_valid_configuration() {
     let settings = Settings {
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         version: 1,
         routers: vec![
             create_test_router("https://example.com", "/api/health", vec!["GET"]),
@@ -0,0 +1,81 @@
+//! Per-IP connection limit tests
+//!
+//! Verifies that `ConnectionLimit` rejects a client IP over its concurrent
+//! request cap with 503 while other IPs are still served, and that slots are
+//! released when requests complete.
+
+use actix_web::{http::StatusCode, test, web, App, HttpResponse};
+use kairos_rs::middleware::connection_limit::ConnectionLimit;
+use kairos_rs::models::settings::Settings;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+async fn slow() -> HttpResponse {
+    actix_web::rt::time::sleep(Duration::from_millis(200)).await;
+    HttpResponse::Ok().finish()
+}
+
+fn request(peer: SocketAddr) -> test::TestRequest {
+    test::TestRequest::get().uri("/slow").peer_addr(peer)
+}
+
+#[actix_web::test]
+async fn test_ip_over_connection_cap_is_rejected() {
+    let limit = ConnectionLimit::new(1);
+    let app = test::init_service(
+        App::new()
+            .wrap(limit.clone())
+            .route("/slow", web::get().to(slow)),
+    )
+    .await;
+
+    let client_a: SocketAddr = "10.0.0.1:40000".parse().unwrap();
+    let client_a_again: SocketAddr = "10.0.0.1:40001".parse().unwrap();
+    let client_b: SocketAddr = "10.0.0.2:40000".parse().unwrap();
+
+    let (first, second, other) = futures::join!(
+        test::try_call_service(&app, request(client_a).to_request()),
+        test::try_call_service(&app, request(client_a_again).to_request()),
+        test::try_call_service(&app, request(client_b).to_request()),
+    );
+
+    assert_eq!(first.unwrap().status(), StatusCode::OK);
+    let rejected = second.expect_err("second request from the same IP should be rejected");
+    assert_eq!(
+        rejected.as_response_error().status_code(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert_eq!(other.unwrap().status(), StatusCode::OK);
+
+    assert_eq!(limit.active_connections(client_a.ip()), 0);
+    assert_eq!(limit.active_connections(client_b.ip()), 0);
+}
+
+#[actix_web::test]
+async fn test_slot_is_released_after_completion() {
+    let limit = ConnectionLimit::new(1);
+    let app = test::init_service(
+        App::new()
+            .wrap(limit.clone())
+            .route("/slow", web::get().to(slow)),
+    )
+    .await;
+
+    let client: SocketAddr = "10.0.0.1:40000".parse().unwrap();
+    for _ in 0..3 {
+        let resp = test::try_call_service(&app, request(client).to_request()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+    assert_eq!(limit.active_connections(client.ip()), 0);
+}
+
+#[actix_web::test]
+async fn test_zero_connection_cap_is_invalid() {
+    let settings: Settings = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "max_connections_per_ip": 0,
+        "routers": []
+    }))
+    .unwrap();
+    assert!(settings.validate().is_err());
+}
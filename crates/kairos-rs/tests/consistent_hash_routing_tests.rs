@@ -0,0 +1,115 @@
+//! Consistent hash routing tests
+//!
+//! Verifies that routes using the `consistent_hash` strategy send requests
+//! with the same header or path parameter value to the same backend, and
+//! fall back to the client IP when the key is missing.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::collections::HashSet;
+use std::net::{SocketAddr, TcpListener};
+
+/// Starts a mock upstream that answers every request with its port.
+fn spawn_upstream() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = HttpServer::new(move || {
+        App::new().default_service(web::to(move || async move {
+            HttpResponse::Ok().body(port.to_string())
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+    actix_web::rt::spawn(server);
+    port
+}
+
+fn route(ports: &[u16], external_path: &str, key: serde_json::Value) -> Router {
+    let backends: Vec<_> = ports
+        .iter()
+        .map(|port| serde_json::json!({"host": "http://127.0.0.1", "port": port}))
+        .collect();
+    serde_json::from_value(serde_json::json!({
+        "backends": backends,
+        "external_path": external_path,
+        "internal_path": "/orders",
+        "methods": ["GET"],
+        "load_balancing_strategy": {"consistent_hash": key}
+    }))
+    .unwrap()
+}
+
+#[actix_web::test]
+async fn test_same_key_reaches_same_backend() {
+    let ports = [spawn_upstream(), spawn_upstream(), spawn_upstream()];
+    let handler = RouteHandler::new(
+        vec![
+            route(&ports, "/api/orders", serde_json::json!({"header": "X-Tenant-Id"})),
+            route(&ports, "/api/{tenant}/orders", serde_json::json!({"path_param": "tenant"})),
+        ],
+        5,
+    );
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let mut by_header = HashSet::new();
+    let mut by_param = HashSet::new();
+    for tenant in 0..20 {
+        let mut seen = HashSet::new();
+        for _ in 0..3 {
+            let req = test::TestRequest::get()
+                .uri("/api/orders")
+                .insert_header(("X-Tenant-Id", format!("tenant-{}", tenant)))
+                .to_request();
+            seen.insert(test::read_body(test::call_service(&app, req).await).await);
+        }
+        assert_eq!(seen.len(), 1, "tenant-{} was spread over backends", tenant);
+        by_header.extend(seen);
+
+        let mut seen = HashSet::new();
+        for _ in 0..3 {
+            let req = test::TestRequest::get()
+                .uri(&format!("/api/tenant-{}/orders", tenant))
+                .to_request();
+            seen.insert(test::read_body(test::call_service(&app, req).await).await);
+        }
+        assert_eq!(seen.len(), 1, "tenant-{} was spread over backends", tenant);
+        by_param.extend(seen);
+    }
+
+    // Different keys are spread over the pool
+    assert!(by_header.len() > 1);
+    assert!(by_param.len() > 1);
+}
+
+#[actix_web::test]
+async fn test_missing_key_hashes_client_ip() {
+    let ports = [spawn_upstream(), spawn_upstream(), spawn_upstream()];
+    let handler = RouteHandler::new(
+        vec![route(&ports, "/api/orders", serde_json::json!({"header": "X-Tenant-Id"}))],
+        5,
+    );
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let peer: SocketAddr = "10.0.0.7:40000".parse().unwrap();
+    let mut seen = HashSet::new();
+    for _ in 0..5 {
+        let req = test::TestRequest::get()
+            .uri("/api/orders")
+            .peer_addr(peer)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        seen.insert(test::read_body(resp).await);
+    }
+    assert_eq!(seen.len(), 1);
+}
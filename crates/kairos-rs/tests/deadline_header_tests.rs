@@ -0,0 +1,132 @@
+//! Deadline header tests
+//!
+//! Verifies that routes with a `deadline_header` tell the backend how long
+//! the gateway will wait for it, in the configured format, and that invalid
+//! header names are rejected.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::models::router::{Backend, DeadlineFormat, DeadlineHeader, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Starts a mock upstream that echoes the value of the `name` header.
+fn spawn_upstream(name: &'static str) -> u16 {
+    common::spawn_server(move || {
+        App::new().default_service(web::to(move |req: HttpRequest| async move {
+            let values: Vec<String> = req
+                .headers()
+                .get_all(name)
+                .map(|v| v.to_str().unwrap().to_string())
+                .collect();
+            HttpResponse::Ok().body(values.join(","))
+        }))
+    })
+}
+
+fn route(port: u16, timeout_secs: Option<u64>, deadline_header: Option<DeadlineHeader>) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs,
+        }]),
+        external_path: "/api/reports".to_string(),
+        internal_path: "/reports".to_string(),
+        methods: vec!["GET".to_string()],
+        deadline_header,
+        ..Default::default()
+    }
+}
+
+fn deadline(name: &str, format: DeadlineFormat) -> Option<DeadlineHeader> {
+    Some(DeadlineHeader {
+        name: name.to_string(),
+        format,
+    })
+}
+
+/// Returns the header values the upstream received for a request.
+async fn received(router: Router, req: test::TestRequest) -> String {
+    let handler = RouteHandler::new(vec![router], 30);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let resp = test::call_service(&app, req.uri("/api/reports").to_request()).await;
+    assert_eq!(resp.status(), 200);
+    String::from_utf8(test::read_body(resp).await.to_vec()).unwrap()
+}
+
+#[actix_web::test]
+async fn test_deadline_uses_backend_timeout() {
+    let name = "x-request-timeout-ms";
+    let router = route(
+        spawn_upstream(name),
+        Some(2),
+        deadline("X-Request-Timeout-Ms", DeadlineFormat::Milliseconds),
+    );
+    assert_eq!(received(router, test::TestRequest::get()).await, "2000");
+
+    // Without a per-backend timeout the gateway default applies
+    let router = route(
+        spawn_upstream(name),
+        None,
+        deadline("X-Request-Timeout-Ms", DeadlineFormat::Milliseconds),
+    );
+    assert_eq!(received(router, test::TestRequest::get()).await, "30000");
+}
+
+#[actix_web::test]
+async fn test_grpc_timeout_format() {
+    let router = route(
+        spawn_upstream("grpc-timeout"),
+        Some(5),
+        deadline("grpc-timeout", DeadlineFormat::GrpcTimeout),
+    );
+    assert_eq!(received(router, test::TestRequest::get()).await, "5000m");
+}
+
+#[actix_web::test]
+async fn test_unix_millis_format() {
+    let router = route(
+        spawn_upstream("x-request-deadline"),
+        Some(10),
+        deadline("X-Request-Deadline", DeadlineFormat::UnixMillis),
+    );
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+
+    let value: u128 = received(router, test::TestRequest::get()).await.parse().unwrap();
+    assert!(value >= now + 9_000 && value <= now + 11_000, "deadline {} from {}", value, now);
+}
+
+#[actix_web::test]
+async fn test_client_supplied_deadline_is_replaced() {
+    let router = route(
+        spawn_upstream("x-request-timeout-ms"),
+        Some(1),
+        deadline("X-Request-Timeout-Ms", DeadlineFormat::Milliseconds),
+    );
+    let req = test::TestRequest::get().insert_header(("X-Request-Timeout-Ms", "999999"));
+    assert_eq!(received(router, req).await, "1000");
+}
+
+#[actix_web::test]
+async fn test_no_deadline_header_by_default() {
+    let router = route(spawn_upstream("x-request-timeout-ms"), Some(2), None);
+    assert_eq!(received(router, test::TestRequest::get()).await, "");
+}
+
+#[actix_web::test]
+async fn test_invalid_header_name_is_rejected() {
+    let router = route(8080, None, deadline("X Request Timeout", DeadlineFormat::Milliseconds));
+    assert!(router.validate().is_err());
+
+    let router = route(8080, None, deadline("X-Request-Timeout-Ms", DeadlineFormat::Milliseconds));
+    assert!(router.validate().is_ok());
+}
@@ -0,0 +1,134 @@
+//! Per-route debug logging tests
+//!
+//! Verifies that routes with `debug_logging` log the forwarded request and
+//! the backend's response status at DEBUG, with credentials redacted and
+//! body snippets capped, and that other routes log nothing about their
+//! requests.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::sync::{Mutex, Once};
+
+/// Messages logged by the gateway during the tests.
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CapturingLogger;
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Debug && metadata.target().starts_with("kairos_rs")
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            MESSAGES.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Starts capturing debug messages, once per test binary.
+fn capture_messages() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&CapturingLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+    });
+}
+
+/// Returns the captured messages that mention `fragment`.
+fn messages_for(fragment: &str) -> Vec<String> {
+    MESSAGES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|message| message.contains(fragment))
+        .cloned()
+        .collect()
+}
+
+/// Starts a mock upstream accepting any request and answering `201`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Created().finish() }))
+    })
+}
+
+fn route(port: u16, name: &str, debug_logging: bool) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": format!("/api/{}", name),
+        "internal_path": format!("/{}-upstream", name),
+        "methods": ["POST"],
+        "debug_logging": debug_logging
+    }))
+    .unwrap()
+}
+
+async fn post(handler: &RouteHandler, uri: &str, body: Vec<u8>) {
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+    let req = test::TestRequest::post()
+        .uri(uri)
+        .insert_header(("Authorization", "Bearer top-secret-token"))
+        .insert_header(("X-Trace-Me", "yes"))
+        .set_payload(body)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+}
+
+#[actix_web::test]
+async fn test_debug_logging_logs_request_and_response() {
+    capture_messages();
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "verbose", true)], 5);
+
+    post(&handler, "/api/verbose?page=2", b"hello".to_vec()).await;
+    let target = format!("127.0.0.1:{}/verbose-upstream", port);
+    let messages = messages_for(&target);
+    let request = messages
+        .iter()
+        .find(|m| m.contains("POST /api/verbose?page=2"))
+        .unwrap_or_else(|| panic!("no request line in {:?}", messages));
+    assert!(request.contains("x-trace-me: yes"), "{request}");
+    assert!(request.contains("authorization: [redacted]"), "{request}");
+    assert!(request.contains("hello"), "{request}");
+    assert!(
+        messages.iter().any(|m| m.contains("responded with 201")),
+        "{:?}",
+        messages
+    );
+    assert!(messages_for("top-secret-token").is_empty());
+}
+
+#[actix_web::test]
+async fn test_debug_logging_caps_body_snippet() {
+    capture_messages();
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "large", true)], 5);
+
+    post(&handler, "/api/large", vec![b'x'; 5000]).await;
+    let messages = messages_for("POST /api/large");
+    assert_eq!(messages.len(), 1, "{:?}", messages);
+    assert!(messages[0].contains("(5000 bytes)"), "{}", messages[0]);
+    assert!(!messages[0].contains(&"x".repeat(1025)), "{}", messages[0]);
+}
+
+#[actix_web::test]
+async fn test_routes_without_debug_logging_stay_quiet() {
+    capture_messages();
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "quiet", false)], 5);
+
+    post(&handler, "/api/quiet", b"hello".to_vec()).await;
+    assert!(messages_for("POST /api/quiet").is_empty());
+    assert!(messages_for("responded with").iter().all(|m| !m.contains("quiet-upstream")));
+}
@@ -0,0 +1,136 @@
+//! Debug path override tests
+//!
+//! Verifies that `X-Kairos-Override-Path` from a trusted peer replaces the
+//! upstream path of a request, that the header is ignored from other peers
+//! and when debug overrides are off, that it is never forwarded upstream,
+//! and that invalid paths and trusted proxies are rejected.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::models::router::Router;
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::{RouteHandler, OVERRIDE_PATH_HEADER};
+use serde_json::json;
+
+/// Starts a mock upstream echoing the path and query it was asked for and
+/// whether it received the override header.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let forwarded = req.headers().contains_key(OVERRIDE_PATH_HEADER);
+            HttpResponse::Ok().body(format!("{} header forwarded: {}", req.uri(), forwarded))
+        }))
+    })
+}
+
+fn route(port: u16) -> Router {
+    serde_json::from_value(json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": "/api/users/{id}",
+        "internal_path": "/v1/users/{id}",
+        "methods": ["GET"]
+    }))
+    .unwrap()
+}
+
+/// Sends `uri` from `peer` through a gateway with the given debug override
+/// networks, returning the status and response body.
+async fn call(
+    trusted: Option<&str>,
+    peer: &str,
+    uri: &str,
+    override_path: Option<&str>,
+) -> (u16, String) {
+    let mut handler = RouteHandler::new(vec![route(spawn_upstream())], 5);
+    if let Some(trusted) = trusted {
+        handler = handler.with_debug_overrides(vec![trusted.parse().unwrap()]);
+    }
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let mut req = test::TestRequest::get()
+        .uri(uri)
+        .peer_addr(peer.parse().unwrap());
+    if let Some(path) = override_path {
+        req = req.insert_header((OVERRIDE_PATH_HEADER, path));
+    }
+    let resp = test::call_service(&app, req.to_request()).await;
+    let status = resp.status().as_u16();
+    let body = test::read_body(resp).await;
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+#[actix_web::test]
+async fn test_trusted_peer_overrides_upstream_path() {
+    let (status, body) = call(
+        Some("10.0.0.0/8"),
+        "10.1.2.3:4000",
+        "/api/users/42?verbose=1",
+        Some("/internal/users/42/raw"),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "/internal/users/42/raw?verbose=1 header forwarded: false");
+}
+
+#[actix_web::test]
+async fn test_override_is_ignored_from_untrusted_peer() {
+    let (status, body) = call(
+        Some("10.0.0.0/8"),
+        "192.168.1.5:4000",
+        "/api/users/42",
+        Some("/internal/users/42/raw"),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "/v1/users/42 header forwarded: false");
+}
+
+#[actix_web::test]
+async fn test_override_is_ignored_when_disabled() {
+    let (status, body) = call(
+        None,
+        "10.1.2.3:4000",
+        "/api/users/42",
+        Some("/internal/users/42/raw"),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "/v1/users/42 header forwarded: false");
+}
+
+#[actix_web::test]
+async fn test_relative_override_path_is_rejected() {
+    let (status, body) = call(
+        Some("10.0.0.0/8"),
+        "10.1.2.3:4000",
+        "/api/users/42",
+        Some("internal/users"),
+    )
+    .await;
+    assert_eq!(status, 400);
+    assert!(body.contains(OVERRIDE_PATH_HEADER), "{body}");
+}
+
+#[actix_web::test]
+async fn test_debug_overrides_trusted_proxies_are_validated() {
+    let settings = |trusted_proxies: serde_json::Value| -> Settings {
+        serde_json::from_value(json!({
+            "version": 1,
+            "debug_overrides": { "trusted_proxies": trusted_proxies },
+            "routers": []
+        }))
+        .unwrap()
+    };
+
+    assert!(settings(json!(["10.20.0.0/16", "::1"])).validate().is_ok());
+    assert!(settings(json!(["qa.internal"])).validate().is_err());
+    assert!(settings(json!([])).validate().is_err());
+
+    let disabled: Settings = serde_json::from_value(json!({"version": 1, "routers": []})).unwrap();
+    assert!(disabled.debug_overrides.is_none());
+}
@@ -0,0 +1,131 @@
+//! Default upstream header tests
+//!
+//! Verifies that top-level `default_upstream_headers` are added to every
+//! forwarded request, replace client-sent values, can be overridden by a
+//! route's request transformation, and interpolate environment variables.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::config::settings::parse_settings;
+use kairos_rs::models::router::Router;
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream echoing the request headers back as JSON.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let headers: serde_json::Map<String, serde_json::Value> = req
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    (name.to_string(), value.to_str().unwrap_or_default().into())
+                })
+                .collect();
+            HttpResponse::Ok().json(headers)
+        }))
+    })
+}
+
+fn settings(port: u16, default_headers: serde_json::Value) -> Settings {
+    let route = |name: &str, transformation: serde_json::Value| {
+        serde_json::json!({
+            "backends": [{"host": "http://127.0.0.1", "port": port}],
+            "external_path": format!("/api/{}", name),
+            "internal_path": "/echo",
+            "methods": ["GET"],
+            "request_transformation": transformation
+        })
+    };
+    let config = serde_json::json!({
+        "version": 1,
+        "default_upstream_headers": default_headers,
+        "routers": [
+            route("plain", serde_json::Value::Null),
+            route("override", serde_json::json!({
+                "headers": [
+                    {"action": "set", "name": "X-Gateway-Id", "value": "route-specific"},
+                    {"action": "remove", "name": "X-Tenant"}
+                ]
+            }))
+        ]
+    });
+    parse_settings(&config.to_string()).unwrap()
+}
+
+async fn forwarded_headers(settings: &Settings, uri: &str) -> serde_json::Value {
+    let routes: Vec<Router> = settings.routers.clone();
+    let handler = RouteHandler::new(routes, 5)
+        .with_default_upstream_headers(settings.resolved_default_upstream_headers().unwrap());
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+    let req = test::TestRequest::get()
+        .uri(uri)
+        .insert_header(("X-Tenant", "spoofed"))
+        .insert_header(("X-Client", "kept"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    test::read_body_json(resp).await
+}
+
+#[actix_web::test]
+async fn test_default_headers_added_to_every_request() {
+    let port = spawn_upstream();
+    let settings = settings(
+        port,
+        serde_json::json!({"X-Gateway-Id": "kairos-edge", "X-Tenant": "acme"}),
+    );
+
+    let headers = forwarded_headers(&settings, "/api/plain").await;
+    assert_eq!(headers["x-gateway-id"], "kairos-edge");
+    // Client-sent values of a default header are replaced
+    assert_eq!(headers["x-tenant"], "acme");
+    assert_eq!(headers["x-client"], "kept");
+}
+
+#[actix_web::test]
+async fn test_route_transformation_overrides_default_headers() {
+    let port = spawn_upstream();
+    let settings = settings(
+        port,
+        serde_json::json!({"X-Gateway-Id": "kairos-edge", "X-Tenant": "acme"}),
+    );
+
+    let headers = forwarded_headers(&settings, "/api/override").await;
+    assert_eq!(headers["x-gateway-id"], "route-specific");
+    assert!(headers.get("x-tenant").is_none(), "{headers}");
+}
+
+#[actix_web::test]
+async fn test_default_headers_interpolate_environment() {
+    std::env::set_var("KAIROS_TEST_UPSTREAM_SECRET", "s3cret");
+    let port = spawn_upstream();
+    let settings = settings(
+        port,
+        serde_json::json!({"X-Upstream-Key": "key-${KAIROS_TEST_UPSTREAM_SECRET}"}),
+    );
+
+    let headers = forwarded_headers(&settings, "/api/plain").await;
+    assert_eq!(headers["x-upstream-key"], "key-s3cret");
+}
+
+#[actix_web::test]
+async fn test_invalid_default_headers_are_rejected() {
+    let invalid = [
+        serde_json::json!({"X-Key": "${KAIROS_TEST_UNSET_VARIABLE}"}),
+        serde_json::json!({"X-Key": "${KAIROS_TEST_UNCLOSED"}),
+        serde_json::json!({"Bad Header": "value"}),
+        serde_json::json!({"X-Key": "line\nbreak"}),
+    ];
+    for headers in invalid {
+        let mut settings = settings(8080, serde_json::json!({}));
+        settings.default_upstream_headers = serde_json::from_value(headers.clone()).unwrap();
+        let err = settings.validate().unwrap_err();
+        assert!(err.contains("default_upstream_headers"), "{headers}: {err}");
+    }
+}
@@ -0,0 +1,145 @@
+//! DNS discovery tests
+//!
+//! Verifies that discovered records are turned into backends, that routes
+//! using `dns_discovery` validate without inline backends, and that
+//! discovered backends are proxied to and survive route reloads.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{DnsDiscovery, DnsRecordType, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::discovery::{backends_from_srv, DnsDiscoveryResolver};
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream that answers every request with `discovered`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Ok().body("discovered") }))
+    })
+}
+
+fn discovery(name: &str, port: Option<u16>) -> DnsDiscovery {
+    DnsDiscovery {
+        name: name.to_string(),
+        record_type: DnsRecordType::A,
+        port,
+        scheme: "http".to_string(),
+        ttl_secs: 30,
+    }
+}
+
+fn route(dns_discovery: DnsDiscovery) -> Router {
+    Router {
+        external_path: "/api/users".to_string(),
+        internal_path: "/users".to_string(),
+        methods: vec!["GET".to_string()],
+        dns_discovery: Some(dns_discovery),
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_srv_records_use_lowest_priority() {
+    let mut discovery = discovery("_http._tcp.users.default.svc.cluster.local", None);
+    discovery.record_type = DnsRecordType::Srv;
+
+    let backends = backends_from_srv(
+        &discovery,
+        [
+            (10, 0, 8080, "users-1.users.default.svc.cluster.local.".to_string()),
+            (10, 5, 8081, "users-0.users.default.svc.cluster.local.".to_string()),
+            (20, 1, 8080, "users-backup.default.svc.cluster.local.".to_string()),
+        ],
+    );
+
+    let backends: Vec<_> = backends
+        .iter()
+        .map(|b| (b.host.as_str(), b.port, b.weight))
+        .collect();
+    assert_eq!(
+        backends,
+        [
+            ("http://users-0.users.default.svc.cluster.local", 8081, 5),
+            ("http://users-1.users.default.svc.cluster.local", 8080, 1),
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn test_discovery_route_validation() {
+    assert!(route(discovery("users.default.svc.cluster.local", Some(8080)))
+        .validate()
+        .is_ok());
+
+    let error = route(discovery("users.default.svc.cluster.local", None))
+        .validate()
+        .unwrap_err();
+    assert!(error.contains("Port is required"), "{error}");
+
+    let mut srv = discovery("_http._tcp.users", None);
+    srv.record_type = DnsRecordType::Srv;
+    assert!(route(srv).validate().is_ok());
+
+    let mut zero_ttl = discovery("users", Some(8080));
+    zero_ttl.ttl_secs = 0;
+    assert!(route(zero_ttl).validate().is_err());
+
+    let config: DnsDiscovery =
+        serde_json::from_value(serde_json::json!({ "name": "_http._tcp.users", "record_type": "srv" }))
+            .unwrap();
+    assert_eq!(config.record_type, DnsRecordType::Srv);
+    assert_eq!(config.scheme, "http");
+    assert_eq!(config.ttl_secs, 30);
+}
+
+#[actix_web::test]
+async fn test_discovered_backends_are_proxied() {
+    let port = spawn_upstream();
+    let discovery = discovery("127.0.0.1", Some(port));
+    let handler = RouteHandler::new(vec![route(discovery.clone())], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    // Nothing to forward to before the first resolution
+    let req = test::TestRequest::get().uri("/api/users").to_request();
+    assert!(test::call_service(&app, req).await.status().is_server_error());
+
+    assert_eq!(
+        handler.dns_discovery_routes(),
+        [("/api/users".to_string(), discovery.clone())]
+    );
+    let backends = DnsDiscoveryResolver::from_system_conf()
+        .resolve(&discovery)
+        .await
+        .unwrap();
+    assert_eq!(backends.len(), 1);
+    assert_eq!(backends[0].host, "http://127.0.0.1");
+    assert!(handler.set_discovered_backends("/api/users", &discovery, backends.clone()));
+    // Unchanged results do not rebuild the route table
+    assert!(!handler.set_discovered_backends("/api/users", &discovery, backends.clone()));
+
+    let req = test::TestRequest::get().uri("/api/users").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "discovered");
+
+    // A reload with the same discovery settings keeps the discovered backends
+    handler.reload_routes(vec![route(discovery.clone())]).unwrap();
+    let req = test::TestRequest::get().uri("/api/users").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    // Results for outdated discovery settings are ignored
+    let mut stale = discovery.clone();
+    stale.name = "users.old.svc.cluster.local".to_string();
+    assert!(!handler.set_discovered_backends("/api/users", &stale, vec![]));
+
+    // Changing the settings drops the old results until the next resolution
+    let mut changed = discovery;
+    changed.ttl_secs = 5;
+    handler.reload_routes(vec![route(changed)]).unwrap();
+    let req = test::TestRequest::get().uri("/api/users").to_request();
+    assert!(test::call_service(&app, req).await.status().is_server_error());
+}
@@ -3,6 +3,8 @@
 //! Tests for the DNS proxy handler including handler creation,
 //! cache operations, and basic functionality.
 
+#![allow(clippy::assertions_on_constants, clippy::drop_non_drop)]
+
 use kairos_rs::services::dns::DnsHandler;
 
 #[test]
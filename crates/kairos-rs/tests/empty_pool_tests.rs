@@ -0,0 +1,115 @@
+//! Empty backend pool tests
+//!
+//! Verifies that a route whose backends are all drained at runtime answers
+//! with the configured `errors.empty_pool_status` instead of a generic
+//! configuration error.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{Backend, DnsDiscovery, Router};
+use kairos_rs::models::settings::{EmptyPoolStatus, Settings};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use serde_json::{json, Value};
+
+/// Starts a mock upstream that always answers `200 OK`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Ok().body("ok") }))
+    })
+}
+
+fn discovery() -> DnsDiscovery {
+    serde_json::from_value(json!({
+        "name": "users.default.svc.cluster.local",
+        "port": 8080
+    }))
+    .unwrap()
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/users".to_string(),
+        internal_path: "/users".to_string(),
+        methods: vec!["GET".to_string()],
+        dns_discovery: Some(discovery()),
+        ..Default::default()
+    }
+}
+
+/// Drains the route's pool and returns the response to a request for it.
+async fn drained_response(handler: RouteHandler) -> (u16, Option<String>, Value) {
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/users").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    assert!(handler.set_discovered_backends("/api/users", &discovery(), vec![]));
+
+    let req = test::TestRequest::get().uri("/api/users").to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status().as_u16();
+    let retry_after = resp
+        .headers()
+        .get("Retry-After")
+        .map(|v| v.to_str().unwrap().to_string());
+    (status, retry_after, test::read_body_json(resp).await)
+}
+
+#[actix_web::test]
+async fn test_drained_pool_returns_service_unavailable_by_default() {
+    let handler = RouteHandler::new(vec![route(spawn_upstream())], 5);
+    let (status, retry_after, body) = drained_response(handler).await;
+
+    assert_eq!(status, 503);
+    assert!(retry_after.is_some());
+    assert_eq!(body["type"], "no_backends");
+    assert!(body["error"].as_str().unwrap().contains("/api/users"));
+}
+
+#[actix_web::test]
+async fn test_drained_pool_returns_configured_bad_gateway() {
+    let handler = RouteHandler::new(vec![route(spawn_upstream())], 5)
+        .with_empty_pool_status(EmptyPoolStatus::BadGateway);
+    let (status, retry_after, body) = drained_response(handler).await;
+
+    assert_eq!(status, 502);
+    assert_eq!(retry_after, None);
+    assert_eq!(body["type"], "no_backends");
+}
+
+#[actix_web::test]
+async fn test_empty_pool_status_setting() {
+    let settings: Settings = serde_json::from_value(json!({
+        "version": 1,
+        "errors": { "empty_pool_status": "bad_gateway" },
+        "routers": []
+    }))
+    .unwrap();
+    assert_eq!(
+        settings.errors.unwrap().empty_pool_status,
+        EmptyPoolStatus::BadGateway
+    );
+
+    let settings: Settings = serde_json::from_value(json!({
+        "version": 1,
+        "errors": {},
+        "routers": []
+    }))
+    .unwrap();
+    assert_eq!(
+        settings.errors.unwrap().empty_pool_status,
+        EmptyPoolStatus::ServiceUnavailable
+    );
+}
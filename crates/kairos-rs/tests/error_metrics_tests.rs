@@ -0,0 +1,151 @@
+//! Upstream error metrics tests
+//!
+//! Verifies that failed proxy requests are counted by cause: upstream
+//! timeouts, connection failures and open circuit breakers each have their
+//! own counter, on top of the request being counted once as an error, and
+//! that circuit-open rejections are exported per upstream.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::{Backend, CircuitBreakerSettings, Router};
+use kairos_rs::routes::metrics::{self, MetricsCollector};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// Starts a mock upstream that takes two seconds to answer.
+fn spawn_slow_upstream() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = HttpServer::new(|| {
+        App::new().default_service(web::to(|| async {
+            actix_web::rt::time::sleep(Duration::from_secs(2)).await;
+            HttpResponse::Ok().finish()
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    port
+}
+
+/// Returns a port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn route(port: u16, circuit_breaker: Option<CircuitBreakerSettings>) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: Some(1),
+        }]),
+        external_path: "/api/test".to_string(),
+        internal_path: "/test".to_string(),
+        methods: vec!["GET".to_string()],
+        circuit_breaker,
+        ..Default::default()
+    }
+}
+
+/// Sends `requests` requests to a gateway with `route` and returns its metrics.
+async fn send(route: Router, requests: usize) -> MetricsCollector {
+    let handler = RouteHandler::new(vec![route], 5);
+    let collector = MetricsCollector::default();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for _ in 0..requests {
+        let req = test::TestRequest::get().uri("/api/test").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_server_error());
+    }
+    collector
+}
+
+#[actix_web::test]
+async fn test_timeout_is_counted_as_timeout_error() {
+    let metrics = send(route(spawn_slow_upstream(), None), 1).await;
+
+    assert_eq!(metrics.timeout_errors.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.connection_errors.load(Ordering::Relaxed), 0);
+    assert_eq!(metrics.requests_total.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.requests_error.load(Ordering::Relaxed), 1);
+}
+
+#[actix_web::test]
+async fn test_refused_connection_is_counted_as_connection_error() {
+    let metrics = send(route(unused_port(), None), 1).await;
+
+    assert_eq!(metrics.connection_errors.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.timeout_errors.load(Ordering::Relaxed), 0);
+    assert_eq!(metrics.requests_total.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.requests_error.load(Ordering::Relaxed), 1);
+}
+
+#[actix_web::test]
+async fn test_open_circuit_is_counted_separately() {
+    let circuit_breaker = CircuitBreakerSettings {
+        failure_threshold: 1,
+        ..Default::default()
+    };
+    // The first request trips the breaker, the second is rejected by it
+    let metrics = send(route(unused_port(), Some(circuit_breaker)), 2).await;
+
+    assert_eq!(metrics.connection_errors.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.circuit_open_errors.load(Ordering::Relaxed), 1);
+    assert_eq!(metrics.requests_total.load(Ordering::Relaxed), 2);
+}
+
+#[actix_web::test]
+async fn test_circuit_open_rejections_are_exported_per_upstream() {
+    let port = unused_port();
+    let circuit_breaker = CircuitBreakerSettings {
+        failure_threshold: 1,
+        ..Default::default()
+    };
+    let handler = RouteHandler::new(vec![route(port, Some(circuit_breaker))], 5);
+    let collector = MetricsCollector::default();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    // The first request is an upstream failure, the next two never reach it
+    for _ in 0..3 {
+        let req = test::TestRequest::get().uri("/api/test").to_request();
+        test::call_service(&app, req).await;
+    }
+    let service = format!("http://127.0.0.1:{}", port);
+    assert_eq!(collector.circuit_open_rejections(), vec![(service.clone(), 2)]);
+
+    let sample = format!("kairos_circuit_open_rejections_total{{service=\"{}\"}} 2", service);
+    for accept in ["text/plain", "application/openmetrics-text"] {
+        let req = test::TestRequest::get()
+            .uri("/metrics")
+            .insert_header(("Accept", accept))
+            .to_request();
+        let body = test::read_body(test::call_service(&app, req).await).await;
+        let text = String::from_utf8_lossy(&body);
+        assert!(text.contains(&sample), "{text}");
+    }
+}
@@ -0,0 +1,201 @@
+//! Fault injection tests
+//!
+//! Verifies that routes with `fault_injection` abort, drop or delay requests
+//! only when the gateway opts in, and that the flag is refused in production.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::{Backend, FaultInjection, Router};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+/// Starts a mock upstream that answers every request with `ok`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Ok().body("ok") }))
+    })
+}
+
+fn route(port: u16, fault_injection: FaultInjection) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/chaos".to_string(),
+        internal_path: "/chaos".to_string(),
+        methods: vec!["GET".to_string()],
+        fault_injection: Some(fault_injection),
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_full_abort_rate_returns_503() {
+    let port = spawn_upstream();
+    let fault = FaultInjection {
+        abort_rate: 1.0,
+        ..Default::default()
+    };
+    let handler = RouteHandler::new(vec![route(port, fault)], 5).with_fault_injection(true);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for _ in 0..3 {
+        let req = test::TestRequest::get().uri("/api/chaos").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 503);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["type"], "fault_injected");
+    }
+}
+
+#[actix_web::test]
+async fn test_abort_uses_configured_status() {
+    let port = spawn_upstream();
+    let fault = FaultInjection {
+        abort_rate: 1.0,
+        abort_status: 500,
+        ..Default::default()
+    };
+    let handler = RouteHandler::new(vec![route(port, fault)], 5).with_fault_injection(true);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/chaos").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 500);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["type"], "fault_injected");
+}
+
+#[actix_web::test]
+async fn test_drop_closes_connection_without_response() {
+    let port = spawn_upstream();
+    let fault = FaultInjection {
+        drop_rate: 1.0,
+        abort_rate: 1.0,
+        ..Default::default()
+    };
+    let handler = RouteHandler::new(vec![route(port, fault)], 5).with_fault_injection(true);
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let gateway_port = listener.local_addr().unwrap().port();
+    let server = HttpServer::new(move || {
+        let handler = handler.clone();
+        App::new().configure(move |cfg| http::configure_route(cfg, handler))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+    actix_web::rt::spawn(server);
+
+    let response = actix_web::rt::task::spawn_blocking(move || {
+        let mut stream = TcpStream::connect(("127.0.0.1", gateway_port)).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        stream
+            .write_all(b"GET /api/chaos HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = Vec::new();
+        let _ = stream.read_to_end(&mut response);
+        response
+    })
+    .await
+    .unwrap();
+    assert!(response.is_empty(), "{}", String::from_utf8_lossy(&response));
+}
+
+#[actix_web::test]
+async fn test_delay_slows_response() {
+    let port = spawn_upstream();
+    let fault = FaultInjection {
+        delay_ms: 300,
+        delay_rate: 1.0,
+        ..Default::default()
+    };
+    let handler = RouteHandler::new(vec![route(port, fault)], 5).with_fault_injection(true);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let start = Instant::now();
+    let req = test::TestRequest::get().uri("/api/chaos").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+#[actix_web::test]
+async fn test_faults_ignored_unless_enabled() {
+    let port = spawn_upstream();
+    let fault = FaultInjection {
+        abort_rate: 1.0,
+        ..Default::default()
+    };
+    let handler = RouteHandler::new(vec![route(port, fault)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/chaos").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_fault_injection_validation() {
+    let fault: FaultInjection =
+        serde_json::from_value(serde_json::json!({ "abort_rate": 0.25 })).unwrap();
+    assert_eq!(fault.abort_rate, 0.25);
+    assert_eq!(fault.delay_ms, 0);
+    assert!(fault.validate().is_ok());
+
+    assert_eq!(fault.abort_status, 503);
+    assert_eq!(fault.drop_rate, 0.0);
+
+    let fault = FaultInjection {
+        delay_rate: 1.5,
+        ..Default::default()
+    };
+    assert!(fault.validate().is_err());
+
+    let fault = FaultInjection {
+        drop_rate: -0.1,
+        ..Default::default()
+    };
+    assert!(fault.validate().is_err());
+
+    let fault = FaultInjection {
+        abort_status: 200,
+        ..Default::default()
+    };
+    let err = fault.validate().unwrap_err();
+    assert!(err.contains("abort_status"), "{err}");
+}
+
+#[actix_web::test]
+async fn test_flag_refused_in_production() {
+    let mut settings: Settings =
+        serde_json::from_value(serde_json::json!({ "version": 1, "routers": [] })).unwrap();
+    assert!(!settings.fault_injection_enabled);
+    assert!(settings.validate_fault_injection(true).is_ok());
+
+    settings.fault_injection_enabled = true;
+    assert!(settings.validate_fault_injection(false).is_ok());
+    let err = settings.validate_fault_injection(true).unwrap_err();
+    assert!(err.contains("production"), "{err}");
+}
@@ -0,0 +1,119 @@
+//! Form to JSON body transformation tests
+//!
+//! Verifies that a route with `"body": "form_to_json"` forwards
+//! form-encoded bodies as JSON objects with matching `Content-Type` and
+//! `Content-Length`, that repeated keys become arrays, and that other bodies
+//! pass through unchanged.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::middleware::transform::{BodyTransformation, RequestTransformation};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use serde_json::{json, Value};
+
+/// Starts a mock upstream that echoes the body and framing headers it received.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest, body: web::Bytes| async move {
+            let header = |name: &str| {
+                req.headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+            };
+            HttpResponse::Ok().json(json!({
+                "content_type": header("content-type"),
+                "content_length": header("content-length"),
+                "body": String::from_utf8_lossy(&body),
+            }))
+        }))
+    })
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/signup".to_string(),
+        internal_path: "/signup".to_string(),
+        methods: vec!["POST".to_string()],
+        request_transformation: Some(RequestTransformation {
+            body: Some(BodyTransformation::FormToJson),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Posts `body` with `content_type` and returns what the upstream received.
+async fn forward(content_type: &str, body: &'static str) -> Value {
+    let handler = RouteHandler::new(vec![route(spawn_upstream())], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/signup")
+        .insert_header(("Content-Type", content_type))
+        .set_payload(body)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    test::read_body_json(resp).await
+}
+
+#[actix_web::test]
+async fn test_form_body_forwarded_as_json() {
+    let received = forward(
+        "application/x-www-form-urlencoded",
+        "name=Ada+Lovelace&email=ada%40example.com",
+    )
+    .await;
+
+    assert_eq!(received["content_type"], "application/json");
+    let body = received["body"].as_str().unwrap();
+    assert_eq!(received["content_length"], body.len().to_string());
+    assert_eq!(
+        serde_json::from_str::<Value>(body).unwrap(),
+        json!({ "name": "Ada Lovelace", "email": "ada@example.com" })
+    );
+}
+
+#[actix_web::test]
+async fn test_repeated_keys_become_arrays() {
+    let received = forward(
+        "application/x-www-form-urlencoded; charset=UTF-8",
+        "role=admin&team=core&role=ops&role=dev",
+    )
+    .await;
+
+    let body: Value = serde_json::from_str(received["body"].as_str().unwrap()).unwrap();
+    assert_eq!(body, json!({ "role": ["admin", "ops", "dev"], "team": "core" }));
+}
+
+#[actix_web::test]
+async fn test_other_content_types_pass_through() {
+    let received = forward("text/plain", "name=Ada&role=admin").await;
+
+    assert_eq!(received["content_type"], "text/plain");
+    assert_eq!(received["body"], "name=Ada&role=admin");
+}
+
+#[actix_web::test]
+async fn test_body_transformation_setting() {
+    let transformation: RequestTransformation =
+        serde_json::from_value(json!({ "body": "form_to_json" })).unwrap();
+    assert_eq!(transformation.body, Some(BodyTransformation::FormToJson));
+
+    let transformation: RequestTransformation = serde_json::from_value(json!({})).unwrap();
+    assert_eq!(transformation.body, None);
+}
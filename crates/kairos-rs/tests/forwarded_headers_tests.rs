@@ -0,0 +1,241 @@
+//! Forwarded header tests
+//!
+//! Verifies that with `forwarded_headers` enabled upstream requests carry
+//! `X-Forwarded-Proto`, `X-Forwarded-Host` and `X-Forwarded-Port` describing
+//! the external request rather than the gateway's bind address, on plain and
+//! TLS listeners, and that forwarding headers sent by clients are ignored.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse, HttpServer};
+use kairos_rs::config::tls::TlsSettings;
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::models::settings::{ForwardedHeadersSettings, Settings};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use serde_json::{json, Value};
+use std::net::TcpListener;
+use std::path::PathBuf;
+
+/// Starts a mock upstream that echoes the forwarded headers it received.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let header = |name: &str| {
+                req.headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+            };
+            HttpResponse::Ok().json(json!({
+                "proto": header("x-forwarded-proto"),
+                "host": header("x-forwarded-host"),
+                "port": header("x-forwarded-port"),
+            }))
+        }))
+    })
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/echo".to_string(),
+        internal_path: "/echo".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+/// Sends `req` through a gateway configured with `forwarded` and returns
+/// the forwarded headers the upstream saw.
+async fn forwarded(forwarded: Option<ForwardedHeadersSettings>, req: test::TestRequest) -> Value {
+    let mut handler = RouteHandler::new(vec![route(spawn_upstream())], 5);
+    if let Some(settings) = forwarded {
+        handler = handler.with_forwarded_headers(settings);
+    }
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let resp = test::call_service(&app, req.to_request()).await;
+    assert!(resp.status().is_success());
+    test::read_body_json(resp).await
+}
+
+/// Port test services listen on, as far as requests can tell
+const TEST_SERVICE_PORT: &str = "8080";
+
+#[actix_web::test]
+async fn test_tls_request_forwards_https_and_listening_port() {
+    let req = test::TestRequest::get()
+        .uri("https://api.example.com:8443/api/echo")
+        .insert_header(("Host", "api.example.com:8443"));
+    let headers = forwarded(Some(ForwardedHeadersSettings::default()), req).await;
+
+    assert_eq!(headers["proto"], "https");
+    assert_eq!(headers["host"], "api.example.com:8443");
+    assert_eq!(headers["port"], TEST_SERVICE_PORT);
+}
+
+#[actix_web::test]
+async fn test_port_defaults_to_listening_port() {
+    let req = test::TestRequest::get()
+        .uri("/api/echo")
+        .insert_header(("Host", "api.example.com"));
+    let headers = forwarded(Some(ForwardedHeadersSettings::default()), req).await;
+
+    assert_eq!(headers["proto"], "http");
+    assert_eq!(headers["host"], "api.example.com");
+    assert_eq!(headers["port"], TEST_SERVICE_PORT);
+}
+
+#[actix_web::test]
+async fn test_configured_values_describe_tls_terminated_in_front() {
+    let settings = ForwardedHeadersSettings {
+        scheme: Some("https".to_string()),
+        port: Some(443),
+    };
+    let req = test::TestRequest::get()
+        .uri("/api/echo")
+        .insert_header(("Host", "api.example.com"))
+        .insert_header(("X-Forwarded-Port", "1234"));
+    let headers = forwarded(Some(settings), req).await;
+
+    assert_eq!(headers["proto"], "https");
+    assert_eq!(headers["host"], "api.example.com");
+    assert_eq!(headers["port"], "443");
+}
+
+#[actix_web::test]
+async fn test_no_forwarded_headers_by_default() {
+    let req = test::TestRequest::get()
+        .uri("/api/echo")
+        .insert_header(("Host", "api.example.com"));
+    let headers = forwarded(None, req).await;
+
+    assert_eq!(headers["proto"], Value::Null);
+    assert_eq!(headers["port"], Value::Null);
+}
+
+#[actix_web::test]
+async fn test_client_forwarding_headers_are_ignored() {
+    let req = test::TestRequest::get()
+        .uri("/api/echo")
+        .insert_header(("Host", "api.example.com"))
+        .insert_header(("X-Forwarded-Proto", "https"))
+        .insert_header(("X-Forwarded-Host", "evil.example.com:8443"))
+        .insert_header(("Forwarded", "proto=https;host=evil.example.com"));
+    let headers = forwarded(Some(ForwardedHeadersSettings::default()), req).await;
+
+    assert_eq!(headers["proto"], "http");
+    assert_eq!(headers["host"], "api.example.com");
+    assert_eq!(headers["port"], TEST_SERVICE_PORT);
+}
+
+/// Starts a gateway with forwarded headers on a real listener, using TLS if
+/// `tls` is set, and returns its port.
+fn spawn_gateway(tls: bool) -> u16 {
+    let handler = RouteHandler::new(vec![route(spawn_upstream())], 5)
+        .with_forwarded_headers(ForwardedHeadersSettings::default());
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = HttpServer::new(move || {
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone()))
+    })
+    .workers(1);
+    let server = if tls {
+        let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+        let tls = TlsSettings {
+            cert_path: fixtures.join("cert.pem"),
+            key_path: fixtures.join("key.pem"),
+            redirect_port: None,
+        };
+        server
+            .listen_rustls_0_23(listener, tls.load_server_config().unwrap())
+            .unwrap()
+    } else {
+        server.listen(listener).unwrap()
+    };
+    actix_web::rt::spawn(server.run());
+    port
+}
+
+/// Requests `url` from a real client and checks that the forwarded headers
+/// the upstream saw rebuild it.
+async fn assert_forwarded_headers_rebuild(url: &str, scheme: &str, port: u16) {
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    let headers: Value = client
+        .get(url)
+        .header("X-Forwarded-Proto", "gopher")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(headers["proto"], scheme);
+    assert_eq!(headers["host"], format!("127.0.0.1:{}", port));
+    assert_eq!(headers["port"], port.to_string());
+
+    let rebuilt = format!(
+        "{}://{}/api/echo",
+        headers["proto"].as_str().unwrap(),
+        headers["host"].as_str().unwrap()
+    );
+    assert_eq!(rebuilt, url);
+}
+
+#[actix_web::test]
+async fn test_plain_listener_forwards_listening_port() {
+    let port = spawn_gateway(false);
+    let url = format!("http://127.0.0.1:{}/api/echo", port);
+    assert_forwarded_headers_rebuild(&url, "http", port).await;
+}
+
+#[actix_web::test]
+async fn test_tls_listener_forwards_https_and_listening_port() {
+    let port = spawn_gateway(true);
+    let url = format!("https://127.0.0.1:{}/api/echo", port);
+    assert_forwarded_headers_rebuild(&url, "https", port).await;
+}
+
+#[actix_web::test]
+async fn test_host_without_port_forwards_listening_port() {
+    let port = spawn_gateway(false);
+    let headers: Value = reqwest::Client::new()
+        .get(format!("http://127.0.0.1:{}/api/echo", port))
+        .header("Host", "api.example.com")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(headers["proto"], "http");
+    assert_eq!(headers["host"], "api.example.com");
+    assert_eq!(headers["port"], port.to_string());
+}
+
+#[actix_web::test]
+async fn test_unknown_scheme_is_invalid() {
+    let settings: Settings = serde_json::from_value(json!({
+        "version": 1,
+        "forwarded_headers": { "scheme": "ftp" },
+        "routers": []
+    }))
+    .unwrap();
+    assert!(settings.validate().is_err());
+}
@@ -3,6 +3,8 @@
 //! Tests for the FTP proxy handler including handler creation
 //! and basic functionality.
 
+#![allow(clippy::assertions_on_constants, clippy::drop_non_drop)]
+
 use kairos_rs::services::ftp::FtpHandler;
 
 #[test]
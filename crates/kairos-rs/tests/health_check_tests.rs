@@ -0,0 +1,314 @@
+//! Background health check tests
+//!
+//! Verifies that backends failing their health checks are taken out of
+//! rotation and put back once they recover, that a route whose backends are
+//! all down still forwards requests, that probes can require a status and body
+//! content, and that health is reported on `/metrics`.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::{Backend, HealthCheckSettings, Router};
+use kairos_rs::routes::{http, metrics};
+use kairos_rs::services::health_check::spawn_health_checks;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Starts a mock upstream whose `/healthz` answers `200` while the returned
+/// flag is set and `503` otherwise. Other paths answer with the port.
+fn spawn_upstream() -> (u16, Arc<AtomicBool>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let healthy = Arc::new(AtomicBool::new(true));
+    let flag = healthy.clone();
+
+    let server = HttpServer::new(move || {
+        let flag = flag.clone();
+        App::new()
+            .route(
+                "/healthz",
+                web::get().to(move || {
+                    let healthy = flag.load(Ordering::SeqCst);
+                    async move {
+                        if healthy {
+                            HttpResponse::Ok().finish()
+                        } else {
+                            HttpResponse::ServiceUnavailable().finish()
+                        }
+                    }
+                }),
+            )
+            .default_service(web::to(move || async move {
+                HttpResponse::Ok().body(port.to_string())
+            }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    (port, healthy)
+}
+
+/// Starts a mock upstream whose `/healthz` answers with the status and body in
+/// the returned cell.
+fn spawn_reporting_upstream() -> (u16, Arc<Mutex<(u16, &'static str)>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let report = Arc::new(Mutex::new((200, "{\"status\":\"ok\"}")));
+    let cell = report.clone();
+
+    let server = HttpServer::new(move || {
+        let cell = cell.clone();
+        App::new().route(
+            "/healthz",
+            web::get().to(move || {
+                let (status, body) = *cell.lock().unwrap();
+                async move {
+                    HttpResponse::build(actix_web::http::StatusCode::from_u16(status).unwrap())
+                        .body(body)
+                }
+            }),
+        )
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    (port, report)
+}
+
+fn route(ports: &[u16], health_check: HealthCheckSettings) -> Router {
+    Router {
+        backends: Some(
+            ports
+                .iter()
+                .map(|port| Backend {
+                    host: "http://127.0.0.1".to_string(),
+                    port: *port,
+                    weight: 1,
+                    health_check_path: Some("/healthz".to_string()),
+                    timeout_secs: None,
+                })
+                .collect(),
+        ),
+        external_path: "/api/items".to_string(),
+        internal_path: "/items".to_string(),
+        methods: vec!["GET".to_string()],
+        health_check: Some(health_check),
+        ..Default::default()
+    }
+}
+
+fn thresholds(unhealthy_threshold: u32, healthy_threshold: u32) -> HealthCheckSettings {
+    HealthCheckSettings {
+        interval_secs: 1,
+        timeout_secs: 1,
+        unhealthy_threshold,
+        healthy_threshold,
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_unhealthy_backend_leaves_rotation() {
+    let (up, _) = spawn_upstream();
+    let (flaky, flaky_healthy) = spawn_upstream();
+    let settings = thresholds(2, 2);
+    let handler = RouteHandler::new(vec![route(&[up, flaky], settings.clone())], 5);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(handler.clone()))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+    let flaky_service = format!("http://127.0.0.1:{}", flaky);
+
+    // One failed probe is not enough to take the backend out
+    flaky_healthy.store(false, Ordering::SeqCst);
+    assert!(handler.check_backend_health("/api/items", &settings).await.is_empty());
+    assert!(handler.backend_health()[&flaky_service]);
+
+    let transitions = handler.check_backend_health("/api/items", &settings).await;
+    assert_eq!(transitions, vec![(flaky_service.clone(), false)]);
+
+    for _ in 0..4 {
+        let req = test::TestRequest::get().uri("/api/items").to_request();
+        let body = test::read_body(test::call_service(&app, req).await).await;
+        assert_eq!(body, up.to_string());
+    }
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let text = String::from_utf8_lossy(&body);
+    assert!(text.contains("# TYPE kairos_backend_healthy gauge"), "{text}");
+    assert!(
+        text.contains(&format!("kairos_backend_healthy{{service=\"{}\"}} 0", flaky_service)),
+        "{text}"
+    );
+    assert!(
+        text.contains(&format!("kairos_backend_healthy{{service=\"http://127.0.0.1:{}\"}} 1", up)),
+        "{text}"
+    );
+
+    // Two passing probes put it back
+    flaky_healthy.store(true, Ordering::SeqCst);
+    assert!(handler.check_backend_health("/api/items", &settings).await.is_empty());
+    let transitions = handler.check_backend_health("/api/items", &settings).await;
+    assert_eq!(transitions, vec![(flaky_service.clone(), true)]);
+
+    let mut seen = Vec::new();
+    for _ in 0..2 {
+        let req = test::TestRequest::get().uri("/api/items").to_request();
+        seen.push(test::read_body(test::call_service(&app, req).await).await);
+    }
+    assert!(seen.contains(&flaky.to_string().into()));
+
+    // Health survives a route reload
+    flaky_healthy.store(false, Ordering::SeqCst);
+    handler.check_backend_health("/api/items", &settings).await;
+    handler.check_backend_health("/api/items", &settings).await;
+    handler.reload_routes(vec![route(&[up, flaky], settings)]).unwrap();
+    assert!(!handler.backend_health()[&flaky_service]);
+}
+
+#[actix_web::test]
+async fn test_all_backends_down_still_forwards() {
+    let (first, first_healthy) = spawn_upstream();
+    let (second, second_healthy) = spawn_upstream();
+    let settings = thresholds(1, 1);
+    let handler = RouteHandler::new(vec![route(&[first, second], settings.clone())], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    first_healthy.store(false, Ordering::SeqCst);
+    second_healthy.store(false, Ordering::SeqCst);
+    assert_eq!(handler.check_backend_health("/api/items", &settings).await.len(), 2);
+    assert!(handler.backend_health().values().all(|healthy| !healthy));
+
+    let req = test::TestRequest::get().uri("/api/items").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_background_task_marks_backend_down() {
+    let (port, healthy) = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(&[port], thresholds(1, 1))], 5);
+    let service = format!("http://127.0.0.1:{}", port);
+
+    healthy.store(false, Ordering::SeqCst);
+    let task = spawn_health_checks(handler.clone());
+    let mut down = false;
+    for _ in 0..50 {
+        if !handler.backend_health()[&service] {
+            down = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    task.abort();
+    assert!(down);
+    assert!(!handler.backend_statuses()[0].up);
+}
+
+#[actix_web::test]
+async fn test_expected_status_replaces_any_2xx() {
+    let (port, report) = spawn_reporting_upstream();
+    let settings = HealthCheckSettings {
+        expected_status: Some(204),
+        ..thresholds(1, 1)
+    };
+    let handler = RouteHandler::new(vec![route(&[port], settings.clone())], 5);
+    let service = format!("http://127.0.0.1:{}", port);
+
+    // A 200 is not the expected 204
+    let transitions = handler.check_backend_health("/api/items", &settings).await;
+    assert_eq!(transitions, vec![(service.clone(), false)]);
+
+    *report.lock().unwrap() = (204, "");
+    let transitions = handler.check_backend_health("/api/items", &settings).await;
+    assert_eq!(transitions, vec![(service.clone(), true)]);
+
+    // Any expected status counts, even a non-2xx one
+    let settings = HealthCheckSettings {
+        expected_status: Some(418),
+        ..thresholds(1, 1)
+    };
+    *report.lock().unwrap() = (418, "");
+    assert!(handler.check_backend_health("/api/items", &settings).await.is_empty());
+    assert!(handler.backend_health()[&service]);
+}
+
+#[actix_web::test]
+async fn test_expected_body_contains_detects_degraded_backend() {
+    let (port, report) = spawn_reporting_upstream();
+    let settings = HealthCheckSettings {
+        expected_status: Some(200),
+        expected_body_contains: Some("\"status\":\"ok\"".to_string()),
+        ..thresholds(1, 1)
+    };
+    let handler = RouteHandler::new(vec![route(&[port], settings.clone())], 5);
+    let service = format!("http://127.0.0.1:{}", port);
+
+    assert!(handler.check_backend_health("/api/items", &settings).await.is_empty());
+    assert!(handler.backend_health()[&service]);
+
+    // Responding, but not actually healthy
+    *report.lock().unwrap() = (200, "{\"status\":\"degraded\"}");
+    let transitions = handler.check_backend_health("/api/items", &settings).await;
+    assert_eq!(transitions, vec![(service.clone(), false)]);
+
+    // The body alone is not enough when the status does not match
+    *report.lock().unwrap() = (503, "{\"status\":\"ok\"}");
+    assert!(handler.check_backend_health("/api/items", &settings).await.is_empty());
+    assert!(!handler.backend_health()[&service]);
+
+    *report.lock().unwrap() = (200, "{\"status\":\"ok\"}");
+    let transitions = handler.check_backend_health("/api/items", &settings).await;
+    assert_eq!(transitions, vec![(service, true)]);
+}
+
+#[actix_web::test]
+async fn test_health_check_validation() {
+    let settings: HealthCheckSettings =
+        serde_json::from_value(serde_json::json!({ "interval_secs": 5 })).unwrap();
+    assert_eq!(settings.interval_secs, 5);
+    assert_eq!(settings.unhealthy_threshold, 3);
+    assert_eq!(settings.healthy_threshold, 2);
+    assert!(settings.validate().is_ok());
+
+    let settings = HealthCheckSettings {
+        unhealthy_threshold: 0,
+        ..Default::default()
+    };
+    assert!(settings.validate().is_err());
+
+    let settings: HealthCheckSettings = serde_json::from_value(serde_json::json!({
+        "expected_status": 200,
+        "expected_body_contains": "ok"
+    }))
+    .unwrap();
+    assert_eq!(settings.expected_status, Some(200));
+    assert_eq!(settings.expected_body_contains.as_deref(), Some("ok"));
+    assert!(settings.validate().is_ok());
+
+    let settings = HealthCheckSettings {
+        expected_status: Some(42),
+        ..Default::default()
+    };
+    assert!(settings.validate().unwrap_err().contains("expected_status"));
+
+    let settings = HealthCheckSettings {
+        expected_body_contains: Some(String::new()),
+        ..Default::default()
+    };
+    assert!(settings.validate().unwrap_err().contains("expected_body_contains"));
+}
@@ -49,8 +49,8 @@ async fn test_metrics_endpoint() {
     let metrics_collector = metrics::MetricsCollector::default();
     
     // Record some test metrics
-    metrics_collector.record_request(true, Duration::from_millis(100), 200, Some(1024), Some(2048));
-    metrics_collector.record_request(false, Duration::from_millis(200), 500, Some(512), Some(0));
+    metrics_collector.record_request(true, Duration::from_millis(100), 200, Some(1024), Some(2048), None);
+    metrics_collector.record_request(false, Duration::from_millis(200), 500, Some(512), Some(0), None);
     metrics_collector.increment_connections();
 
     let app = test::init_service(
@@ -147,6 +147,7 @@ async fn test_circuit_breaker_functionality() {
         success_threshold: 2,
         timeout: Duration::from_secs(1),
         reset_timeout: Duration::from_millis(100),
+        warmup: Duration::ZERO,
     };
     
     let cb = CircuitBreaker::new("test-service".to_string(), config);
@@ -291,7 +292,8 @@ async fn test_concurrent_requests() {
                 duration, 
                 if i % 10 != 0 { 200 } else { 500 }, 
                 Some(1024), 
-                Some(2048)
+                Some(2048),
+                None
             ); // 90% success rate
         });
         
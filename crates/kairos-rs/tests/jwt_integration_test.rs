@@ -4,7 +4,7 @@ use actix_web::{test, App};
 use kairos_rs::{
     middleware::auth::{create_test_token, Claims},
     models::{
-        router::{Backend, Protocol, Router},
+        router::{Backend, Router},
         settings::{JwtSettings, Settings},
     },
     routes::auth_http,
@@ -28,7 +28,25 @@ fn create_test_settings() -> Settings {
         version: 1,
         jwt: Some(create_test_jwt_config()),
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![
             // Public route - no authentication required
             Router {
@@ -37,19 +55,14 @@ fn create_test_settings() -> Settings {
                 external_path: "/public/test".to_string(),
                 internal_path: "/status/200".to_string(),
                 methods: vec!["GET".to_string()],
-                auth_required: false,
                 backends: Some(vec![Backend {
                     host: "http://httpbin.org".to_string(),
                     port: 80,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }]),
-                load_balancing_strategy: Default::default(),
-                retry: None,
-                protocol: Protocol::Http,
-                request_transformation: None,
-                response_transformation: None,
-                ai_policy: None,
+                ..Default::default()
             },
             // Protected route - authentication required
             Router {
@@ -64,13 +77,9 @@ fn create_test_settings() -> Settings {
                     port: 80,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }]),
-                load_balancing_strategy: Default::default(),
-                retry: None,
-                protocol: Protocol::Http,
-                request_transformation: None,
-                response_transformation: None,
-                ai_policy: None,
+                ..Default::default()
             },
         ],
     }
@@ -190,7 +199,25 @@ async fn test_jwt_config_validation() {
         version: 1,
         jwt: None,
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![Router {
             host: Some("http://example.com".to_string()),
             port: Some(80),
@@ -203,13 +230,9 @@ async fn test_jwt_config_validation() {
                 port: 80,
                 weight: 1,
                 health_check_path: None,
+                timeout_secs: None,
             }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }],
     };
 
@@ -233,7 +256,25 @@ async fn test_jwt_secret_validation() {
             required_claims: vec![],
         }),
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![Router {
             host: Some("http://example.com".to_string()),
             port: Some(80),
@@ -246,13 +287,9 @@ async fn test_jwt_secret_validation() {
                 port: 80,
                 weight: 1,
                 health_check_path: None,
+                timeout_secs: None,
             }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }],
     };
 
@@ -269,7 +306,25 @@ async fn test_jwt_secret_validation() {
             required_claims: vec![],
         }),
         rate_limit: None,
+        disable_rate_limiting: false,
         ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
         routers: vec![Router {
             host: Some("http://example.com".to_string()),
             port: Some(80),
@@ -282,13 +337,9 @@ async fn test_jwt_secret_validation() {
                 port: 80,
                 weight: 1,
                 health_check_path: None,
+                timeout_secs: None,
             }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }],
     };
 
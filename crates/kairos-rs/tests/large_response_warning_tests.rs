@@ -0,0 +1,134 @@
+//! Large response warning tests
+//!
+//! Verifies that routes with `warn_response_bytes` log a warning naming the
+//! request path and size for buffered and streamed responses over the
+//! threshold, and stay quiet for smaller responses and other routes.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::sync::{Mutex, Once};
+
+/// Warnings logged by the gateway during the tests.
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CapturingLogger;
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            WARNINGS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Starts capturing warnings, once per test binary.
+fn capture_warnings() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&CapturingLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Warn);
+    });
+}
+
+/// Returns the captured warnings that mention `path`.
+fn warnings_for(path: &str) -> Vec<String> {
+    WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.contains(path))
+        .cloned()
+        .collect()
+}
+
+/// Starts a mock upstream answering `/sized/{n}` with `n` bytes and
+/// `/streamed/{n}` with `n` bytes sent without a `Content-Length`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new()
+            .route(
+                "/sized/{n}",
+                web::get().to(|n: web::Path<usize>| async move {
+                    HttpResponse::Ok().body(vec![b'x'; n.into_inner()])
+                }),
+            )
+            .route(
+                "/streamed/{n}",
+                web::get().to(|n: web::Path<usize>| async move {
+                    let chunk = web::Bytes::from(vec![b'x'; n.into_inner()]);
+                    HttpResponse::Ok()
+                        .streaming(futures::stream::once(async { Ok::<_, std::io::Error>(chunk) }))
+                }),
+            )
+    })
+}
+
+fn route(port: u16, external_path: &str, warn_response_bytes: Option<u64>) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": format!("{}/{{mode}}/{{n}}", external_path),
+        "internal_path": "/{mode}/{n}",
+        "methods": ["GET"],
+        "warn_response_bytes": warn_response_bytes
+    }))
+    .unwrap()
+}
+
+async fn get(handler: &RouteHandler, uri: &str) {
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+    let req = test::TestRequest::get().uri(uri).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    test::read_body(resp).await;
+}
+
+#[actix_web::test]
+async fn test_large_responses_are_logged() {
+    capture_warnings();
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "/api/limited", Some(1000))], 5);
+
+    for mode in ["sized", "streamed"] {
+        get(&handler, &format!("/api/limited/{}/1000", mode)).await;
+        assert!(warnings_for(&format!("/api/limited/{}/1000", mode)).is_empty());
+
+        let path = format!("/api/limited/{}/1500", mode);
+        get(&handler, &path).await;
+        let warnings = warnings_for(&path);
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("1500 bytes"), "{}", warnings[0]);
+        assert!(warnings[0].contains("(1000)"), "{}", warnings[0]);
+    }
+}
+
+#[actix_web::test]
+async fn test_routes_without_threshold_do_not_warn() {
+    capture_warnings();
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "/api/unlimited", None)], 5);
+
+    for mode in ["sized", "streamed"] {
+        get(&handler, &format!("/api/unlimited/{}/100000", mode)).await;
+    }
+    assert!(warnings_for("/api/unlimited").is_empty());
+}
+
+#[actix_web::test]
+async fn test_zero_threshold_is_invalid() {
+    let err = route(8080, "/api/zero", Some(0)).validate().unwrap_err();
+    assert!(err.contains("warn_response_bytes"), "{err}");
+    assert!(route(8080, "/api/zero", Some(1)).validate().is_ok());
+}
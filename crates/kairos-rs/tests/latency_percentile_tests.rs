@@ -0,0 +1,79 @@
+//! Response time percentile tests
+//!
+//! Verifies that `/metrics` reports p50/p95/p99 response time gauges that
+//! reflect tail latency, and a Prometheus histogram with cumulative
+//! `_bucket`, `_sum` and `_count` series usable by `histogram_quantile`.
+
+use actix_web::{test, web, App};
+use kairos_rs::routes::metrics::{self, MetricsCollector};
+use std::time::Duration;
+
+async fn scrape(collector: MetricsCollector, accept: Option<&str>) -> String {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let mut req = test::TestRequest::get().uri("/metrics");
+    if let Some(accept) = accept {
+        req = req.insert_header(("Accept", accept));
+    }
+    let resp = test::call_service(&app, req.to_request()).await;
+    assert_eq!(resp.status(), 200);
+    String::from_utf8(test::read_body(resp).await.to_vec()).unwrap()
+}
+
+/// Records 98 fast requests and 2 slow ones.
+fn collector_with_slow_tail() -> MetricsCollector {
+    let collector = MetricsCollector::default();
+    for _ in 0..98 {
+        collector.record_request(true, Duration::from_millis(20), 200, None, None, None);
+    }
+    for _ in 0..2 {
+        collector.record_request(true, Duration::from_millis(6_000), 200, None, None, None);
+    }
+    collector
+}
+
+fn gauge(body: &str, name: &str) -> f64 {
+    body.lines()
+        .find_map(|line| line.strip_prefix(&format!("{} ", name)))
+        .unwrap_or_else(|| panic!("{} missing from:\n{}", name, body))
+        .parse()
+        .unwrap()
+}
+
+#[actix_web::test]
+async fn test_percentiles_expose_tail_latency() {
+    let [p50, p95, p99] = collector_with_slow_tail().response_time_percentiles();
+    assert!((p50 - 20.0).abs() < 1.0, "p50 = {}", p50);
+    assert!((p95 - 20.0).abs() < 1.0, "p95 = {}", p95);
+    assert!((p99 - 6_000.0).abs() < 6_000.0 * 0.04, "p99 = {}", p99);
+}
+
+#[actix_web::test]
+async fn test_prometheus_output_has_percentiles_and_histogram() {
+    let body = scrape(collector_with_slow_tail(), None).await;
+
+    assert!(gauge(&body, "kairos_response_time_p50") < 21.0);
+    assert!(gauge(&body, "kairos_response_time_p95") < 21.0);
+    assert!(gauge(&body, "kairos_response_time_p99") > 5_000.0);
+
+    assert!(body.contains("# TYPE kairos_response_time histogram\n"));
+    assert!(body.contains("kairos_response_time_bucket{le=\"100\"} 98\n"));
+    assert!(body.contains("kairos_response_time_bucket{le=\"5000\"} 98\n"));
+    assert!(body.contains("kairos_response_time_bucket{le=\"+Inf\"} 100\n"));
+    assert!(body.contains("kairos_response_time_sum 13960\n"));
+    assert!(body.contains("kairos_response_time_count 100\n"));
+}
+
+#[actix_web::test]
+async fn test_openmetrics_output_has_percentiles() {
+    let body = scrape(collector_with_slow_tail(), Some("application/openmetrics-text")).await;
+
+    assert!(body.contains("# TYPE kairos_response_time_p99 gauge\n"));
+    assert!(gauge(&body, "kairos_response_time_p50") < 21.0);
+    assert!(gauge(&body, "kairos_response_time_p99") > 5_000.0);
+}
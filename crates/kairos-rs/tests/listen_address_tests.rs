@@ -0,0 +1,66 @@
+//! Listen address tests
+//!
+//! Verifies that `server.listen` and `KAIROS_BIND` style lists parse into
+//! socket addresses, IPv4 and IPv6 alike, and that malformed or empty lists
+//! are rejected by configuration validation.
+
+use kairos_rs::models::settings::{parse_listen_addresses, ServerSettings, Settings};
+use serde_json::json;
+use std::net::SocketAddr;
+
+fn settings(listen: serde_json::Value) -> Settings {
+    serde_json::from_value(json!({
+        "version": 1,
+        "server": { "listen": listen },
+        "routers": []
+    }))
+    .unwrap()
+}
+
+#[actix_web::test]
+async fn test_listen_addresses_parse() {
+    let server = ServerSettings {
+        listen: vec![
+            "0.0.0.0:5900".to_string(),
+            "[::]:5900".to_string(),
+            "127.0.0.1:5901".to_string(),
+        ],
+    };
+    let addresses = server.listen_addresses().unwrap();
+    assert_eq!(
+        addresses,
+        vec![
+            "0.0.0.0:5900".parse::<SocketAddr>().unwrap(),
+            "[::]:5900".parse().unwrap(),
+            "127.0.0.1:5901".parse().unwrap(),
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn test_bind_list_tolerates_spaces() {
+    let addresses = parse_listen_addresses(" 0.0.0.0:8080 ,[::1]:8081".split(',')).unwrap();
+    assert_eq!(addresses.len(), 2);
+    assert_eq!(addresses[0].port(), 8080);
+    assert!(addresses[1].ip().is_loopback());
+}
+
+#[actix_web::test]
+async fn test_invalid_listen_addresses_are_rejected() {
+    for invalid in ["localhost:5900", "0.0.0.0", "::1:5900", "0.0.0.0:99999"] {
+        let err = parse_listen_addresses([invalid]).unwrap_err();
+        assert!(err.contains(invalid), "{err}");
+    }
+
+    assert!(settings(json!(["0.0.0.0:5900", "[::]:5900"])).validate().is_ok());
+    let err = settings(json!(["0.0.0.0:5900", "gateway:5900"])).validate().unwrap_err();
+    assert!(err.contains("gateway:5900"), "{err}");
+    assert!(settings(json!([])).validate().is_err());
+}
+
+#[actix_web::test]
+async fn test_server_section_is_optional() {
+    let settings: Settings = serde_json::from_value(json!({"version": 1, "routers": []})).unwrap();
+    assert!(settings.server.is_none());
+    assert!(settings.validate().is_ok());
+}
@@ -1,11 +1,13 @@
 //! Integration tests for load balancing functionality.
 
-use kairos_rs::models::router::{Backend, LoadBalancingStrategy, Protocol, Router};
+use kairos_rs::models::router::{Backend, HashKey, LoadBalancingStrategy, Router};
 use kairos_rs::services::load_balancer::{
     LoadBalancerFactory, RoundRobinBalancer, WeightedBalancer, LoadBalancer,
-    LeastConnectionsBalancer, RandomBalancer, IpHashBalancer,
+    LeastConnectionsBalancer, RandomBalancer, IpHashBalancer, LeastLatencyBalancer,
+    ConsistentHashBalancer,
 };
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[test]
 fn test_backend_validation() {
@@ -14,6 +16,7 @@ fn test_backend_validation() {
         port: 8080,
         weight: 1,
         health_check_path: Some("/health".to_string()),
+        timeout_secs: None,
     };
     assert!(valid_backend.validate().is_ok());
 
@@ -22,6 +25,7 @@ fn test_backend_validation() {
         port: 8080,
         weight: 1,
         health_check_path: None,
+        timeout_secs: None,
     };
     assert!(invalid_host_backend.validate().is_err());
 
@@ -30,6 +34,7 @@ fn test_backend_validation() {
         port: 0,
         weight: 1,
         health_check_path: None,
+        timeout_secs: None,
     };
     assert!(zero_port_backend.validate().is_err());
 
@@ -38,6 +43,7 @@ fn test_backend_validation() {
         port: 8080,
         weight: 0,
         health_check_path: None,
+        timeout_secs: None,
     };
     assert!(zero_weight_backend.validate().is_err());
 }
@@ -45,32 +51,27 @@ fn test_backend_validation() {
 #[test]
 fn test_router_with_backends() {
     let router = Router {
-        host: None,
-        port: None,
         backends: Some(vec![
             Backend {
                 host: "http://backend-1".to_string(),
                 port: 8080,
                 weight: 1,
                 health_check_path: Some("/health".to_string()),
+                timeout_secs: None,
             },
             Backend {
                 host: "http://backend-2".to_string(),
                 port: 8080,
                 weight: 2,
                 health_check_path: Some("/health".to_string()),
+                timeout_secs: None,
             },
         ]),
         load_balancing_strategy: LoadBalancingStrategy::Weighted,
         external_path: "/api/test".to_string(),
         internal_path: "/test".to_string(),
         methods: vec!["GET".to_string()],
-        auth_required: false,
-        retry: None,
-        protocol: Protocol::Http,
-        request_transformation: None,
-        response_transformation: None,
-        ai_policy: None,
+        ..Default::default()
     };
 
     assert!(router.validate().is_ok());
@@ -86,17 +87,10 @@ fn test_router_legacy_mode() {
     let router = Router {
         host: Some("http://legacy-backend".to_string()),
         port: Some(8080),
-        backends: None,
-        load_balancing_strategy: LoadBalancingStrategy::RoundRobin,
         external_path: "/api/legacy".to_string(),
         internal_path: "/legacy".to_string(),
         methods: vec!["GET".to_string()],
-        auth_required: false,
-        retry: None,
-        protocol: Protocol::Http,
-        request_transformation: None,
-        response_transformation: None,
-        ai_policy: None,
+        ..Default::default()
     };
 
     assert!(router.validate().is_ok());
@@ -115,6 +109,8 @@ fn test_load_balancer_factory() {
         LoadBalancingStrategy::Random,
         LoadBalancingStrategy::Weighted,
         LoadBalancingStrategy::IpHash,
+        LoadBalancingStrategy::LeastLatency,
+        LoadBalancingStrategy::ConsistentHash(HashKey::Header("X-Tenant-Id".to_string())),
     ];
 
     for strategy in strategies {
@@ -132,18 +128,21 @@ fn test_round_robin_balancer() {
             port: 8080,
             weight: 1,
             health_check_path: None,
+            timeout_secs: None,
         },
         Backend {
             host: "http://backend-2".to_string(),
             port: 8080,
             weight: 1,
             health_check_path: None,
+            timeout_secs: None,
         },
         Backend {
             host: "http://backend-3".to_string(),
             port: 8080,
             weight: 1,
             health_check_path: None,
+            timeout_secs: None,
         },
     ];
 
@@ -164,12 +163,14 @@ fn test_weighted_balancer() {
             port: 8080,
             weight: 2,
             health_check_path: None,
+            timeout_secs: None,
         },
         Backend {
             host: "http://backend-2".to_string(),
             port: 8080,
             weight: 1,
             health_check_path: None,
+            timeout_secs: None,
         },
     ];
 
@@ -197,6 +198,8 @@ fn test_retry_config_validation() {
         backoff_multiplier: 2.0,
         retry_on_status_codes: vec![502, 503, 504],
         retry_on_connection_error: true,
+        retry_budget_ratio: None,
+        retry_non_idempotent: false,
     };
     assert!(valid_config.validate().is_ok());
 
@@ -231,6 +234,8 @@ fn test_retry_config_backoff_calculation() {
         backoff_multiplier: 2.0,
         retry_on_status_codes: vec![502, 503, 504],
         retry_on_connection_error: true,
+        retry_budget_ratio: None,
+        retry_non_idempotent: false,
     };
 
     assert_eq!(config.calculate_backoff(0), 100);
@@ -242,6 +247,37 @@ fn test_retry_config_backoff_calculation() {
     assert_eq!(config.calculate_backoff(10), 5000); // Should cap at max_backoff_ms
 }
 
+#[test]
+fn test_retry_config_backoff_clamped_for_high_attempts() {
+    use kairos_rs::models::router::RetryConfig;
+
+    let config = RetryConfig {
+        initial_backoff_ms: 100,
+        max_backoff_ms: 2000,
+        backoff_multiplier: 3.0,
+        ..RetryConfig::default()
+    };
+
+    assert_eq!(config.calculate_backoff(2), 900);
+    assert_eq!(config.calculate_backoff(3), 2000);
+    assert_eq!(config.calculate_backoff(1_000), 2000);
+    assert_eq!(config.calculate_backoff(u32::MAX), 2000);
+}
+
+#[test]
+fn test_retry_config_max_backoff_may_equal_initial_backoff() {
+    use kairos_rs::models::router::RetryConfig;
+
+    let constant = RetryConfig {
+        initial_backoff_ms: 500,
+        max_backoff_ms: 500,
+        ..RetryConfig::default()
+    };
+    assert!(constant.validate().is_ok());
+    assert_eq!(constant.calculate_backoff(0), 500);
+    assert_eq!(constant.calculate_backoff(7), 500);
+}
+
 fn create_test_backends(count: usize) -> Vec<Backend> {
     (0..count)
         .map(|i| Backend {
@@ -249,6 +285,7 @@ fn create_test_backends(count: usize) -> Vec<Backend> {
             port: 8080,
             weight: 1,
             health_check_path: None,
+            timeout_secs: None,
         })
         .collect()
 }
@@ -326,6 +363,75 @@ fn test_ip_hash_consistency() {
     }
 }
 
+#[test]
+fn test_least_latency_prefers_faster_backend() {
+    let balancer = LeastLatencyBalancer::new();
+    let backends = create_test_backends(3);
+    // backend-1 is consistently the fastest
+    let response_time = |backend: &Backend| match backend.host.as_str() {
+        "http://backend-0" => Duration::from_millis(80),
+        "http://backend-1" => Duration::from_millis(10),
+        _ => Duration::from_millis(40),
+    };
+
+    let mut counts = HashMap::new();
+    for _ in 0..100 {
+        let selected = balancer.select_backend(&backends, None).unwrap();
+        balancer.record_success(&selected, response_time(&selected));
+        *counts.entry(selected.host).or_insert(0) += 1;
+    }
+
+    // Every backend is tried once while it has no samples, then the fastest wins
+    assert_eq!(counts["http://backend-0"], 1);
+    assert_eq!(counts["http://backend-1"], 98);
+    assert_eq!(counts["http://backend-2"], 1);
+    assert!((balancer.average_latency_ms(&backends[1]) - 10.0).abs() < 0.01);
+}
+
+#[test]
+fn test_least_latency_adapts_to_slowdowns_and_failures() {
+    let balancer = LeastLatencyBalancer::new();
+    let backends = create_test_backends(2);
+    balancer.record_success(&backends[0], Duration::from_millis(10));
+    balancer.record_success(&backends[1], Duration::from_millis(30));
+    assert_eq!(balancer.select_backend(&backends, None).unwrap().host, "http://backend-0");
+
+    // The average moves towards recent samples
+    balancer.record_success(&backends[0], Duration::from_millis(110));
+    assert!((balancer.average_latency_ms(&backends[0]) - 40.0).abs() < 0.01);
+    assert_eq!(balancer.select_backend(&backends, None).unwrap().host, "http://backend-1");
+
+    // A fast failure does not make a backend look fast
+    balancer.record_failure(&backends[1]);
+    assert!(balancer.average_latency_ms(&backends[1]) > 40.0);
+    assert_eq!(balancer.select_backend(&backends, None).unwrap().host, "http://backend-0");
+}
+
+#[test]
+fn test_least_latency_retries_backend_after_temporary_slowdown() {
+    let balancer = LeastLatencyBalancer::with_decay_half_life(Duration::from_millis(20));
+    let backends = create_test_backends(2);
+    balancer.record_success(&backends[0], Duration::from_millis(10));
+    balancer.record_success(&backends[1], Duration::from_millis(10));
+
+    // One slow response and a failure push backend-0 out
+    balancer.record_success(&backends[0], Duration::from_millis(500));
+    balancer.record_failure(&backends[0]);
+    assert_eq!(balancer.select_backend(&backends, None).unwrap().host, "http://backend-1");
+
+    // Serving backend-1 keeps its average fresh while backend-0's decays,
+    // until backend-0 is tried again; having recovered, it keeps a share
+    let mut counts = HashMap::new();
+    for _ in 0..200 {
+        let selected = balancer.select_backend(&backends, None).unwrap();
+        balancer.record_success(&selected, Duration::from_millis(10));
+        *counts.entry(selected.host).or_insert(0) += 1;
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    assert!(counts.get("http://backend-0").copied().unwrap_or(0) > 20, "{:?}", counts);
+    assert!(balancer.average_latency_ms(&backends[0]) < 50.0);
+}
+
 #[test]
 fn test_empty_backends() {
     let backends = vec![];
@@ -344,4 +450,132 @@ fn test_empty_backends() {
     
     let ip_balancer = IpHashBalancer::new();
     assert!(ip_balancer.select_backend(&backends, None).is_none());
+
+    let latency_balancer = LeastLatencyBalancer::new();
+    assert!(latency_balancer.select_backend(&backends, None).is_none());
+
+    let hash_balancer = ConsistentHashBalancer::new();
+    assert!(hash_balancer.select_backend(&backends, Some("tenant")).is_none());
+}
+
+/// Returns the backend host each of `keys` is routed to.
+fn assign(balancer: &ConsistentHashBalancer, backends: &[Backend], keys: &[String]) -> Vec<String> {
+    keys.iter()
+        .map(|key| balancer.select_backend(backends, Some(key)).unwrap().host)
+        .collect()
+}
+
+#[test]
+fn test_consistent_hash_is_stable_across_backend_changes() {
+    let balancer = ConsistentHashBalancer::new();
+    let keys: Vec<String> = (0..2000).map(|i| format!("tenant-{}", i)).collect();
+    let backends = create_test_backends(5);
+    let before = assign(&balancer, &backends, &keys);
+
+    // Every backend gets a share of the keys, and the same key always lands
+    // on the same backend, whatever order the backends are listed in
+    for backend in &backends {
+        let share = before.iter().filter(|host| **host == backend.host).count();
+        assert!(share > 200, "{} got {} keys", backend.host, share);
+    }
+    let mut reversed = backends.clone();
+    reversed.reverse();
+    assert_eq!(assign(&balancer, &reversed, &keys), before);
+
+    // Adding a sixth backend only moves keys to it, about 1/6 of them
+    let grown = create_test_backends(6);
+    let after = assign(&balancer, &grown, &keys);
+    let moved: Vec<_> = before.iter().zip(&after).filter(|(a, b)| a != b).collect();
+    assert!(moved.iter().all(|(_, to)| *to == "http://backend-5"));
+    assert!(moved.len() > 150 && moved.len() < 550, "{} keys moved", moved.len());
+
+    // Removing a backend only moves the keys it had
+    let shrunk: Vec<_> = backends.iter().filter(|b| b.host != "http://backend-2").cloned().collect();
+    let after = assign(&balancer, &shrunk, &keys);
+    for (from, to) in before.iter().zip(&after) {
+        if from != "http://backend-2" {
+            assert_eq!(from, to);
+        }
+    }
+}
+
+#[test]
+fn test_consistent_hash_mapping_is_fixed() {
+    // Pinned so that a change of hash algorithm, which would move keys to
+    // other backends after an upgrade, does not go unnoticed
+    let balancer = ConsistentHashBalancer::new();
+    let backends = create_test_backends(5);
+    let keys: Vec<String> = ["tenant-a", "tenant-b", "tenant-c", "tenant-d"]
+        .iter()
+        .map(|key| key.to_string())
+        .collect();
+    assert_eq!(
+        assign(&balancer, &backends, &keys),
+        ["http://backend-3", "http://backend-0", "http://backend-1", "http://backend-3"]
+    );
+}
+
+#[test]
+fn test_consistent_hash_without_key_round_robins() {
+    let balancer = ConsistentHashBalancer::new();
+    let backends = create_test_backends(3);
+    let hosts: Vec<String> = (0..6)
+        .map(|_| balancer.select_backend(&backends, None).unwrap().host)
+        .collect();
+    assert_eq!(
+        hosts,
+        [
+            "http://backend-0",
+            "http://backend-1",
+            "http://backend-2",
+            "http://backend-0",
+            "http://backend-1",
+            "http://backend-2",
+        ]
+    );
+}
+
+#[test]
+fn test_consistent_hash_strategy_config() {
+    let route = |strategy: serde_json::Value, external_path: &str| -> Router {
+        serde_json::from_value(serde_json::json!({
+            "backends": [
+                {"host": "http://backend-0", "port": 8080},
+                {"host": "http://backend-1", "port": 8080}
+            ],
+            "external_path": external_path,
+            "internal_path": "/orders",
+            "methods": ["GET"],
+            "load_balancing_strategy": strategy
+        }))
+        .unwrap()
+    };
+
+    let by_header = route(
+        serde_json::json!({"consistent_hash": {"header": "X-Tenant-Id"}}),
+        "/api/orders",
+    );
+    assert_eq!(
+        by_header.load_balancing_strategy,
+        LoadBalancingStrategy::ConsistentHash(HashKey::Header("X-Tenant-Id".to_string()))
+    );
+    assert!(by_header.validate().is_ok());
+
+    let by_param = route(
+        serde_json::json!({"consistent_hash": {"path_param": "tenant"}}),
+        "/api/{tenant}/orders",
+    );
+    assert!(by_param.validate().is_ok());
+
+    let unknown_param = route(
+        serde_json::json!({"consistent_hash": {"path_param": "tenant"}}),
+        "/api/orders",
+    );
+    assert!(unknown_param.validate().unwrap_err().contains("tenant"));
+
+    let bad_header = route(
+        serde_json::json!({"consistent_hash": {"header": "X Tenant"}}),
+        "/api/orders",
+    );
+    assert!(bad_header.validate().is_err());
 }
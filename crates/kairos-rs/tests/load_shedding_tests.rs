@@ -0,0 +1,106 @@
+//! Load shedding tests
+//!
+//! Verifies that requests over `max_concurrent_requests` are rejected right
+//! away with a 503 and `Retry-After`, that permits are released when requests
+//! complete, that shed and in-flight requests are reported in the metrics,
+//! and that a zero limit is rejected by configuration validation.
+
+use actix_web::{http::StatusCode, test, web, App, HttpResponse};
+use kairos_rs::middleware::load_shedding::LoadShedding;
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::metrics::{self, MetricsCollector};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+async fn slow() -> HttpResponse {
+    actix_web::rt::time::sleep(Duration::from_millis(300)).await;
+    HttpResponse::Ok().body("done")
+}
+
+#[actix_web::test]
+async fn test_requests_over_limit_are_shed() {
+    let collector = MetricsCollector::default();
+    let limit = LoadShedding::new(1);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .wrap(limit.clone())
+            .route("/slow", web::get().to(slow)),
+    )
+    .await;
+
+    let first = test::try_call_service(&app, test::TestRequest::get().uri("/slow").to_request());
+    let second = async {
+        // Let the first request take the only permit
+        actix_web::rt::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(limit.in_flight(), 1);
+        assert_eq!(collector.concurrent_requests.load(Ordering::Relaxed), 1);
+        test::try_call_service(&app, test::TestRequest::get().uri("/slow").to_request()).await
+    };
+    let (first, second) = futures::join!(first, second);
+
+    assert_eq!(first.unwrap().status(), StatusCode::OK);
+    let rejected = second
+        .expect_err("second request should be shed")
+        .error_response();
+    assert_eq!(rejected.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(rejected.headers().get("Retry-After").unwrap(), "1");
+    let body = actix_web::body::to_bytes(rejected.into_body()).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["type"], "overloaded");
+
+    assert_eq!(limit.in_flight(), 0);
+    assert_eq!(collector.requests_shed.load(Ordering::Relaxed), 1);
+    assert_eq!(collector.concurrent_requests.load(Ordering::Relaxed), 0);
+
+    // The permit is back once the first request completed
+    let resp = test::try_call_service(&app, test::TestRequest::get().uri("/slow").to_request())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[actix_web::test]
+async fn test_shed_requests_in_metrics() {
+    let collector = MetricsCollector::default();
+    collector.set_concurrent_requests(3);
+    collector.record_request_shed();
+    collector.record_request_shed();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let text = String::from_utf8_lossy(&body);
+    assert!(text.contains("kairos_concurrent_requests 3\n"), "{text}");
+    assert!(text.contains("kairos_requests_shed_total 2\n"), "{text}");
+
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Accept", "application/openmetrics-text"))
+        .to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let text = String::from_utf8_lossy(&body);
+    assert!(text.contains("# TYPE kairos_requests_shed counter"), "{text}");
+    assert!(text.contains("kairos_requests_shed_total 2\n"), "{text}");
+    assert!(text.contains("kairos_concurrent_requests 3\n"), "{text}");
+}
+
+#[actix_web::test]
+async fn test_zero_max_concurrent_requests_is_invalid() {
+    let mut settings: Settings = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "routers": []
+    }))
+    .unwrap();
+    settings.max_concurrent_requests = Some(0);
+    let err = settings.validate().unwrap_err();
+    assert!(err.contains("max_concurrent_requests"), "{err}");
+
+    settings.max_concurrent_requests = Some(100);
+    assert!(settings.validate().is_ok());
+}
@@ -0,0 +1,228 @@
+//! Metrics endpoint authentication tests
+//!
+//! Verifies that `MetricsConfig::auth` protects `/metrics` with bearer,
+//! basic or JWT credentials while leaving the endpoint open when unset.
+
+use actix_web::{test, web, App};
+use base64::{engine::general_purpose, Engine};
+use kairos_rs::middleware::auth::{create_test_token, Claims, JwtConfig};
+use kairos_rs::models::settings::{JwtSettings, MetricsAuth, MetricsConfig, Settings};
+use kairos_rs::routes::metrics;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const JWT_SECRET: &str = "test-secret-key-that-is-long-enough-for-security-requirements";
+
+fn bearer_config() -> MetricsConfig {
+    MetricsConfig {
+        auth: Some(MetricsAuth::Bearer {
+            token: "scrape-token".to_string(),
+        }),
+        body_size_buckets: None,
+        collect_per_route: false,
+        backend: Default::default(),
+    }
+}
+
+fn basic_config() -> MetricsConfig {
+    MetricsConfig {
+        auth: Some(MetricsAuth::Basic {
+            username: "prometheus".to_string(),
+            password: "secret".to_string(),
+        }),
+        body_size_buckets: None,
+        collect_per_route: false,
+        backend: Default::default(),
+    }
+}
+
+#[actix_web::test]
+async fn test_metrics_open_without_auth_config() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(MetricsConfig::default()))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_metrics_bearer_auth() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(bearer_config()))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    // Missing credentials
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+    assert_eq!(
+        resp.headers().get("www-authenticate").unwrap(),
+        "Bearer realm=\"metrics\""
+    );
+
+    // Wrong token
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Authorization", "Bearer wrong-token"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+
+    // Correct token
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Authorization", "Bearer scrape-token"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let body = test::read_body(resp).await;
+    assert!(String::from_utf8_lossy(&body).contains("kairos_requests_total"));
+}
+
+#[actix_web::test]
+async fn test_metrics_basic_auth() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(basic_config()))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+
+    let wrong = general_purpose::STANDARD.encode("prometheus:wrong");
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Authorization", format!("Basic {}", wrong)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+
+    let valid = general_purpose::STANDARD.encode("prometheus:secret");
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Authorization", format!("Basic {}", valid)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_metrics_auth_deserializes_from_config() {
+    let config: MetricsConfig =
+        serde_json::from_str(r#"{"auth": {"type": "bearer", "token": "scrape-token"}}"#).unwrap();
+    assert_eq!(config.auth, bearer_config().auth);
+
+    let config: MetricsConfig = serde_json::from_str("{}").unwrap();
+    assert!(config.auth.is_none());
+}
+
+fn jwt_token(secret: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as usize;
+    let claims = Claims {
+        sub: "prometheus".to_string(),
+        exp: now + 3600,
+        iat: now,
+        iss: None,
+        aud: None,
+        roles: None,
+    };
+    create_test_token(claims, secret).unwrap()
+}
+
+#[actix_web::test]
+async fn test_metrics_jwt_auth() {
+    let jwt = JwtSettings {
+        secret: JWT_SECRET.to_string(),
+        issuer: None,
+        audience: None,
+        required_claims: vec![],
+    };
+    let config: MetricsConfig = serde_json::from_str(r#"{"auth": {"type": "jwt"}}"#).unwrap();
+    assert_eq!(config.auth, Some(MetricsAuth::Jwt));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(config))
+            .app_data(web::Data::new(JwtConfig::from(&jwt)))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+    assert_eq!(
+        resp.headers().get("www-authenticate").unwrap(),
+        "Bearer realm=\"metrics\""
+    );
+
+    let forged = jwt_token("a-different-secret-that-is-also-long-enough-to-sign");
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Authorization", format!("Bearer {}", forged)))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 401);
+
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Authorization", format!("Bearer {}", jwt_token(JWT_SECRET))))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_metrics_jwt_auth_rejects_all_without_jwt_config() {
+    let config = MetricsConfig {
+        auth: Some(MetricsAuth::Jwt),
+        ..MetricsConfig::default()
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(config))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/metrics")
+        .insert_header(("Authorization", format!("Bearer {}", jwt_token(JWT_SECRET))))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 401);
+}
+
+#[actix_web::test]
+async fn test_metrics_jwt_auth_requires_jwt_settings() {
+    let mut settings: Settings = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "metrics": {"auth": {"type": "jwt"}},
+        "routers": []
+    }))
+    .unwrap();
+    let err = settings.validate().unwrap_err();
+    assert!(err.contains("Metrics JWT auth requires jwt settings"), "{err}");
+
+    settings.jwt = Some(JwtSettings {
+        secret: JWT_SECRET.to_string(),
+        ..JwtSettings::default()
+    });
+    assert!(settings.validate().is_ok());
+}
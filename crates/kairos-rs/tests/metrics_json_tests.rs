@@ -0,0 +1,159 @@
+//! JSON metrics endpoint tests
+//!
+//! Verifies that `/metrics.json` reports the same counters as the Prometheus
+//! text on `/metrics`, lists circuit breaker states as an array and honours
+//! the metrics credentials.
+
+use actix_web::{test, web, App};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::models::settings::{MetricsAuth, MetricsConfig};
+use kairos_rs::routes::metrics::{self, MetricsCollector};
+use kairos_rs::services::http::RouteHandler;
+use serde_json::Value;
+use std::time::Duration;
+
+fn route() -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port: 9,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/test".to_string(),
+        internal_path: "/test".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+fn collector() -> MetricsCollector {
+    let collector = MetricsCollector::default();
+    collector.record_request(
+        true,
+        Duration::from_millis(30),
+        200,
+        Some(10),
+        Some(90),
+        None,
+    );
+    collector.record_request(
+        true,
+        Duration::from_millis(50),
+        200,
+        Some(20),
+        Some(80),
+        None,
+    );
+    collector.record_request(false, Duration::from_millis(700), 502, None, None, None);
+    collector
+}
+
+fn prometheus_value(text: &str, name: &str) -> f64 {
+    text.lines()
+        .find_map(|line| line.strip_prefix(&format!("{} ", name)))
+        .unwrap_or_else(|| panic!("{} missing", name))
+        .parse()
+        .unwrap()
+}
+
+#[actix_web::test]
+async fn test_json_matches_prometheus_text() {
+    let handler = RouteHandler::new(vec![route()], 5);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector()))
+            .app_data(web::Data::new(handler))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics.json").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let json: Value = test::read_body_json(resp).await;
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let text = String::from_utf8(test::call_and_read_body(&app, req).await.to_vec()).unwrap();
+
+    assert_eq!(json["requests_total"], 3);
+    assert_eq!(json["requests_success"], 2);
+    assert_eq!(json["requests_error"], 1);
+    assert_eq!(json["http_5xx_errors"], 1);
+    assert_eq!(json["response_time_bucket_100ms"], 2);
+    assert_eq!(json["request_bytes_total"], 30);
+    assert_eq!(json["response_bytes_total"], 170);
+    assert_eq!(json["data_transferred_bytes"], 200);
+
+    for (field, series) in [
+        ("requests_total", "kairos_requests_total"),
+        ("requests_error", "kairos_requests_error_total"),
+        ("success_rate", "kairos_success_rate"),
+        ("response_time_avg", "kairos_response_time_avg"),
+        ("response_time_p99", "kairos_response_time_p99"),
+        ("request_bytes_total", "kairos_request_bytes_total"),
+        ("response_bytes_total", "kairos_response_bytes_total"),
+    ] {
+        let json_value = json[field].as_f64().unwrap();
+        let text_value = prometheus_value(&text, series);
+        assert!(
+            (json_value - text_value).abs() < 0.01,
+            "{}: {} != {}",
+            field,
+            json_value,
+            text_value
+        );
+    }
+
+    let breakers = json["circuit_breakers"].as_array().unwrap();
+    assert_eq!(breakers.len(), 1);
+    assert_eq!(breakers[0]["route"], "http://127.0.0.1:9");
+    assert_eq!(breakers[0]["state"], "Closed");
+    assert_eq!(breakers[0]["failure_count"], 0);
+}
+
+#[actix_web::test]
+async fn test_json_without_route_handler_has_no_breakers() {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(MetricsCollector::default()))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics.json").to_request();
+    let json: Value = test::call_and_read_body_json(&app, req).await;
+
+    assert_eq!(json["requests_total"], 0);
+    assert_eq!(json["success_rate"], 100.0);
+    assert_eq!(json["circuit_breakers"], Value::Array(vec![]));
+}
+
+#[actix_web::test]
+async fn test_json_requires_metrics_credentials() {
+    let config = MetricsConfig {
+        auth: Some(MetricsAuth::Bearer {
+            token: "scrape-token".to_string(),
+        }),
+        body_size_buckets: None,
+        collect_per_route: false,
+        backend: Default::default(),
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(MetricsCollector::default()))
+            .app_data(web::Data::new(config))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics.json").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 401);
+
+    let req = test::TestRequest::get()
+        .uri("/metrics.json")
+        .insert_header(("Authorization", "Bearer scrape-token"))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
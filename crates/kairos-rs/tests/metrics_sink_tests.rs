@@ -0,0 +1,159 @@
+//! Metrics sink tests
+//!
+//! Verifies that a `MetricsCollector` with a sink forwards requests, error
+//! counters and gauges to it while keeping its own counters, that
+//! `StatsdSink` sends them as StatsD datagrams, and that `metrics.backend`
+//! is parsed and validated.
+
+use kairos_rs::models::settings::{MetricsBackend, Settings};
+use kairos_rs::routes::metrics::MetricsCollector;
+use kairos_rs::services::metrics_sink::{MetricsSink, StatsdSink};
+use std::net::UdpSocket;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Sink remembering every call, formatted as text.
+#[derive(Debug, Default)]
+struct RecordingSink {
+    calls: Mutex<Vec<String>>,
+}
+
+impl MetricsSink for RecordingSink {
+    fn record_request(&self, route: Option<&str>, status_code: u16, response_time: Duration) {
+        self.calls.lock().unwrap().push(format!(
+            "request {:?} {} {}ms",
+            route,
+            status_code,
+            response_time.as_millis()
+        ));
+    }
+
+    fn increment_counter(&self, name: &str) {
+        self.calls.lock().unwrap().push(format!("counter {}", name));
+    }
+
+    fn record_gauge(&self, name: &str, value: f64) {
+        self.calls.lock().unwrap().push(format!("gauge {} {}", name, value));
+    }
+}
+
+/// Binds a local StatsD server and returns it with its address.
+fn statsd_server() -> (UdpSocket, String) {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+    let address = socket.local_addr().unwrap().to_string();
+    (socket, address)
+}
+
+fn receive(socket: &UdpSocket) -> String {
+    let mut buf = [0; 1024];
+    let len = socket.recv(&mut buf).expect("no datagram received");
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+#[test]
+fn test_collector_forwards_to_sink() {
+    let sink = Arc::new(RecordingSink::default());
+    let collector = MetricsCollector::default().with_sink(sink.clone());
+
+    collector.increment_connections();
+    collector.record_request(
+        false,
+        Duration::from_millis(42),
+        504,
+        None,
+        None,
+        Some("/api/users/{id}"),
+    );
+    collector.record_timeout_error();
+    collector.decrement_connections();
+    collector.set_config_validation_warnings(3);
+
+    assert_eq!(
+        *sink.calls.lock().unwrap(),
+        [
+            "gauge active_connections 1",
+            "request Some(\"/api/users/{id}\") 504 42ms",
+            "counter errors.timeout",
+            "gauge active_connections 0",
+            "gauge config_validation_warnings 3",
+        ]
+    );
+    // The in-memory counters behind /metrics are still kept
+    assert_eq!(collector.requests_total.load(Ordering::Relaxed), 1);
+    assert_eq!(collector.timeout_errors.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_statsd_sink_sends_datagrams() {
+    let (server, address) = statsd_server();
+    let sink = StatsdSink::connect(&address, "kairos", false).unwrap();
+
+    sink.record_request(Some("/api/users/{id}"), 200, Duration::from_millis(12));
+    assert_eq!(
+        receive(&server),
+        "kairos.requests:1|c\nkairos.responses.200:1|c\nkairos.response_time:12|ms"
+    );
+
+    sink.increment_counter("errors.timeout");
+    assert_eq!(receive(&server), "kairos.errors.timeout:1|c");
+
+    sink.record_gauge("active_connections", 7.0);
+    assert_eq!(receive(&server), "kairos.active_connections:7|g");
+}
+
+#[test]
+fn test_statsd_sink_adds_dogstatsd_tags() {
+    let (server, address) = statsd_server();
+    let sink = StatsdSink::connect(&address, "", true).unwrap();
+
+    sink.record_request(Some("/api/a,b"), 503, Duration::from_millis(5));
+    let tags = "|#route:/api/a_b,status:503";
+    assert_eq!(
+        receive(&server),
+        format!("requests:1|c{tags}\nresponses.503:1|c{tags}\nresponse_time:5|ms{tags}")
+    );
+
+    sink.record_request(None, 404, Duration::from_millis(1));
+    assert!(receive(&server).contains("|#route:none,status:404"));
+}
+
+#[test]
+fn test_metrics_backend_config() {
+    let settings: Settings = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "metrics": {"backend": {"type": "statsd", "address": "127.0.0.1:8125"}},
+        "routers": []
+    }))
+    .unwrap();
+    let backend = &settings.metrics.as_ref().unwrap().backend;
+    assert_eq!(
+        *backend,
+        MetricsBackend::Statsd {
+            address: "127.0.0.1:8125".to_string(),
+            prefix: "kairos".to_string(),
+            tags: false,
+        }
+    );
+    assert!(settings.validate().is_ok());
+
+    let default: Settings = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "metrics": {},
+        "routers": []
+    }))
+    .unwrap();
+    assert_eq!(default.metrics.unwrap().backend, MetricsBackend::Prometheus);
+
+    for (address, prefix) in [("statsd", "kairos"), ("statsd:port", "kairos"), ("statsd:8125", "a|b")] {
+        let mut invalid = settings.clone();
+        invalid.metrics.as_mut().unwrap().backend = MetricsBackend::Statsd {
+            address: address.to_string(),
+            prefix: prefix.to_string(),
+            tags: false,
+        };
+        let err = invalid.validate().unwrap_err();
+        assert!(err.contains("StatsD"), "{address} {prefix}: {err}");
+    }
+}
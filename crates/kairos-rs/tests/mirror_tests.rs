@@ -0,0 +1,198 @@
+//! Request mirroring tests
+//!
+//! Verifies that request bodies are teed to a route's mirror backend when
+//! they fit within the mirror body limit, only sent to the primary backend
+//! when they do not, and that mirror outcomes are counted in metrics.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::routes::metrics::MetricsCollector;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Received = Arc<Mutex<Vec<web::Bytes>>>;
+
+/// Starts a mock upstream that records every request body it receives.
+fn spawn_recording_upstream() -> (u16, Received) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+
+    let store = received.clone();
+    let server = HttpServer::new(move || {
+        let store = store.clone();
+        App::new().default_service(web::to(move |body: web::Bytes| {
+            let store = store.clone();
+            async move {
+                store.lock().unwrap().push(body);
+                HttpResponse::Ok().body("primary")
+            }
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    (port, received)
+}
+
+fn backend(port: u16) -> Backend {
+    Backend {
+        host: "http://127.0.0.1".to_string(),
+        port,
+        weight: 1,
+        health_check_path: None,
+        timeout_secs: None,
+    }
+}
+
+fn route(primary_port: u16, mirror_port: u16) -> Router {
+    Router {
+        backends: Some(vec![backend(primary_port)]),
+        external_path: "/orders".to_string(),
+        internal_path: "/orders".to_string(),
+        methods: vec!["POST".to_string()],
+        mirror_to: Some(backend(mirror_port)),
+        ..Default::default()
+    }
+}
+
+/// Waits until `received` holds at least one body or the deadline passes.
+async fn wait_for_body(received: &Received, deadline: Duration) {
+    let start = std::time::Instant::now();
+    while received.lock().unwrap().is_empty() && start.elapsed() < deadline {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+#[actix_web::test]
+async fn test_small_body_reaches_primary_and_mirror() {
+    let (primary_port, primary) = spawn_recording_upstream();
+    let (mirror_port, mirror) = spawn_recording_upstream();
+
+    let handler = RouteHandler::new(vec![route(primary_port, mirror_port)], 5)
+        .with_mirror_body_limit(1024);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let payload = r#"{"order_id": 42, "items": ["book"]}"#;
+    let req = test::TestRequest::post()
+        .uri("/orders")
+        .set_payload(payload)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "primary");
+
+    wait_for_body(&mirror, Duration::from_secs(2)).await;
+
+    let primary = primary.lock().unwrap();
+    let mirror = mirror.lock().unwrap();
+    assert_eq!(primary.len(), 1);
+    assert_eq!(mirror.len(), 1);
+    assert_eq!(primary[0], payload);
+    assert_eq!(mirror[0], primary[0], "mirror should see an identical body");
+}
+
+#[actix_web::test]
+async fn test_large_body_reaches_only_primary() {
+    let (primary_port, primary) = spawn_recording_upstream();
+    let (mirror_port, mirror) = spawn_recording_upstream();
+
+    let handler = RouteHandler::new(vec![route(primary_port, mirror_port)], 5)
+        .with_mirror_body_limit(1024);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let payload = vec![b'x'; 4096];
+    let req = test::TestRequest::post()
+        .uri("/orders")
+        .set_payload(payload.clone())
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    // Give a misbehaving mirror request time to arrive before asserting
+    wait_for_body(&mirror, Duration::from_millis(500)).await;
+
+    assert_eq!(primary.lock().unwrap().len(), 1);
+    assert_eq!(primary.lock().unwrap()[0], payload);
+    assert!(mirror.lock().unwrap().is_empty(), "oversized body must not be mirrored");
+}
+
+#[actix_web::test]
+async fn test_unreachable_mirror_does_not_affect_client() {
+    let (primary_port, primary) = spawn_recording_upstream();
+
+    // Nothing listens on this port
+    let unused_port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+
+    let handler = RouteHandler::new(vec![route(primary_port, unused_port)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/orders")
+        .set_payload("{}")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(primary.lock().unwrap().len(), 1);
+}
+
+/// Waits until `counter` is non-zero or two seconds pass.
+async fn wait_for_count(counter: &AtomicU64) -> u64 {
+    for _ in 0..100 {
+        if counter.load(Ordering::Relaxed) > 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    counter.load(Ordering::Relaxed)
+}
+
+#[actix_web::test]
+async fn test_mirror_outcomes_are_counted() {
+    let (primary_port, _primary) = spawn_recording_upstream();
+    let (mirror_port, _mirror) = spawn_recording_upstream();
+    let unused_port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+
+    let mut failing = route(primary_port, unused_port);
+    failing.external_path = "/failing".to_string();
+    let handler = RouteHandler::new(vec![route(primary_port, mirror_port), failing], 5);
+    let metrics = MetricsCollector::default();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics.clone()))
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    for uri in ["/orders", "/failing"] {
+        let req = test::TestRequest::post().uri(uri).set_payload("{}").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+
+    assert_eq!(wait_for_count(&metrics.mirror_requests_success).await, 1);
+    assert_eq!(wait_for_count(&metrics.mirror_requests_failed).await, 1);
+}
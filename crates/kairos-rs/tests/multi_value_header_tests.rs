@@ -0,0 +1,73 @@
+//! Repeated header forwarding tests
+//!
+//! Verifies that headers sent more than once, such as `Set-Cookie` in
+//! responses or `Accept` in requests, are forwarded as separate entries
+//! instead of being collapsed to a single value.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream that sets two cookies and echoes the `Accept`
+/// values it received, one per line.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let accept: Vec<&str> = req
+                .headers()
+                .get_all("accept")
+                .map(|v| v.to_str().unwrap())
+                .collect();
+            HttpResponse::Ok()
+                .append_header(("Set-Cookie", "session=abc; HttpOnly"))
+                .append_header(("Set-Cookie", "theme=dark"))
+                .body(accept.join("\n"))
+        }))
+    })
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/session".to_string(),
+        internal_path: "/session".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_repeated_headers_are_preserved_both_ways() {
+    let handler = RouteHandler::new(vec![route(spawn_upstream())], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/session")
+        .append_header(("Accept", "application/json"))
+        .append_header(("Accept", "text/plain"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let cookies: Vec<&str> = resp
+        .headers()
+        .get_all("set-cookie")
+        .map(|v| v.to_str().unwrap())
+        .collect();
+    assert_eq!(cookies, ["session=abc; HttpOnly", "theme=dark"]);
+
+    let body = test::read_body(resp).await;
+    assert_eq!(body, "application/json\ntext/plain");
+}
@@ -0,0 +1,116 @@
+//! OpenMetrics exposition tests
+//!
+//! Verifies that `/metrics` negotiates the OpenMetrics format through the
+//! `Accept` header, terminates it with `# EOF` and attaches trace exemplars
+//! to the response time histogram.
+
+use actix_web::{test, web, App};
+use kairos_rs::routes::metrics::{self, trace_id_from_traceparent, MetricsCollector};
+use std::time::Duration;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+async fn scrape(collector: MetricsCollector, accept: Option<&str>) -> (String, String) {
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let mut req = test::TestRequest::get().uri("/metrics");
+    if let Some(accept) = accept {
+        req = req.insert_header(("Accept", accept));
+    }
+    let resp = test::call_service(&app, req.to_request()).await;
+    assert_eq!(resp.status(), 200);
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    (content_type, body)
+}
+
+#[actix_web::test]
+async fn test_openmetrics_negotiated_via_accept() {
+    let collector = MetricsCollector::default();
+    collector.record_request(true, Duration::from_millis(40), 200, None, None, None);
+
+    // Prometheus' default scrape Accept header prefers OpenMetrics
+    let (content_type, body) = scrape(
+        collector,
+        Some("application/openmetrics-text;version=1.0.0,text/plain;version=0.0.4;q=0.5,*/*;q=0.1"),
+    )
+    .await;
+
+    assert!(content_type.starts_with("application/openmetrics-text; version=1.0.0"));
+    assert!(body.ends_with("# EOF\n"));
+    assert_eq!(body.matches("# EOF").count(), 1);
+    assert!(body.contains("# TYPE kairos_requests counter\n"));
+    assert!(body.contains("kairos_requests_total 1\n"));
+    assert!(body.contains("# TYPE kairos_response_time histogram\n"));
+    assert!(body.contains("kairos_response_time_bucket{le=\"+Inf\"} 1\n"));
+    assert!(body.contains("kairos_response_time_count 1\n"));
+}
+
+#[actix_web::test]
+async fn test_prometheus_format_is_default() {
+    let (content_type, body) = scrape(MetricsCollector::default(), None).await;
+    assert!(content_type.starts_with("text/plain; version=0.0.4"));
+    assert!(!body.contains("# EOF"));
+
+    let (content_type, _) = scrape(
+        MetricsCollector::default(),
+        Some("application/openmetrics-text;q=0, text/plain"),
+    )
+    .await;
+    assert!(content_type.starts_with("text/plain"));
+}
+
+#[actix_web::test]
+async fn test_exemplars_carry_trace_id() {
+    let collector = MetricsCollector::default();
+    collector.record_request(true, Duration::from_millis(320), 200, None, None, None);
+    collector.record_exemplar(Duration::from_millis(320), TRACE_ID);
+
+    let (_, body) = scrape(collector, Some("application/openmetrics-text")).await;
+    let bucket = body
+        .lines()
+        .find(|line| line.starts_with("kairos_response_time_bucket{le=\"500\"}"))
+        .unwrap();
+    assert!(bucket.starts_with(&format!(
+        "kairos_response_time_bucket{{le=\"500\"}} 1 # {{trace_id=\"{}\"}} 320 ",
+        TRACE_ID
+    )));
+
+    // Other buckets have no exemplar
+    let bucket = body
+        .lines()
+        .find(|line| line.starts_with("kairos_response_time_bucket{le=\"100\"}"))
+        .unwrap();
+    assert_eq!(bucket, "kairos_response_time_bucket{le=\"100\"} 0");
+}
+
+#[actix_web::test]
+async fn test_traceparent_parsing() {
+    let header = format!("00-{}-00f067aa0ba902b7-01", TRACE_ID);
+    assert_eq!(trace_id_from_traceparent(&header), Some(TRACE_ID));
+
+    assert_eq!(trace_id_from_traceparent("garbage"), None);
+    assert_eq!(
+        trace_id_from_traceparent("00-00000000000000000000000000000000-00f067aa0ba902b7-01"),
+        None
+    );
+    assert_eq!(
+        trace_id_from_traceparent(&format!("00-{}-00f067aa0ba902b7", TRACE_ID)),
+        None
+    );
+    assert_eq!(
+        trace_id_from_traceparent(&format!("00-{}-00F067AA0BA902B7-01", TRACE_ID)),
+        None
+    );
+}
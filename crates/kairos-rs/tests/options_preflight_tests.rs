@@ -0,0 +1,152 @@
+//! Automatic OPTIONS response tests
+//!
+//! Verifies that `OPTIONS` requests to routes that do not list `OPTIONS` are
+//! answered by the gateway from the route's methods, with
+//! `Access-Control-Allow-Methods` for CORS preflights, that routes listing
+//! `OPTIONS` still forward it, and that preflights to protected routes do not
+//! need a token.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::middleware::security::cors_headers;
+use kairos_rs::models::router::Router;
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::{auth_http, http};
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream answering every request with `upstream`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Ok().body("upstream") }))
+    })
+}
+
+fn route(external_path: &str, methods: &[&str], auth_required: bool, port: u16) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": external_path,
+        "internal_path": "/",
+        "methods": methods,
+        "auth_required": auth_required
+    }))
+    .unwrap()
+}
+
+fn header(resp: &actix_web::dev::ServiceResponse, name: &str) -> Option<String> {
+    resp.headers().get(name).map(|v| v.to_str().unwrap().to_string())
+}
+
+#[actix_web::test]
+async fn test_options_answered_from_route_methods() {
+    let handler = RouteHandler::new(vec![route("/api/users/{id}", &["GET", "PUT"], false, 1)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::default()
+        .method(actix_web::http::Method::OPTIONS)
+        .uri("/api/users/7")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+    assert_eq!(header(&resp, "Allow").as_deref(), Some("GET, PUT, OPTIONS"));
+    assert_eq!(header(&resp, "Access-Control-Allow-Methods"), None);
+
+    let req = test::TestRequest::default()
+        .method(actix_web::http::Method::OPTIONS)
+        .uri("/api/users/7")
+        .insert_header(("Origin", "https://app.example.com"))
+        .insert_header(("Access-Control-Request-Method", "PUT"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+    assert_eq!(
+        header(&resp, "Access-Control-Allow-Methods").as_deref(),
+        Some("GET, PUT, OPTIONS")
+    );
+
+    // Paths without a route are still not found
+    let req = test::TestRequest::default()
+        .method(actix_web::http::Method::OPTIONS)
+        .uri("/api/unknown")
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+}
+
+#[actix_web::test]
+async fn test_route_methods_override_generic_cors_headers() {
+    let handler = RouteHandler::new(vec![route("/api/reports", &["GET"], false, 1)], 5);
+    let app = test::init_service(
+        App::new()
+            .wrap(cors_headers())
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::default()
+        .method(actix_web::http::Method::OPTIONS)
+        .uri("/api/reports")
+        .insert_header(("Origin", "https://app.example.com"))
+        .insert_header(("Access-Control-Request-Method", "GET"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+    assert_eq!(
+        header(&resp, "Access-Control-Allow-Methods").as_deref(),
+        Some("GET, OPTIONS")
+    );
+    assert_eq!(header(&resp, "Access-Control-Allow-Origin").as_deref(), Some("*"));
+}
+
+#[actix_web::test]
+async fn test_routes_listing_options_forward_it() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route("/api/files", &["GET", "OPTIONS"], false, port)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::default()
+        .method(actix_web::http::Method::OPTIONS)
+        .uri("/api/files")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "upstream");
+}
+
+#[actix_web::test]
+async fn test_preflight_to_protected_route_needs_no_token() {
+    let mut settings: Settings = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "jwt": {"secret": "test-secret-key-that-is-long-enough-for-security-requirements"},
+        "routers": []
+    }))
+    .unwrap();
+    settings.routers = vec![
+        route("/api/public", &["GET"], false, 1),
+        route("/api/private", &["GET", "POST"], true, 1),
+    ];
+    let handler = RouteHandler::new(settings.routers.clone(), 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| auth_http::configure_auth_routes(cfg, handler.clone(), &settings)),
+    )
+    .await;
+
+    for (uri, allow) in [("/api/public", "GET, OPTIONS"), ("/api/private", "GET, POST, OPTIONS")] {
+        let req = test::TestRequest::default()
+            .method(actix_web::http::Method::OPTIONS)
+            .uri(uri)
+            .insert_header(("Access-Control-Request-Method", "GET"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 204, "{}", uri);
+        assert_eq!(header(&resp, "Access-Control-Allow-Methods").as_deref(), Some(allow));
+    }
+
+    let req = test::TestRequest::get().uri("/api/private").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 401);
+}
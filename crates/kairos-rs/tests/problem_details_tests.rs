@@ -0,0 +1,168 @@
+//! Problem Details error format tests
+//!
+//! Verifies that with `errors.format` set to `problem_details` gateway errors,
+//! including rejections by the rate and connection limiting middleware, are
+//! RFC 7807 `application/problem+json` documents, and that apps without the
+//! setting keep plain JSON errors.
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::middleware::connection_limit::ConnectionLimit;
+use kairos_rs::middleware::error_format::ErrorFormatting;
+use kairos_rs::middleware::rate_limit::{
+    AdvancedRateLimit, LimitStrategy, RateLimitConfig, WindowType,
+};
+use kairos_rs::models::settings::{ErrorFormat, Settings};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+#[actix_web::test]
+async fn test_errors_are_problem_details() {
+    let handler = RouteHandler::new(vec![], 5);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ErrorFormat::ProblemDetails))
+            .wrap(ErrorFormatting)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/missing").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(
+        resp.headers().get("Content-Type").unwrap(),
+        "application/problem+json"
+    );
+    let request_id = resp
+        .headers()
+        .get("X-Request-ID")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let body: Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+    assert_eq!(body["type"], "urn:kairos-rs:error:route_not_found");
+    assert_eq!(body["title"], "Not Found");
+    assert_eq!(body["status"], 404);
+    assert_eq!(body["detail"], "No route found for path: /api/missing");
+    assert_eq!(body["instance"], format!("urn:uuid:{}", request_id));
+    assert_eq!(body.get("error"), None);
+}
+
+#[actix_web::test]
+async fn test_errors_default_to_json() {
+    let handler = RouteHandler::new(vec![], 5);
+    let app = test::init_service(
+        App::new()
+            .wrap(ErrorFormatting)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/missing").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("Content-Type").unwrap(), "application/json");
+    let body: Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+    assert_eq!(body["type"], "route_not_found");
+}
+
+#[actix_web::test]
+async fn test_rate_limit_rejection_is_problem_details() {
+    let rate_limit = AdvancedRateLimit::new(RateLimitConfig {
+        strategy: LimitStrategy::PerIP,
+        requests_per_window: 1,
+        window_duration: Duration::from_secs(60),
+        burst_allowance: 0,
+        window_type: WindowType::FixedWindow,
+        enable_redis: false,
+        redis_key_prefix: "test".to_string(),
+    });
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ErrorFormat::ProblemDetails))
+            .wrap(rate_limit)
+            .wrap(ErrorFormatting)
+            .route("/", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+
+    let peer: SocketAddr = "10.0.0.1:40000".parse().unwrap();
+    let request = || test::TestRequest::get().uri("/").peer_addr(peer).to_request();
+    assert_eq!(test::call_service(&app, request()).await.status(), 200);
+
+    let rejected = test::try_call_service(&app, request())
+        .await
+        .expect_err("second request should be rate limited")
+        .error_response();
+    assert_eq!(rejected.status(), 429);
+    assert_eq!(rejected.headers().get("Retry-After").unwrap(), "60");
+    assert_eq!(
+        rejected.headers().get("Content-Type").unwrap(),
+        "application/problem+json"
+    );
+    let body = actix_web::body::to_bytes(rejected.into_body()).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["type"], "urn:kairos-rs:error:rate_limited");
+    assert_eq!(body["status"], 429);
+}
+
+#[actix_web::test]
+async fn test_connection_limit_rejection_is_problem_details() {
+    async fn slow() -> HttpResponse {
+        actix_web::rt::time::sleep(Duration::from_millis(200)).await;
+        HttpResponse::Ok().finish()
+    }
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(ErrorFormat::ProblemDetails))
+            .wrap(ConnectionLimit::new(1))
+            .wrap(ErrorFormatting)
+            .route("/slow", web::get().to(slow)),
+    )
+    .await;
+
+    let peer: SocketAddr = "10.0.0.1:40000".parse().unwrap();
+    let request = || test::TestRequest::get().uri("/slow").peer_addr(peer).to_request();
+    let (first, second) = futures::join!(
+        test::try_call_service(&app, request()),
+        test::try_call_service(&app, request()),
+    );
+    assert_eq!(first.unwrap().status(), 200);
+    let rejected = second
+        .expect_err("second request from the same IP should be rejected")
+        .error_response();
+    assert_eq!(rejected.status(), 503);
+    assert_eq!(rejected.headers().get("Retry-After").unwrap(), "1");
+    assert_eq!(
+        rejected.headers().get("Content-Type").unwrap(),
+        "application/problem+json"
+    );
+    let body = actix_web::body::to_bytes(rejected.into_body()).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["type"], "urn:kairos-rs:error:connection_limited");
+}
+
+#[actix_web::test]
+async fn test_error_format_setting() {
+    let settings: Settings = serde_json::from_value(json!({
+        "version": 1,
+        "errors": { "format": "problem_details" },
+        "routers": []
+    }))
+    .unwrap();
+    assert_eq!(settings.errors.unwrap().format, ErrorFormat::ProblemDetails);
+
+    let settings: Settings = serde_json::from_value(json!({
+        "version": 1,
+        "errors": {},
+        "routers": []
+    }))
+    .unwrap();
+    assert_eq!(settings.errors.unwrap().format, ErrorFormat::Json);
+}
@@ -0,0 +1,80 @@
+//! Rate limiting opt-out tests
+//!
+//! Verifies that `disable_rate_limiting` removes the basic fallback limiter
+//! that is otherwise installed when no `rate_limit` section is configured.
+
+use actix_governor::Governor;
+use actix_web::{middleware::Condition, test, web, App, HttpResponse};
+use kairos_rs::config::validation::ConfigValidator;
+use kairos_rs::middleware::rate_limit::basic_governor_config;
+use kairos_rs::models::settings::Settings;
+use std::net::SocketAddr;
+
+const BURST_REQUESTS: usize = 500;
+
+fn settings(disable_rate_limiting: bool) -> Settings {
+    serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "disable_rate_limiting": disable_rate_limiting,
+        "routers": []
+    }))
+    .unwrap()
+}
+
+/// Sends a burst of requests from a single client and returns how many succeeded.
+async fn send_burst(settings: &Settings) -> usize {
+    let governor_conf = basic_governor_config();
+    let app = test::init_service(
+        App::new()
+            .wrap(Condition::new(
+                !settings.disable_rate_limiting,
+                Governor::new(&governor_conf),
+            ))
+            .route("/ping", web::get().to(|| async { HttpResponse::Ok().finish() })),
+    )
+    .await;
+
+    let peer: SocketAddr = "10.0.0.1:40000".parse().unwrap();
+    let mut succeeded = 0;
+    for _ in 0..BURST_REQUESTS {
+        let req = test::TestRequest::get()
+            .uri("/ping")
+            .peer_addr(peer)
+            .to_request();
+        if test::call_service(&app, req).await.status().is_success() {
+            succeeded += 1;
+        }
+    }
+    succeeded
+}
+
+#[actix_web::test]
+async fn test_default_limiter_rejects_bursts() {
+    let succeeded = send_burst(&settings(false)).await;
+    assert!(
+        succeeded < BURST_REQUESTS,
+        "basic limiter should reject part of the burst"
+    );
+}
+
+#[actix_web::test]
+async fn test_disabled_rate_limiting_allows_bursts() {
+    let succeeded = send_burst(&settings(true)).await;
+    assert_eq!(succeeded, BURST_REQUESTS);
+}
+
+#[actix_web::test]
+async fn test_disable_rate_limiting_defaults_to_false() {
+    let settings: Settings =
+        serde_json::from_str(r#"{"version": 1, "routers": []}"#).unwrap();
+    assert!(!settings.disable_rate_limiting);
+}
+
+#[actix_web::test]
+async fn test_disabled_rate_limiting_is_reported_by_validation() {
+    let result = ConfigValidator::validate_comprehensive(&settings(true));
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w.contains("Rate limiting is disabled")));
+}
@@ -0,0 +1,189 @@
+//! Rate limit snapshot tests
+//!
+//! Verifies that the advanced limiter's counters can be exported from one
+//! gateway and restored into another, so a client near its limit stays
+//! limited across a restart, and that restoring requires credentials.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::middleware::rate_limit::{
+    AdvancedRateLimit, LimitStrategy, RateLimitConfig, RateLimitSnapshot, WindowType,
+};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::management;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+fn config(window_type: WindowType) -> RateLimitConfig {
+    RateLimitConfig {
+        strategy: LimitStrategy::PerIP,
+        requests_per_window: 3,
+        window_duration: Duration::from_secs(60),
+        burst_allowance: 0,
+        window_type,
+        enable_redis: false,
+        redis_key_prefix: "kairos".to_string(),
+    }
+}
+
+fn limiter(window_type: WindowType) -> AdvancedRateLimit {
+    AdvancedRateLimit::new(config(window_type))
+}
+
+/// Gateway settings, with JWT authentication for the admin endpoints if
+/// `jwt` is set.
+fn settings(jwt: bool) -> Settings {
+    let mut settings: Settings = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "routers": []
+    }))
+    .unwrap();
+    if jwt {
+        settings.jwt = Some(common::jwt_settings());
+    }
+    settings
+}
+
+#[actix_web::test]
+async fn test_snapshot_restores_into_new_gateway() {
+    for window_type in [
+        WindowType::FixedWindow,
+        WindowType::SlidingWindow,
+        WindowType::TokenBucket,
+    ] {
+        let peer: SocketAddr = "10.0.0.1:40000".parse().unwrap();
+        let settings = settings(true);
+
+        // The old replica sees two of the client's three allowed requests
+        let old = limiter(window_type.clone());
+        let old_app = test::init_service(
+            App::new()
+                .app_data(web::Data::from(old.store()))
+                .configure(|cfg| management::configure_admin(cfg, &settings))
+                .service(
+                    web::resource("/orders")
+                        .wrap(old)
+                        .to(|| async { HttpResponse::Ok().finish() }),
+                ),
+        )
+        .await;
+        for _ in 0..2 {
+            let req = test::TestRequest::get().uri("/orders").peer_addr(peer).to_request();
+            assert_eq!(test::call_service(&old_app, req).await.status(), 200);
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/admin/rate-limit/snapshot")
+            .insert_header(("Authorization", common::bearer_token()))
+            .to_request();
+        let resp = test::call_service(&old_app, req).await;
+        assert_eq!(resp.status(), 200);
+        let snapshot: RateLimitSnapshot = test::read_body_json(resp).await;
+        assert!(snapshot.entries.contains_key("ip:10.0.0.1"), "{snapshot:?}");
+
+        // Its replacement picks up where it left off
+        let new = limiter(window_type.clone());
+        let new_app = test::init_service(
+            App::new()
+                .app_data(web::Data::from(new.store()))
+                .configure(|cfg| management::configure_admin(cfg, &settings))
+                .service(
+                    web::resource("/orders")
+                        .wrap(new)
+                        .to(|| async { HttpResponse::Ok().finish() }),
+                ),
+        )
+        .await;
+        let req = test::TestRequest::post()
+            .uri("/admin/rate-limit/restore")
+            .insert_header(("Authorization", common::bearer_token()))
+            .set_json(&snapshot)
+            .to_request();
+        let resp = test::call_service(&new_app, req).await;
+        assert_eq!(resp.status(), 200);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["restored"], 1);
+
+        let req = test::TestRequest::get().uri("/orders").peer_addr(peer).to_request();
+        assert_eq!(test::call_service(&new_app, req).await.status(), 200);
+        let req = test::TestRequest::get().uri("/orders").peer_addr(peer).to_request();
+        let err = test::try_call_service(&new_app, req)
+            .await
+            .expect_err("restored client must stay limited");
+        assert_eq!(err.as_response_error().status_code(), 429, "{window_type:?}");
+    }
+}
+
+#[actix_web::test]
+async fn test_snapshot_uses_ages() {
+    let config = config(WindowType::SlidingWindow);
+    let store = AdvancedRateLimit::new(config.clone()).store();
+    assert!(store.check_rate_limit("ip:10.0.0.2", &config).unwrap());
+
+    let value = serde_json::to_value(store.snapshot().unwrap()).unwrap();
+    let entry = &value["entries"]["ip:10.0.0.2"];
+    assert_eq!(entry["request_ages_ms"].as_array().unwrap().len(), 1);
+    assert!(entry["request_ages_ms"][0].as_u64().unwrap() < 1000, "{entry}");
+    assert!(entry["window_age_ms"].as_u64().is_some(), "{entry}");
+
+    // Requests older than the window are expired once restored
+    let mut snapshot = store.snapshot().unwrap();
+    let stale = snapshot.entries.get_mut("ip:10.0.0.2").unwrap();
+    stale.request_ages_ms = vec![61_000, 62_000, 63_000];
+    let restored = AdvancedRateLimit::new(config.clone()).store();
+    restored.restore(&snapshot).unwrap();
+    assert!(restored.check_rate_limit("ip:10.0.0.2", &config).unwrap());
+}
+
+#[actix_web::test]
+async fn test_snapshot_without_advanced_rate_limiting() {
+    let settings = settings(true);
+    let app = test::init_service(
+        App::new().configure(|cfg| management::configure_admin(cfg, &settings)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/admin/rate-limit/snapshot")
+        .insert_header(("Authorization", common::bearer_token()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    let req = test::TestRequest::post()
+        .uri("/admin/rate-limit/restore")
+        .insert_header(("Authorization", common::bearer_token()))
+        .set_json(RateLimitSnapshot::default())
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+}
+
+#[actix_web::test]
+async fn test_restore_rejected_without_credentials() {
+    let config = config(WindowType::FixedWindow);
+    let source = AdvancedRateLimit::new(config.clone()).store();
+    for _ in 0..3 {
+        assert!(source.check_rate_limit("ip:10.0.0.3", &config).unwrap());
+    }
+    let snapshot = source.snapshot().unwrap();
+
+    for jwt in [true, false] {
+        let store = AdvancedRateLimit::new(config.clone()).store();
+        let settings = settings(jwt);
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::from(store.clone()))
+                .configure(|cfg| management::configure_admin(cfg, &settings)),
+        )
+        .await;
+
+        // Without JWT settings the endpoint is not served at all
+        let req = test::TestRequest::post()
+            .uri("/admin/rate-limit/restore")
+            .set_json(&snapshot)
+            .to_request();
+        let expected = if jwt { 401 } else { 404 };
+        assert_eq!(test::call_service(&app, req).await.status(), expected);
+        assert!(store.snapshot().unwrap().entries.is_empty(), "jwt: {jwt}");
+    }
+}
@@ -0,0 +1,143 @@
+//! Request start header tests
+//!
+//! Verifies that forwarded requests carry the time the gateway received
+//! them when the header is enabled globally or per route, that routes can
+//! rename or disable it, that client-sent values are replaced, and that
+//! invalid header names are rejected by validation.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::models::router::{RequestStartHeader, Router};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use serde_json::json;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Starts a mock upstream echoing the `X-Request-Start` and `X-Received-At`
+/// headers it received as `start received`, with `none` for missing ones.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let header = |name: &str| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or("none")
+                    .to_string()
+            };
+            HttpResponse::Ok().body(format!(
+                "{} {}",
+                header("x-request-start"),
+                header("x-received-at")
+            ))
+        }))
+    })
+}
+
+fn route(port: u16, request_start_header: serde_json::Value) -> Router {
+    let mut route = json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": "/api/orders",
+        "internal_path": "/orders",
+        "methods": ["GET"]
+    });
+    if !request_start_header.is_null() {
+        route["request_start_header"] = request_start_header;
+    }
+    serde_json::from_value(route).unwrap()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// Sends `GET /api/orders` through `handler` with `headers` and returns the
+/// upstream's `(x-request-start, x-received-at)` values.
+async fn forward(handler: RouteHandler, headers: &[(&str, &str)]) -> (String, String) {
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+    let mut req = test::TestRequest::get().uri("/api/orders");
+    for &header in headers {
+        req = req.insert_header(header);
+    }
+    let resp = test::call_service(&app, req.to_request()).await;
+    assert_eq!(resp.status(), 200);
+    let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+    let (start, received) = body.split_once(' ').unwrap();
+    (start.to_string(), received.to_string())
+}
+
+#[actix_web::test]
+async fn test_route_header_carries_receive_time() {
+    let handler = RouteHandler::new(vec![route(spawn_upstream(), json!({}))], 5);
+    let before = now_millis();
+    let (start, received) = forward(handler, &[("X-Request-Start", "1")]).await;
+    let start: u64 = start.parse().unwrap();
+    assert!(start >= before && start <= now_millis(), "{start}");
+    assert_eq!(received, "none");
+}
+
+#[actix_web::test]
+async fn test_global_header_applies_to_routes_without_setting() {
+    let global = RequestStartHeader {
+        enabled: true,
+        name: Some("X-Received-At".to_string()),
+    };
+    let handler = RouteHandler::new(vec![route(spawn_upstream(), serde_json::Value::Null)], 5)
+        .with_request_start_header(global.clone());
+    let (start, received) = forward(handler, &[]).await;
+    assert_eq!(start, "none");
+    assert!(received.parse::<u64>().is_ok(), "{received}");
+
+    // A route enabling the header without a name uses the global one
+    let handler = RouteHandler::new(vec![route(spawn_upstream(), json!({}))], 5)
+        .with_request_start_header(global);
+    let (start, received) = forward(handler, &[]).await;
+    assert_eq!(start, "none");
+    assert!(received.parse::<u64>().is_ok(), "{received}");
+}
+
+#[actix_web::test]
+async fn test_route_setting_overrides_global() {
+    let global = RequestStartHeader {
+        enabled: true,
+        name: None,
+    };
+    let disabled = route(spawn_upstream(), json!({"enabled": false}));
+    let handler = RouteHandler::new(vec![disabled], 5).with_request_start_header(global.clone());
+    assert_eq!(forward(handler, &[]).await, ("none".to_string(), "none".to_string()));
+
+    let renamed = route(spawn_upstream(), json!({"name": "X-Received-At"}));
+    let handler = RouteHandler::new(vec![renamed], 5).with_request_start_header(global);
+    let (start, received) = forward(handler, &[]).await;
+    assert_eq!(start, "none");
+    assert!(received.parse::<u64>().is_ok(), "{received}");
+}
+
+#[actix_web::test]
+async fn test_disabled_by_default() {
+    let handler = RouteHandler::new(vec![route(spawn_upstream(), serde_json::Value::Null)], 5);
+    let (start, _) = forward(handler, &[("X-Request-Start", "1")]).await;
+    assert_eq!(start, "1");
+}
+
+#[actix_web::test]
+async fn test_invalid_header_names_are_rejected() {
+    let err = route(8080, json!({"name": "bad header"})).validate().unwrap_err();
+    assert!(err.starts_with("Request start header validation failed"), "{err}");
+
+    let settings: Settings = serde_json::from_value(json!({
+        "version": 1,
+        "request_start_header": {"name": "bad header"},
+        "routers": []
+    }))
+    .unwrap();
+    assert!(settings.validate().is_err());
+}
@@ -0,0 +1,167 @@
+//! Streamed request body tests
+//!
+//! Verifies that routes with `stream_request_body` forward bodies larger than
+//! the gateway-wide buffer limit, that routes needing the whole body still
+//! buffer it, and that `max_body_bytes` applies to streamed bodies without
+//! counting against the backend's circuit breaker.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use futures::StreamExt;
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+
+/// Starts a mock upstream that answers with the number of body bytes it read.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|mut payload: web::Payload| async move {
+            let mut received = 0;
+            while let Some(chunk) = payload.next().await {
+                match chunk {
+                    Ok(chunk) => received += chunk.len(),
+                    Err(_) => return HttpResponse::BadRequest().finish(),
+                }
+            }
+            HttpResponse::Ok().body(received.to_string())
+        }))
+    })
+}
+
+fn route(port: u16, extra: serde_json::Value) -> Router {
+    let mut route = serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": "/uploads",
+        "internal_path": "/files",
+        "methods": ["PUT"],
+        "stream_request_body": true
+    });
+    route
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+    serde_json::from_value(route).unwrap()
+}
+
+#[actix_web::test]
+async fn test_large_bodies_are_streamed_on_opted_in_routes() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, serde_json::json!({}))], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let size = 3 * http::MAX_PAYLOAD_BYTES;
+    let req = test::TestRequest::put()
+        .uri("/uploads")
+        .set_payload(vec![b'x'; size])
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, size.to_string());
+}
+
+#[actix_web::test]
+async fn test_routes_needing_the_body_buffer_it() {
+    let port = spawn_upstream();
+    let body_routed = route(
+        port,
+        serde_json::json!({
+            "body_routing": {"field": "$.kind", "backends": {"image": 0}}
+        }),
+    );
+    assert!(!body_routed.streams_request_body());
+    let buffered = route(port, serde_json::json!({"stream_request_body": false}));
+    assert!(!buffered.streams_request_body());
+    assert!(route(port, serde_json::json!({})).streams_request_body());
+
+    for router in [body_routed, buffered] {
+        let handler = RouteHandler::new(vec![router], 5);
+        let app = test::init_service(
+            App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+        )
+        .await;
+
+        let req = test::TestRequest::put()
+            .uri("/uploads")
+            .set_payload(vec![b'x'; 2 * http::MAX_PAYLOAD_BYTES])
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 413);
+
+        let req = test::TestRequest::put()
+            .uri("/uploads")
+            .set_payload(vec![b'x'; 1000])
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(test::read_body(resp).await, "1000");
+    }
+}
+
+#[actix_web::test]
+async fn test_declared_length_over_route_limit_is_rejected() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(
+        vec![route(port, serde_json::json!({"max_body_bytes": 1000}))],
+        5,
+    );
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::put()
+        .uri("/uploads")
+        .set_payload(vec![b'x'; 1001])
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 413);
+}
+
+#[actix_web::test]
+async fn test_streamed_body_over_route_limit_is_cut_off() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(
+        vec![route(
+            port,
+            serde_json::json!({
+                "max_body_bytes": 1000,
+                "circuit_breaker": {"failure_threshold": 1}
+            }),
+        )],
+        5,
+    );
+
+    // A real listener, so the body is sent chunked without a Content-Length
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let gateway_port = listener.local_addr().unwrap().port();
+    let server = HttpServer::new(move || {
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone()))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+    actix_web::rt::spawn(server);
+
+    let client = reqwest::Client::new();
+    let url = format!("http://127.0.0.1:{}/uploads", gateway_port);
+    let upload = |chunks: usize| {
+        let stream = futures::stream::iter(
+            (0..chunks).map(|_| Ok::<_, std::io::Error>(vec![b'x'; 400])),
+        );
+        client.put(&url).body(reqwest::Body::wrap_stream(stream)).send()
+    };
+
+    for _ in 0..2 {
+        let resp = upload(3).await.unwrap();
+        assert_eq!(resp.status(), 413);
+    }
+
+    // Oversized uploads do not open the circuit
+    let resp = upload(2).await.unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.text().await.unwrap(), "800");
+}
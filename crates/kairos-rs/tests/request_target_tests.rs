@@ -0,0 +1,111 @@
+//! Request target normalization tests
+//!
+//! Verifies that requests with several Host headers are rejected, that
+//! absolute-form request targets are matched on their path, and that encoded
+//! path parameters are merged with the client's query.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream that echoes the path and query it received.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            HttpResponse::Ok().body(format!("{}?{}", req.path(), req.query_string()))
+        }))
+    })
+}
+
+fn route(port: u16, external_path: &str, internal_path: &str) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: external_path.to_string(),
+        internal_path: internal_path.to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_multiple_host_headers_are_rejected() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "/api/users/{id}", "/users/{id}")], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/users/1")
+        .append_header(("Host", "public.example.com"))
+        .append_header(("Host", "internal.example.com"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["type"], "bad_request");
+
+    // A single Host header is fine
+    let req = test::TestRequest::get()
+        .uri("/api/users/1")
+        .insert_header(("Host", "public.example.com"))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_absolute_form_target_matches_on_path() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(
+        vec![
+            route(port, "/api/users/{id}", "/users/{id}"),
+            route(port, "/api/orders", "/orders"),
+        ],
+        5,
+    );
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("http://gateway.example.com/api/users/7?fields=name")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "/users/7?fields=name");
+
+    let req = test::TestRequest::get()
+        .uri("http://gateway.example.com:8080/api/orders")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "/orders?");
+}
+
+#[actix_web::test]
+async fn test_encoded_parameter_in_internal_query() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "/api/search/{term}", "/search?q={term:encoded}")], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/search/a+b&c=d?page=2")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "/search?q=a%2Bb%26c%3Dd&page=2");
+}
@@ -0,0 +1,220 @@
+//! Request transformation proxy tests
+//!
+//! Verifies that a route's `request_transformation` is applied to the
+//! forwarded headers, path and query parameters, that a `when` condition gates
+//! it, that repeated query parameters survive query rules, and that routes
+//! without one forward the client's query unchanged.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::middleware::transform::{
+    HeaderTransformation, PathTransformation, QueryTransformation, RequestTransformation,
+    TransformAction,
+};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream that echoes the request it received as JSON.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            let header = |name: &str| {
+                req.headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+            };
+            HttpResponse::Ok().json(serde_json::json!({
+                "path": req.path(),
+                "query": req.query_string(),
+                "x_gateway": header("x-gateway"),
+                "authorization": header("authorization"),
+                "user_agent": header("user-agent"),
+            }))
+        }))
+    })
+}
+
+fn route(port: u16, request_transformation: Option<RequestTransformation>) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/users/{id}".to_string(),
+        internal_path: "/v1/users/{id}".to_string(),
+        methods: vec!["GET".to_string()],
+        request_transformation,
+        ..Default::default()
+    }
+}
+
+fn header_rule(
+    action: TransformAction,
+    name: &str,
+    value: Option<&str>,
+    pattern: Option<&str>,
+    replacement: Option<&str>,
+) -> HeaderTransformation {
+    HeaderTransformation {
+        action,
+        name: name.to_string(),
+        value: value.map(str::to_string),
+        pattern: pattern.map(str::to_string),
+        replacement: replacement.map(str::to_string),
+    }
+}
+
+fn query_rule(
+    action: TransformAction,
+    name: &str,
+    value: Option<&str>,
+    pattern: Option<&str>,
+    replacement: Option<&str>,
+) -> QueryTransformation {
+    QueryTransformation {
+        action,
+        name: name.to_string(),
+        value: value.map(str::to_string),
+        pattern: pattern.map(str::to_string),
+        replacement: replacement.map(str::to_string),
+    }
+}
+
+async fn proxy(router: Router, uri: &str) -> serde_json::Value {
+    let handler = RouteHandler::new(vec![router], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri(uri)
+        .insert_header(("Authorization", "Bearer client-token"))
+        .insert_header(("User-Agent", "client/1.4"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    test::read_body_json(resp).await
+}
+
+#[actix_web::test]
+async fn test_transformation_applied_to_forwarded_request() {
+    let port = spawn_upstream();
+    let transformation = RequestTransformation {
+        headers: vec![
+            header_rule(TransformAction::Set, "X-Gateway", Some("kairos"), None, None),
+            header_rule(TransformAction::Remove, "Authorization", None, None, None),
+            header_rule(
+                TransformAction::Replace,
+                "User-Agent",
+                None,
+                Some(r"/(\d+)\.(\d+)"),
+                Some("-v$1"),
+            ),
+        ],
+        path: Some(PathTransformation {
+            pattern: r"^/v1/(.+)$".to_string(),
+            replacement: "/v2/$1".to_string(),
+        }),
+        query_params: vec![
+            QueryTransformation {
+                action: TransformAction::Add,
+                name: "api_key".to_string(),
+                value: Some("secret".to_string()),
+                pattern: None,
+                replacement: None,
+            },
+            QueryTransformation {
+                action: TransformAction::Remove,
+                name: "debug".to_string(),
+                value: None,
+                pattern: None,
+                replacement: None,
+            },
+        ],
+        when: None,
+        body: None,
+    };
+
+    let echoed = proxy(
+        route(port, Some(transformation)),
+        "/api/users/42?debug=true&fields=name",
+    )
+    .await;
+
+    assert_eq!(echoed["path"], "/v2/users/42");
+    assert_eq!(echoed["query"], "fields=name&api_key=secret");
+    assert_eq!(echoed["x_gateway"], "kairos");
+    assert!(echoed["authorization"].is_null());
+    assert_eq!(echoed["user_agent"], "client-v1");
+}
+
+#[actix_web::test]
+async fn test_query_rules_keep_repeated_parameters() {
+    let port = spawn_upstream();
+    let transformation = RequestTransformation {
+        query_params: vec![
+            query_rule(TransformAction::Replace, "tag", None, Some("^old-"), Some("new-")),
+            query_rule(TransformAction::Set, "page", Some("1"), None, None),
+            query_rule(TransformAction::Add, "api_key", Some("secret"), None, None),
+        ],
+        ..Default::default()
+    };
+
+    let echoed = proxy(
+        route(port, Some(transformation)),
+        "/api/users/42?tag=old-a&page=3&tag=b&page=4&tag=old-c",
+    )
+    .await;
+
+    assert_eq!(
+        echoed["query"],
+        "tag=new-a&page=1&tag=b&tag=new-c&api_key=secret"
+    );
+}
+
+#[actix_web::test]
+async fn test_query_forwarded_unchanged_without_transformation() {
+    let port = spawn_upstream();
+
+    let echoed = proxy(route(port, None), "/api/users/42?b=2&a=1&a=3").await;
+
+    assert_eq!(echoed["path"], "/v1/users/42");
+    assert_eq!(echoed["query"], "b=2&a=1&a=3");
+    assert_eq!(echoed["authorization"], "Bearer client-token");
+    assert!(echoed["x_gateway"].is_null());
+}
+
+#[actix_web::test]
+async fn test_conditional_transformation_applied_only_when_matching() {
+    let port = spawn_upstream();
+    let transformation = RequestTransformation {
+        headers: vec![header_rule(TransformAction::Set, "X-Gateway", Some("debug"), None, None)],
+        when: Some("header.X-Debug == 'on'".to_string()),
+        body: None,
+        ..Default::default()
+    };
+    let handler = RouteHandler::new(vec![route(port, Some(transformation))], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/users/42")
+        .insert_header(("X-Debug", "on"))
+        .to_request();
+    let echoed: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(echoed["x_gateway"], "debug");
+
+    let req = test::TestRequest::get().uri("/api/users/42").to_request();
+    let echoed: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert!(echoed["x_gateway"].is_null());
+    assert_eq!(echoed["path"], "/v1/users/42");
+}
@@ -0,0 +1,188 @@
+//! Response schema validation tests
+//!
+//! Verifies that upstream responses are checked against a route's
+//! `response_schema`: shadow mode passes violations through while counting
+//! them, enforce mode rejects them with a 502.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::config::validation::ConfigValidator;
+use kairos_rs::models::router::{Backend, ResponseSchemaMode, Router};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::{http, metrics};
+use kairos_rs::services::http::RouteHandler;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use tempfile::NamedTempFile;
+
+const USER_SCHEMA: &str = r#"{
+  "type": "object",
+  "required": ["id", "name"],
+  "properties": {
+    "id": { "type": "integer" },
+    "name": { "type": "string" }
+  }
+}"#;
+
+/// Starts a mock upstream returning a conforming user on `/users/1` and a
+/// user with a string id everywhere else.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new()
+            .route(
+                "/users/1",
+                web::get().to(|| async {
+                    HttpResponse::Ok().json(serde_json::json!({"id": 1, "name": "Ada"}))
+                }),
+            )
+            .default_service(web::to(|| async {
+                HttpResponse::Ok().json(serde_json::json!({"id": "two", "name": "Grace"}))
+            }))
+    })
+}
+
+fn schema_file() -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(USER_SCHEMA.as_bytes()).unwrap();
+    file
+}
+
+fn route(port: u16, schema: PathBuf, mode: ResponseSchemaMode) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/users/{id}".to_string(),
+        internal_path: "/users/{id}".to_string(),
+        methods: vec!["GET".to_string()],
+        response_schema: Some(schema),
+        response_schema_mode: mode,
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_shadow_mode_passes_violations_through() {
+    let port = spawn_upstream();
+    let schema = schema_file();
+    let collector = metrics::MetricsCollector::default();
+    let handler = RouteHandler::new(
+        vec![route(port, schema.path().to_path_buf(), ResponseSchemaMode::Shadow)],
+        5,
+    );
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/users/2").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["id"], "two");
+    assert_eq!(collector.response_schema_violations.load(Ordering::Relaxed), 1);
+}
+
+#[actix_web::test]
+async fn test_enforce_mode_rejects_violations() {
+    let port = spawn_upstream();
+    let schema = schema_file();
+    let collector = metrics::MetricsCollector::default();
+    let handler = RouteHandler::new(
+        vec![route(port, schema.path().to_path_buf(), ResponseSchemaMode::Enforce)],
+        5,
+    );
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/users/2").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 502);
+    assert_eq!(collector.response_schema_violations.load(Ordering::Relaxed), 1);
+
+    // Conforming responses are unaffected
+    let req = test::TestRequest::get().uri("/users/1").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["name"], "Ada");
+    assert_eq!(collector.response_schema_violations.load(Ordering::Relaxed), 1);
+}
+
+#[actix_web::test]
+async fn test_violations_are_exported() {
+    let collector = metrics::MetricsCollector::default();
+    collector.record_response_schema_violation();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector))
+            .configure(metrics::configure_metrics),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    assert!(String::from_utf8_lossy(&body).contains("kairos_response_schema_violations_total 1"));
+}
+
+#[actix_web::test]
+async fn test_mode_defaults_to_shadow() {
+    let router: Router = serde_json::from_value(serde_json::json!({
+        "external_path": "/users/{id}",
+        "internal_path": "/users/{id}",
+        "methods": ["GET"],
+        "backends": [{"host": "http://127.0.0.1", "port": 8080}],
+        "response_schema": "schemas/user.json"
+    }))
+    .unwrap();
+    assert_eq!(router.response_schema_mode, ResponseSchemaMode::Shadow);
+}
+
+#[actix_web::test]
+async fn test_unreadable_schema_fails_validation() {
+    let settings = Settings {
+        version: 1,
+        jwt: None,
+        rate_limit: None,
+        disable_rate_limiting: false,
+        ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
+        routers: vec![route(
+            8080,
+            PathBuf::from("/nonexistent/user.schema.json"),
+            ResponseSchemaMode::Enforce,
+        )],
+    };
+    let result = ConfigValidator::validate_comprehensive(&settings);
+    assert!(!result.is_valid);
+    assert!(result.errors.iter().any(|e| e.contains("response schema")));
+}
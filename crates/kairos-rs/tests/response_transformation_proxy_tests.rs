@@ -0,0 +1,101 @@
+//! Response transformation proxy tests
+//!
+//! Verifies that a route's `response_transformation` rewrites the status code
+//! and headers of upstream responses, and that other routes are unaffected.
+
+mod common;
+
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::middleware::transform::{
+    HeaderTransformation, ResponseTransformation, StatusCodeMapping, TransformAction,
+};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+/// Starts a mock upstream that answers every request with a 404.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async {
+            HttpResponse::NotFound()
+                .insert_header(("Server", "upstream/1.0"))
+                .append_header(("Set-Cookie", "a=1"))
+                .append_header(("Set-Cookie", "b=2"))
+                .body("not found")
+        }))
+    })
+}
+
+fn route(
+    port: u16,
+    path: &str,
+    response_transformation: Option<ResponseTransformation>,
+) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: path.to_string(),
+        internal_path: path.to_string(),
+        methods: vec!["GET".to_string()],
+        response_transformation,
+        ..Default::default()
+    }
+}
+
+fn header_rule(action: TransformAction, name: &str, value: Option<&str>) -> HeaderTransformation {
+    HeaderTransformation {
+        action,
+        name: name.to_string(),
+        value: value.map(str::to_string),
+        pattern: None,
+        replacement: None,
+    }
+}
+
+#[actix_web::test]
+async fn test_backend_404_mapped_to_200_with_transformed_headers() {
+    let port = spawn_upstream();
+    let transformation = ResponseTransformation {
+        headers: vec![
+            header_rule(TransformAction::Remove, "Server", None),
+            header_rule(TransformAction::Set, "X-Gateway", Some("kairos")),
+        ],
+        status_code_mappings: vec![StatusCodeMapping {
+            from: StatusCode::NOT_FOUND,
+            to: StatusCode::OK,
+            condition: None,
+        }],
+    };
+    let handler = RouteHandler::new(
+        vec![
+            route(port, "/health", Some(transformation)),
+            route(port, "/users", None),
+        ],
+        5,
+    );
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/health").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("server").is_none());
+    assert_eq!(resp.headers().get("x-gateway").unwrap(), "kairos");
+    assert_eq!(resp.headers().get_all("set-cookie").count(), 2);
+    assert_eq!(test::read_body(resp).await, "not found");
+
+    // Routes without a transformation keep the upstream response
+    let req = test::TestRequest::get().uri("/users").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers().get("server").unwrap(), "upstream/1.0");
+    assert!(resp.headers().get("x-gateway").is_none());
+}
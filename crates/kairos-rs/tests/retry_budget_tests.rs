@@ -0,0 +1,158 @@
+//! Retry budget tests
+//!
+//! Verifies that retries stop once an upstream's retry budget is spent, that
+//! regular requests refill it, and that budgets are reported on `/metrics`.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::{Backend, RetryConfig, Router};
+use kairos_rs::routes::{http, metrics};
+use kairos_rs::services::http::RouteHandler;
+use kairos_rs::services::retry_budget::RETRY_BUDGET_CAPACITY;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Starts a mock upstream that answers every request with `503`, counting them.
+fn spawn_failing_upstream() -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+
+    let server = HttpServer::new(move || {
+        let counter = counter.clone();
+        App::new().default_service(web::to(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { HttpResponse::ServiceUnavailable().body("down") }
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    (port, hits)
+}
+
+fn route(port: u16, retry_budget_ratio: Option<f64>) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/api/orders".to_string(),
+        internal_path: "/orders".to_string(),
+        methods: vec!["GET".to_string()],
+        retry: Some(RetryConfig {
+            max_retries: 3,
+            initial_backoff_ms: 0,
+            max_backoff_ms: 0,
+            retry_budget_ratio,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_empty_budget_suppresses_retries() {
+    let (port, hits) = spawn_failing_upstream();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port, Some(0.0))], 5);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .app_data(web::Data::new(handler.clone()))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    // The initial budget pays for ten retries: three requests retry three
+    // times each, the fourth retries once before the budget runs dry
+    for _ in 0..5 {
+        let req = test::TestRequest::get().uri("/api/orders").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 503);
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 3 * 4 + 2 + 1);
+    assert_eq!(handler.get_retry_budgets()[&service], (0.0, 2));
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let text = String::from_utf8_lossy(&body);
+    assert!(
+        text.contains(&format!("kairos_retry_budget_tokens{{service=\"{}\"}} 0.00", service)),
+        "{text}"
+    );
+    assert!(
+        text.contains(&format!("kairos_retries_suppressed_total{{service=\"{}\"}} 2", service)),
+        "{text}"
+    );
+
+    // The budget survives a route reload
+    handler.reload_routes(vec![route(port, Some(0.0))]).unwrap();
+    assert_eq!(handler.get_retry_budgets()[&service], (0.0, 2));
+}
+
+#[actix_web::test]
+async fn test_requests_refill_budget() {
+    let (port, hits) = spawn_failing_upstream();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port, Some(0.5))], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    assert_eq!(handler.get_retry_budgets()[&service], (RETRY_BUDGET_CAPACITY, 0));
+
+    // Each request adds half a token, so the budget lasts four requests
+    for _ in 0..4 {
+        let req = test::TestRequest::get().uri("/api/orders").to_request();
+        test::call_service(&app, req).await;
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 3 * 4 + 3);
+    assert_eq!(handler.get_retry_budgets()[&service], (0.5, 1));
+
+    // The next request completes a token and retries once more
+    hits.store(0, Ordering::SeqCst);
+    let req = test::TestRequest::get().uri("/api/orders").to_request();
+    test::call_service(&app, req).await;
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+    assert_eq!(handler.get_retry_budgets()[&service], (0.0, 2));
+}
+
+#[actix_web::test]
+async fn test_routes_without_ratio_have_no_budget() {
+    let (port, hits) = spawn_failing_upstream();
+    let handler = RouteHandler::new(vec![route(port, None)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for _ in 0..5 {
+        let req = test::TestRequest::get().uri("/api/orders").to_request();
+        test::call_service(&app, req).await;
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 5 * 4);
+    assert!(handler.get_retry_budgets().is_empty());
+}
+
+#[actix_web::test]
+async fn test_retry_budget_ratio_validation() {
+    let config: RetryConfig =
+        serde_json::from_value(serde_json::json!({ "retry_budget_ratio": 0.1 })).unwrap();
+    assert_eq!(config.retry_budget_ratio, Some(0.1));
+    assert!(config.validate().is_ok());
+
+    let config = RetryConfig {
+        retry_budget_ratio: Some(1.5),
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+}
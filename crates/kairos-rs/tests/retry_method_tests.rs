@@ -0,0 +1,105 @@
+//! Retry method tests
+//!
+//! Verifies that failed requests are only retried for idempotent methods,
+//! unless the route's retry configuration sets `retry_non_idempotent`.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::{RetryConfig, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Starts a mock upstream that counts requests and always fails with 503.
+fn spawn_failing_upstream() -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let hits = Arc::new(AtomicUsize::new(0));
+
+    let counter = hits.clone();
+    let server = HttpServer::new(move || {
+        let counter = counter.clone();
+        App::new().default_service(web::to(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { HttpResponse::ServiceUnavailable().body("down") }
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    (port, hits)
+}
+
+fn route(port: u16, retry_non_idempotent: bool) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": "/api/orders",
+        "internal_path": "/orders",
+        "methods": ["GET", "PUT", "POST", "PATCH"],
+        "retry": {
+            "max_retries": 2,
+            "initial_backoff_ms": 0,
+            "max_backoff_ms": 0,
+            "retry_non_idempotent": retry_non_idempotent
+        },
+        "circuit_breaker": {"failure_threshold": 100}
+    }))
+    .unwrap()
+}
+
+/// Sends one `method` request and returns how often the upstream was hit.
+async fn upstream_attempts(retry_non_idempotent: bool, method: &str) -> usize {
+    let (port, hits) = spawn_failing_upstream();
+    let handler = RouteHandler::new(vec![route(port, retry_non_idempotent)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::default()
+        .method(method.parse().unwrap())
+        .uri("/api/orders")
+        .set_payload("{\"item\": 1}")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 503);
+    hits.load(Ordering::SeqCst)
+}
+
+#[actix_web::test]
+async fn test_idempotent_methods_are_retried() {
+    assert_eq!(upstream_attempts(false, "GET").await, 3);
+    assert_eq!(upstream_attempts(false, "PUT").await, 3);
+}
+
+#[actix_web::test]
+async fn test_non_idempotent_methods_are_not_retried_by_default() {
+    assert_eq!(upstream_attempts(false, "POST").await, 1);
+    assert_eq!(upstream_attempts(false, "PATCH").await, 1);
+}
+
+#[actix_web::test]
+async fn test_non_idempotent_methods_are_retried_when_opted_in() {
+    assert_eq!(upstream_attempts(true, "POST").await, 3);
+    assert_eq!(upstream_attempts(true, "PATCH").await, 3);
+}
+
+#[actix_web::test]
+async fn test_retries_method() {
+    let config = RetryConfig::default();
+    assert!(!config.retry_non_idempotent);
+    for method in ["GET", "HEAD", "PUT", "DELETE", "OPTIONS"] {
+        assert!(config.retries_method(method), "{method}");
+    }
+    assert!(!config.retries_method("POST"));
+    assert!(!config.retries_method("PATCH"));
+
+    let opted_in: RetryConfig =
+        serde_json::from_value(serde_json::json!({ "retry_non_idempotent": true })).unwrap();
+    assert!(opted_in.retries_method("POST"));
+    assert!(opted_in.retries_method("PATCH"));
+}
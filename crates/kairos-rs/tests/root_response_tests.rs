@@ -0,0 +1,146 @@
+//! Root response tests
+//!
+//! Verifies that `GET /` is answered by the configured `root_response`
+//! before the proxy's catch-all route, and is only proxied when asked to.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::models::settings::{RootResponse, Settings};
+use kairos_rs::routes::{http, root};
+use kairos_rs::services::http::RouteHandler;
+use serde_json::{json, Value};
+
+/// Starts a mock upstream that answers every request with `upstream`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Ok().body("upstream") }))
+    })
+}
+
+fn root_route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/".to_string(),
+        internal_path: "/".to_string(),
+        methods: vec!["GET".to_string(), "POST".to_string()],
+        ..Default::default()
+    }
+}
+
+/// Sends `req` to a gateway with `response` at the root and a proxy route
+/// for `/`.
+async fn call(response: RootResponse, req: test::TestRequest) -> actix_web::dev::ServiceResponse {
+    let handler = RouteHandler::new(vec![root_route(spawn_upstream())], 5);
+    let app = test::init_service(
+        App::new()
+            .configure(|cfg| root::configure_root(cfg, response))
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+    test::call_service(&app, req.to_request()).await
+}
+
+fn settings(value: Value) -> Settings {
+    serde_json::from_value(value).unwrap()
+}
+
+#[actix_web::test]
+async fn test_default_root_response_reports_service_and_version() {
+    let resp = call(RootResponse::default(), test::TestRequest::get().uri("/")).await;
+    assert_eq!(resp.status(), 200);
+
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["service"], "kairos-rs");
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(body.get("docs"), None);
+}
+
+#[actix_web::test]
+async fn test_info_response_uses_configured_name_and_docs() {
+    let response = RootResponse::Info {
+        name: Some("acme-gateway".to_string()),
+        docs_url: Some("https://docs.example.com".to_string()),
+    };
+    let resp = call(response, test::TestRequest::get().uri("/")).await;
+
+    let body: Value = test::read_body_json(resp).await;
+    assert_eq!(body["service"], "acme-gateway");
+    assert_eq!(body["docs"], "https://docs.example.com");
+}
+
+#[actix_web::test]
+async fn test_redirect_response() {
+    let response = RootResponse::Redirect {
+        location: "/docs".to_string(),
+        permanent: false,
+    };
+    let resp = call(response, test::TestRequest::get().uri("/")).await;
+    assert_eq!(resp.status(), 302);
+    assert_eq!(resp.headers().get("Location").unwrap(), "/docs");
+
+    let response = RootResponse::Redirect {
+        location: "https://example.com/".to_string(),
+        permanent: true,
+    };
+    let resp = call(response, test::TestRequest::get().uri("/")).await;
+    assert_eq!(resp.status(), 301);
+}
+
+#[actix_web::test]
+async fn test_other_methods_reach_the_proxy() {
+    let resp = call(RootResponse::default(), test::TestRequest::post().uri("/")).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "upstream");
+}
+
+#[actix_web::test]
+async fn test_proxy_response_forwards_root() {
+    let resp = call(RootResponse::Proxy, test::TestRequest::get().uri("/")).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "upstream");
+}
+
+#[actix_web::test]
+async fn test_resolved_root_response() {
+    let config = settings(json!({ "version": 1, "routers": [] }));
+    assert_eq!(config.resolved_root_response(), RootResponse::default());
+
+    // An existing route for `/` keeps working without configuration
+    let config = settings(json!({
+        "version": 1,
+        "routers": [serde_json::to_value(root_route(8080)).unwrap()]
+    }));
+    assert_eq!(config.resolved_root_response(), RootResponse::Proxy);
+
+    let config = settings(json!({
+        "version": 1,
+        "root_response": { "type": "redirect", "location": "/docs" },
+        "routers": []
+    }));
+    assert_eq!(
+        config.resolved_root_response(),
+        RootResponse::Redirect {
+            location: "/docs".to_string(),
+            permanent: false
+        }
+    );
+    assert!(config.validate().is_ok());
+}
+
+#[actix_web::test]
+async fn test_empty_redirect_location_is_invalid() {
+    let config = settings(json!({
+        "version": 1,
+        "root_response": { "type": "redirect", "location": " " },
+        "routers": []
+    }));
+    assert!(config.validate().is_err());
+}
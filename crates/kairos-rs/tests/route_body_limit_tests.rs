@@ -0,0 +1,205 @@
+//! Per-route request body limit tests
+//!
+//! Verifies that `max_body_bytes` rejects oversized requests with
+//! `413 Payload Too Large` before they reach the upstream, that body sizes
+//! and rejections are recorded per route, and that configuration validation
+//! flags limits above the gateway-wide cap.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::config::validation::ConfigValidator;
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::http;
+use kairos_rs::routes::metrics::{self, MetricsCollector};
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const ROUTE_LIMIT: usize = 10 * 1024;
+
+/// Starts a mock upstream that counts the requests it receives.
+fn spawn_counting_upstream() -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let hits = Arc::new(AtomicUsize::new(0));
+
+    let counter = hits.clone();
+    let server = HttpServer::new(move || {
+        let counter = counter.clone();
+        App::new().default_service(web::to(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { HttpResponse::Ok().body("accepted") }
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    (port, hits)
+}
+
+fn route(port: u16, max_body_bytes: Option<usize>) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/upload".to_string(),
+        internal_path: "/upload".to_string(),
+        methods: vec!["POST".to_string()],
+        max_body_bytes,
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_body_within_limit_is_forwarded() {
+    let (port, hits) = spawn_counting_upstream();
+    let handler = RouteHandler::new(vec![route(port, Some(ROUTE_LIMIT))], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/upload")
+        .set_payload(vec![b'x'; ROUTE_LIMIT])
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+async fn test_oversized_body_is_rejected_before_forwarding() {
+    let (port, hits) = spawn_counting_upstream();
+    let handler = RouteHandler::new(vec![route(port, Some(ROUTE_LIMIT))], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/upload")
+        .set_payload(vec![b'x'; ROUTE_LIMIT + 1])
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 413);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["type"], "payload_too_large");
+    assert_eq!(hits.load(Ordering::SeqCst), 0, "upstream must not be called");
+}
+
+#[actix_web::test]
+async fn test_body_sizes_and_rejections_recorded_per_route() {
+    let (port, _) = spawn_counting_upstream();
+    let handler = RouteHandler::new(vec![route(port, Some(ROUTE_LIMIT))], 5);
+    let collector = MetricsCollector::default().with_body_size_buckets(vec![16_384, 1_024]);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/upload")
+        .set_payload(vec![b'x'; 2_000])
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = test::TestRequest::post()
+        .uri("/upload")
+        .set_payload(vec![b'x'; ROUTE_LIMIT + 1])
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 413);
+
+    let (route, stats) = &collector.route_body_stats()[0];
+    assert_eq!(route, "/upload");
+    assert_eq!(stats.count, 2);
+    assert_eq!(stats.too_large, 1);
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("kairos_request_body_bytes_bucket{route=\"/upload\",le=\"1024\"} 0\n"));
+    assert!(body.contains("kairos_request_body_bytes_bucket{route=\"/upload\",le=\"16384\"} 2\n"));
+    assert!(body.contains("kairos_request_body_bytes_bucket{route=\"/upload\",le=\"+Inf\"} 2\n"));
+    assert!(body.contains(&format!(
+        "kairos_request_body_bytes_sum{{route=\"/upload\"}} {}\n",
+        2_000 + ROUTE_LIMIT + 1
+    )));
+    assert!(body.contains("kairos_body_too_large_total{route=\"/upload\"} 1\n"));
+}
+
+#[actix_web::test]
+async fn test_route_without_limit_accepts_large_body() {
+    let (port, hits) = spawn_counting_upstream();
+    let handler = RouteHandler::new(vec![route(port, None)], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/upload")
+        .set_payload(vec![b'x'; ROUTE_LIMIT * 4])
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+async fn test_validation_warns_when_limit_exceeds_gateway_cap() {
+    let settings = Settings {
+        version: 1,
+        jwt: None,
+        rate_limit: None,
+        disable_rate_limiting: false,
+        ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
+        routers: vec![route(8080, Some(http::MAX_PAYLOAD_BYTES + 1))],
+    };
+    let result = ConfigValidator::validate_comprehensive(&settings);
+    assert!(result
+        .warnings
+        .iter()
+        .any(|w| w.contains("max_body_bytes") && w.contains("/upload")));
+
+    let settings = Settings {
+        routers: vec![route(8080, Some(ROUTE_LIMIT))],
+        ..settings
+    };
+    let result = ConfigValidator::validate_comprehensive(&settings);
+    assert!(!result.warnings.iter().any(|w| w.contains("max_body_bytes")));
+}
+
+#[actix_web::test]
+async fn test_zero_limit_is_rejected() {
+    assert!(route(8080, Some(0)).validate().is_err());
+}
@@ -0,0 +1,112 @@
+//! Route debug endpoint tests
+//!
+//! Verifies that `GET /admin/routes/{external_path}/debug` reports the
+//! compiled regex, parameter names and match counters of dynamic routes.
+
+use actix_web::{test, web, App};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::{http, management};
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+
+/// Returns a local port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn route(external_path: &str, internal_path: &str, port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: external_path.to_string(),
+        internal_path: internal_path.to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+fn routes(port: u16) -> Vec<Router> {
+    vec![
+        route("/api/users/{id}/posts/{post_id}", "/users/{id}/posts/{post_id}", port),
+        route("/api/users/{id}", "/users/{id}", port),
+        route("/api/health", "/health", port),
+    ]
+}
+
+fn settings() -> Settings {
+    serde_json::from_value(serde_json::json!({ "version": 1, "routers": [] })).unwrap()
+}
+
+#[actix_web::test]
+async fn test_dynamic_route_debug() {
+    let port = unused_port();
+    let handler = RouteHandler::new(routes(port), 5);
+    let settings = settings();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(handler.clone()))
+            .configure(|cfg| management::configure_admin(cfg, &settings))
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let debug_uri = "/admin/routes/%2Fapi%2Fusers%2F%7Bid%7D%2Fposts%2F%7Bpost_id%7D/debug";
+    let req = test::TestRequest::get().uri(debug_uri).to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["external_path"], "/api/users/{id}/posts/{post_id}");
+    assert_eq!(body["regex"], "^/api/users/([^/]+)/posts/([^/]+)$");
+    assert_eq!(body["param_names"], serde_json::json!(["id", "post_id"]));
+    assert_eq!(body["hits"], 0);
+    assert_eq!(body["misses"], 0);
+
+    // Paths are tried against the more specific route first
+    for uri in ["/api/users/1/posts/2", "/api/users/1/posts/3", "/api/users/1"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        test::call_service(&app, req).await;
+    }
+
+    let req = test::TestRequest::get().uri(debug_uri).to_request();
+    let body: serde_json::Value = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(body["hits"], 2);
+    assert_eq!(body["misses"], 1);
+
+    let info = handler.route_debug("/api/users/{id}").unwrap();
+    assert_eq!(info.param_names, vec!["id".to_string()]);
+    assert_eq!((info.hits, info.misses), (1, 0));
+
+    // Counters survive a reload that keeps the pattern
+    handler.reload_routes(routes(port)).unwrap();
+    assert_eq!(handler.route_debug("/api/users/{id}").unwrap().hits, 1);
+}
+
+#[actix_web::test]
+async fn test_static_and_unknown_routes_not_found() {
+    let handler = RouteHandler::new(routes(unused_port()), 5);
+    let settings = settings();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(handler))
+            .configure(|cfg| management::configure_admin(cfg, &settings)),
+    )
+    .await;
+
+    for uri in ["/admin/routes/%2Fapi%2Fhealth/debug", "/admin/routes/%2Fmissing/debug"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 404);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["success"], false);
+    }
+}
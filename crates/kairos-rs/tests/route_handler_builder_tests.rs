@@ -0,0 +1,134 @@
+//! Route handler builder tests
+//!
+//! Verifies that handlers built with `RouteHandlerBuilder` send upstream
+//! requests with a caller-supplied client, resolve backend names with a
+//! custom DNS resolver, go through a configured proxy, and that invalid
+//! routes are reported as errors instead of panics.
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse, HttpServer};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Starts a mock server answering with `label`, the request target as it
+/// arrived and the `X-Embedder` header, if any.
+fn spawn_server(label: &'static str) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = HttpServer::new(move || {
+        App::new().default_service(web::to(move |req: HttpRequest| async move {
+            let embedder = req
+                .headers()
+                .get("x-embedder")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("none")
+                .to_string();
+            HttpResponse::Ok().body(format!("{} {} {}", label, req.uri(), embedder))
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+    actix_web::rt::spawn(server);
+    port
+}
+
+fn route(host: &str, port: u16) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": host, "port": port}],
+        "external_path": "/api/items",
+        "internal_path": "/items",
+        "methods": ["GET"]
+    }))
+    .unwrap()
+}
+
+async fn get(handler: RouteHandler) -> (u16, String) {
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+    let req = test::TestRequest::get().uri("/api/items").to_request();
+    let resp = test::call_service(&app, req).await;
+    let status = resp.status().as_u16();
+    let body = test::read_body(resp).await;
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Resolves every name to the loopback address, counting lookups.
+struct LoopbackResolver {
+    lookups: AtomicUsize,
+}
+
+impl Resolve for LoopbackResolver {
+    fn resolve(&self, _name: Name) -> Resolving {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async {
+            let addrs: Addrs = Box::new(std::iter::once(SocketAddr::from(([127, 0, 0, 1], 0))));
+            Ok(addrs)
+        })
+    }
+}
+
+#[actix_web::test]
+async fn test_custom_client_is_used() {
+    let port = spawn_server("backend");
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("x-embedder", "acme".parse().unwrap());
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    let handler = RouteHandler::builder(vec![route("http://127.0.0.1", port)], 5)
+        .client(client)
+        .build()
+        .unwrap();
+    assert_eq!(get(handler).await, (200, "backend /items acme".to_string()));
+}
+
+#[actix_web::test]
+async fn test_custom_dns_resolver_resolves_backends() {
+    let port = spawn_server("backend");
+    let resolver = Arc::new(LoopbackResolver {
+        lookups: AtomicUsize::new(0),
+    });
+
+    let handler = RouteHandler::builder(vec![route("http://items.service.internal", port)], 5)
+        .dns_resolver(resolver.clone())
+        .build()
+        .unwrap();
+    assert_eq!(get(handler).await, (200, "backend /items none".to_string()));
+    assert_eq!(resolver.lookups.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+async fn test_requests_go_through_proxy() {
+    let proxy_port = spawn_server("proxy");
+    let proxy = reqwest::Proxy::http(format!("http://127.0.0.1:{}", proxy_port)).unwrap();
+
+    // The backend does not exist; only the proxy can answer
+    let handler = RouteHandler::builder(vec![route("http://items.invalid", 8080)], 5)
+        .proxy(proxy)
+        .build()
+        .unwrap();
+    let (status, body) = get(handler).await;
+    assert_eq!(status, 200);
+    assert_eq!(body, "proxy http://items.invalid:8080/items none");
+}
+
+#[actix_web::test]
+async fn test_invalid_route_is_an_error() {
+    let mut invalid = route("http://127.0.0.1", 8080);
+    invalid.external_path = "/api/{id".to_string();
+    let err = RouteHandler::builder(vec![invalid], 5)
+        .build()
+        .err()
+        .expect("invalid route should fail the build");
+    assert!(err.contains("route matcher"), "{err}");
+}
@@ -1,6 +1,5 @@
 use kairos_rs::models::router::{Router, Backend};
 use kairos_rs::utils::route_matcher::{RouteMatcher, RouteMatchError};
-use kairos_rs::models::router::Protocol;
 
 /// Helper function to create test routes
 fn create_test_routes() -> Vec<Router> {
@@ -11,14 +10,8 @@ fn create_test_routes() -> Vec<Router> {
             external_path: "/api/identity/register/v3".to_string(),
             internal_path: "/api/identity/register".to_string(),
             methods: vec!["POST".to_string(), "GET".to_string()],
-            auth_required: false,
-            backends: Some(vec![Backend { host: "http://localhost".to_string(), port: 3000, weight: 1, health_check_path: None }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            backends: Some(vec![Backend { host: "http://localhost".to_string(), port: 3000, weight: 1, health_check_path: None, timeout_secs: None }]),
+            ..Default::default()
         },
         Router {
             host: Some("https://google.com".to_string()),
@@ -26,14 +19,8 @@ fn create_test_routes() -> Vec<Router> {
             external_path: "/identity/register/v2".to_string(),
             internal_path: "/".to_string(),
             methods: vec!["POST".to_string(), "GET".to_string()],
-            auth_required: false,
-            backends: Some(vec![Backend { host: "https://google.com".to_string(), port: 443, weight: 1, health_check_path: None }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            backends: Some(vec![Backend { host: "https://google.com".to_string(), port: 443, weight: 1, health_check_path: None, timeout_secs: None }]),
+            ..Default::default()
         },
         Router {
             host: Some("https://http.cat".to_string()),
@@ -41,14 +28,8 @@ fn create_test_routes() -> Vec<Router> {
             external_path: "/cats/{id}".to_string(),
             internal_path: "/{id}".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
-            backends: Some(vec![Backend { host: "https://http.cat".to_string(), port: 443, weight: 1, health_check_path: None }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            backends: Some(vec![Backend { host: "https://http.cat".to_string(), port: 443, weight: 1, health_check_path: None, timeout_secs: None }]),
+            ..Default::default()
         },
         Router {
             host: Some("http://api.example.com".to_string()),
@@ -56,14 +37,8 @@ fn create_test_routes() -> Vec<Router> {
             external_path: "/api/users/{user_id}".to_string(),
             internal_path: "/users/{user_id}".to_string(),
             methods: vec!["GET".to_string(), "PUT".to_string(), "DELETE".to_string()],
-            auth_required: false,
-            backends: Some(vec![Backend { host: "http://api.example.com".to_string(), port: 80, weight: 1, health_check_path: None }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            backends: Some(vec![Backend { host: "http://api.example.com".to_string(), port: 80, weight: 1, health_check_path: None, timeout_secs: None }]),
+            ..Default::default()
         },
         Router {
             host: Some("http://api.example.com".to_string()),
@@ -71,14 +46,8 @@ fn create_test_routes() -> Vec<Router> {
             external_path: "/api/products/{product_id}/details".to_string(),
             internal_path: "/products/{product_id}/info".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
-            backends: Some(vec![Backend { host: "http://api.example.com".to_string(), port: 80, weight: 1, health_check_path: None }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            backends: Some(vec![Backend { host: "http://api.example.com".to_string(), port: 80, weight: 1, health_check_path: None, timeout_secs: None }]),
+            ..Default::default()
         },
         Router {
             host: Some("http://api.example.com".to_string()),
@@ -86,14 +55,8 @@ fn create_test_routes() -> Vec<Router> {
             external_path: "/api/orders/{order_id}/items/{item_id}".to_string(),
             internal_path: "/orders/{order_id}/items/{item_id}".to_string(),
             methods: vec!["GET".to_string(), "PUT".to_string()],
-            auth_required: false,
-            backends: Some(vec![Backend { host: "http://api.example.com".to_string(), port: 80, weight: 1, health_check_path: None }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            backends: Some(vec![Backend { host: "http://api.example.com".to_string(), port: 80, weight: 1, health_check_path: None, timeout_secs: None }]),
+            ..Default::default()
         },
         Router {
             host: Some("http://static.example.com".to_string()),
@@ -101,14 +64,8 @@ fn create_test_routes() -> Vec<Router> {
             external_path: "/api/static/path".to_string(),
             internal_path: "/static".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
-            backends: Some(vec![Backend { host: "http://static.example.com".to_string(), port: 80, weight: 1, health_check_path: None }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            backends: Some(vec![Backend { host: "http://static.example.com".to_string(), port: 80, weight: 1, health_check_path: None, timeout_secs: None }]),
+            ..Default::default()
         },
         Router {
             host: Some("http://static.example.com".to_string()),
@@ -116,14 +73,8 @@ fn create_test_routes() -> Vec<Router> {
             external_path: "/api/static/path/details".to_string(),
             internal_path: "/static/details".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
-            backends: Some(vec![Backend { host: "http://static.example.com".to_string(), port: 80, weight: 1, health_check_path: None }]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            backends: Some(vec![Backend { host: "http://static.example.com".to_string(), port: 80, weight: 1, health_check_path: None, timeout_secs: None }]),
+            ..Default::default()
         },
     ]
 }
@@ -274,6 +225,115 @@ mod route_matcher_tests {
         assert_eq!(internal_path, "/test%20space");
     }
 
+    #[test]
+    fn test_parameter_encoding() {
+        let route = |external: &str, internal: &str| Router {
+            external_path: external.to_string(),
+            internal_path: internal.to_string(),
+            ..create_test_routes()[0].clone()
+        };
+        let matcher = RouteMatcher::new(vec![
+            route("/api/files/{name}", "/files/{name:encoded}?owner={name:encoded}"),
+            route("/api/raw/{name}", "/raw/{name:raw}"),
+            route("/api/plain/{name}", "/plain?name={name}"),
+        ])
+        .unwrap();
+
+        // `a b&c=d/é`, as sent by the client
+        let value = "a%20b&c=d%2F%C3%A9";
+
+        let (_, internal_path) = matcher.find_match(&format!("/api/files/{}", value)).unwrap();
+        assert_eq!(
+            internal_path,
+            "/files/a%20b%26c%3Dd%2F%C3%A9?owner=a%20b%26c%3Dd%2F%C3%A9"
+        );
+
+        let (_, internal_path) = matcher.find_match(&format!("/api/raw/{}", value)).unwrap();
+        assert_eq!(internal_path, "/raw/a b&c=d/é");
+
+        // Without an encoding the value is forwarded as received
+        let (_, internal_path) = matcher.find_match(&format!("/api/plain/{}", value)).unwrap();
+        assert_eq!(internal_path, format!("/plain?name={}", value));
+
+        assert!(route("/api/files/{name}", "/files/{name:encoded}").validate().is_ok());
+        let error = route("/api/files/{name}", "/files/{name:base64}")
+            .validate()
+            .unwrap_err();
+        assert!(error.contains("{name:base64}"), "{error}");
+    }
+
+    #[test]
+    fn test_duplicate_routes() {
+        let route = |external: &str, internal: &str, method: &str| Router {
+            external_path: external.to_string(),
+            internal_path: internal.to_string(),
+            methods: vec![method.to_string()],
+            ..create_test_routes()[0].clone()
+        };
+
+        // Exact duplicate
+        let result = RouteMatcher::new(vec![
+            route("/api/users", "/v1/users", "GET"),
+            route("/api/users", "/v2/users", "GET"),
+        ]);
+        assert_eq!(
+            result.unwrap_err(),
+            RouteMatchError::DuplicateRoute {
+                path: "/api/users".to_string(),
+                existing: "/api/users".to_string(),
+            }
+        );
+
+        // Routes are matched by path, so disjoint methods still shadow each other
+        let result = RouteMatcher::new(vec![
+            route("/api/users", "/users", "GET"),
+            route("/api/users", "/users", "POST"),
+        ]);
+        assert!(matches!(result.unwrap_err(), RouteMatchError::DuplicateRoute { .. }));
+
+        // Dynamic patterns differing only in parameter names
+        let result = RouteMatcher::new(vec![
+            route("/api/users/{id}", "/users/{id}", "GET"),
+            route("/api/users/{user_id}", "/users/{user_id}", "GET"),
+        ]);
+        assert_eq!(
+            result.unwrap_err(),
+            RouteMatchError::DuplicateRoute {
+                path: "/api/users/{user_id}".to_string(),
+                existing: "/api/users/{id}".to_string(),
+            }
+        );
+
+        // A static route takes precedence over a dynamic route matching it
+        let matcher = RouteMatcher::new(vec![
+            route("/api/users/{id}", "/users/{id}", "GET"),
+            route("/api/users/me", "/me", "GET"),
+        ])
+        .unwrap();
+        assert_eq!(matcher.find_match("/api/users/me").unwrap().1, "/me");
+        assert_eq!(matcher.find_match("/api/users/7").unwrap().1, "/users/7");
+    }
+
+    #[test]
+    fn test_lenient_duplicate_routes_last_wins() {
+        let route = |external: &str, internal: &str| Router {
+            external_path: external.to_string(),
+            internal_path: internal.to_string(),
+            ..create_test_routes()[0].clone()
+        };
+        let matcher = RouteMatcher::new_lenient(vec![
+            route("/api/users", "/v1/users"),
+            route("/api/users/{id}", "/v1/users/{id}"),
+            route("/api/users", "/v2/users"),
+            route("/api/users/{user_id}", "/v2/users/{user_id}"),
+        ])
+        .unwrap();
+
+        assert_eq!(matcher.find_match("/api/users").unwrap().1, "/v2/users");
+        assert_eq!(matcher.find_match("/api/users/7").unwrap().1, "/v2/users/7");
+        assert!(matcher.dynamic_route("/api/users/{id}").is_none());
+    }
+
     #[test]
     fn test_invalid_route_patterns() {
         let invalid_routes = vec![
@@ -283,14 +343,8 @@ mod route_matcher_tests {
                 external_path: "/api/users/{user_id".to_string(), // Missing closing brace
                 internal_path: "/users/{user_id}".to_string(),
                 methods: vec!["GET".to_string()],
-                auth_required: false,
-                backends: Some(vec![Backend { host: "http://localhost".to_string(), port: 3000, weight: 1, health_check_path: None }]),
-                load_balancing_strategy: Default::default(),
-                retry: None,
-                protocol: Protocol::Http,
-                request_transformation: None,
-                response_transformation: None,
-                ai_policy: None,
+                backends: Some(vec![Backend { host: "http://localhost".to_string(), port: 3000, weight: 1, health_check_path: None, timeout_secs: None }]),
+                ..Default::default()
             },
             Router {
                 host: Some("http://localhost".to_string()),
@@ -298,14 +352,8 @@ mod route_matcher_tests {
                 external_path: "/api/users/{user id}".to_string(), // Space in parameter name
                 internal_path: "/users/{user_id}".to_string(),
                 methods: vec!["GET".to_string()],
-                auth_required: false,
-                backends: Some(vec![Backend { host: "http://localhost".to_string(), port: 3000, weight: 1, health_check_path: None }]),
-                load_balancing_strategy: Default::default(),
-                retry: None,
-                protocol: Protocol::Http,
-                request_transformation: None,
-                response_transformation: None,
-                ai_policy: None,
+                backends: Some(vec![Backend { host: "http://localhost".to_string(), port: 3000, weight: 1, health_check_path: None, timeout_secs: None }]),
+                ..Default::default()
             },
             Router {
                 host: Some("http://localhost".to_string()),
@@ -313,14 +361,8 @@ mod route_matcher_tests {
                 external_path: "/api/users/{}".to_string(), // Empty parameter name
                 internal_path: "/users/{}".to_string(),
                 methods: vec!["GET".to_string()],
-                auth_required: false,
-                backends: Some(vec![Backend { host: "http://localhost".to_string(), port: 3000, weight: 1, health_check_path: None }]),
-                load_balancing_strategy: Default::default(),
-                retry: None,
-                protocol: Protocol::Http,
-                request_transformation: None,
-                response_transformation: None,
-                ai_policy: None,
+                backends: Some(vec![Backend { host: "http://localhost".to_string(), port: 3000, weight: 1, health_check_path: None, timeout_secs: None }]),
+                ..Default::default()
             },
         ];
 
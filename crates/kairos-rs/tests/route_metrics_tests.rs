@@ -0,0 +1,173 @@
+//! Per-route request metrics tests
+//!
+//! Verifies that with `collect_per_route` enabled `/metrics` reports request
+//! counts, error counts, average response times and in-flight requests
+//! labelled by route pattern, and that nothing is collected per route when it
+//! is disabled.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::{http, metrics};
+use kairos_rs::services::http::RouteHandler;
+use std::time::Duration;
+
+/// Starts a mock upstream that answers `/fail` with `500`, `/slow` after ten
+/// seconds and everything else with `ok`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new()
+            .route("/fail", web::get().to(|| async { HttpResponse::InternalServerError().finish() }))
+            .route(
+                "/slow",
+                web::get().to(|| async {
+                    actix_web::rt::time::sleep(Duration::from_secs(10)).await;
+                    HttpResponse::Ok().body("slow")
+                }),
+            )
+            .default_service(web::to(|| async { HttpResponse::Ok().body("ok") }))
+    })
+}
+
+fn route(external_path: &str, internal_path: &str, port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: external_path.to_string(),
+        internal_path: internal_path.to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+async fn scrape(collector: metrics::MetricsCollector, openmetrics: bool) -> String {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(
+        vec![
+            route("/api/users/{id}", "/users/{id}", port),
+            route("/api/broken", "/fail", port),
+        ],
+        5,
+    );
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for uri in ["/api/users/1", "/api/users/2", "/api/broken", "/api/unknown"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        test::call_service(&app, req).await;
+    }
+
+    let mut req = test::TestRequest::get().uri("/metrics");
+    if openmetrics {
+        req = req.insert_header(("Accept", "application/openmetrics-text"));
+    }
+    let body = test::read_body(test::call_service(&app, req.to_request()).await).await;
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+#[actix_web::test]
+async fn test_per_route_metrics() {
+    let text = scrape(metrics::MetricsCollector::default().with_per_route_metrics(true), false).await;
+
+    // Labelled by pattern, not by concrete path; unmatched paths get no series
+    assert!(text.contains("kairos_requests_total 4\nkairos_requests_total{route=\"/api/broken\"} 1\nkairos_requests_total{route=\"/api/users/{id}\"} 2\n"), "{text}");
+    assert!(text.contains("kairos_requests_error_total{route=\"/api/broken\"} 1"), "{text}");
+    assert!(text.contains("kairos_requests_error_total{route=\"/api/users/{id}\"} 0"), "{text}");
+    assert!(text.contains("# TYPE kairos_route_response_time_avg gauge"), "{text}");
+    assert!(text.contains("kairos_route_response_time_avg{route=\"/api/users/{id}\"} "), "{text}");
+    assert!(text.contains("# TYPE kairos_route_in_flight gauge"), "{text}");
+    assert!(text.contains("kairos_route_in_flight{route=\"/api/users/{id}\"} 0\n"), "{text}");
+    assert!(!text.contains("route=\"/api/users/1\""), "{text}");
+    assert!(!text.contains("route=\"/api/unknown\""), "{text}");
+    assert_eq!(text.matches("# TYPE kairos_requests_total").count(), 1);
+}
+
+#[actix_web::test]
+async fn test_per_route_metrics_openmetrics() {
+    let text = scrape(metrics::MetricsCollector::default().with_per_route_metrics(true), true).await;
+
+    assert!(text.contains("kairos_requests_total 4\nkairos_requests_total{route=\"/api/broken\"} 1\n"), "{text}");
+    assert!(text.contains("kairos_requests_error_total{route=\"/api/broken\"} 1"), "{text}");
+    assert!(text.contains("# TYPE kairos_route_response_time_avg gauge"), "{text}");
+    assert!(text.contains("kairos_route_in_flight{route=\"/api/broken\"} 0\n"), "{text}");
+    assert!(text.ends_with("# EOF\n"));
+}
+
+#[actix_web::test]
+async fn test_per_route_metrics_disabled_by_default() {
+    let collector = metrics::MetricsCollector::default();
+    let text = scrape(collector.clone(), false).await;
+
+    assert!(text.contains("kairos_requests_total 4\n"), "{text}");
+    assert!(!text.contains("kairos_requests_total{route="), "{text}");
+    assert!(!text.contains("kairos_route_response_time_avg"), "{text}");
+    assert!(!text.contains("kairos_route_in_flight"), "{text}");
+    assert!(collector.route_metrics().is_empty());
+}
+
+#[actix_web::test]
+async fn test_route_in_flight_tracking() {
+    let collector = metrics::MetricsCollector::default().with_per_route_metrics(true);
+    collector.route_request_started("/api/users/{id}", &Default::default());
+    collector.route_request_started("/api/users/{id}", &Default::default());
+    collector.route_request_started("/api/broken", &Default::default());
+    collector.route_request_finished("/api/users/{id}");
+    collector.route_request_finished("/api/broken");
+
+    let in_flight: Vec<_> = collector
+        .route_metrics()
+        .into_iter()
+        .map(|(route, stats)| (route, stats.in_flight))
+        .collect();
+    assert_eq!(
+        in_flight,
+        vec![("/api/broken".to_string(), 0), ("/api/users/{id}".to_string(), 1)]
+    );
+
+    let disabled = metrics::MetricsCollector::default();
+    disabled.route_request_started("/api/users/{id}", &Default::default());
+    assert!(disabled.route_metrics().is_empty());
+}
+
+#[actix_web::test]
+async fn test_route_in_flight_released_when_request_dropped() {
+    let port = spawn_upstream();
+    let collector = metrics::MetricsCollector::default().with_per_route_metrics(true);
+    let handler = RouteHandler::new(vec![route("/api/slow", "/slow", port)], 30);
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+    let in_flight = || {
+        collector
+            .route_metrics()
+            .into_iter()
+            .find(|(route, _)| route == "/api/slow")
+            .map(|(_, stats)| stats.in_flight)
+    };
+
+    // The client goes away while the backend is still answering
+    let request = actix_web::rt::spawn(async move {
+        let req = test::TestRequest::get().uri("/api/slow").to_request();
+        test::call_service(&app, req).await;
+    });
+    actix_web::rt::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(in_flight(), Some(1));
+
+    request.abort();
+    assert!(request.await.unwrap_err().is_cancelled());
+    assert_eq!(in_flight(), Some(0));
+}
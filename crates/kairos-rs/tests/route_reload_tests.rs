@@ -0,0 +1,338 @@
+//! Route-only reload tests
+//!
+//! Verifies that `RouteHandler::reload_routes` swaps routes, backends and
+//! circuit breakers while rate limit counters and unchanged breakers keep
+//! their state, and that config file updates are applied to a running handler,
+//! whether the file watcher or `POST /admin/config/reload` triggers them.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::config::hot_reload::{apply_route_updates, ConfigManager, ConfigWatcher};
+use kairos_rs::middleware::rate_limit::{
+    AdvancedRateLimit, LimitStrategy, RateLimitConfig, WindowType,
+};
+use kairos_rs::models::router::{Backend, CircuitBreakerSettings, Router};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::{http, management};
+use kairos_rs::services::circuit_breaker::CircuitState;
+use kairos_rs::services::http::RouteHandler;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+
+/// Starts a mock upstream that answers every request with `200 OK`.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Ok().body("ok") }))
+    })
+}
+
+/// Returns a local port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn route(port: u16, path: &str) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: path.to_string(),
+        internal_path: path.to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+fn settings(routers: Vec<Router>) -> Settings {
+    Settings {
+        version: 1,
+        jwt: None,
+        rate_limit: None,
+        disable_rate_limiting: false,
+        ai: None,
+        streaming: None,
+        metrics: None,
+        warmup: None,
+        errors: None,
+        fault_injection_enabled: false,
+        max_connections_per_ip: None,
+        max_concurrent_requests: None,
+        forwarded_headers: None,
+        default_upstream_headers: Default::default(),
+        circuit_breaker_bypass: None,
+        debug_overrides: None,
+        request_start_header: None,
+        trailing_slash: Default::default(),
+        server: None,
+        root_response: None,
+        backend_pools: Default::default(),
+        tcp_proxies: Vec::new(),
+        routers,
+    }
+}
+
+#[actix_web::test]
+async fn test_reload_keeps_rate_limit_counters() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "/orders")], 5);
+    let control = handler.clone();
+    let limiter = AdvancedRateLimit::new(RateLimitConfig {
+        strategy: LimitStrategy::PerIP,
+        requests_per_window: 3,
+        window_duration: Duration::from_secs(60),
+        burst_allowance: 0,
+        window_type: WindowType::FixedWindow,
+        enable_redis: false,
+        redis_key_prefix: "kairos".to_string(),
+    });
+    let app = test::init_service(
+        App::new()
+            .wrap(limiter)
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let peer: SocketAddr = "10.0.0.1:40000".parse().unwrap();
+    for _ in 0..2 {
+        let req = test::TestRequest::get()
+            .uri("/orders")
+            .peer_addr(peer)
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+    }
+
+    control
+        .reload_routes(vec![route(port, "/orders"), route(port, "/invoices")])
+        .unwrap();
+
+    // The new route is live and still counts against the same limit
+    let req = test::TestRequest::get()
+        .uri("/invoices")
+        .peer_addr(peer)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = test::TestRequest::get()
+        .uri("/orders")
+        .peer_addr(peer)
+        .to_request();
+    let err = test::try_call_service(&app, req)
+        .await
+        .expect_err("client near its limit must stay limited after a reload");
+    assert_eq!(err.as_response_error().status_code(), 429);
+}
+
+#[actix_web::test]
+async fn test_reload_swaps_routes_and_keeps_unchanged_breakers() {
+    let port = unused_port();
+    let service = format!("http://127.0.0.1:{}", port);
+    let handler = RouteHandler::new(vec![route(port, "/orders")], 1);
+    let control = handler.clone();
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    for _ in 0..5 {
+        let req = test::TestRequest::get().uri("/orders").to_request();
+        test::call_service(&app, req).await;
+    }
+    assert_eq!(control.get_circuit_breaker_states()[&service].0, CircuitState::Open);
+
+    // Same backend and breaker settings: the open breaker is carried over
+    control
+        .reload_routes(vec![route(port, "/orders"), route(port, "/invoices")])
+        .unwrap();
+    assert_eq!(control.get_circuit_breaker_states()[&service].0, CircuitState::Open);
+    let req = test::TestRequest::get().uri("/invoices").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 503);
+
+    // Removed routes stop matching
+    control.reload_routes(vec![route(port, "/invoices")]).unwrap();
+    let req = test::TestRequest::get().uri("/orders").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    // New breaker settings start from a fresh breaker
+    let mut tuned = route(port, "/invoices");
+    tuned.circuit_breaker = Some(CircuitBreakerSettings {
+        failure_threshold: 2,
+        ..Default::default()
+    });
+    control.reload_routes(vec![tuned]).unwrap();
+    assert_eq!(control.get_circuit_breaker_states()[&service].0, CircuitState::Closed);
+}
+
+#[actix_web::test]
+async fn test_invalid_routes_keep_current_ones() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port, "/orders")], 5);
+    let control = handler.clone();
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    assert!(control.reload_routes(vec![route(port, "/orders/{id")]).is_err());
+
+    let req = test::TestRequest::get().uri("/orders").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_config_updates_reload_routes() {
+    let port = spawn_upstream();
+    let initial = settings(vec![route(port, "/orders")]);
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), serde_json::to_string(&initial).unwrap()).unwrap();
+
+    let handler = RouteHandler::new(initial.routers.clone(), 5);
+    let watcher = ConfigWatcher::new(initial.clone(), file.path().display().to_string());
+    actix_web::rt::spawn(apply_route_updates(
+        watcher.subscribe(),
+        handler.clone(),
+        initial,
+    ));
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let updated = settings(vec![route(port, "/invoices")]);
+    std::fs::write(file.path(), serde_json::to_string(&updated).unwrap()).unwrap();
+    watcher.manual_reload().await.unwrap();
+
+    let mut status = 0;
+    for _ in 0..50 {
+        let req = test::TestRequest::get().uri("/invoices").to_request();
+        status = test::call_service(&app, req).await.status().as_u16();
+        if status == 200 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(status, 200);
+}
+
+#[actix_web::test]
+async fn test_file_watcher_reloads_routes() {
+    let port = spawn_upstream();
+    let initial = settings(vec![route(port, "/orders")]);
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), serde_json::to_string(&initial).unwrap()).unwrap();
+
+    let handler = RouteHandler::new(initial.routers.clone(), 5);
+    let watcher = ConfigWatcher::new(initial.clone(), file.path().display().to_string());
+    actix_web::rt::spawn(apply_route_updates(
+        watcher.subscribe(),
+        handler.clone(),
+        initial,
+    ));
+    watcher.start_watching().await;
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    // An invalid file is rejected and the current routes stay in place
+    std::fs::write(file.path(), "{ not json").unwrap();
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert_eq!(watcher.get_current_config().await.version, 1);
+    let req = test::TestRequest::get().uri("/orders").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    // No manual trigger: the watcher picks up the change on its own
+    let updated = settings(vec![route(port, "/invoices")]);
+    std::fs::write(file.path(), serde_json::to_string(&updated).unwrap()).unwrap();
+    let mut status = 0;
+    for _ in 0..100 {
+        let req = test::TestRequest::get().uri("/invoices").to_request();
+        status = test::call_service(&app, req).await.status().as_u16();
+        if status == 200 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(status, 200);
+
+    let req = test::TestRequest::get().uri("/orders").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+}
+
+#[actix_web::test]
+async fn test_admin_reload_endpoint() {
+    let port = spawn_upstream();
+    let initial = settings(vec![route(port, "/orders")]);
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), serde_json::to_string(&initial).unwrap()).unwrap();
+
+    let handler = RouteHandler::new(initial.routers.clone(), 5);
+    let manager = Arc::new(ConfigManager::new(
+        initial.clone(),
+        file.path().display().to_string(),
+    ));
+    actix_web::rt::spawn(apply_route_updates(
+        manager.subscribe_to_updates(),
+        handler.clone(),
+        initial.clone(),
+    ));
+    let admin = Settings {
+        jwt: Some(common::jwt_settings()),
+        ..initial.clone()
+    };
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(manager))
+            .configure(|cfg| management::configure_admin(cfg, &admin))
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    // An invalid configuration is rejected and the current routes stay in place
+    let mut invalid = route(port, "/invoices");
+    invalid.methods = vec!["FETCH".to_string()];
+    std::fs::write(file.path(), serde_json::to_string(&settings(vec![invalid])).unwrap()).unwrap();
+    let req = test::TestRequest::post()
+        .uri("/admin/config/reload")
+        .insert_header(("Authorization", common::bearer_token()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 500);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["success"], false);
+    let req = test::TestRequest::get().uri("/orders").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let updated = settings(vec![route(port, "/invoices")]);
+    std::fs::write(file.path(), serde_json::to_string(&updated).unwrap()).unwrap();
+    let req = test::TestRequest::post()
+        .uri("/admin/config/reload")
+        .insert_header(("Authorization", common::bearer_token()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["success"], true);
+    assert_eq!(body["version"], 2);
+
+    let mut status = 0;
+    for _ in 0..100 {
+        let req = test::TestRequest::get().uri("/invoices").to_request();
+        status = test::call_service(&app, req).await.status().as_u16();
+        if status == 200 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(status, 200);
+}
@@ -0,0 +1,130 @@
+//! Route latency target tests
+//!
+//! Verifies that requests slower than a route's `sla_ms` are counted in
+//! `kairos_sla_breach_total`, that `kairos_sla_compliance` reports the share
+//! of recent requests within the target, and that routes without `sla_ms`
+//! export neither.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::{http, metrics};
+use kairos_rs::services::http::RouteHandler;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Starts a mock upstream answering `/slow` after 200ms and everything else
+/// right away.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new()
+            .route(
+                "/slow",
+                web::get().to(|| async {
+                    actix_web::rt::time::sleep(Duration::from_millis(200)).await;
+                    HttpResponse::Ok().body("slow")
+                }),
+            )
+            .default_service(web::to(|| async { HttpResponse::Ok().body("fast") }))
+    })
+}
+
+fn route(port: u16, external_path: &str, sla_ms: Option<u64>) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": format!("{}/{{path}}", external_path),
+        "internal_path": "/{path}",
+        "methods": ["GET"],
+        "sla_ms": sla_ms,
+        "tags": {"team": "checkout"}
+    }))
+    .unwrap()
+}
+
+async fn scrape(openmetrics: bool) -> String {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(
+        vec![route(port, "/api/checkout", Some(100)), route(port, "/api/other", None)],
+        5,
+    );
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(metrics::MetricsCollector::default()))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for uri in [
+        "/api/checkout/fast",
+        "/api/checkout/fast",
+        "/api/checkout/fast",
+        "/api/checkout/slow",
+        "/api/other/slow",
+    ] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        test::read_body(test::call_service(&app, req).await).await;
+    }
+
+    let mut req = test::TestRequest::get().uri("/metrics");
+    if openmetrics {
+        req = req.insert_header(("Accept", "application/openmetrics-text"));
+    }
+    let body = test::read_body(test::call_service(&app, req.to_request()).await).await;
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+#[actix_web::test]
+async fn test_sla_breaches_and_compliance() {
+    let text = scrape(false).await;
+    let labels = "route=\"/api/checkout/{path}\",team=\"checkout\"";
+    assert!(text.contains("# TYPE kairos_sla_breach_total counter"), "{text}");
+    assert!(text.contains(&format!("kairos_sla_breach_total{{{}}} 1\n", labels)), "{text}");
+    assert!(text.contains("# TYPE kairos_sla_compliance gauge"), "{text}");
+    assert!(text.contains(&format!("kairos_sla_compliance{{{}}} 0.7500\n", labels)), "{text}");
+    // Routes without sla_ms are not tracked
+    assert!(
+        !text.lines().any(|line| line.starts_with("kairos_sla") && line.contains("/api/other")),
+        "{text}"
+    );
+}
+
+#[actix_web::test]
+async fn test_sla_metrics_openmetrics() {
+    let text = scrape(true).await;
+    assert!(text.contains("# TYPE kairos_sla_breach counter"), "{text}");
+    assert!(text.contains("kairos_sla_breach_total{route=\"/api/checkout/{path}\",team=\"checkout\"} 1\n"), "{text}");
+    assert!(text.contains("kairos_sla_compliance{route=\"/api/checkout/{path}\",team=\"checkout\"} 0.7500\n"), "{text}");
+    assert!(text.ends_with("# EOF\n"));
+}
+
+#[actix_web::test]
+async fn test_compliance_covers_recent_requests() {
+    let collector = metrics::MetricsCollector::default();
+    let tags = BTreeMap::new();
+    let sla = Duration::from_millis(100);
+
+    for _ in 0..metrics::SLA_WINDOW_REQUESTS {
+        collector.record_sla("/api", &tags, sla, Duration::from_millis(500));
+    }
+    let (_, stats) = collector.route_sla().remove(0);
+    assert_eq!(stats.breaches, metrics::SLA_WINDOW_REQUESTS as u64);
+    assert_eq!(stats.compliance(), 0.0);
+
+    // Compliant requests push the breaches out of the window
+    for _ in 0..metrics::SLA_WINDOW_REQUESTS / 2 {
+        collector.record_sla("/api", &tags, sla, Duration::from_millis(100));
+    }
+    let (_, stats) = collector.route_sla().remove(0);
+    assert_eq!(stats.breaches, metrics::SLA_WINDOW_REQUESTS as u64);
+    assert_eq!(stats.recent.len(), metrics::SLA_WINDOW_REQUESTS);
+    assert_eq!(stats.compliance(), 0.5);
+}
+
+#[actix_web::test]
+async fn test_zero_sla_is_invalid() {
+    let err = route(8080, "/api", Some(0)).validate().unwrap_err();
+    assert!(err.contains("sla_ms"), "{err}");
+    assert!(route(8080, "/api", Some(250)).validate().is_ok());
+}
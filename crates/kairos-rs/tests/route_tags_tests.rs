@@ -0,0 +1,150 @@
+//! Route tag tests
+//!
+//! Verifies that a route's `tags` are added as labels to its per-route
+//! metrics in both exposition formats and as fields to log lines about its
+//! requests, and that tag names are validated as label names.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::{http, metrics};
+use kairos_rs::services::http::RouteHandler;
+use std::sync::{Mutex, Once};
+
+/// Warnings logged by the gateway during the tests.
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct CapturingLogger;
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            WARNINGS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Starts capturing warnings, once per test binary.
+fn capture_warnings() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&CapturingLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Warn);
+    });
+}
+
+/// Starts a mock upstream answering every request with a 100 byte body.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Ok().body(vec![b'x'; 100]) }))
+    })
+}
+
+fn route(port: u16, external_path: &str, tags: serde_json::Value) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": external_path,
+        "internal_path": "/",
+        "methods": ["GET"],
+        "warn_response_bytes": 10,
+        "tags": tags
+    }))
+    .unwrap()
+}
+
+async fn scrape(openmetrics: bool) -> String {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(
+        vec![
+            route(
+                port,
+                "/api/payments",
+                serde_json::json!({"team": "payments", "tier": "critical"}),
+            ),
+            route(port, "/api/untagged", serde_json::json!({})),
+        ],
+        5,
+    );
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(
+                metrics::MetricsCollector::default().with_per_route_metrics(true),
+            ))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    for uri in ["/api/payments", "/api/untagged"] {
+        let req = test::TestRequest::get().uri(uri).to_request();
+        test::read_body(test::call_service(&app, req).await).await;
+    }
+
+    let mut req = test::TestRequest::get().uri("/metrics");
+    if openmetrics {
+        req = req.insert_header(("Accept", "application/openmetrics-text"));
+    }
+    let body = test::read_body(test::call_service(&app, req.to_request()).await).await;
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+#[actix_web::test]
+async fn test_tags_label_per_route_metrics() {
+    for openmetrics in [false, true] {
+        let text = scrape(openmetrics).await;
+        let labels = "route=\"/api/payments\",team=\"payments\",tier=\"critical\"";
+        assert!(text.contains(&format!("kairos_requests_total{{{}}} 1\n", labels)), "{text}");
+        assert!(text.contains(&format!("kairos_requests_error_total{{{}}} 0\n", labels)), "{text}");
+        assert!(text.contains(&format!("kairos_route_response_time_avg{{{}}} ", labels)), "{text}");
+        assert!(text.contains(&format!("kairos_route_in_flight{{{}}} 0\n", labels)), "{text}");
+        // Untagged routes keep only the route label
+        assert!(text.contains("kairos_requests_total{route=\"/api/untagged\"} 1\n"), "{text}");
+    }
+}
+
+#[actix_web::test]
+async fn test_tags_added_to_request_log_lines() {
+    capture_warnings();
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(
+        vec![route(port, "/api/logged", serde_json::json!({"team": "payments", "tier": "critical"}))],
+        5,
+    );
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+    let req = test::TestRequest::get().uri("/api/logged").to_request();
+    test::read_body(test::call_service(&app, req).await).await;
+
+    let warnings: Vec<String> = WARNINGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|warning| warning.contains("/api/logged"))
+        .cloned()
+        .collect();
+    assert_eq!(warnings.len(), 1, "{:?}", warnings);
+    assert!(warnings[0].ends_with(" [team=payments tier=critical]"), "{}", warnings[0]);
+}
+
+#[actix_web::test]
+async fn test_tag_names_are_validated() {
+    assert!(route(8080, "/api", serde_json::json!({"team": "a", "_tier2": "b"}))
+        .validate()
+        .is_ok());
+
+    for name in ["", "2fa", "team-name", "team name", "__internal", "route", "le"] {
+        let err = route(8080, "/api", serde_json::json!({ name: "value" }))
+            .validate()
+            .unwrap_err();
+        assert!(err.contains("Tag name"), "{name}: {err}");
+    }
+}
@@ -2,7 +2,6 @@ use actix_web::{test, web, App};
 use kairos_rs::routes::{http, metrics};
 use kairos_rs::services::http::RouteHandler;
 use kairos_rs::models::router::{Router, Backend};
-use kairos_rs::models::router::Protocol;
 
 #[actix_web::test]
 async fn test_simple_circuit_breaker() {
@@ -14,21 +13,16 @@ async fn test_simple_circuit_breaker() {
             external_path: "/test".to_string(),
             internal_path: "/test".to_string(),
             methods: vec!["GET".to_string()],
-            auth_required: false,
             backends: Some(vec![
                 Backend {
                     host: "http://localhost".to_string(),
                     port: 9999,
                     weight: 1,
                     health_check_path: None,
+                    timeout_secs: None,
                 }
             ]),
-            load_balancing_strategy: Default::default(),
-            retry: None,
-            protocol: Protocol::Http,
-            request_transformation: None,
-            response_transformation: None,
-            ai_policy: None,
+            ..Default::default()
         }
     ];
     let route_handler = RouteHandler::new(routes, 5);
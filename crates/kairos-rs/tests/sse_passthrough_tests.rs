@@ -0,0 +1,104 @@
+//! Server-Sent Events passthrough tests
+//!
+//! Verifies that `text/event-stream` responses are forwarded chunk by chunk,
+//! even through the Compress middleware, while the upstream stream is still open.
+
+use actix_web::body::MessageBody;
+use actix_web::{middleware::Compress, test, web, App, HttpResponse, HttpServer};
+use futures_util::{future::poll_fn, stream, StreamExt};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::circuit_breaker::CircuitState;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// Starts a mock SSE upstream that emits two events and then never closes.
+fn spawn_sse_upstream() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = HttpServer::new(|| {
+        App::new().default_service(web::to(|| async {
+            let events = stream::iter(["data: first\n\n", "data: second\n\n"])
+                .map(|event| Ok::<_, actix_web::Error>(web::Bytes::from(event)))
+                .chain(stream::pending());
+            HttpResponse::Ok()
+                .content_type("text/event-stream")
+                .streaming(events)
+        }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    port
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/events".to_string(),
+        internal_path: "/events".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_sse_events_arrive_before_stream_closes() {
+    let port = spawn_sse_upstream();
+    let handler = RouteHandler::new(vec![route(port)], 1);
+    let states_handler = handler.clone();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(Compress::default())
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+        .uri("/events")
+        .insert_header(("Accept-Encoding", "gzip"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "text/event-stream"
+    );
+    assert_eq!(resp.headers().get("content-encoding").unwrap(), "identity");
+
+    // Read events while the upstream connection is still open
+    let mut body = resp.into_body();
+    let mut received = String::new();
+    while !received.contains("data: second") {
+        let chunk = tokio::time::timeout(
+            Duration::from_secs(2),
+            poll_fn(|cx| Pin::new(&mut body).poll_next(cx)),
+        )
+        .await
+        .expect("event should arrive before the stream closes")
+        .expect("stream should still be open")
+        .unwrap();
+        received.push_str(&String::from_utf8_lossy(&chunk));
+    }
+    assert_eq!(received, "data: first\n\ndata: second\n\n");
+
+    // The stream outlives the 1s request timeout without tripping the breaker
+    tokio::time::sleep(Duration::from_millis(1200)).await;
+    let states = states_handler.get_circuit_breaker_states();
+    let (state, failures, _) = states.values().next().unwrap();
+    assert_eq!(*state, CircuitState::Closed);
+    assert_eq!(*failures, 0);
+}
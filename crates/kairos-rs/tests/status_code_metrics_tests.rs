@@ -0,0 +1,97 @@
+//! Per-status-code metrics tests
+//!
+//! Verifies that `kairos_responses_total{code="..."}` keeps a separate
+//! counter for every status code returned by the gateway.
+
+use actix_web::{test, web, App, HttpResponse, HttpServer};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::{http, metrics};
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+
+/// Starts a mock upstream that rejects every request with 401.
+fn spawn_unauthorized_upstream() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = HttpServer::new(|| {
+        App::new().default_service(web::to(|| async { HttpResponse::Unauthorized().finish() }))
+    })
+    .listen(listener)
+    .unwrap()
+    .workers(1)
+    .run();
+
+    actix_web::rt::spawn(server);
+    port
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/secret".to_string(),
+        internal_path: "/secret".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_distinct_status_codes_have_separate_counters() {
+    let port = spawn_unauthorized_upstream();
+    let handler = RouteHandler::new(vec![route(port)], 5);
+    let collector = metrics::MetricsCollector::default();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(metrics::configure_metrics)
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    // Two 401s from the upstream
+    for _ in 0..2 {
+        let req = test::TestRequest::get().uri("/secret").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 401);
+    }
+
+    // One 404 from the gateway itself
+    let req = test::TestRequest::get().uri("/missing").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+
+    assert_eq!(collector.status_code_counts(), vec![(401, 2), (404, 1)]);
+
+    let req = test::TestRequest::get().uri("/metrics").to_request();
+    let body = test::read_body(test::call_service(&app, req).await).await;
+    let text = String::from_utf8_lossy(&body);
+
+    assert!(text.contains("# TYPE kairos_responses_total counter"));
+    assert!(text.contains("kairos_responses_total{code=\"401\"} 2"));
+    assert!(text.contains("kairos_responses_total{code=\"404\"} 1"));
+    assert!(!text.contains("kairos_responses_total{code=\"200\"}"));
+}
+
+#[actix_web::test]
+async fn test_record_request_counts_exact_status_codes() {
+    let collector = metrics::MetricsCollector::default();
+    let latency = std::time::Duration::from_millis(5);
+
+    collector.record_request(true, latency, 200, None, None, None);
+    collector.record_request(false, latency, 403, None, None, None);
+    collector.record_request(false, latency, 403, None, None, None);
+    collector.record_request(false, latency, 503, None, None, None);
+
+    assert_eq!(
+        collector.status_code_counts(),
+        vec![(200, 1), (403, 2), (503, 1)]
+    );
+}
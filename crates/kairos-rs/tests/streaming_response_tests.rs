@@ -0,0 +1,117 @@
+//! Response streaming tests
+//!
+//! Verifies that large and chunked upstream bodies are streamed through the
+//! gateway intact, while small bodies are still buffered with their length.
+
+mod common;
+
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::{test, web, App, HttpResponse};
+use futures_util::stream;
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+
+const LARGE_BODY_SIZE: usize = 256 * 1024;
+
+/// Starts a mock upstream serving a sized body on `/large`, a chunked body
+/// on `/chunked` and a small body everywhere else.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new()
+            .route(
+                "/large",
+                web::get().to(|| async { HttpResponse::Ok().body(vec![b'x'; LARGE_BODY_SIZE]) }),
+            )
+            .route(
+                "/chunked",
+                web::get().to(|| async {
+                    let chunks = (0..4).map(|i| {
+                        Ok::<_, actix_web::Error>(web::Bytes::from(format!("chunk-{};", i)))
+                    });
+                    HttpResponse::Ok().streaming(stream::iter(chunks))
+                }),
+            )
+            .default_service(web::to(|| async { HttpResponse::Ok().body("small") }))
+    })
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: "/files/{name}".to_string(),
+        internal_path: "/{name}".to_string(),
+        methods: vec!["GET".to_string(), "HEAD".to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_large_response_is_streamed_with_content_length() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port)], 30).with_streaming_threshold(1024);
+
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/files/large").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.response().body().size(),
+        BodySize::Sized(LARGE_BODY_SIZE as u64),
+        "streamed body should keep the upstream length"
+    );
+
+    let body = test::read_body(resp).await;
+    assert_eq!(body.len(), LARGE_BODY_SIZE);
+    assert!(body.iter().all(|b| *b == b'x'));
+}
+
+#[actix_web::test]
+async fn test_chunked_response_is_streamed() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port)], 30);
+
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/files/chunked").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.response().body().size(), BodySize::Stream);
+    assert!(resp.headers().get("content-length").is_none());
+    assert!(resp.headers().get("transfer-encoding").is_none());
+
+    let body = test::read_body(resp).await;
+    assert_eq!(body, "chunk-0;chunk-1;chunk-2;chunk-3;");
+}
+
+#[actix_web::test]
+async fn test_small_response_is_buffered() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port)], 30).with_streaming_threshold(1024);
+
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/files/small").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.response().body().size(), BodySize::Sized(5));
+
+    let body = test::read_body(resp).await;
+    assert_eq!(body, "small");
+}
@@ -0,0 +1,191 @@
+//! TCP proxy tests
+//!
+//! Verifies that bytes are copied both ways between clients and upstreams,
+//! that connections are spread across upstreams and counted in metrics, that
+//! unreachable upstreams close the client connection, and that TCP proxy
+//! settings are validated.
+
+use kairos_rs::models::router::Backend;
+use kairos_rs::models::settings::{Settings, TcpProxySettings};
+use kairos_rs::routes::metrics::MetricsCollector;
+use kairos_rs::services::tcp_proxy::spawn_tcp_proxy;
+use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Starts an upstream that answers every read with `tag` followed by the bytes read.
+async fn spawn_upstream(tag: &'static str) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut buf = [0u8; 1024];
+                while let Ok(n) = stream.read(&mut buf).await {
+                    if n == 0 {
+                        break;
+                    }
+                    let mut reply = tag.as_bytes().to_vec();
+                    reply.extend_from_slice(&buf[..n]);
+                    if stream.write_all(&reply).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    port
+}
+
+fn settings(json: serde_json::Value) -> TcpProxySettings {
+    serde_json::from_value(json).unwrap()
+}
+
+fn upstream(port: u16) -> Backend {
+    Backend {
+        host: "127.0.0.1".to_string(),
+        port,
+        weight: 1,
+        health_check_path: None,
+        timeout_secs: None,
+    }
+}
+
+async fn exchange(address: SocketAddr, message: &str) -> String {
+    let mut client = TcpStream::connect(address).await.unwrap();
+    client.write_all(message.as_bytes()).await.unwrap();
+    let mut buf = [0u8; 1024];
+    let n = client.read(&mut buf).await.unwrap();
+    String::from_utf8_lossy(&buf[..n]).into_owned()
+}
+
+#[tokio::test]
+async fn test_bytes_are_copied_both_ways() {
+    let port = spawn_upstream("echo:").await;
+    let proxy = settings(serde_json::json!({
+        "listen_port": 0,
+        "upstream_host": "127.0.0.1",
+        "upstream_port": port
+    }));
+    let metrics = MetricsCollector::default();
+    let (address, _) = spawn_tcp_proxy("127.0.0.1", &proxy, metrics.clone()).await.unwrap();
+
+    let mut client = TcpStream::connect(address).await.unwrap();
+    let mut buf = [0u8; 1024];
+    for message in ["PING", "SELECT 1"] {
+        client.write_all(message.as_bytes()).await.unwrap();
+        let n = client.read(&mut buf).await.unwrap();
+        assert_eq!(&buf[..n], format!("echo:{}", message).as_bytes());
+    }
+    assert_eq!(metrics.tcp_active_connections.load(Ordering::Relaxed), 1);
+
+    drop(client);
+    for _ in 0..50 {
+        if metrics.tcp_active_connections.load(Ordering::Relaxed) == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(metrics.tcp_active_connections.load(Ordering::Relaxed), 0);
+    assert_eq!(metrics.tcp_connections_total.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn test_connections_are_balanced_across_upstreams() {
+    let first = spawn_upstream("a:").await;
+    let second = spawn_upstream("b:").await;
+    let proxy = TcpProxySettings {
+        backends: Some(vec![upstream(first), upstream(second)]),
+        ..settings(serde_json::json!({"listen_port": 0}))
+    };
+    let (address, _) = spawn_tcp_proxy("127.0.0.1", &proxy, MetricsCollector::default())
+        .await
+        .unwrap();
+
+    let mut replies = vec![
+        exchange(address, "x").await,
+        exchange(address, "x").await,
+        exchange(address, "x").await,
+    ];
+    replies.sort();
+    assert_eq!(replies, vec!["a:x", "a:x", "b:x"]);
+}
+
+#[tokio::test]
+async fn test_unreachable_upstream_closes_connection() {
+    // Bind and drop a listener to get a port nothing listens on
+    let closed_port = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let proxy = settings(serde_json::json!({
+        "listen_port": 0,
+        "upstream_host": "127.0.0.1",
+        "upstream_port": closed_port,
+        "connect_timeout_secs": 1
+    }));
+    let metrics = MetricsCollector::default();
+    let (address, _) = spawn_tcp_proxy("127.0.0.1", &proxy, metrics.clone()).await.unwrap();
+
+    let mut client = TcpStream::connect(address).await.unwrap();
+    let mut buf = [0u8; 16];
+    let n = tokio::time::timeout(Duration::from_secs(5), client.read(&mut buf))
+        .await
+        .unwrap()
+        .unwrap_or(0);
+    assert_eq!(n, 0);
+    assert_eq!(metrics.tcp_connections_total.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_tcp_proxy_settings_validation() {
+    let proxy = settings(serde_json::json!({
+        "listen_port": 15432,
+        "upstream_host": "db.internal",
+        "upstream_port": 5432
+    }));
+    assert!(proxy.validate().is_ok());
+    assert_eq!(proxy.connect_timeout_secs, 5);
+    assert_eq!(proxy.upstreams()[0].host, "db.internal");
+
+    let error = TcpProxySettings {
+        upstream_host: Some("tcp://db.internal".to_string()),
+        ..proxy.clone()
+    }
+    .validate()
+    .unwrap_err();
+    assert!(error.contains("without a scheme"), "{error}");
+
+    let error = TcpProxySettings {
+        upstream_port: None,
+        ..proxy.clone()
+    }
+    .validate()
+    .unwrap_err();
+    assert!(error.contains("needs upstream_host and upstream_port"), "{error}");
+
+    let error = TcpProxySettings {
+        listen_port: 0,
+        ..proxy.clone()
+    }
+    .validate()
+    .unwrap_err();
+    assert!(error.contains("listen_port"), "{error}");
+
+    let mut config: Settings = serde_json::from_value(serde_json::json!({
+        "version": 1,
+        "routers": [],
+        "tcp_proxies": [proxy, proxy]
+    }))
+    .unwrap();
+    let error = config.validate().unwrap_err();
+    assert!(error.contains("Several TCP proxies listen on port 15432"), "{error}");
+
+    config.tcp_proxies.pop();
+    assert!(config.validate().is_ok());
+}
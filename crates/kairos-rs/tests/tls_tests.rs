@@ -0,0 +1,128 @@
+//! TLS termination tests
+//!
+//! Verifies that certificates and keys are loaded from PEM files with clear
+//! errors for missing or mismatched files, that the TLS environment variables
+//! are parsed strictly, and that the plain HTTP listener redirects to HTTPS.
+
+use actix_web::{test, App};
+use kairos_rs::config::tls::TlsSettings;
+use kairos_rs::routes::https_redirect::configure_https_redirect;
+use std::path::PathBuf;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/tls")
+        .join(name)
+}
+
+fn settings(cert: &str, key: &str) -> TlsSettings {
+    TlsSettings {
+        cert_path: fixture(cert),
+        key_path: fixture(key),
+        redirect_port: None,
+    }
+}
+
+#[actix_web::test]
+async fn test_valid_certificate_and_key_load() {
+    assert!(settings("cert.pem", "key.pem").load_server_config().is_ok());
+}
+
+#[actix_web::test]
+async fn test_missing_certificate_names_file() {
+    let err = settings("missing.pem", "key.pem").load_server_config().unwrap_err();
+    assert!(err.contains("Cannot read TLS certificate"), "{}", err);
+    assert!(err.contains("missing.pem"), "{}", err);
+}
+
+#[actix_web::test]
+async fn test_file_without_certificates_is_rejected() {
+    let err = settings("key.pem", "key.pem").load_server_config().unwrap_err();
+    assert!(err.contains("contains no PEM certificates"), "{}", err);
+}
+
+#[actix_web::test]
+async fn test_missing_key_names_file() {
+    let err = settings("cert.pem", "cert.pem").load_server_config().unwrap_err();
+    assert!(err.contains("Cannot read TLS private key"), "{}", err);
+    assert!(err.contains("cert.pem"), "{}", err);
+}
+
+#[actix_web::test]
+async fn test_mismatched_key_is_rejected() {
+    let err = settings("cert.pem", "other_key.pem").load_server_config().unwrap_err();
+    assert!(err.contains("cannot be used with certificate"), "{}", err);
+}
+
+#[actix_web::test]
+async fn test_tls_settings_from_env() {
+    let vars = [
+        "KAIROS_TLS_CERT",
+        "KAIROS_TLS_KEY",
+        "KAIROS_HTTP_REDIRECT_PORT",
+    ];
+    let set = |values: [Option<&str>; 3]| {
+        for (name, value) in vars.iter().zip(values) {
+            match value {
+                Some(value) => std::env::set_var(name, value),
+                None => std::env::remove_var(name),
+            }
+        }
+        TlsSettings::from_env()
+    };
+
+    assert_eq!(set([None, None, None]), Ok(None));
+    assert_eq!(
+        set([Some("cert.pem"), Some("key.pem"), Some("80")]),
+        Ok(Some(TlsSettings {
+            cert_path: PathBuf::from("cert.pem"),
+            key_path: PathBuf::from("key.pem"),
+            redirect_port: Some(80),
+        }))
+    );
+    assert!(set([Some("cert.pem"), None, None]).is_err());
+    assert!(set([None, Some("key.pem"), None]).is_err());
+    assert!(set([None, None, Some("80")]).is_err());
+    assert!(set([Some("cert.pem"), Some("key.pem"), Some("http")]).is_err());
+
+    set([None, None, None]).unwrap();
+}
+
+async fn redirect(https_port: u16, req: test::TestRequest) -> (u16, Option<String>) {
+    let app = test::init_service(
+        App::new().configure(|cfg| configure_https_redirect(cfg, https_port)),
+    )
+    .await;
+    let resp = test::call_service(&app, req.to_request()).await;
+    let location = resp
+        .headers()
+        .get("Location")
+        .map(|v| v.to_str().unwrap().to_string());
+    (resp.status().as_u16(), location)
+}
+
+#[actix_web::test]
+async fn test_http_redirects_to_https() {
+    let req = test::TestRequest::post()
+        .uri("/api/users?page=2")
+        .insert_header(("Host", "gateway.example.com:8080"));
+    assert_eq!(
+        redirect(443, req).await,
+        (301, Some("https://gateway.example.com/api/users?page=2".to_string()))
+    );
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header(("Host", "[::1]:8080"));
+    assert_eq!(
+        redirect(8443, req).await,
+        (301, Some("https://[::1]:8443/".to_string()))
+    );
+}
+
+#[actix_web::test]
+async fn test_redirect_without_host_is_rejected() {
+    let (status, location) = redirect(443, test::TestRequest::get().uri("/")).await;
+    assert_eq!(status, 400);
+    assert_eq!(location, None);
+}
@@ -0,0 +1,167 @@
+//! Trailing slash tests
+//!
+//! Verifies that, for static and dynamic routes alike, `strict` matching
+//! treats `/users` and `/users/` as distinct, `ignore` routes both to the
+//! configured route, and `redirect` answers the other form with a 301 to the
+//! configured one. Exact matches always win, and the mode survives route
+//! reloads.
+
+mod common;
+
+use actix_web::{test, web, App, HttpRequest, HttpResponse};
+use kairos_rs::models::router::{Router, TrailingSlash};
+use kairos_rs::models::settings::Settings;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use kairos_rs::utils::route_matcher::{RouteMatchError, RouteMatcher};
+use serde_json::json;
+
+/// Starts a mock upstream answering with the request target it received.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new().default_service(web::to(|req: HttpRequest| async move {
+            HttpResponse::Ok().body(req.uri().to_string())
+        }))
+    })
+}
+
+fn route(port: u16, external_path: &str, internal_path: &str) -> Router {
+    serde_json::from_value(json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": external_path,
+        "internal_path": internal_path,
+        "methods": ["GET"]
+    }))
+    .unwrap()
+}
+
+/// A static route, a dynamic route and a static route configured with a
+/// trailing slash.
+fn routes(port: u16) -> Vec<Router> {
+    vec![
+        route(port, "/users", "/v1/users"),
+        route(port, "/users/{id}", "/v1/users/{id}"),
+        route(port, "/teams/", "/v1/teams/"),
+    ]
+}
+
+fn matcher(mode: TrailingSlash) -> RouteMatcher {
+    RouteMatcher::new(routes(8080)).unwrap().with_trailing_slash(mode)
+}
+
+fn internal_path(matcher: &RouteMatcher, path: &str) -> Result<String, RouteMatchError> {
+    matcher.find_match(path).map(|(_, internal_path)| internal_path)
+}
+
+fn redirect(location: &str) -> Result<String, RouteMatchError> {
+    Err(RouteMatchError::TrailingSlashRedirect {
+        location: location.to_string(),
+    })
+}
+
+#[actix_web::test]
+async fn test_strict_mode_requires_exact_paths() {
+    let matcher = matcher(TrailingSlash::Strict);
+    assert_eq!(internal_path(&matcher, "/users").unwrap(), "/v1/users");
+    assert_eq!(internal_path(&matcher, "/users/42").unwrap(), "/v1/users/42");
+    assert_eq!(internal_path(&matcher, "/teams/").unwrap(), "/v1/teams/");
+    for path in ["/users/", "/users/42/", "/teams"] {
+        assert!(
+            matches!(matcher.find_match(path), Err(RouteMatchError::NoMatch { .. })),
+            "{path}"
+        );
+    }
+}
+
+#[actix_web::test]
+async fn test_ignore_mode_matches_either_form() {
+    let matcher = matcher(TrailingSlash::Ignore);
+    assert_eq!(internal_path(&matcher, "/users").unwrap(), "/v1/users");
+    assert_eq!(internal_path(&matcher, "/users/").unwrap(), "/v1/users");
+    assert_eq!(internal_path(&matcher, "/users/42").unwrap(), "/v1/users/42");
+    assert_eq!(internal_path(&matcher, "/users/42/").unwrap(), "/v1/users/42");
+    assert_eq!(internal_path(&matcher, "/teams").unwrap(), "/v1/teams/");
+    assert!(matcher.find_match("/users//").is_err());
+    assert!(matcher.find_match("/").is_err());
+}
+
+#[actix_web::test]
+async fn test_redirect_mode_points_to_configured_form() {
+    let matcher = matcher(TrailingSlash::Redirect);
+    assert_eq!(internal_path(&matcher, "/users").unwrap(), "/v1/users");
+    assert_eq!(internal_path(&matcher, "/users/"), redirect("/users"));
+    assert_eq!(internal_path(&matcher, "/users/42").unwrap(), "/v1/users/42");
+    assert_eq!(internal_path(&matcher, "/users/42/"), redirect("/users/42"));
+    assert_eq!(internal_path(&matcher, "/teams"), redirect("/teams/"));
+    assert!(matches!(
+        matcher.find_match("/missing/"),
+        Err(RouteMatchError::NoMatch { .. })
+    ));
+}
+
+#[actix_web::test]
+async fn test_exact_match_wins_over_other_form() {
+    let mut routes = routes(8080);
+    routes.push(route(8080, "/users/", "/v1/users-index"));
+    for mode in [TrailingSlash::Ignore, TrailingSlash::Redirect] {
+        let matcher = RouteMatcher::new(routes.clone()).unwrap().with_trailing_slash(mode);
+        assert_eq!(internal_path(&matcher, "/users").unwrap(), "/v1/users");
+        assert_eq!(internal_path(&matcher, "/users/").unwrap(), "/v1/users-index");
+    }
+}
+
+#[actix_web::test]
+async fn test_gateway_redirects_and_forwards() {
+    let handler = RouteHandler::builder(routes(spawn_upstream()), 5)
+        .trailing_slash(TrailingSlash::Redirect)
+        .build()
+        .unwrap();
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/users/42/?expand=teams").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 301);
+    assert_eq!(
+        resp.headers().get("location").unwrap(),
+        "/users/42?expand=teams"
+    );
+
+    // The mode is kept when routes are reloaded
+    handler.reload_routes(routes(spawn_upstream())).unwrap();
+    let req = test::TestRequest::get().uri("/teams").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 301);
+    assert_eq!(resp.headers().get("location").unwrap(), "/teams/");
+
+    let handler = RouteHandler::builder(routes(spawn_upstream()), 5)
+        .trailing_slash(TrailingSlash::Ignore)
+        .build()
+        .unwrap();
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+    let req = test::TestRequest::get().uri("/users/42/?expand=teams").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "/v1/users/42?expand=teams");
+}
+
+#[actix_web::test]
+async fn test_trailing_slash_setting_parses() {
+    let parse = |settings: serde_json::Value| serde_json::from_value::<Settings>(settings);
+    let default = parse(json!({"version": 1, "routers": []})).unwrap();
+    assert_eq!(default.trailing_slash, TrailingSlash::Strict);
+    for (name, mode) in [
+        ("strict", TrailingSlash::Strict),
+        ("ignore", TrailingSlash::Ignore),
+        ("redirect", TrailingSlash::Redirect),
+    ] {
+        let settings = parse(json!({"version": 1, "trailing_slash": name, "routers": []})).unwrap();
+        assert_eq!(settings.trailing_slash, mode);
+    }
+    assert!(parse(json!({"version": 1, "trailing_slash": "strip", "routers": []})).is_err());
+}
@@ -0,0 +1,92 @@
+//! Request and response byte metrics tests
+//!
+//! Verifies that proxied requests add their body sizes to
+//! `kairos_request_bytes_total` and `kairos_response_bytes_total`, including
+//! streamed responses whose size is only known once they have been sent.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::{http, metrics::MetricsCollector};
+use kairos_rs::services::http::RouteHandler;
+use std::sync::atomic::Ordering;
+
+/// Starts a mock upstream answering `/small` with a buffered body and
+/// `/chunked` with a streamed body of unknown length.
+fn spawn_upstream() -> u16 {
+    common::spawn_server(|| {
+        App::new()
+            .route("/small", web::post().to(|| async { HttpResponse::Ok().body("hello") }))
+            .route(
+                "/chunked",
+                web::get().to(|| async {
+                    let chunks = ["abcd", "efgh", "ijkl"]
+                        .map(|chunk| Ok::<_, actix_web::Error>(web::Bytes::from_static(chunk.as_bytes())));
+                    HttpResponse::Ok().streaming(futures::stream::iter(chunks))
+                }),
+            )
+    })
+}
+
+fn route(external_path: &str, internal_path: &str, method: &str, port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: None,
+            timeout_secs: None,
+        }]),
+        external_path: external_path.to_string(),
+        internal_path: internal_path.to_string(),
+        methods: vec![method.to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_buffered_request_and_response_bytes_are_recorded() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route("/api/small", "/small", "POST", port)], 5);
+    let collector = MetricsCollector::default();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/small")
+        .set_payload("payload")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(test::read_body(resp).await, "hello");
+
+    assert_eq!(collector.request_bytes_total.load(Ordering::Relaxed), 7);
+    assert_eq!(collector.response_bytes_total.load(Ordering::Relaxed), 5);
+}
+
+#[actix_web::test]
+async fn test_streamed_response_bytes_are_recorded_when_sent() {
+    let port = spawn_upstream();
+    let handler = RouteHandler::new(vec![route("/api/chunked", "/chunked", "GET", port)], 5);
+    let collector = MetricsCollector::default();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(collector.clone()))
+            .configure(|cfg| http::configure_route(cfg, handler.clone())),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/chunked").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(collector.response_bytes_total.load(Ordering::Relaxed), 0);
+
+    assert_eq!(test::read_body(resp).await, "abcdefghijkl");
+    assert_eq!(collector.request_bytes_total.load(Ordering::Relaxed), 0);
+    assert_eq!(collector.response_bytes_total.load(Ordering::Relaxed), 12);
+}
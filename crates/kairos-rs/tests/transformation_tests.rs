@@ -1,5 +1,7 @@
+#![allow(clippy::unnecessary_get_then_check)]
+
 use kairos_rs::middleware::transform::*;
-use actix_web::http::{header::{HeaderMap, HeaderValue, HOST, AUTHORIZATION, COOKIE, CONTENT_TYPE, USER_AGENT, SERVER}, StatusCode};
+use actix_web::http::{header::{HeaderMap, HeaderName, HeaderValue, HOST, AUTHORIZATION, COOKIE, CONTENT_TYPE, USER_AGENT, SERVER}, StatusCode};
 use std::collections::HashMap;
 
 #[test]
@@ -23,6 +25,8 @@ fn test_request_header_add() {
         ],
         path: None,
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -46,6 +50,8 @@ fn test_request_header_set_override() {
         }],
         path: None,
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -69,6 +75,8 @@ fn test_request_header_add_no_override() {
         }],
         path: None,
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -102,6 +110,8 @@ fn test_request_header_remove() {
         ],
         path: None,
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -129,6 +139,8 @@ fn test_request_header_replace_regex() {
         }],
         path: None,
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -149,6 +161,8 @@ fn test_path_rewrite_simple() {
             replacement: "/v2/$1".to_string(),
         }),
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -167,6 +181,8 @@ fn test_path_rewrite_remove_prefix() {
             replacement: "/$1".to_string(),
         }),
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -184,6 +200,8 @@ fn test_path_rewrite_add_prefix() {
             replacement: "/backend/$1".to_string(),
         }),
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -201,6 +219,8 @@ fn test_path_rewrite_complex_pattern() {
             replacement: "/v$1/api/$2/$3".to_string(),
         }),
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -225,13 +245,19 @@ fn test_query_param_add() {
                 action: TransformAction::Add,
                 name: "api_key".to_string(),
                 value: Some("secret123".to_string()),
+                pattern: None,
+                replacement: None,
             },
             QueryTransformation {
                 action: TransformAction::Add,
                 name: "version".to_string(),
                 value: Some("v1".to_string()),
+                pattern: None,
+                replacement: None,
             },
         ],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -252,7 +278,11 @@ fn test_query_param_set() {
             action: TransformAction::Set,
             name: "format".to_string(),
             value: Some("json".to_string()),
+            pattern: None,
+            replacement: None,
         }],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -274,13 +304,19 @@ fn test_query_param_remove() {
                 action: TransformAction::Remove,
                 name: "debug".to_string(),
                 value: None,
+                pattern: None,
+                replacement: None,
             },
             QueryTransformation {
                 action: TransformAction::Remove,
                 name: "internal".to_string(),
                 value: None,
+                pattern: None,
+                replacement: None,
             },
         ],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -296,6 +332,38 @@ fn test_query_param_remove() {
     assert!(params.get("user_id").is_some());
 }
 
+#[test]
+fn test_query_param_replace_version() {
+    let config = RequestTransformation {
+        headers: vec![],
+        path: None,
+        query_params: vec![QueryTransformation {
+            action: TransformAction::Replace,
+            name: "v".to_string(),
+            value: None,
+            pattern: Some(r"^v(\d+)\.\d+\.\d+$".to_string()),
+            replacement: Some("v$1".to_string()),
+        }],
+        when: None,
+        body: None,
+    };
+
+    let transformer = RequestTransformer::new(config);
+    let mut params = HashMap::new();
+    params.insert("v".to_string(), "v2.4.1".to_string());
+    params.insert("user_id".to_string(), "123".to_string());
+    
+    transformer.transform_query_params(&mut params);
+    
+    assert_eq!(params.get("v").unwrap(), "v2");
+    assert_eq!(params.get("user_id").unwrap(), "123");
+
+    // Replace never adds a missing parameter
+    let mut params = HashMap::new();
+    transformer.transform_query_params(&mut params);
+    assert!(params.is_empty());
+}
+
 #[test]
 fn test_response_header_transformation() {
     let config = ResponseTransformation {
@@ -350,9 +418,9 @@ fn test_response_status_code_mapping() {
 
     let transformer = ResponseTransformer::new(config);
     
-    assert_eq!(transformer.transform_status_code(StatusCode::NOT_FOUND, "/test"), StatusCode::OK);
-    assert_eq!(transformer.transform_status_code(StatusCode::BAD_GATEWAY, "/test"), StatusCode::SERVICE_UNAVAILABLE);
-    assert_eq!(transformer.transform_status_code(StatusCode::OK, "/test"), StatusCode::OK); // No mapping
+    assert_eq!(transformer.transform_status_code(StatusCode::NOT_FOUND, "/test", "GET", &HeaderMap::new()), StatusCode::OK);
+    assert_eq!(transformer.transform_status_code(StatusCode::BAD_GATEWAY, "/test", "GET", &HeaderMap::new()), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(transformer.transform_status_code(StatusCode::OK, "/test", "GET", &HeaderMap::new()), StatusCode::OK); // No mapping
 }
 
 #[test]
@@ -382,7 +450,11 @@ fn test_combined_request_transformation() {
             action: TransformAction::Add,
             name: "source".to_string(),
             value: Some("gateway".to_string()),
+            pattern: None,
+            replacement: None,
         }],
+        when: None,
+        body: None,
     };
 
     let transformer = RequestTransformer::new(config);
@@ -418,6 +490,8 @@ fn test_serialization_deserialization() {
             replacement: "/$1".to_string(),
         }),
         query_params: vec![],
+        when: None,
+        body: None,
     };
 
     let json = serde_json::to_string(&config).unwrap();
@@ -427,3 +501,48 @@ fn test_serialization_deserialization() {
     assert_eq!(deserialized.headers[0].name, "X-Custom");
     assert!(deserialized.path.is_some());
 }
+
+#[test]
+fn test_conditional_request_transformation() {
+    let conditional = |when: &str| {
+        RequestTransformer::new(RequestTransformation {
+            headers: vec![HeaderTransformation {
+                action: TransformAction::Set,
+                name: "X-Debug-Upstream".to_string(),
+                value: Some("true".to_string()),
+                pattern: None,
+                replacement: None,
+            }],
+            path: None,
+            query_params: vec![],
+            when: Some(when.to_string()),
+            body: None,
+        })
+    };
+
+    let mut debug_headers = HeaderMap::new();
+    debug_headers.insert(HeaderName::from_static("x-debug"), HeaderValue::from_static("1"));
+    let plain_headers = HeaderMap::new();
+
+    // Fires only when the request carries the header
+    let transformer = conditional("has_header 'X-Debug'");
+    assert!(transformer.applies_to("/api/users", "GET", &debug_headers));
+    assert!(!transformer.applies_to("/api/users", "GET", &plain_headers));
+
+    // Fires only for requests under the path prefix
+    let transformer = conditional("path starts_with '/internal'");
+    assert!(transformer.applies_to("/internal/jobs", "GET", &plain_headers));
+    assert!(!transformer.applies_to("/api/users", "GET", &plain_headers));
+
+    // Malformed conditions never fire
+    let transformer = conditional("has_header X-Debug");
+    assert!(!transformer.applies_to("/api/users", "GET", &debug_headers));
+
+    // No condition applies to every request
+    let transformer = RequestTransformer::new(RequestTransformation::default());
+    assert!(transformer.applies_to("/api/users", "GET", &plain_headers));
+
+    let config: RequestTransformation =
+        serde_json::from_str(r#"{"headers": [], "when": "header.X-Env == 'staging'"}"#).unwrap();
+    assert_eq!(config.when.as_deref(), Some("header.X-Env == 'staging'"));
+}
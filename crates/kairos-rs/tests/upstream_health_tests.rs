@@ -0,0 +1,106 @@
+//! Upstream health endpoint tests
+//!
+//! Verifies that `/health/upstreams` probes every backend's health check
+//! path and reports backends without one as unknown.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::routes::health;
+use kairos_rs::services::http::RouteHandler;
+use std::net::TcpListener;
+
+/// Starts a mock upstream whose `/healthz` answers with `status`.
+fn spawn_upstream(status: u16) -> u16 {
+    common::spawn_server(move || {
+        App::new().route(
+            "/healthz",
+            web::get().to(move || async move {
+                HttpResponse::build(actix_web::http::StatusCode::from_u16(status).unwrap()).finish()
+            }),
+        )
+    })
+}
+
+/// Returns a local port with nothing listening on it.
+fn unused_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn backend(port: u16, health_check_path: Option<&str>) -> Backend {
+    Backend {
+        host: "http://127.0.0.1".to_string(),
+        port,
+        weight: 1,
+        health_check_path: health_check_path.map(str::to_string),
+        timeout_secs: None,
+    }
+}
+
+fn route(backends: Vec<Backend>) -> Router {
+    Router {
+        backends: Some(backends),
+        external_path: "/api/users".to_string(),
+        internal_path: "/users".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_upstream_probes() {
+    let healthy = spawn_upstream(200);
+    let failing = spawn_upstream(500);
+    let down = unused_port();
+    let unprobed = unused_port();
+    let handler = RouteHandler::new(
+        vec![route(vec![
+            backend(healthy, Some("/healthz")),
+            backend(failing, Some("/healthz")),
+            backend(down, Some("/healthz")),
+            backend(unprobed, None),
+        ])],
+        5,
+    );
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(handler))
+            .configure(health::configure_health),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/health/upstreams").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    let upstream = |port: u16| body[format!("http://127.0.0.1:{}", port)].clone();
+
+    assert_eq!(upstream(healthy)["healthy"], true);
+    assert_eq!(upstream(healthy)["status_code"], 200);
+    assert!(upstream(healthy)["latency_ms"].is_u64());
+
+    assert_eq!(upstream(failing)["healthy"], false);
+    assert_eq!(upstream(failing)["status_code"], 500);
+
+    assert_eq!(upstream(down)["healthy"], false);
+    assert!(upstream(down)["status_code"].is_null());
+
+    assert_eq!(upstream(unprobed), "unknown");
+    assert_eq!(body.as_object().unwrap().len(), 4);
+}
+
+#[actix_web::test]
+async fn test_upstream_probes_without_handler() {
+    let app = test::init_service(App::new().configure(health::configure_health)).await;
+
+    let req = test::TestRequest::get().uri("/health/upstreams").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body, serde_json::json!({}));
+}
@@ -0,0 +1,135 @@
+//! Startup warmup tests
+//!
+//! Verifies that proxy routes answer `503` with `Retry-After` until the
+//! warmup finishes, while health endpoints keep responding.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use kairos_rs::models::router::{Backend, Router};
+use kairos_rs::models::settings::WarmupSettings;
+use kairos_rs::routes::{health, http};
+use kairos_rs::services::http::RouteHandler;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Starts a mock upstream that counts requests to its health check path.
+fn spawn_upstream() -> (u16, Arc<AtomicUsize>) {
+    let probes = Arc::new(AtomicUsize::new(0));
+
+    let counter = probes.clone();
+    let port = common::spawn_server(move || {
+        let counter = counter.clone();
+        App::new()
+            .route(
+                "/healthz",
+                web::get().to(move || {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    async { HttpResponse::Ok().finish() }
+                }),
+            )
+            .default_service(web::to(|| async { HttpResponse::Ok().body("warm") }))
+    });
+
+    (port, probes)
+}
+
+fn route(port: u16) -> Router {
+    Router {
+        backends: Some(vec![Backend {
+            host: "http://127.0.0.1".to_string(),
+            port,
+            weight: 1,
+            health_check_path: Some("/healthz".to_string()),
+            timeout_secs: None,
+        }]),
+        external_path: "/api/items".to_string(),
+        internal_path: "/items".to_string(),
+        methods: vec!["GET".to_string()],
+        ..Default::default()
+    }
+}
+
+#[actix_web::test]
+async fn test_proxy_routes_return_503_until_warm() {
+    let (port, _) = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port)], 5).with_warmup(Duration::from_secs(30));
+    let control = handler.clone();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(handler.clone()))
+            .configure(health::configure_health)
+            .configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/items").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 503);
+    let retry_after: u64 = resp
+        .headers()
+        .get("retry-after")
+        .expect("Retry-After header")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=30).contains(&retry_after));
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["type"], "warming_up");
+
+    // Health endpoints stay up; readiness reflects the warmup
+    let req = test::TestRequest::get().uri("/health").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+    let req = test::TestRequest::get().uri("/ready").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 503);
+
+    control.finish_warmup();
+
+    let req = test::TestRequest::get().uri("/api/items").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(test::read_body(resp).await, "warm");
+    let req = test::TestRequest::get().uri("/ready").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_warm_up_probes_backends_and_flips_readiness() {
+    let (port, probes) = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port)], 5).with_warmup(Duration::from_secs(30));
+    assert!(!handler.is_ready());
+
+    // Finishes as soon as the backend answered, well before the grace period
+    tokio::time::timeout(Duration::from_secs(5), handler.warm_up(true))
+        .await
+        .expect("warmup should end once backends answered");
+
+    assert!(handler.is_ready());
+    assert_eq!(probes.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+async fn test_warm_up_ends_after_grace_period() {
+    let (port, probes) = spawn_upstream();
+    let handler = RouteHandler::new(vec![route(port)], 5).with_warmup(Duration::from_millis(200));
+
+    // Without connection warming the full grace period is waited out
+    let start = std::time::Instant::now();
+    handler.warm_up(false).await;
+    assert!(start.elapsed() >= Duration::from_millis(150));
+    assert!(handler.is_ready());
+    assert_eq!(probes.load(Ordering::SeqCst), 0);
+}
+
+#[actix_web::test]
+async fn test_handler_without_warmup_is_ready() {
+    let handler = RouteHandler::new(vec![route(8080)], 5);
+    assert!(handler.is_ready());
+
+    let settings: WarmupSettings = serde_json::from_str("{}").unwrap();
+    assert_eq!(settings.grace_period_secs, 10);
+    assert!(settings.warm_connections);
+}
@@ -0,0 +1,181 @@
+//! Webhook signature verification tests
+//!
+//! Verifies that routes with `webhook_verify` forward requests carrying a
+//! valid HMAC of the raw body, reject tampered, missing and malformed
+//! signatures with a 401 without reaching the backend, and that the secret
+//! is interpolated from the environment and validated.
+
+mod common;
+
+use actix_web::{test, web, App, HttpResponse};
+use base64::{engine::general_purpose, Engine};
+use kairos_rs::models::router::Router;
+use kairos_rs::routes::http;
+use kairos_rs::services::http::RouteHandler;
+use ring::hmac;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const SECRET: &str = "webhook-test-secret";
+
+/// Starts a mock upstream echoing the body it receives and counting requests.
+fn spawn_upstream() -> (u16, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    let port = common::spawn_server(move || {
+        let counter = counter.clone();
+        App::new().default_service(web::to(move |body: web::Bytes| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { HttpResponse::Ok().body(body) }
+        }))
+    });
+
+    (port, hits)
+}
+
+fn route(port: u16, webhook_verify: serde_json::Value) -> Router {
+    serde_json::from_value(serde_json::json!({
+        "backends": [{"host": "http://127.0.0.1", "port": port}],
+        "external_path": "/hooks/github",
+        "internal_path": "/events",
+        "methods": ["POST"],
+        "webhook_verify": webhook_verify,
+        "request_transformation": {"body": "form_to_json"}
+    }))
+    .unwrap()
+}
+
+fn github_style(port: u16) -> Router {
+    route(
+        port,
+        serde_json::json!({
+            "header": "X-Hub-Signature-256",
+            "algorithm": "sha256",
+            "secret": SECRET,
+            "prefix": "sha256="
+        }),
+    )
+}
+
+fn sign(algorithm: hmac::Algorithm, body: &[u8]) -> Vec<u8> {
+    hmac::sign(&hmac::Key::new(algorithm, SECRET.as_bytes()), body)
+        .as_ref()
+        .to_vec()
+}
+
+/// Posts `body` with the given signature header, returning the status and
+/// response body.
+async fn post(route: Router, body: &'static str, signature: Option<(&str, String)>) -> (u16, String) {
+    let handler = RouteHandler::new(vec![route], 5);
+    let app = test::init_service(
+        App::new().configure(|cfg| http::configure_route(cfg, handler)),
+    )
+    .await;
+    let mut req = test::TestRequest::post()
+        .uri("/hooks/github")
+        .insert_header(("Content-Type", "application/x-www-form-urlencoded"))
+        .set_payload(body);
+    if let Some(signature) = signature {
+        req = req.insert_header(signature);
+    }
+    let resp = test::call_service(&app, req.to_request()).await;
+    let status = resp.status().as_u16();
+    let body = test::read_body(resp).await;
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+#[actix_web::test]
+async fn test_valid_signature_is_forwarded() {
+    let (port, hits) = spawn_upstream();
+    let body = "action=opened";
+    let signature = format!("sha256={}", hex::encode(sign(hmac::HMAC_SHA256, body.as_bytes())));
+
+    let (status, forwarded) =
+        post(github_style(port), body, Some(("X-Hub-Signature-256", signature))).await;
+    assert_eq!(status, 200);
+    // Verified over the raw body, then transformed as usual
+    assert_eq!(forwarded, r#"{"action":"opened"}"#);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+async fn test_tampered_body_is_rejected() {
+    let (port, hits) = spawn_upstream();
+    let signature = format!(
+        "sha256={}",
+        hex::encode(sign(hmac::HMAC_SHA256, b"action=opened"))
+    );
+
+    let (status, body) = post(
+        github_style(port),
+        "action=closed",
+        Some(("X-Hub-Signature-256", signature)),
+    )
+    .await;
+    assert_eq!(status, 401);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["type"], "unauthorized");
+    assert_eq!(hits.load(Ordering::SeqCst), 0);
+}
+
+#[actix_web::test]
+async fn test_missing_or_malformed_signature_is_rejected() {
+    let (port, hits) = spawn_upstream();
+    let body = "action=opened";
+    let digest = hex::encode(sign(hmac::HMAC_SHA256, body.as_bytes()));
+
+    let signatures = [
+        None,
+        Some(("X-Hub-Signature-256", "sha256=not-hex".to_string())),
+        // Right digest without the expected prefix
+        Some(("X-Hub-Signature-256", digest)),
+        // Signed with another algorithm
+        Some((
+            "X-Hub-Signature-256",
+            format!("sha256={}", hex::encode(sign(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, body.as_bytes()))),
+        )),
+    ];
+    for signature in signatures {
+        let (status, _) = post(github_style(port), body, signature.clone()).await;
+        assert_eq!(status, 401, "{:?}", signature);
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 0);
+}
+
+#[actix_web::test]
+async fn test_base64_signature_with_environment_secret() {
+    std::env::set_var("KAIROS_TEST_WEBHOOK_SECRET", SECRET);
+    let (port, hits) = spawn_upstream();
+    let route = route(
+        port,
+        serde_json::json!({
+            "header": "X-Shopify-Hmac-Sha256",
+            "secret": "${KAIROS_TEST_WEBHOOK_SECRET}",
+            "encoding": "base64"
+        }),
+    );
+    let body = "order=opened";
+    let signature = general_purpose::STANDARD.encode(sign(hmac::HMAC_SHA256, body.as_bytes()));
+
+    let (status, _) = post(route, body, Some(("X-Shopify-Hmac-Sha256", signature))).await;
+    assert_eq!(status, 200);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[actix_web::test]
+async fn test_invalid_webhook_config_is_rejected() {
+    let invalid = [
+        serde_json::json!({"header": "X-Signature", "secret": "${KAIROS_TEST_UNSET_WEBHOOK_SECRET}"}),
+        serde_json::json!({"header": "X-Signature", "secret": ""}),
+        serde_json::json!({"header": "Bad Header", "secret": SECRET}),
+    ];
+    for config in invalid {
+        let err = route(8080, config.clone()).validate().unwrap_err();
+        assert!(err.contains("Webhook verification"), "{config}: {err}");
+    }
+    assert!(github_style(8080).validate().is_ok());
+    // Signatures need the whole body
+    let mut streaming = github_style(8080);
+    streaming.stream_request_body = true;
+    assert!(!streaming.streams_request_body());
+}
@@ -185,6 +185,7 @@ impl MetricsData {
                                     0 => CircuitBreakerState::Closed,
                                     1 => CircuitBreakerState::Open,
                                     2 => CircuitBreakerState::HalfOpen,
+                                    3 => CircuitBreakerState::Open,
                                     _ => CircuitBreakerState::Closed,
                                 };
                                 found = true;
@@ -198,6 +199,7 @@ impl MetricsData {
                                     0 => CircuitBreakerState::Closed,
                                     1 => CircuitBreakerState::Open,
                                     2 => CircuitBreakerState::HalfOpen,
+                                    3 => CircuitBreakerState::Open,
                                     _ => CircuitBreakerState::Closed,
                                 },
                                 failure_count: 0,